@@ -13,6 +13,8 @@ use crate::poly::classes::monomial::Monomial;
 use crate::poly::classes::univariate_polynomial::UnivariatePolynomial;
 use crate::poly::instances::univariate_polynomial_instance::UnivariatePolynomialInstance;
 use crate::utilities::utils;
+use crate::error::{unwrap_or_panic, Error, Result};
+use num_integer::Integer;
 
 use crate::numbers::instances::QQ_instance::QQinstance;
 use crate::numbers::instances::ZZ_instance::ZZinstance;
@@ -116,7 +118,11 @@ impl Zmod {
     }
 
     pub fn div(&self, x: ZmodInstance, y: ZmodInstance) -> ZmodInstance  {
-        self.apply(x.value * self.clone().inverse(y).value)
+        unwrap_or_panic(self.try_div(x, y))
+    }
+
+    pub fn try_div(&self, x: ZmodInstance, y: ZmodInstance) -> Result<ZmodInstance>  {
+        Ok(self.apply(x.value * self.clone().try_inverse(y)?.value))
     }
 
     pub fn neg(self, x: ZmodInstance) -> ZmodInstance {
@@ -124,7 +130,18 @@ impl Zmod {
     }
 
     pub fn inverse(self, x: ZmodInstance) -> ZmodInstance {
-        self.apply(utils::modular_inverse(x.value.value.clone(), self.clone().module.unwrap().value))
+        unwrap_or_panic(self.try_inverse(x))
+    }
+
+    pub fn try_inverse(self, x: ZmodInstance) -> Result<ZmodInstance> {
+        let module: BigInt = match self.module.clone() {
+            Some(module) => module.value,
+            None => return Err(Error::InvalidInput("The modulus must be set".to_string()))
+        };
+        if x.value.value.gcd(&module) != BigInt::from(1) {
+            return Err(Error::NotInvertible(format!("{} is not invertible modulo {}", x.value.value, module)));
+        }
+        Ok(self.apply(utils::modular_inverse(x.value.value.clone(), module)))
     }
 }
 
@@ -18,6 +18,8 @@ use std::cell::RefCell;
 use std::cmp::Ordering;
 use crate::numbers::numbers::generic_pow;
 use crate::algebras::FiniteField::classes::Zmod::Zmod;
+use crate::utilities::utils;
+use crate::error::{unwrap_or_panic, Error, Result};
 
 /*
     Zmod INSTANCE
@@ -37,12 +39,64 @@ impl Eq for ZmodInstance {}
 
 impl ZmodInstance {
     pub fn inverse(&self) -> ZmodInstance {
-        self.class.clone().into_inner().inverse((*self).clone()) 
+        unwrap_or_panic(self.try_inverse())
+    }
+
+    pub fn try_inverse(&self) -> Result<ZmodInstance> {
+        self.class.clone().into_inner().try_inverse((*self).clone())
+    }
+
+    // the field of the operation, a value without modulus takes the one of the other operand
+    fn common_field(&self, rhs: &ZmodInstance) -> Result<Zmod> {
+        if rhs.class.borrow().module.is_none() {
+            Ok(self.class.clone().into_inner())
+        } else if self.class.borrow().module.is_none() {
+            Ok(rhs.class.clone().into_inner())
+        } else if self.class == rhs.class {
+            Ok(self.class.clone().into_inner())
+        } else {
+            Err(Error::IncompatibleOperands(format!("The values are not in the same field {} {}", self.class.borrow().module.clone().unwrap(), rhs.class.borrow().module.clone().unwrap())))
+        }
+    }
+
+    pub fn try_add(self, rhs: ZmodInstance) -> Result<ZmodInstance> {
+        Ok(self.common_field(&rhs)?.add(self, rhs))
+    }
+
+    pub fn try_sub(self, rhs: ZmodInstance) -> Result<ZmodInstance> {
+        Ok(self.common_field(&rhs)?.sub(self, rhs))
+    }
+
+    pub fn try_mul(self, rhs: ZmodInstance) -> Result<ZmodInstance> {
+        Ok(self.common_field(&rhs)?.mul(self, rhs))
+    }
+
+    pub fn try_div(self, rhs: ZmodInstance) -> Result<ZmodInstance> {
+        self.common_field(&rhs)?.try_div(self, rhs)
     }
 
     pub fn get_bigint_value(&self) -> ZZinstance {
         self.value.clone()
     }
+
+    /*
+        Legendre symbol of the value, the modulus must be an odd prime
+    */
+    pub fn legendre_symbol(&self) -> i32 {
+        utils::legendre_symbol(self.value.value.clone(), self.class.borrow().module.clone().unwrap().value)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.legendre_symbol() != -1
+    }
+
+    /*
+        One of the square roots of the value (None if it is not a square), the modulus must be prime
+    */
+    pub fn sqrt(&self) -> Option<ZmodInstance> {
+        let root = utils::modular_sqrt(self.value.value.clone(), self.class.borrow().module.clone().unwrap().value);
+        root.map(|r| self.class.clone().into_inner().apply(r))
+    }
 }
 
 // ------------- OPERATIONS ---------------------
@@ -62,18 +116,7 @@ impl std::ops::Neg for ZmodInstance {
 impl std::ops::Add<ZmodInstance> for ZmodInstance {
     type Output = ZmodInstance;
     fn add(self, rhs: ZmodInstance) -> ZmodInstance {
-        
-        if rhs.class.clone().into_inner().module.is_none() {
-            return  self.class.clone().into_inner().add(self, rhs)  
-        } else if self.class.clone().into_inner().module.is_none() {
-            return rhs.class.clone().into_inner().add(self, rhs)
-        } else {
-            if self.class == rhs.class {
-                self.class.clone().into_inner().add(self, rhs)
-            } else {
-                panic!("The values are not in the same field")
-            }
-        }
+        unwrap_or_panic(self.try_add(rhs))
     }
 }
 
@@ -102,17 +145,7 @@ impl std::ops::Add<RRinstance> for ZmodInstance {
 impl std::ops::Sub<ZmodInstance> for ZmodInstance {
     type Output = ZmodInstance;
     fn sub(self, rhs: ZmodInstance) -> ZmodInstance {
-        if rhs.class.clone().into_inner().module.is_none() {
-            return  self.class.clone().into_inner().sub(self, rhs)  
-        } else if self.class.clone().into_inner().module.is_none() {
-            return rhs.class.clone().into_inner().sub(self, rhs)
-        } else {
-            if self.class == rhs.class {
-                self.class.clone().into_inner().sub(self, rhs)
-            } else {
-                panic!("The values are not in the same field {} {}", self.class.into_inner().module.unwrap(), rhs.class.into_inner().module.unwrap());
-            }
-        }
+        unwrap_or_panic(self.try_sub(rhs))
     }
 }
 impl std::ops::Sub<ZZinstance> for ZmodInstance {
@@ -142,17 +175,7 @@ impl std::ops::Sub<RRinstance> for ZmodInstance {
 impl std::ops::Mul<ZmodInstance> for ZmodInstance {
     type Output = ZmodInstance;
     fn mul(self, rhs: ZmodInstance) -> ZmodInstance {
-        if rhs.class.clone().into_inner().module.is_none() {
-            return  self.class.clone().into_inner().mul(self, rhs)  
-        } else if self.class.clone().into_inner().module.is_none() {
-            return rhs.class.clone().into_inner().mul(self, rhs)
-        } else {
-            if self.class == rhs.class {
-                self.class.clone().into_inner().mul(self, rhs)
-            } else {
-                panic!("The values are not in the same field")
-            }
-        }
+        unwrap_or_panic(self.try_mul(rhs))
     }
 }
 impl std::ops::Mul<ZZinstance> for ZmodInstance {
@@ -182,17 +205,7 @@ impl std::ops::Mul<RRinstance> for ZmodInstance {
 impl std::ops::Div<ZmodInstance> for ZmodInstance {
     type Output = ZmodInstance;
     fn div(self, rhs: ZmodInstance) -> ZmodInstance {
-        if rhs.class.clone().into_inner().module.is_none() {
-            return  self.class.clone().into_inner().div(self, rhs)  
-        } else if self.class.clone().into_inner().module.is_none() {
-            return rhs.class.clone().into_inner().div(self, rhs)
-        } else {
-            if self.class == rhs.class {
-                self.class.clone().into_inner().div(self, rhs)
-            } else {
-                panic!("The values are not in the same field")
-            }
-        }
+        unwrap_or_panic(self.try_div(rhs))
     }
 }
 impl std::ops::Div<ZZinstance> for ZmodInstance {
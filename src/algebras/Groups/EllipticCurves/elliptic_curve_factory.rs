@@ -1,14 +1,66 @@
 use crate::numbers::{numbers::{ClassInstance, Instance, Operand}, sets::Class::ClassTypes};
 
+use num_bigint::BigInt;
+use num_traits::Num;
+
+use crate::algebras::FiniteField::classes::Zmod::Zmod;
+use crate::algebras::FiniteField::instances::Zmod_instance::ZmodInstance;
+use crate::numbers::classes::ZZ::ZZ;
+use crate::numbers::instances::QQ_instance::QQinstance;
+use crate::numbers::numbers::Class;
+
+use super::elliptic_curve_finite_field::EllipticCurve_finite_field;
 use super::elliptic_curve_generic::EllipticCurve_generic;
+use super::elliptic_curve_rational_field::EllipticCurve_rational_field;
+use super::elliptic_curve_point::EllipticCurvePoint;
 
 
 pub trait EllipticCurve<T> {
     fn discriminant(&self) -> T;
     fn b_invariants(&self) -> (T,T,T,T);
+    fn c_invariants(&self) -> (T,T);
+    fn j_invariant(&self) -> T;
 }
 
 
+// standardized curves with a prime order generator (cofactor 1)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum NamedCurves {
+    P256,
+    P384,
+    SECP256K1
+}
+
+// p, a, b, Gx, Gy, n
+fn named_curve_parameters(curve: NamedCurves) -> [&'static str; 6] {
+    match curve {
+        NamedCurves::P256 => [
+            "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+            "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+            "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+            "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+            "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+            "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+        ],
+        NamedCurves::P384 => [
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff",
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000fffffffc",
+            "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef",
+            "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7",
+            "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973"
+        ],
+        NamedCurves::SECP256K1 => [
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            "0",
+            "7",
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+        ]
+    }
+}
+
 #[derive(Clone)]
 pub struct EllipticCurveFactory {
 }
@@ -30,6 +82,42 @@ impl EllipticCurveFactory {
         } 
 
     }
-}
 
+    /*
+    Curve over a prime field, with point counting and group structure
+     */
+    pub fn gen_finite_field_curve(coefficients: Vec<ZmodInstance>) -> Box<EllipticCurve_finite_field> {
+        if coefficients.is_empty() {
+            panic!("You should set the curve parameters");
+        }
+        Box::new(EllipticCurve_finite_field::new(coefficients))
+    }
+
+    /*
+    Curve over QQ, with minimal models, local data, torsion and reduction modulo primes
+     */
+    pub fn gen_rational_field_curve(coefficients: Vec<QQinstance>) -> Box<EllipticCurve_rational_field> {
+        if coefficients.is_empty() {
+            panic!("You should set the curve parameters");
+        }
+        Box::new(EllipticCurve_rational_field::new(coefficients))
+    }
 
+    /*
+    Standardized curve together with its generator, the order of the curve is already set
+     */
+    pub fn gen_named_curve(curve: NamedCurves) -> (Box<EllipticCurve_finite_field>, EllipticCurvePoint<ZmodInstance>) {
+        let parameters: Vec<BigInt> = named_curve_parameters(curve).iter().map(|value| BigInt::from_str_radix(value, 16).unwrap()).collect();
+        let field = Zmod::new(Some(ZZ::new().new_instance(parameters[0].clone())));
+        let elliptic_curve = EllipticCurve_finite_field::new(vec![
+            field.zero(),
+            field.zero(),
+            field.zero(),
+            field.apply(parameters[1].clone()),
+            field.apply(parameters[2].clone())
+        ]);
+        elliptic_curve.set_order_unchecked(parameters[5].clone());
+        let generator = elliptic_curve.point(field.apply(parameters[3].clone()), field.apply(parameters[4].clone()));
+        (Box::new(elliptic_curve), generator)
+    }
+}
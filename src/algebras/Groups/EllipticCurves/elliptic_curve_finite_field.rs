@@ -0,0 +1,781 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+
+use crate::algebras::FiniteField::classes::Zmod::Zmod;
+use crate::algebras::FiniteField::instances::Zmod_instance::ZmodInstance;
+use crate::arith::primes::is_prime;
+use crate::arith::random::get_random_bigint_with_bounds;
+use crate::error::{unwrap_or_panic, Error, Result};
+use crate::numbers::numbers::{Class, Operand};
+use crate::poly::gcd::{add, divmod, gcd, invmod, monic, mul, mulmod, powmod, scale, sub, trim};
+use crate::utilities::utils::{legendre_symbol, modular_inverse, modular_sqrt};
+
+use super::elliptic_curve_factory::EllipticCurve;
+use super::elliptic_curve_generic::EllipticCurve_generic;
+use super::elliptic_curve_point::EllipticCurvePoint;
+
+
+/*
+    Algorithms available to count the points of a curve over a prime field.
+    Naive is O(p), BabyStepGiantStep is O(p^(1/4)) group operations (Mestre), Schoof is polynomial in log(p).
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PointCountingAlgorithm {
+    Naive,
+    BabyStepGiantStep,
+    Schoof
+}
+
+// below these bounds the cheaper algorithm is used when the order is computed automatically,
+// above the last one the plain Schoof algorithm is impractical and the order must be set
+const NAIVE_COUNTING_BOUND: u64 = 1 << 16;
+const BSGS_COUNTING_BOUND: u128 = 1 << 64;
+
+/*
+    ELLIPTIC CURVE over a prime field F_p (Zmod with a prime modulus)
+*/
+#[derive(Clone)]
+pub struct EllipticCurve_finite_field {
+    curve: EllipticCurve_generic<ZmodInstance>,
+    field: Zmod,
+    characteristic: BigInt,
+    order: RefCell<Option<BigInt>>
+}
+
+
+impl EllipticCurve_finite_field {
+
+    /*
+    Construct an elliptic curve over F_p from Weierstrass `a`-coefficients.
+     */
+    pub fn new(weierstrass_coefficients: Vec<ZmodInstance>) -> EllipticCurve_finite_field {
+        EllipticCurve_finite_field::from_curve(EllipticCurve_generic::new(weierstrass_coefficients))
+    }
+
+    pub fn from_curve(curve: EllipticCurve_generic<ZmodInstance>) -> EllipticCurve_finite_field {
+        let field = curve.a_invariants().0.class.clone().into_inner();
+        let characteristic = match field.module.clone() {
+            Some(module) => module.value,
+            None => panic!("The field modulus must be set")
+        };
+        if !is_prime(characteristic.to_biguint().unwrap()) {
+            panic!("The modulus must be a prime number");
+        }
+        EllipticCurve_finite_field { curve, field, characteristic, order: RefCell::new(None) }
+    }
+
+    pub fn base_curve(&self) -> EllipticCurve_generic<ZmodInstance> {
+        self.curve.clone()
+    }
+
+    pub fn base_field(&self) -> Zmod {
+        self.field.clone()
+    }
+
+    pub fn characteristic(&self) -> BigInt {
+        self.characteristic.clone()
+    }
+
+    pub fn point(&self, x: ZmodInstance, y: ZmodInstance) -> EllipticCurvePoint<ZmodInstance> {
+        self.curve.point(x, y)
+    }
+
+    pub fn infinity(&self) -> EllipticCurvePoint<ZmodInstance> {
+        self.curve.infinity()
+    }
+
+    /*
+    Returns a point with the given x coordinate if there is one
+     */
+    pub fn lift_x(&self, x: ZmodInstance) -> Option<EllipticCurvePoint<ZmodInstance>> {
+        let p = self.characteristic.clone();
+        if p == BigInt::from(2) {
+            for y in [self.field.zero(), self.field.one()] {
+                if self.curve.is_on_curve(&x, &y) {
+                    return Some(self.curve.point(x, y));
+                }
+            }
+            return None;
+        }
+        // y^2 + (a1 x + a3) y - f(x) = 0  ->  (2y + a1 x + a3)^2 = (a1 x + a3)^2 + 4 f(x)
+        let (a1, a2, a3, a4, a6) = self.curve.a_invariants();
+        let linear = a1.mul(&x).add(&a3);
+        let cubic = x.mul(&x).mul(&x).add(&a2.mul(&x).mul(&x)).add(&a4.mul(&x)).add(&a6);
+        let discriminant = linear.mul(&linear).add(&self.field.apply(BigInt::from(4)).mul(&cubic));
+        match discriminant.sqrt() {
+            Some(root) => {
+                let y = root.sub(&linear).div(&self.field.apply(BigInt::from(2)));
+                Some(self.curve.point(x, y))
+            },
+            None => None
+        }
+    }
+
+    /*
+    SEC 1 (section 2.3.3) octet string of a point: 0x04 || X || Y, or 0x02/0x03 || X when compressed
+    (the prefix carries the parity of y). The point at infinity is the single byte 0x00.
+     */
+    pub fn encode_point(&self, point: &EllipticCurvePoint<ZmodInstance>, compressed: bool) -> Vec<u8> {
+        if point.is_infinity() {
+            return vec![0x00];
+        }
+        let (x, y) = point.xy();
+        let mut bytes = if !compressed {
+            vec![0x04]
+        } else if y.value.value.is_odd() {
+            vec![0x03]
+        } else {
+            vec![0x02]
+        };
+        bytes.extend(self.field_element_to_bytes(&x.value.value));
+        if !compressed {
+            bytes.extend(self.field_element_to_bytes(&y.value.value));
+        }
+        bytes
+    }
+
+    /*
+    SEC 1 (section 2.3.4): None if the octet string is malformed or is not a point of the curve
+     */
+    pub fn decode_point(&self, bytes: &[u8]) -> Option<EllipticCurvePoint<ZmodInstance>> {
+        let length = self.field_byte_length();
+        if bytes == [0x00] {
+            return Some(self.infinity());
+        }
+        if bytes.is_empty() || bytes.len() != 1 + length * (if bytes[0] == 0x04 { 2 } else { 1 }) {
+            return None;
+        }
+        let x = BigUint::from_bytes_be(&bytes[1..length + 1]);
+        if x >= self.characteristic.to_biguint().unwrap() {
+            return None;
+        }
+        let x = self.field.apply(BigInt::from(x));
+
+        match bytes[0] {
+            0x04 => {
+                let y = BigUint::from_bytes_be(&bytes[length + 1..]);
+                if y >= self.characteristic.to_biguint().unwrap() {
+                    return None;
+                }
+                let y = self.field.apply(BigInt::from(y));
+                if !self.curve.is_on_curve(&x, &y) {
+                    return None;
+                }
+                Some(self.point(x, y))
+            },
+            0x02 | 0x03 => {
+                let point = self.lift_x(x)?;
+                let (_, y) = point.xy();
+                if y.value.value.is_odd() == (bytes[0] == 0x03) {
+                    Some(point)
+                } else {
+                    Some(-point)
+                }
+            },
+            _ => None
+        }
+    }
+
+    fn field_byte_length(&self) -> usize {
+        self.characteristic.bits().div_ceil(8) as usize
+    }
+
+    fn field_element_to_bytes(&self, value: &BigInt) -> Vec<u8> {
+        let (_, bytes) = value.to_bytes_be();
+        let mut padded = vec![0u8; self.field_byte_length() - bytes.len()];
+        padded.extend(bytes);
+        padded
+    }
+
+    /*
+    Returns a random affine point of the curve (or the point at infinity when it is the only point)
+     */
+    pub fn random_point(&self) -> EllipticCurvePoint<ZmodInstance> {
+        if self.characteristic <= BigInt::from(3) {
+            let points = self.points();
+            let index = get_random_bigint_with_bounds(BigInt::from(0), BigInt::from(points.len()));
+            return points[usize::try_from(index).unwrap()].clone();
+        }
+        loop {
+            let x = self.field.apply(get_random_bigint_with_bounds(BigInt::from(0), self.characteristic.clone()));
+            if let Some(point) = self.lift_x(x) {
+                // both square roots are equally likely
+                if get_random_bigint_with_bounds(BigInt::from(0), BigInt::from(2)) == BigInt::from(0) {
+                    return -point;
+                }
+                return point;
+            }
+        }
+    }
+
+    /*
+    Lists every point of the curve (point at infinity included). Only meant for small fields.
+     */
+    pub fn points(&self) -> Vec<EllipticCurvePoint<ZmodInstance>> {
+        let mut points = vec![self.infinity()];
+        let mut x = BigInt::from(0);
+        while x < self.characteristic {
+            let x_value = self.field.apply(x.clone());
+            let mut y = BigInt::from(0);
+            while y < self.characteristic {
+                let y_value = self.field.apply(y.clone());
+                if self.curve.is_on_curve(&x_value, &y_value) {
+                    points.push(self.curve.point(x_value.clone(), y_value));
+                }
+                y += 1;
+            }
+            x += 1;
+        }
+        points
+    }
+
+    /*
+    Number of points of the curve over F_p. The algorithm is chosen according to the size of p:
+    naive counting for small p, baby-step giant-step up to 2^64. Beyond that the order is not
+    computed automatically, it must be given with set_order (Schoof is still available explicitly).
+     */
+    pub fn order(&self) -> BigInt {
+        unwrap_or_panic(self.try_order())
+    }
+
+    pub fn try_order(&self) -> Result<BigInt> {
+        if let Some(order) = self.order.borrow().clone() {
+            return Ok(order);
+        }
+        let algorithm = if self.characteristic < BigInt::from(NAIVE_COUNTING_BOUND) {
+            PointCountingAlgorithm::Naive
+        } else if self.characteristic < BigInt::from(BSGS_COUNTING_BOUND) {
+            PointCountingAlgorithm::BabyStepGiantStep
+        } else {
+            return Err(Error::InvalidInput("The order of a curve over a field larger than 2^64 must be given with set_order".to_string()));
+        };
+        let order = self.try_order_with_algorithm(algorithm)?;
+        *self.order.borrow_mut() = Some(order.clone());
+        Ok(order)
+    }
+
+    /*
+    Sets a known group order (e.g. of standardized curves, too large for point counting).
+    The order must lie in the Hasse interval and kill random points.
+     */
+    pub fn set_order(&self, order: BigInt) {
+        unwrap_or_panic(self.try_set_order(order))
+    }
+
+    pub fn try_set_order(&self, order: BigInt) -> Result<()> {
+        let p = self.characteristic.clone();
+        let width: BigInt = (p.clone() * BigInt::from(4)).sqrt();
+        if order < p.clone() + 1 - &width || order > p + 1 + width {
+            return Err(Error::InvalidInput("The order is outside the Hasse interval".to_string()));
+        }
+        for _ in 0..4 {
+            if !(self.random_point() * order.clone()).is_infinity() {
+                return Err(Error::InvalidInput("The value is not the order of the curve".to_string()));
+            }
+        }
+        *self.order.borrow_mut() = Some(order);
+        Ok(())
+    }
+
+    // used for standardized curves whose order is already known
+    pub(crate) fn set_order_unchecked(&self, order: BigInt) {
+        *self.order.borrow_mut() = Some(order);
+    }
+
+    pub fn order_with_algorithm(&self, algorithm: PointCountingAlgorithm) -> BigInt {
+        unwrap_or_panic(self.try_order_with_algorithm(algorithm))
+    }
+
+    pub fn try_order_with_algorithm(&self, algorithm: PointCountingAlgorithm) -> Result<BigInt> {
+        match algorithm {
+            PointCountingAlgorithm::Naive => Ok(self.naive_order()),
+            PointCountingAlgorithm::BabyStepGiantStep => self.bsgs_order(),
+            PointCountingAlgorithm::Schoof => self.schoof_order()
+        }
+    }
+
+    /*
+    t = p + 1 - #E
+     */
+    pub fn trace_of_frobenius(&self) -> BigInt {
+        unwrap_or_panic(self.try_trace_of_frobenius())
+    }
+
+    pub fn try_trace_of_frobenius(&self) -> Result<BigInt> {
+        Ok(self.characteristic.clone() + 1 - self.try_order()?)
+    }
+
+    /*
+    Order of a point, computed from the factorization of the group order
+     */
+    pub fn point_order(&self, point: &EllipticCurvePoint<ZmodInstance>) -> BigInt {
+        unwrap_or_panic(self.try_point_order(point))
+    }
+
+    pub fn try_point_order(&self, point: &EllipticCurvePoint<ZmodInstance>) -> Result<BigInt> {
+        if point.curve.borrow().clone() != self.curve {
+            return Err(Error::InvalidInput("The point is not on the curve".to_string()));
+        }
+        Ok(order_from_multiple(self.try_order()?, |k| (point.clone() * k.clone()).is_infinity()))
+    }
+
+    /*
+    Invariants (n1, n2) such that E(F_p) is isomorphic to Z/n1 x Z/n2 with n2 | n1 and n2 | p - 1.
+    n1 is the exponent of the group, obtained as the lcm of the orders of random points:
+    the result is correct with overwhelming probability.
+     */
+    pub fn abelian_group(&self) -> (BigInt, BigInt) {
+        unwrap_or_panic(self.try_abelian_group())
+    }
+
+    pub fn try_abelian_group(&self) -> Result<(BigInt, BigInt)> {
+        let order = self.try_order()?;
+        let p_minus_one: BigInt = self.characteristic.clone() - 1;
+        let mut exponent = BigInt::from(1);
+        let mut unchanged = 0;
+
+        while exponent != order {
+            let point = self.random_point();
+            let updated = exponent.lcm(&self.try_point_order(&point)?);
+            if updated == exponent {
+                unchanged += 1;
+                let cofactor = order.clone() / exponent.clone();
+                if unchanged >= 40 && exponent.is_multiple_of(&cofactor) && p_minus_one.is_multiple_of(&cofactor) {
+                    break;
+                }
+            } else {
+                exponent = updated;
+                unchanged = 0;
+            }
+        }
+        let cofactor = order / exponent.clone();
+        Ok((exponent, cofactor))
+    }
+
+    /*
+    Short Weierstrass model y^2 = x^3 + a x + b isomorphic to the curve (p > 3):
+    a = -27 c4, b = -54 c6
+     */
+    fn short_weierstrass_model(&self) -> Result<(BigInt, BigInt)> {
+        if self.characteristic <= BigInt::from(3) {
+            return Err(Error::InvalidInput("The short Weierstrass model requires a characteristic greater than 3".to_string()));
+        }
+        let (c4, c6) = self.curve.c_invariants();
+        let p = &self.characteristic;
+        Ok(((BigInt::from(-27) * c4.value.value).mod_floor(p), (BigInt::from(-54) * c6.value.value).mod_floor(p)))
+    }
+
+
+    /*
+        NAIVE COUNTING
+        For odd p the number of points with abscissa x is 1 + legendre((a1 x + a3)^2 + 4 f(x))
+    */
+    fn naive_order(&self) -> BigInt {
+        let p = self.characteristic.clone();
+        if p == BigInt::from(2) {
+            return BigInt::from(self.points().len());
+        }
+        let (a1, a2, a3, a4, a6) = self.curve.a_invariants();
+        let (a1, a2, a3, a4, a6) = (a1.value.value, a2.value.value, a3.value.value, a4.value.value, a6.value.value);
+
+        let mut order = p.clone() + 1;
+        let mut x = BigInt::from(0);
+        while x < p {
+            let linear = &a1 * &x + &a3;
+            let cubic = ((&x + &a2) * &x + &a4) * &x + &a6;
+            order += legendre_symbol(&linear * &linear + cubic * 4, p.clone());
+            x += 1;
+        }
+        order
+    }
+
+
+    /*
+        BABY-STEP GIANT-STEP (Mestre)
+        The orders of random points of the curve and of its quadratic twist are combined until a single
+        value in the Hasse interval [p + 1 - 2 sqrt(p), p + 1 + 2 sqrt(p)] is compatible with both.
+    */
+    fn bsgs_order(&self) -> Result<BigInt> {
+        let p = self.characteristic.clone();
+        if p < BigInt::from(500) {
+            // Mestre's theorem only guarantees termination for p > 229
+            return Ok(self.naive_order());
+        }
+        let (a, b) = self.short_weierstrass_model()?;
+        let mut non_residue = BigInt::from(2);
+        while legendre_symbol(non_residue.clone(), p.clone()) != -1 {
+            non_residue += 1;
+        }
+        let twist_a = (&a * &non_residue * &non_residue).mod_floor(&p);
+        let twist_b = (&b * &non_residue * &non_residue * &non_residue).mod_floor(&p);
+
+        let width: BigInt = (p.clone() * BigInt::from(4)).sqrt();
+        let (lower, upper) = (p.clone() + 1 - &width, p.clone() + 1 + &width);
+        let twist_sum: BigInt = p.clone() * 2 + 2;
+
+        let mut curve_exponent = BigInt::from(1);
+        let mut twist_exponent = BigInt::from(1);
+        for _ in 0..200 {
+            for (coefficient_a, coefficient_b, exponent) in [(&a, &b, &mut curve_exponent), (&twist_a, &twist_b, &mut twist_exponent)] {
+                let point = short_random_point(coefficient_a, coefficient_b, &p);
+                let multiple = bsgs_multiple(&point, coefficient_a, &p, &lower, &upper)?;
+                let point_order = order_from_multiple(multiple, |k| short_mul(&point, k, coefficient_a, &p).is_none());
+                *exponent = exponent.lcm(&point_order);
+            }
+
+            if (upper.clone() - &lower) / &curve_exponent > BigInt::from(10000) {
+                continue;
+            }
+            let mut candidates: Vec<BigInt> = Vec::new();
+            let mut candidate = lower.clone().div_ceil(&curve_exponent) * &curve_exponent;
+            while candidate <= upper {
+                if (twist_sum.clone() - &candidate).is_multiple_of(&twist_exponent) {
+                    candidates.push(candidate.clone());
+                }
+                candidate += &curve_exponent;
+            }
+            if candidates.len() == 1 {
+                return Ok(candidates[0].clone());
+            }
+        }
+        Err(Error::InvalidInput("Baby-step giant-step could not determine the group order".to_string()))
+    }
+
+
+    /*
+        SCHOOF
+        The trace t is computed modulo small primes l until their product exceeds 4 sqrt(p), then recovered
+        with the CRT. Computations modulo l happen in F_p[x] / psi_l(x) on the short Weierstrass model.
+        This is the plain Schoof algorithm without the Elkies/Atkin (SEA) improvements: it takes a few seconds
+        for 40-bit primes and grows quickly beyond 64 bits.
+    */
+    fn schoof_order(&self) -> Result<BigInt> {
+        let p = self.characteristic.clone();
+        let (a, b) = self.short_weierstrass_model()?;
+        let division_polynomials = DivisionPolynomials::new(a, b, self.field.clone());
+
+        // t mod 2: t is even iff the curve has a rational 2-torsion point, i.e. gcd(x^p - x, x^3 + a x + b) != 1
+        let (x, cubic) = (division_polynomials.variable(), &division_polynomials.cubic);
+        let two_torsion = gcd(&sub(&powmod(&x, &p, cubic), &x), cubic);
+        let mut residue = if two_torsion.len() > 1 { BigInt::from(0) } else { BigInt::from(1) };
+        let mut modulus = BigInt::from(2);
+
+        let mut l: u64 = 3;
+        while modulus.clone() * &modulus <= p.clone() * 16 {
+            if BigInt::from(l) != p {
+                let trace = division_polynomials.trace_modulo(l)?;
+                // CRT
+                let l_big = BigInt::from(l);
+                let coefficient = ((trace - &residue) * modular_inverse(modulus.clone(), l_big.clone())).mod_floor(&l_big);
+                residue += coefficient * &modulus;
+                modulus *= l_big;
+            }
+            l += 2;
+            while !is_prime(BigUint::from(l)) {
+                l += 2;
+            }
+        }
+
+        let trace = if residue.clone() * 2 > modulus { residue - modulus } else { residue };
+        Ok(p + 1 - trace)
+    }
+}
+
+
+impl EllipticCurve<ZmodInstance> for EllipticCurve_finite_field {
+    fn discriminant(&self) -> ZmodInstance {
+        self.curve.discriminant()
+    }
+
+    fn b_invariants(&self) -> (ZmodInstance, ZmodInstance, ZmodInstance, ZmodInstance) {
+        self.curve.b_invariants()
+    }
+
+    fn c_invariants(&self) -> (ZmodInstance, ZmodInstance) {
+        self.curve.c_invariants()
+    }
+
+    fn j_invariant(&self) -> ZmodInstance {
+        self.curve.j_invariant()
+    }
+}
+
+
+/*
+    Reduces a multiple m of the order of an element to the exact order.
+    `is_identity(k)` must tell whether k times the element is the identity.
+*/
+fn order_from_multiple<F>(multiple: BigInt, is_identity: F) -> BigInt where F: Fn(&BigInt) -> bool {
+    let mut order = multiple.clone();
+    let factors = num_prime::nt_funcs::factorize(multiple.to_biguint().unwrap());
+    for (prime, exponent) in factors {
+        let prime = BigInt::from(prime);
+        for _ in 0..exponent {
+            let candidate = order.clone() / &prime;
+            if is_identity(&candidate) {
+                order = candidate;
+            } else {
+                break;
+            }
+        }
+    }
+    order
+}
+
+
+/*
+    Affine arithmetic on y^2 = x^3 + a x + b over F_p with plain integers (None is the point at infinity).
+    Used by the counting algorithms, which need a lot of group operations.
+*/
+type ShortPoint = Option<(BigInt, BigInt)>;
+
+fn short_add(first: &ShortPoint, second: &ShortPoint, a: &BigInt, p: &BigInt) -> ShortPoint {
+    let (x1, y1) = match first { Some(point) => point, None => return second.clone() };
+    let (x2, y2) = match second { Some(point) => point, None => return first.clone() };
+
+    let lambda = if x1 == x2 {
+        if (y1 + y2).mod_floor(p) == BigInt::from(0) {
+            return None;
+        }
+        (x1 * x1 * 3 + a) * modular_inverse(y1 * 2, p.clone())
+    } else {
+        (y2 - y1) * modular_inverse(x2 - x1, p.clone())
+    };
+    let lambda = lambda.mod_floor(p);
+    let x3 = (&lambda * &lambda - x1 - x2).mod_floor(p);
+    let y3 = (lambda * (x1 - &x3) - y1).mod_floor(p);
+    Some((x3, y3))
+}
+
+fn short_neg(point: &ShortPoint, p: &BigInt) -> ShortPoint {
+    point.as_ref().map(|(x, y)| (x.clone(), (-y).mod_floor(p)))
+}
+
+fn short_mul(point: &ShortPoint, k: &BigInt, a: &BigInt, p: &BigInt) -> ShortPoint {
+    let mut result: ShortPoint = None;
+    for i in (0..k.bits()).rev() {
+        result = short_add(&result, &result, a, p);
+        if k.bit(i) {
+            result = short_add(&result, point, a, p);
+        }
+    }
+    result
+}
+
+fn short_random_point(a: &BigInt, b: &BigInt, p: &BigInt) -> ShortPoint {
+    loop {
+        let x = get_random_bigint_with_bounds(BigInt::from(0), p.clone());
+        let rhs = ((&x * &x + a) * &x + b).mod_floor(p);
+        if let Some(y) = modular_sqrt(rhs, p.clone()) {
+            return Some((x, y));
+        }
+    }
+}
+
+/*
+    Finds a multiple m in [lower, upper + sqrt(upper - lower)] of the order of the point, assuming there is one in [lower, upper].
+    Baby steps j P (0 <= j <= s), giant steps (lower + i s) P: (lower + i s) P = -j P gives m = lower + i s + j.
+*/
+fn bsgs_multiple(point: &ShortPoint, a: &BigInt, p: &BigInt, lower: &BigInt, upper: &BigInt) -> Result<BigInt> {
+    let steps = (upper - lower).sqrt() + 1;
+    let steps_count = u64::try_from(&steps).unwrap();
+
+    let mut baby_steps: HashMap<ShortPoint, u64> = HashMap::new();
+    let mut current: ShortPoint = None;
+    for j in 0..=steps_count {
+        baby_steps.entry(current.clone()).or_insert(j);
+        current = short_add(&current, point, a, p);
+    }
+
+    let giant_step = short_mul(point, &steps, a, p);
+    let mut current = short_mul(point, lower, a, p);
+    for i in 0..=steps_count {
+        if let Some(j) = baby_steps.get(&short_neg(&current, p)) {
+            return Ok(lower + &steps * i + *j);
+        }
+        current = short_add(&current, &giant_step, a, p);
+    }
+    Err(Error::InvalidInput("No multiple of the point order in the Hasse interval".to_string()))
+}
+
+
+/*
+    Division polynomials of y^2 = x^3 + a x + b, stored without the factor y of the even ones
+    (f_n = psi_n for n odd, f_n = psi_n / y for n even). The polynomials are Zmod coefficient
+    vectors handled with the arithmetic of poly::gcd.
+*/
+struct DivisionPolynomials {
+    field: Zmod,
+    p: BigInt,
+    cubic: Vec<ZmodInstance>,
+    polynomials: RefCell<Vec<Vec<ZmodInstance>>>
+}
+
+impl DivisionPolynomials {
+    fn new(a: BigInt, b: BigInt, field: Zmod) -> DivisionPolynomials {
+        let p = field.module.clone().unwrap().value;
+        let polynomial = |coefficients: Vec<BigInt>| trim(&coefficients.into_iter().map(|c| field.apply(c)).collect::<Vec<ZmodInstance>>());
+        let f0: Vec<ZmodInstance> = Vec::new();
+        let f1 = polynomial(vec![BigInt::from(1)]);
+        let f2 = polynomial(vec![BigInt::from(2)]);
+        // 3x^4 + 6a x^2 + 12b x - a^2
+        let f3 = polynomial(vec![-(&a * &a), &b * 12, &a * 6, BigInt::from(0), BigInt::from(3)]);
+        // 4(x^6 + 5a x^4 + 20b x^3 - 5a^2 x^2 - 4ab x - 8b^2 - a^3)
+        let f4 = polynomial(vec![
+            (-(&b * &b) * 8 - &a * &a * &a) * 4,
+            -(&a * &b) * 16,
+            -(&a * &a) * 20,
+            &b * 80,
+            &a * 20,
+            BigInt::from(0),
+            BigInt::from(4)
+        ]);
+        let cubic = polynomial(vec![b, a, BigInt::from(0), BigInt::from(1)]);
+        DivisionPolynomials { field, p, cubic, polynomials: RefCell::new(vec![f0, f1, f2, f3, f4]) }
+    }
+
+    fn constant(&self, value: BigInt) -> Vec<ZmodInstance> {
+        trim(&[self.field.apply(value)])
+    }
+
+    // f_n for n >= -1 (f_-1 = -1)
+    fn get(&self, n: i64) -> Vec<ZmodInstance> {
+        if n == -1 {
+            return self.constant(BigInt::from(-1));
+        }
+        let n = n as usize;
+        while self.polynomials.borrow().len() <= n {
+            let index = self.polynomials.borrow().len();
+            let f = self.polynomials.borrow();
+            let m = index / 2;
+            let cubic_square = mul(&self.cubic, &self.cubic);
+            let next = if index % 2 == 1 {
+                // f_{2m+1} = f_{m+2} f_m^3 - f_{m-1} f_{m+1}^3, with a factor F^2 on the term built from even indices
+                let first = mul(&f[m + 2], &mul(&f[m], &mul(&f[m], &f[m])));
+                let second = mul(&f[m - 1], &mul(&f[m + 1], &mul(&f[m + 1], &f[m + 1])));
+                if m.is_even() {
+                    sub(&mul(&cubic_square, &first), &second)
+                } else {
+                    sub(&first, &mul(&cubic_square, &second))
+                }
+            } else {
+                // f_{2m} = f_m (f_{m+2} f_{m-1}^2 - f_{m-2} f_{m+1}^2) / 2
+                let first = mul(&f[m + 2], &mul(&f[m - 1], &f[m - 1]));
+                let second = mul(&f[m - 2], &mul(&f[m + 1], &f[m + 1]));
+                let half = self.field.apply(modular_inverse(BigInt::from(2), self.p.clone()));
+                trim(&scale(&mul(&f[m], &sub(&first, &second)), &half))
+            };
+            drop(f);
+            self.polynomials.borrow_mut().push(next);
+        }
+        self.polynomials.borrow()[n].clone()
+    }
+
+    /*
+    [n](x, y) = (X_n(x), Y_n(x) y) as elements of F_p[x] / h, with 1 <= n < l so that the denominators are invertible
+     */
+    fn multiple(&self, n: i64, h: &[ZmodInstance]) -> Result<(Vec<ZmodInstance>, Vec<ZmodInstance>)> {
+        let (previous, current, next) = (self.get(n - 1), self.get(n), self.get(n + 1));
+        let numerator_y = sub(
+            &mul(&self.get(n + 2), &mul(&previous, &previous)),
+            &mul(&self.get(n - 2), &mul(&next, &next))
+        );
+        let current_square = mul(&current, &current);
+        let current_cube = mul(&mul(&current_square, &current), &self.constant(BigInt::from(4)));
+
+        let x = self.variable();
+        let (x_numerator, x_denominator, y_denominator) = if n % 2 == 0 {
+            (mul(&previous, &next), mul(&self.cubic, &current_square), mul(&current_cube, &mul(&self.cubic, &self.cubic)))
+        } else {
+            (mul(&self.cubic, &mul(&previous, &next)), current_square, current_cube)
+        };
+        let x_n = sub(&x, &mulmod(&x_numerator, &invmod(&x_denominator, h)?, h));
+        let y_n = mulmod(&numerator_y, &invmod(&y_denominator, h)?, h);
+        Ok((divmod(&x_n, h).1, y_n))
+    }
+
+    /*
+    Sum of the points (X1, Y1 y) and (X2, Y2 y) with coordinates in F_p[x] / h: lambda = y L, x3 = F L^2 - X1 - X2
+     */
+    fn add_points(&self, first: &(Vec<ZmodInstance>, Vec<ZmodInstance>), second: &(Vec<ZmodInstance>, Vec<ZmodInstance>), h: &[ZmodInstance]) -> Result<(Vec<ZmodInstance>, Vec<ZmodInstance>)> {
+        let ((x1, y1), (x2, y2)) = (first, second);
+        let slope = if x1 == x2 {
+            // doubling: L = (3 X^2 + a) / (2 Y F)
+            let numerator = add(&mul(&mulmod(x1, x1, h), &self.constant(BigInt::from(3))), &self.cubic[1..2]);
+            let denominator = mulmod(&mul(y1, &self.constant(BigInt::from(2))), &self.cubic, h);
+            mulmod(&numerator, &invmod(&denominator, h)?, h)
+        } else {
+            mulmod(&sub(y1, y2), &invmod(&sub(x1, x2), h)?, h)
+        };
+        let x3 = sub(&sub(&mulmod(&self.cubic, &mulmod(&slope, &slope, h), h), x1), x2);
+        let y3 = sub(&mulmod(&slope, &sub(x1, &x3), h), y1);
+        Ok((x3, y3))
+    }
+
+    fn variable(&self) -> Vec<ZmodInstance> {
+        vec![self.field.apply(BigInt::from(0)), self.field.apply(BigInt::from(1))]
+    }
+
+    /*
+    Trace of Frobenius modulo the odd prime l != p.
+    Frobenius satisfies pi^2 - t pi + p = 0 on the l-torsion E[l]: t mod l is the tau with pi^2 + p = tau pi.
+    An error is returned, rather than a wrong residue, when a denominator is not invertible modulo psi_l or no tau matches
+     */
+    fn trace_modulo(&self, l: u64) -> Result<BigInt> {
+        let p = &self.p;
+        let h = monic(&self.get(l as i64));
+        let q = (p % l).try_into().unwrap_or(0i64);
+
+        let x = self.variable();
+        let xp = powmod(&x, p, &h);
+        let yp = powmod(&self.cubic, &((p - 1) / 2), &h);
+        let xpp = powmod(&xp, p, &h);
+        let ypp = powmod(&yp, &(p + 1), &h);
+
+        let (xq, yq) = self.multiple(q, &h)?;
+        let difference = sub(&xpp, &xq);
+
+        if gcd(&difference, &h).len() == 1 {
+            // pi^2 P + q P = (x3, y3 y) through the chord law, then look for tau with x(tau pi P) = x3
+            let lambda = mulmod(&sub(&ypp, &yq), &invmod(&difference, &h)?, &h);
+            let x3 = sub(&sub(&mulmod(&self.cubic, &mulmod(&lambda, &lambda, &h), &h), &xpp), &xq);
+            let y3 = sub(&mulmod(&lambda, &sub(&xpp, &x3), &h), &ypp);
+
+            // tau pi P computed with the group law from pi P = (xp, yp y)
+            let mut multiple = (xp.clone(), yp.clone());
+            for tau in 1..=((l as i64 - 1) / 2) {
+                if tau > 1 {
+                    multiple = self.add_points(&multiple, &(xp.clone(), yp.clone()), &h)?;
+                }
+                if multiple.0 == x3 {
+                    if multiple.1 == y3 {
+                        return Ok(BigInt::from(tau));
+                    }
+                    return Ok(BigInt::from(l as i64 - tau));
+                }
+            }
+            // t is not 0 mod l here, one of the tau must match
+            return Err(Error::InvalidInput(format!("The trace of Frobenius modulo {} could not be determined", l)));
+        }
+
+        // pi^2 P = +-q P for some P in E[l]
+        // t = 0 mod l when q is not a square or pi does not act as +-w on E[l]
+        let w = match (1..l as i64).find(|w| (w * w) % (l as i64) == q) {
+            Some(w) => w,
+            None => return Ok(BigInt::from(0))
+        };
+        let (xw, yw) = self.multiple(w, &h)?;
+        let common = gcd(&sub(&xp, &xw), &h);
+        if common.len() == 1 {
+            return Ok(BigInt::from(0));
+        }
+        if gcd(&sub(&yp, &yw), &common).len() > 1 {
+            Ok(BigInt::from((2 * w) % l as i64))
+        } else {
+            Ok(BigInt::from((l as i64 - (2 * w) % l as i64) % l as i64))
+        }
+    }
+}
@@ -1,14 +1,23 @@
 use num_bigint::BigInt;
 
-use crate::numbers::{classes::ZZ::ZZ, numbers::{ClassInstance, Instance, Operand}};
+use crate::error::{unwrap_or_panic, Error, Result};
+use crate::numbers::numbers::{integer_in_class, ClassInstance, Instance, Operand};
 
 use super::elliptic_curve_factory::EllipticCurve;
+use super::elliptic_curve_point::EllipticCurvePoint;
 
 #[derive(Clone)]
 pub struct EllipticCurve_generic<T> {
     a_invariants: (T, T, T, T, T)
 }
 
+impl<T> PartialEq for EllipticCurve_generic<T> where T: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.a_invariants == other.a_invariants
+    }
+}
+impl<T> Eq for EllipticCurve_generic<T> where T: PartialEq {}
+
 
 impl<T> EllipticCurve_generic<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static{
 
@@ -16,34 +25,98 @@ impl<T> EllipticCurve_generic<T> where T: Instance + Operand + PartialEq + Class
     Construct an elliptic curve from Weierstrass `a`-coefficients.
      */
     pub fn new(weierstrass_coefficients: Vec<T>) -> EllipticCurve_generic<T> {
+        unwrap_or_panic(EllipticCurve_generic::try_new(weierstrass_coefficients))
+    }
+
+    pub fn try_new(weierstrass_coefficients: Vec<T>) -> Result<EllipticCurve_generic<T>> {
         if weierstrass_coefficients.len() != 5 {
-            panic!("Wrong number of coefficients");
+            return Err(Error::InvalidInput("Wrong number of coefficients".to_string()));
         }
         let curve = EllipticCurve_generic { a_invariants: (weierstrass_coefficients[0].clone(), weierstrass_coefficients[1].clone(), weierstrass_coefficients[2].clone(), weierstrass_coefficients[3].clone(), weierstrass_coefficients[4].clone()) };
-        if curve.discriminant() == weierstrass_coefficients[0].get_class().zero().as_any().downcast_ref::<T>().unwrap().clone() {
-            panic!("It is a singular curve. The discriminant is ZERO");
+        if curve.discriminant() == curve.zero() {
+            return Err(Error::InvalidInput("It is a singular curve. The discriminant is ZERO".to_string()));
         }
 
-        curve
+        Ok(curve)
+    }
+
+    pub fn a_invariants(&self) -> (T, T, T, T, T) {
+        self.a_invariants.clone()
+    }
+
+    /*
+    True if the curve is in short Weierstrass form y^2 = x^3 + a4*x + a6 (a1 = a2 = a3 = 0)
+     */
+    pub fn is_short_weierstrass(&self) -> bool {
+        let zero = self.zero();
+        self.a_invariants.0 == zero && self.a_invariants.1 == zero && self.a_invariants.2 == zero
+    }
+
+    /*
+    Checks whether the affine point (x, y) satisfies
+    y^2 + a1*x*y + a3*y = x^3 + a2*x^2 + a4*x + a6
+     */
+    pub fn is_on_curve(&self, x: &T, y: &T) -> bool {
+        let (a1, a2, a3, a4, a6) = self.a_invariants.clone();
+        let lhs = y.mul(y).add(&a1.mul(x).mul(y)).add(&a3.mul(y));
+        let rhs = x.mul(x).mul(x).add(&a2.mul(x).mul(x)).add(&a4.mul(x)).add(&a6);
+        lhs == rhs
+    }
+
+    /*
+    Returns the affine point (x, y) of the curve. Panics if the point does not lie on the curve.
+     */
+    pub fn point(&self, x: T, y: T) -> EllipticCurvePoint<T> {
+        EllipticCurvePoint::new_affine(self, x, y)
+    }
+
+    pub fn infinity(&self) -> EllipticCurvePoint<T> {
+        EllipticCurvePoint::infinity(self)
+    }
+
+    pub(crate) fn zero(&self) -> T {
+        self.a_invariants.0.get_class().zero().as_any().downcast_ref::<T>().unwrap().clone()
+    }
+
+    pub(crate) fn one(&self) -> T {
+        self.a_invariants.0.get_class().one().as_any().downcast_ref::<T>().unwrap().clone()
+    }
+
+    pub(crate) fn constant(&self, n: i64) -> T {
+        integer_in_class(&self.a_invariants.0, BigInt::from(n))
     }
 }
 
 
-impl<T> EllipticCurve<T> for EllipticCurve_generic<T> where T: Instance + Operand + PartialEq + Clone + 'static{
+impl<T> EllipticCurve<T> for EllipticCurve_generic<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static{
     fn discriminant(&self) -> T  {
         let (b2, b4, b6, b8) = self.b_invariants();
-        return b2.clone().mul(&b2).mul(&b8).neg().sub(&b4.clone().mul(&b4).mul(&b4).mul(&ZZ::new().new_instance(BigInt::from(8)).as_any().downcast_ref::<T>().unwrap())).sub(&b6.clone().mul(&b6).mul(&ZZ::new().new_instance(BigInt::from(27)).as_any().downcast_ref::<T>().unwrap())).add(&b2.clone().mul(&b4).mul(&b6).mul(ZZ::new().new_instance(BigInt::from(9)).as_any().downcast_ref::<T>().unwrap()));
+        return b2.clone().mul(&b2).mul(&b8).neg().sub(&b4.clone().mul(&b4).mul(&b4).mul(&self.constant(8))).sub(&b6.clone().mul(&b6).mul(&self.constant(27))).add(&b2.clone().mul(&b4).mul(&b6).mul(&self.constant(9)));
     }
 
     fn b_invariants(&self) -> (T, T, T, T)  {
         let (a1, a2, a3, a4, a6) = self.a_invariants.clone();
         return (
-            (a1.clone().mul(&a1)).add(&(a2.clone().mul(&ZZ::new().new_instance(BigInt::from(4)).as_any().downcast_ref::<T>().unwrap()))),
-            (a1.clone().mul(&a3)).add(&(a4.clone().mul(&ZZ::new().new_instance(BigInt::from(2)).as_any().downcast_ref::<T>().unwrap()))),
-            (a3.clone().mul(&a3)).add(&(a6.clone().mul(&ZZ::new().new_instance(BigInt::from(4)).as_any().downcast_ref::<T>().unwrap()))),
-            (a1.clone().mul(&a1).mul(&a6)).add(&a2.clone().mul(&a6).mul(&(ZZ::new().new_instance(BigInt::from(4)).as_any().downcast_ref::<T>().unwrap()))).sub(&a1.clone().mul(&a3).mul(&a4)).add(&a2.clone().mul(&a3).mul(&a3)).sub(&a4.clone().mul(&a4))
+            (a1.clone().mul(&a1)).add(&(a2.clone().mul(&self.constant(4)))),
+            (a1.clone().mul(&a3)).add(&(a4.clone().mul(&self.constant(2)))),
+            (a3.clone().mul(&a3)).add(&(a6.clone().mul(&self.constant(4)))),
+            (a1.clone().mul(&a1).mul(&a6)).add(&a2.clone().mul(&a6).mul(&self.constant(4))).sub(&a1.clone().mul(&a3).mul(&a4)).add(&a2.clone().mul(&a3).mul(&a3)).sub(&a4.clone().mul(&a4))
         )
     }
-}
 
+    fn c_invariants(&self) -> (T, T) {
+        let (b2, b4, b6, _) = self.b_invariants();
+        (
+            b2.mul(&b2).sub(&self.constant(24).mul(&b4)),
+            b2.mul(&b2).mul(&b2).neg().add(&self.constant(36).mul(&b2).mul(&b4)).sub(&self.constant(216).mul(&b6))
+        )
+    }
 
+    /*
+    j = c4^3 / discriminant
+     */
+    fn j_invariant(&self) -> T {
+        let (c4, _) = self.c_invariants();
+        c4.mul(&c4).mul(&c4).div(&self.discriminant())
+    }
+}
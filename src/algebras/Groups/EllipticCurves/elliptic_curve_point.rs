@@ -0,0 +1,326 @@
+use num_bigint::BigInt;
+use std::cell::RefCell;
+use std::fmt::Display;
+
+use crate::numbers::instances::ZZ_instance::ZZinstance;
+use crate::numbers::numbers::{ClassInstance, Instance, Operand};
+
+use super::elliptic_curve_generic::EllipticCurve_generic;
+use crate::error::{unwrap_or_panic, Error, Result};
+
+/*
+    ELLIPTIC CURVE POINT
+
+    Points are stored in projective coordinates (X : Y : Z), the affine point being (X/Z, Y/Z).
+    The point at infinity is represented by (0 : 1 : 0).
+*/
+#[derive(Clone)]
+pub struct EllipticCurvePoint<T> {
+    pub curve: RefCell<EllipticCurve_generic<T>>,
+    pub x: T,
+    pub y: T,
+    pub z: T
+}
+
+
+impl<T> EllipticCurvePoint<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static {
+
+    /*
+    Builds the affine point (x, y). Panics if the point does not lie on the curve.
+     */
+    pub fn new_affine(curve: &EllipticCurve_generic<T>, x: T, y: T) -> EllipticCurvePoint<T> {
+        unwrap_or_panic(EllipticCurvePoint::try_new_affine(curve, x, y))
+    }
+
+    pub fn try_new_affine(curve: &EllipticCurve_generic<T>, x: T, y: T) -> Result<EllipticCurvePoint<T>> {
+        if !curve.is_on_curve(&x, &y) {
+            return Err(Error::InvalidInput("The point is not on the curve".to_string()));
+        }
+        Ok(EllipticCurvePoint { curve: RefCell::new(curve.clone()), x, y, z: curve.one() })
+    }
+
+    /*
+    Builds the projective point (x : y : z). Panics if the point does not lie on the curve.
+     */
+    pub fn new_projective(curve: &EllipticCurve_generic<T>, x: T, y: T, z: T) -> EllipticCurvePoint<T> {
+        unwrap_or_panic(EllipticCurvePoint::try_new_projective(curve, x, y, z))
+    }
+
+    pub fn try_new_projective(curve: &EllipticCurve_generic<T>, x: T, y: T, z: T) -> Result<EllipticCurvePoint<T>> {
+        if z == curve.zero() {
+            if x != curve.zero() || y == curve.zero() {
+                return Err(Error::InvalidInput("The point is not on the curve".to_string()));
+            }
+            return Ok(EllipticCurvePoint::infinity(curve));
+        }
+        let affine_x = x.div(&z);
+        let affine_y = y.div(&z);
+        if !curve.is_on_curve(&affine_x, &affine_y) {
+            return Err(Error::InvalidInput("The point is not on the curve".to_string()));
+        }
+        Ok(EllipticCurvePoint { curve: RefCell::new(curve.clone()), x, y, z })
+    }
+
+    pub fn infinity(curve: &EllipticCurve_generic<T>) -> EllipticCurvePoint<T> {
+        EllipticCurvePoint { curve: RefCell::new(curve.clone()), x: curve.zero(), y: curve.one(), z: curve.zero() }
+    }
+
+    pub fn get_curve(&self) -> EllipticCurve_generic<T> {
+        self.curve.clone().into_inner()
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.z == self.curve.borrow().zero()
+    }
+
+    /*
+    Returns the affine coordinates (x, y). Panics on the point at infinity.
+     */
+    pub fn xy(&self) -> (T, T) {
+        unwrap_or_panic(self.try_xy())
+    }
+
+    pub fn try_xy(&self) -> Result<(T, T)> {
+        if self.is_infinity() {
+            return Err(Error::InvalidInput("The point at infinity has no affine coordinates".to_string()));
+        }
+        Ok((self.x.div(&self.z), self.y.div(&self.z)))
+    }
+
+    pub fn projective_coordinates(&self) -> (T, T, T) {
+        (self.x.clone(), self.y.clone(), self.z.clone())
+    }
+
+    /*
+    Returns the same point normalized so that Z = 1 (or (0 : 1 : 0) for the point at infinity)
+     */
+    pub fn to_affine(&self) -> EllipticCurvePoint<T> {
+        let curve = self.get_curve();
+        if self.is_infinity() {
+            return EllipticCurvePoint::infinity(&curve);
+        }
+        let (x, y) = self.xy();
+        EllipticCurvePoint { curve: RefCell::new(curve.clone()), x, y, z: curve.one() }
+    }
+
+    pub fn double(&self) -> EllipticCurvePoint<T> {
+        let curve = self.get_curve();
+        if self.is_infinity() {
+            return self.clone();
+        }
+        if curve.is_short_weierstrass() {
+            self.projective_double(&curve)
+        } else {
+            self.affine_add(self, &curve)
+        }
+    }
+
+    /*
+    Scalar multiplication through the Montgomery ladder (negative scalars multiply the opposite point)
+     */
+    pub fn scalar_mul(&self, k: &BigInt) -> EllipticCurvePoint<T> {
+        if *k < BigInt::from(0) {
+            return self.negate().scalar_mul(&(-k));
+        }
+
+        let mut r0 = EllipticCurvePoint::infinity(&self.get_curve());
+        let mut r1 = self.clone();
+        for i in (0..k.bits()).rev() {
+            if k.bit(i) {
+                r0 = r0.add_point(&r1);
+                r1 = r1.double();
+            } else {
+                r1 = r0.add_point(&r1);
+                r0 = r0.double();
+            }
+        }
+        r0
+    }
+
+    /*
+    Sum of two points, an error if they are on different curves
+     */
+    pub fn try_add(&self, other: &EllipticCurvePoint<T>) -> Result<EllipticCurvePoint<T>> {
+        if self.curve != other.curve {
+            return Err(Error::IncompatibleOperands("The points are not on the same curve".to_string()));
+        }
+        Ok(self.add_point(other))
+    }
+
+    fn add_point(&self, other: &EllipticCurvePoint<T>) -> EllipticCurvePoint<T> {
+        if self.curve != other.curve {
+            panic!("The points are not on the same curve");
+        }
+        if self.is_infinity() {
+            return other.clone();
+        }
+        if other.is_infinity() {
+            return self.clone();
+        }
+        let curve = self.get_curve();
+        if curve.is_short_weierstrass() {
+            self.projective_add(other, &curve)
+        } else {
+            self.affine_add(other, &curve)
+        }
+    }
+
+    fn negate(&self) -> EllipticCurvePoint<T> {
+        let curve = self.get_curve();
+        if self.is_infinity() {
+            return self.clone();
+        }
+        let (a1, _, a3, _, _) = curve.a_invariants();
+        let y = self.y.neg().sub(&a1.mul(&self.x)).sub(&a3.mul(&self.z));
+        EllipticCurvePoint { curve: RefCell::new(curve), x: self.x.clone(), y, z: self.z.clone() }
+    }
+
+    /*
+    Projective addition on y^2 = x^3 + a4*x + a6 (both points finite)
+     */
+    fn projective_add(&self, other: &EllipticCurvePoint<T>, curve: &EllipticCurve_generic<T>) -> EllipticCurvePoint<T> {
+        let u = other.y.mul(&self.z).sub(&self.y.mul(&other.z));
+        let v = other.x.mul(&self.z).sub(&self.x.mul(&other.z));
+        if v == curve.zero() {
+            if u == curve.zero() {
+                return self.projective_double(curve);
+            }
+            return EllipticCurvePoint::infinity(curve);
+        }
+
+        let uu = u.mul(&u);
+        let vv = v.mul(&v);
+        let vvv = v.mul(&vv);
+        let z1z2 = self.z.mul(&other.z);
+        let r = vv.mul(&self.x).mul(&other.z);
+        let a = uu.mul(&z1z2).sub(&vvv).sub(&r.add(&r));
+
+        let x3 = v.mul(&a);
+        let y3 = u.mul(&r.sub(&a)).sub(&vvv.mul(&self.y).mul(&other.z));
+        let z3 = vvv.mul(&z1z2);
+        EllipticCurvePoint { curve: RefCell::new(curve.clone()), x: x3, y: y3, z: z3 }
+    }
+
+    /*
+    Projective doubling on y^2 = x^3 + a4*x + a6 (finite point)
+     */
+    fn projective_double(&self, curve: &EllipticCurve_generic<T>) -> EllipticCurvePoint<T> {
+        if self.y == curve.zero() {
+            return EllipticCurvePoint::infinity(curve);
+        }
+        let (_, _, _, a4, _) = curve.a_invariants();
+
+        let w = a4.mul(&self.z).mul(&self.z).add(&curve.constant(3).mul(&self.x).mul(&self.x));
+        let s = self.y.mul(&self.z);
+        let b = self.x.mul(&self.y).mul(&s);
+        let h = w.mul(&w).sub(&curve.constant(8).mul(&b));
+
+        let x3 = curve.constant(2).mul(&h).mul(&s);
+        let y3 = w.mul(&curve.constant(4).mul(&b).sub(&h)).sub(&curve.constant(8).mul(&self.y).mul(&self.y).mul(&s).mul(&s));
+        let z3 = curve.constant(8).mul(&s).mul(&s).mul(&s);
+        EllipticCurvePoint { curve: RefCell::new(curve.clone()), x: x3, y: y3, z: z3 }
+    }
+
+    /*
+    Chord and tangent law on the general Weierstrass equation (both points finite).
+    Used for curves that are not in short Weierstrass form, the result is normalized to Z = 1.
+     */
+    fn affine_add(&self, other: &EllipticCurvePoint<T>, curve: &EllipticCurve_generic<T>) -> EllipticCurvePoint<T> {
+        let (a1, a2, a3, a4, a6) = curve.a_invariants();
+        let (x1, y1) = self.xy();
+        let (x2, y2) = other.xy();
+
+        let (lambda, nu) = if x1 == x2 {
+            // P = -Q
+            if y1.add(&y2).add(&a1.mul(&x2)).add(&a3) == curve.zero() {
+                return EllipticCurvePoint::infinity(curve);
+            }
+            // P = Q
+            let denominator = curve.constant(2).mul(&y1).add(&a1.mul(&x1)).add(&a3);
+            let lambda = curve.constant(3).mul(&x1).mul(&x1).add(&curve.constant(2).mul(&a2).mul(&x1)).add(&a4).sub(&a1.mul(&y1)).div(&denominator);
+            let nu = x1.mul(&x1).mul(&x1).neg().add(&a4.mul(&x1)).add(&curve.constant(2).mul(&a6)).sub(&a3.mul(&y1)).div(&denominator);
+            (lambda, nu)
+        } else {
+            let denominator = x2.sub(&x1);
+            let lambda = y2.sub(&y1).div(&denominator);
+            let nu = y1.mul(&x2).sub(&y2.mul(&x1)).div(&denominator);
+            (lambda, nu)
+        };
+
+        let x3 = lambda.mul(&lambda).add(&a1.mul(&lambda)).sub(&a2).sub(&x1).sub(&x2);
+        let y3 = lambda.add(&a1).mul(&x3).neg().sub(&nu).sub(&a3);
+        EllipticCurvePoint { curve: RefCell::new(curve.clone()), x: x3, y: y3, z: curve.one() }
+    }
+}
+
+
+// ------------- OPERATIONS ---------------------
+/*
+    NEGATION
+*/
+impl<T> std::ops::Neg for EllipticCurvePoint<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static {
+    type Output = EllipticCurvePoint<T>;
+    fn neg(self) -> EllipticCurvePoint<T> {
+        EllipticCurvePoint::negate(&self)
+    }
+}
+
+/*
+    SUM
+*/
+impl<T> std::ops::Add<EllipticCurvePoint<T>> for EllipticCurvePoint<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static {
+    type Output = EllipticCurvePoint<T>;
+    fn add(self, rhs: EllipticCurvePoint<T>) -> EllipticCurvePoint<T> {
+        EllipticCurvePoint::add_point(&self, &rhs)
+    }
+}
+
+/*
+    SUBTRACTION
+*/
+impl<T> std::ops::Sub<EllipticCurvePoint<T>> for EllipticCurvePoint<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static {
+    type Output = EllipticCurvePoint<T>;
+    fn sub(self, rhs: EllipticCurvePoint<T>) -> EllipticCurvePoint<T> {
+        EllipticCurvePoint::add_point(&self, &EllipticCurvePoint::negate(&rhs))
+    }
+}
+
+/*
+    SCALAR MULTIPLICATION
+*/
+impl<T> std::ops::Mul<BigInt> for EllipticCurvePoint<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static {
+    type Output = EllipticCurvePoint<T>;
+    fn mul(self, rhs: BigInt) -> EllipticCurvePoint<T> {
+        self.scalar_mul(&rhs)
+    }
+}
+
+impl<T> std::ops::Mul<ZZinstance> for EllipticCurvePoint<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static {
+    type Output = EllipticCurvePoint<T>;
+    fn mul(self, rhs: ZZinstance) -> EllipticCurvePoint<T> {
+        self.scalar_mul(&rhs.value)
+    }
+}
+
+
+// COMPARISON
+impl<T> PartialEq for EllipticCurvePoint<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static {
+    fn eq(&self, other: &Self) -> bool {
+        if self.curve != other.curve {
+            return false;
+        }
+        if self.is_infinity() || other.is_infinity() {
+            return self.is_infinity() && other.is_infinity();
+        }
+        self.x.mul(&other.z) == other.x.mul(&self.z) && self.y.mul(&other.z) == other.y.mul(&self.z)
+    }
+}
+impl<T> Eq for EllipticCurvePoint<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + 'static {}
+
+
+impl<T> std::fmt::Display for EllipticCurvePoint<T> where T: Instance + Operand + PartialEq + ClassInstance + Clone + Display + 'static {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let point = self.to_affine();
+        write!(f, "({0} : {1} : {2})", point.x, point.y, point.z)
+    }
+}
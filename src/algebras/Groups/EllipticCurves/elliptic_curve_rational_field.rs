@@ -0,0 +1,667 @@
+use std::fmt;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+use crate::algebras::FiniteField::classes::Zmod::Zmod;
+use crate::algebras::FiniteField::instances::Zmod_instance::ZmodInstance;
+use crate::arith::primes::is_prime;
+use crate::error::{unwrap_or_panic, Error, Result};
+use crate::numbers::classes::QQ::QQ;
+use crate::numbers::classes::ZZ::ZZ;
+use crate::numbers::instances::QQ_instance::QQinstance;
+use crate::numbers::numbers::Class;
+use crate::poly::gcd::{gcd, powmod, sub};
+use crate::utilities::utils::{legendre_symbol, modular_inverse};
+
+use super::elliptic_curve_factory::EllipticCurve;
+use super::elliptic_curve_finite_field::EllipticCurve_finite_field;
+use super::elliptic_curve_generic::EllipticCurve_generic;
+use super::elliptic_curve_point::EllipticCurvePoint;
+
+
+/*
+    Kodaira symbol of the special fiber of the Neron model at a prime
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KodairaSymbol {
+    I(u64),
+    II,
+    III,
+    IV,
+    IStar(u64),
+    IVStar,
+    IIIStar,
+    IIStar
+}
+
+impl fmt::Display for KodairaSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KodairaSymbol::I(n) => write!(f, "I{}", n),
+            KodairaSymbol::II => write!(f, "II"),
+            KodairaSymbol::III => write!(f, "III"),
+            KodairaSymbol::IV => write!(f, "IV"),
+            KodairaSymbol::IStar(n) => write!(f, "I{}*", n),
+            KodairaSymbol::IVStar => write!(f, "IV*"),
+            KodairaSymbol::IIIStar => write!(f, "III*"),
+            KodairaSymbol::IIStar => write!(f, "II*")
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReductionType {
+    Good,
+    SplitMultiplicative,
+    NonSplitMultiplicative,
+    Additive
+}
+
+/*
+    Output of Tate's algorithm at the prime p
+*/
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LocalData {
+    pub prime: BigInt,
+    pub reduction_type: ReductionType,
+    pub kodaira_symbol: KodairaSymbol,
+    pub conductor_exponent: u64,
+    pub tamagawa_number: u64,
+    pub discriminant_valuation: u64 // of a model minimal at p
+}
+
+
+/*
+    ELLIPTIC CURVE over the rational numbers QQ
+*/
+#[derive(Clone)]
+pub struct EllipticCurve_rational_field {
+    curve: EllipticCurve_generic<QQinstance>
+}
+
+impl EllipticCurve_rational_field {
+
+    /*
+    Construct an elliptic curve over QQ from Weierstrass `a`-coefficients.
+     */
+    pub fn new(weierstrass_coefficients: Vec<QQinstance>) -> EllipticCurve_rational_field {
+        EllipticCurve_rational_field { curve: EllipticCurve_generic::new(weierstrass_coefficients) }
+    }
+
+    pub fn from_curve(curve: EllipticCurve_generic<QQinstance>) -> EllipticCurve_rational_field {
+        EllipticCurve_rational_field { curve }
+    }
+
+    pub fn base_curve(&self) -> EllipticCurve_generic<QQinstance> {
+        self.curve.clone()
+    }
+
+    pub fn a_invariants(&self) -> (QQinstance, QQinstance, QQinstance, QQinstance, QQinstance) {
+        self.curve.a_invariants()
+    }
+
+    pub fn point(&self, x: QQinstance, y: QQinstance) -> EllipticCurvePoint<QQinstance> {
+        self.curve.point(x, y)
+    }
+
+    pub fn infinity(&self) -> EllipticCurvePoint<QQinstance> {
+        self.curve.infinity()
+    }
+
+    pub fn is_integral(&self) -> bool {
+        let (a1, a2, a3, a4, a6) = self.curve.a_invariants();
+        [a1, a2, a3, a4, a6].iter().all(|a| a.denominator == BigInt::one())
+    }
+
+    /*
+    Global minimal model (Laska-Kraus-Connell) in reduced form: a1, a3 in {0, 1} and a2 in {-1, 0, 1}.
+    The minimal model is unique, so two curves are isomorphic over QQ if and only if their minimal models are equal.
+     */
+    pub fn minimal_model(&self) -> EllipticCurve_rational_field {
+        let model = IntegralModel::from_curve(&self.curve).minimal();
+        let qq = QQ::new();
+        EllipticCurve_rational_field::new(model.coefficients().into_iter().map(|a| qq.apply(a)).collect())
+    }
+
+    pub fn is_minimal(&self) -> bool {
+        self.is_integral() && self.minimal_discriminant() == self.discriminant().numerator
+    }
+
+    pub fn minimal_discriminant(&self) -> BigInt {
+        IntegralModel::from_curve(&self.curve).minimal().discriminant()
+    }
+
+    /*
+    Reduction type, Kodaira symbol, exponent of the conductor and Tamagawa number at p (Tate's algorithm)
+     */
+    pub fn local_data(&self, p: BigInt) -> LocalData {
+        unwrap_or_panic(self.try_local_data(p))
+    }
+
+    pub fn try_local_data(&self, p: BigInt) -> Result<LocalData> {
+        check_prime(&p)?;
+        Ok(tate(&IntegralModel::from_curve(&self.curve).minimal(), &p))
+    }
+
+    pub fn conductor_exponent(&self, p: BigInt) -> u64 {
+        unwrap_or_panic(self.try_conductor_exponent(p))
+    }
+
+    pub fn try_conductor_exponent(&self, p: BigInt) -> Result<u64> {
+        Ok(self.try_local_data(p)?.conductor_exponent)
+    }
+
+    pub fn has_good_reduction(&self, p: BigInt) -> bool {
+        !self.minimal_discriminant().is_multiple_of(&p)
+    }
+
+    /*
+    Product of p^f_p over the primes of bad reduction, the minimal discriminant is factored
+    so this is meant for curves with a discriminant of moderate size
+     */
+    pub fn conductor(&self) -> BigInt {
+        let model = IntegralModel::from_curve(&self.curve).minimal();
+        let mut conductor = BigInt::one();
+        for p in prime_divisors(&model.discriminant()) {
+            let local_data = tate(&model, &p);
+            conductor *= p.pow(local_data.conductor_exponent as u32);
+        }
+        conductor
+    }
+
+    /*
+    Reduction of the minimal model modulo a prime of good reduction
+     */
+    pub fn reduction(&self, p: BigInt) -> EllipticCurve_finite_field {
+        unwrap_or_panic(self.try_reduction(p))
+    }
+
+    pub fn try_reduction(&self, p: BigInt) -> Result<EllipticCurve_finite_field> {
+        check_prime(&p)?;
+        if !self.has_good_reduction(p.clone()) {
+            return Err(Error::InvalidInput(format!("The curve has bad reduction at {}", p)));
+        }
+        let field = Zmod::new(Some(ZZ::new().new_instance(p)));
+        let model = IntegralModel::from_curve(&self.curve).minimal();
+        Ok(EllipticCurve_finite_field::new(model.coefficients().into_iter().map(|a| field.apply(a)).collect()))
+    }
+
+    /*
+    Rational torsion points, found with the Nagell-Lutz theorem on an integral model y^2 = x^3 + A x + B:
+    a torsion point has integer coordinates and either y = 0 or y^2 divides 4 A^3 + 27 B^2
+     */
+    pub fn torsion_points(&self) -> Vec<EllipticCurvePoint<QQinstance>> {
+        let qq = QQ::new();
+        let (a1, _, a3, _, _) = self.curve.a_invariants();
+        let (b2, _, _, _) = self.curve.b_invariants();
+        let (c4, c6) = self.curve.c_invariants();
+
+        // y^2 = x^3 - 27 c4 x - 54 c6 with x = 36 X + 3 b2, y = 108 (2 Y + a1 X + a3), scaled by D to make it integral
+        let (a, b) = (qq.apply(BigInt::from(-27)) * c4, qq.apply(BigInt::from(-54)) * c6);
+        let d = a.denominator.lcm(&b.denominator);
+        let a = (a * qq.apply(d.pow(4))).numerator;
+        let b = (b * qq.apply(d.pow(6))).numerator;
+        let short_curve = EllipticCurve_generic::new(vec![qq.zero(), qq.zero(), qq.zero(), qq.apply(a.clone()), qq.apply(b.clone())]);
+
+        let mut candidates: Vec<(BigInt, BigInt)> = Vec::new();
+        let discriminant: BigInt = BigInt::from(4) * a.pow(3) + BigInt::from(27) * b.pow(2);
+        for y in square_divisors(&discriminant).into_iter().chain([BigInt::zero()]) {
+            for x in integer_roots_of_depressed_cubic(&a, &(b.clone() - y.pow(2))) {
+                candidates.push((x.clone(), y.clone()));
+                if !y.is_zero() {
+                    candidates.push((x, -y.clone()));
+                }
+            }
+        }
+
+        let mut torsion = vec![self.infinity()];
+        for (x, y) in candidates {
+            let point = short_curve.point(qq.apply(x.clone()), qq.apply(y.clone()));
+            if !is_torsion_short_point(&point) {
+                continue;
+            }
+            // back to the original model
+            let (x, y) = (qq.apply(x) / qq.apply(d.pow(2)), qq.apply(y) / qq.apply(d.pow(3)));
+            let x = (x - b2.clone() * qq.apply(BigInt::from(3))) / qq.apply(BigInt::from(36));
+            let y = (y / qq.apply(BigInt::from(108)) - a1.clone() * x.clone() - a3.clone()) / qq.apply(BigInt::from(2));
+            torsion.push(self.point(x, y));
+        }
+        torsion
+    }
+
+    pub fn torsion_order(&self) -> usize {
+        self.torsion_points().len()
+    }
+
+    /*
+    (n1, n2) such that the torsion subgroup is Z/n1 x Z/n2 (n2 is 2 exactly when the full 2-torsion is rational)
+     */
+    pub fn torsion_structure(&self) -> (usize, usize) {
+        let torsion = self.torsion_points();
+        let two_torsion = torsion.iter().filter(|point| point.double().is_infinity()).count();
+        if two_torsion == 4 {
+            (torsion.len() / 2, 2)
+        } else {
+            (torsion.len(), 1)
+        }
+    }
+}
+
+impl EllipticCurve<QQinstance> for EllipticCurve_rational_field {
+    fn discriminant(&self) -> QQinstance {
+        self.curve.discriminant()
+    }
+
+    fn b_invariants(&self) -> (QQinstance, QQinstance, QQinstance, QQinstance) {
+        self.curve.b_invariants()
+    }
+
+    fn c_invariants(&self) -> (QQinstance, QQinstance) {
+        self.curve.c_invariants()
+    }
+
+    fn j_invariant(&self) -> QQinstance {
+        self.curve.j_invariant()
+    }
+}
+
+
+// by Mazur's theorem a rational torsion point has order at most 12, by Nagell-Lutz its multiples are integral
+fn is_torsion_short_point(point: &EllipticCurvePoint<QQinstance>) -> bool {
+    let mut multiple = point.clone();
+    for _ in 0..12 {
+        if multiple.is_infinity() {
+            return true;
+        }
+        let (x, y) = multiple.xy();
+        if x.denominator != BigInt::one() || y.denominator != BigInt::one() {
+            return false;
+        }
+        multiple = multiple + point.clone();
+    }
+    false
+}
+
+
+/*
+    Integral Weierstrass model, the coordinate changes are x = u^2 x' + r, y = u^3 y' + u^2 s x' + t
+*/
+#[derive(Clone, Debug)]
+struct IntegralModel {
+    a1: BigInt,
+    a2: BigInt,
+    a3: BigInt,
+    a4: BigInt,
+    a6: BigInt
+}
+
+impl IntegralModel {
+    // a_i -> u^i a_i with u the smallest common denominator
+    fn from_curve(curve: &EllipticCurve_generic<QQinstance>) -> IntegralModel {
+        let (a1, a2, a3, a4, a6) = curve.a_invariants();
+        let u = [&a1, &a2, &a3, &a4, &a6].iter().fold(BigInt::one(), |u, a| u.lcm(&a.denominator));
+        let scale = |a: &QQinstance, i: u32| (a.numerator.clone() * u.pow(i)) / a.denominator.clone();
+        IntegralModel { a1: scale(&a1, 1), a2: scale(&a2, 2), a3: scale(&a3, 3), a4: scale(&a4, 4), a6: scale(&a6, 6) }
+    }
+
+    fn coefficients(&self) -> Vec<BigInt> {
+        vec![self.a1.clone(), self.a2.clone(), self.a3.clone(), self.a4.clone(), self.a6.clone()]
+    }
+
+    fn b_invariants(&self) -> (BigInt, BigInt, BigInt, BigInt) {
+        let (a1, a2, a3, a4, a6) = (&self.a1, &self.a2, &self.a3, &self.a4, &self.a6);
+        (
+            a1 * a1 + a2 * 4,
+            a1 * a3 + a4 * 2,
+            a3 * a3 + a6 * 4,
+            a1 * a1 * a6 + a2 * a6 * 4 - a1 * a3 * a4 + a2 * a3 * a3 - a4 * a4
+        )
+    }
+
+    fn c_invariants(&self) -> (BigInt, BigInt) {
+        let (b2, b4, b6, _) = self.b_invariants();
+        (&b2 * &b2 - &b4 * 24, -(&b2 * &b2 * &b2) + &b2 * &b4 * 36 - &b6 * 216)
+    }
+
+    fn discriminant(&self) -> BigInt {
+        let (b2, b4, b6, b8) = self.b_invariants();
+        -(&b2 * &b2 * &b8) - &b4 * &b4 * &b4 * 8 - &b6 * &b6 * 27 + &b2 * &b4 * &b6 * 9
+    }
+
+    fn transform(&mut self, r: &BigInt, s: &BigInt, t: &BigInt) {
+        let (a1, a2, a3, a4, a6) = (self.a1.clone(), self.a2.clone(), self.a3.clone(), self.a4.clone(), self.a6.clone());
+        self.a1 = &a1 + s * 2;
+        self.a2 = &a2 - s * &a1 + r * 3 - s * s;
+        self.a3 = &a3 + r * &a1 + t * 2;
+        self.a4 = &a4 - s * &a3 + r * &a2 * 2 - (t + r * s) * &a1 + r * r * 3 - s * t * 2;
+        self.a6 = &a6 + r * &a4 + r * r * &a2 + r * r * r - t * &a3 - t * t - r * t * &a1;
+    }
+
+    // u = p, r = s = t = 0
+    fn scale_down(&mut self, p: &BigInt) {
+        self.a1 /= p;
+        self.a2 /= p.pow(2);
+        self.a3 /= p.pow(3);
+        self.a4 /= p.pow(4);
+        self.a6 /= p.pow(6);
+    }
+
+    /*
+    Minimal model from the invariants c4, c6: the largest u with u^4 | c4, u^6 | c6, u^12 | discriminant
+    satisfying Kraus' conditions at 2 and 3, then the reduced model with invariants c4 / u^4, c6 / u^6
+    (Cremona, Algorithms for modular elliptic curves, section 3.2)
+     */
+    fn minimal(&self) -> IntegralModel {
+        let (c4, c6) = self.c_invariants();
+        let discriminant = self.discriminant();
+        let mut u = BigInt::one();
+        for p in prime_divisors(&c4.gcd(&c6)) {
+            let mut d = valuation(&discriminant, &p) / 12;
+            if !c4.is_zero() {
+                d = d.min(valuation(&c4, &p) / 4);
+            }
+            if !c6.is_zero() {
+                d = d.min(valuation(&c6, &p) / 6);
+            }
+            while d > 0 {
+                let power = p.pow(d as u32);
+                let (c4, c6) = (c4.clone() / power.pow(4), c6.clone() / power.pow(6));
+                let kraus = if p == BigInt::from(2) {
+                    c6.mod_floor(&BigInt::from(4)) == BigInt::from(3)
+                        || ((c4.is_zero() || valuation(&c4, &p) >= 4) && [BigInt::from(0), BigInt::from(8)].contains(&c6.mod_floor(&BigInt::from(32))))
+                } else if p == BigInt::from(3) {
+                    c6.is_zero() || valuation(&c6, &p) != 2
+                } else {
+                    true
+                };
+                if kraus {
+                    break;
+                }
+                d -= 1;
+            }
+            u *= p.pow(d as u32);
+        }
+
+        let (c4, c6) = (c4 / u.pow(4), c6 / u.pow(6));
+        let mut b2 = (-&c6).mod_floor(&BigInt::from(12));
+        if b2 > BigInt::from(6) {
+            b2 -= 12;
+        }
+        let b4: BigInt = (&b2 * &b2 - &c4) / 24;
+        let b6: BigInt = (-(&b2 * &b2 * &b2) + &b2 * &b4 * 36 - &c6) / 216;
+        let a1 = b2.mod_floor(&BigInt::from(2));
+        let a3 = b6.mod_floor(&BigInt::from(2));
+        IntegralModel {
+            a2: (&b2 - &a1) / 4,
+            a4: (&b4 - &a1 * &a3) / 2,
+            a6: (&b6 - &a3) / 4,
+            a1,
+            a3
+        }
+    }
+}
+
+
+/*
+    TATE'S ALGORITHM (Silverman, Advanced topics, IV.9.4), with the coordinate changes of Cremona for p = 2, 3.
+    Non-minimal models are scaled down by p until they are minimal at p.
+*/
+fn tate(model: &IntegralModel, p: &BigInt) -> LocalData {
+    let mut e = model.clone();
+    let (zero, one, two, three) = (BigInt::zero(), BigInt::one(), BigInt::from(2), BigInt::from(3));
+    let (p2, p3, p4, p6) = (p.pow(2), p.pow(3), p.pow(4), p.pow(6));
+    let inverse = |a: BigInt, modulus: &BigInt| modular_inverse(a.mod_floor(modulus), modulus.clone()).mod_floor(modulus);
+    let local_data = |reduction_type, kodaira_symbol, conductor_exponent, tamagawa_number, discriminant_valuation| LocalData {
+        prime: p.clone(), reduction_type, kodaira_symbol, conductor_exponent, tamagawa_number, discriminant_valuation
+    };
+
+    loop {
+        let (b2, b4, b6, _) = e.b_invariants();
+        let (c4, c6) = e.c_invariants();
+        let n = valuation(&e.discriminant(), p);
+        if n == 0 {
+            return local_data(ReductionType::Good, KodairaSymbol::I(0), 0, 1, 0);
+        }
+
+        // move the singular point of the reduction to (0, 0), so that p | a3, a4, a6
+        let (r, t) = if *p == two {
+            if b2.is_multiple_of(p) {
+                let r = e.a4.mod_floor(p);
+                let t = (&r * (&one + &e.a2 + &e.a4) + &e.a6).mod_floor(p);
+                (r, t)
+            } else {
+                let r = e.a3.mod_floor(p);
+                let t = (&r + &e.a4).mod_floor(p);
+                (r, t)
+            }
+        } else if *p == three {
+            let r = if b2.is_multiple_of(p) { (-&b6).mod_floor(p) } else { (-&b2 * &b4).mod_floor(p) };
+            let t = (&e.a1 * &r + &e.a3).mod_floor(p);
+            (r, t)
+        } else {
+            let r = if c4.is_multiple_of(p) {
+                (-inverse(BigInt::from(12), p) * &b2).mod_floor(p)
+            } else {
+                (-inverse(&c4 * 12, p) * (&c6 + &b2 * &c4)).mod_floor(p)
+            };
+            let t = (-inverse(two.clone(), p) * (&e.a1 * &r + &e.a3)).mod_floor(p);
+            (r, t)
+        };
+        e.transform(&r, &zero, &t);
+        let (_, _, b6, b8) = e.b_invariants();
+
+        if !c4.is_multiple_of(p) {
+            let split = quadratic_has_root(&one, &e.a1, &-&e.a2, p);
+            let (reduction_type, tamagawa_number) = if split {
+                (ReductionType::SplitMultiplicative, n)
+            } else {
+                (ReductionType::NonSplitMultiplicative, if n % 2 == 1 { 1 } else { 2 })
+            };
+            return local_data(reduction_type, KodairaSymbol::I(n), 1, tamagawa_number, n);
+        }
+        if !e.a6.is_multiple_of(&p2) {
+            return local_data(ReductionType::Additive, KodairaSymbol::II, n, 1, n);
+        }
+        if !b8.is_multiple_of(&p3) {
+            return local_data(ReductionType::Additive, KodairaSymbol::III, n - 1, 2, n);
+        }
+        if !b6.is_multiple_of(&p3) {
+            let tamagawa_number = if quadratic_has_root(&one, &(&e.a3 / p), &-(&e.a6 / &p2), p) { 3 } else { 1 };
+            return local_data(ReductionType::Additive, KodairaSymbol::IV, n - 2, tamagawa_number, n);
+        }
+
+        // p | a1, a2, p^2 | a3, a4, p^3 | a6
+        let (s, t) = if *p == two {
+            (e.a2.mod_floor(p), &two * (&e.a6 / BigInt::from(4)).mod_floor(p))
+        } else {
+            let half: BigInt = (&p2 + 1) / 2;
+            let (s, t): (BigInt, BigInt) = (-&e.a1 * &half, -&e.a3 * &half);
+            (s.mod_floor(p), t.mod_floor(&p2))
+        };
+        e.transform(&zero, &s, &t);
+
+        // P(T) = T^3 + b T^2 + c T + d
+        let (b, c, d) = (&e.a2 / p, &e.a4 / &p2, &e.a6 / &p3);
+        let w: BigInt = &d * &d * 27 - &b * &b * &c * &c + &b * &b * &b * &d * 4 - &b * &c * &d * 18 + &c * &c * &c * 4;
+        let x: BigInt = &c * 3 - &b * &b;
+
+        if !w.is_multiple_of(p) {
+            // distinct roots
+            let tamagawa_number = 1 + cubic_root_count(&b, &c, &d, p);
+            return local_data(ReductionType::Additive, KodairaSymbol::IStar(0), n - 4, tamagawa_number, n);
+        }
+
+        if !x.is_multiple_of(p) {
+            // a double root, moved to T = 0
+            let r = if *p == two {
+                c.mod_floor(p)
+            } else if *p == three {
+                (&b * &c).mod_floor(p)
+            } else {
+                let root: BigInt = (&b * &c - &d * 9) * inverse(&x * 2, p);
+                root.mod_floor(p)
+            };
+            e.transform(&(p * r), &zero, &zero);
+
+            let (mut ix, mut iy) = (3u64, 3u64);
+            let (mut mx, mut my) = (p2.clone(), p2.clone());
+            let tamagawa_number = loop {
+                let (a3t, a6t) = (&e.a3 / &my, &e.a6 / (&mx * &my));
+                let quadratic_discriminant: BigInt = &a3t * &a3t + &a6t * 4;
+                if !quadratic_discriminant.is_multiple_of(p) {
+                    break if quadratic_has_root(&one, &a3t, &-&a6t, p) { 4 } else { 2 };
+                }
+                let t = if *p == two { &my * a6t.mod_floor(p) } else { &my * (-&a3t * inverse(two.clone(), p)).mod_floor(p) };
+                e.transform(&zero, &zero, &t);
+                my *= p;
+                iy += 1;
+
+                let (a2t, a4t, a6t) = (&e.a2 / p, &e.a4 / (p * &mx), &e.a6 / (&mx * &my));
+                let quadratic_discriminant: BigInt = &a4t * &a4t - &a6t * &a2t * 4;
+                if !quadratic_discriminant.is_multiple_of(p) {
+                    break if quadratic_has_root(&a2t, &a4t, &a6t, p) { 4 } else { 2 };
+                }
+                let r = if *p == two { &mx * (&a6t * &a2t).mod_floor(p) } else { &mx * (-&a4t * inverse(&a2t * 2, p)).mod_floor(p) };
+                e.transform(&r, &zero, &zero);
+                mx *= p;
+                ix += 1;
+            };
+            return local_data(ReductionType::Additive, KodairaSymbol::IStar(ix + iy - 5), n - ix - iy + 1, tamagawa_number, n);
+        }
+
+        // a triple root, moved to T = 0
+        let r = if *p == two {
+            b.mod_floor(p)
+        } else if *p == three {
+            (-&d).mod_floor(p)
+        } else {
+            (-&b * inverse(three.clone(), p)).mod_floor(p)
+        };
+        e.transform(&(p * r), &zero, &zero);
+
+        let (x3, x6) = (&e.a3 / &p2, &e.a6 / &p4);
+        let quadratic_discriminant: BigInt = &x3 * &x3 + &x6 * 4;
+        if !quadratic_discriminant.is_multiple_of(p) {
+            let tamagawa_number = if quadratic_has_root(&one, &x3, &-&x6, p) { 3 } else { 1 };
+            return local_data(ReductionType::Additive, KodairaSymbol::IVStar, n - 6, tamagawa_number, n);
+        }
+        let t = if *p == two { x6.mod_floor(p) } else { (&x3 * inverse(two.clone(), p)).mod_floor(p) };
+        e.transform(&zero, &zero, &(-&p2 * t));
+
+        if !e.a4.is_multiple_of(&p4) {
+            return local_data(ReductionType::Additive, KodairaSymbol::IIIStar, n - 7, 2, n);
+        }
+        if !e.a6.is_multiple_of(&p6) {
+            return local_data(ReductionType::Additive, KodairaSymbol::IIStar, n - 8, 1, n);
+        }
+        // the model is not minimal at p
+        e.scale_down(p);
+    }
+}
+
+fn check_prime(p: &BigInt) -> Result<()> {
+    if *p < BigInt::from(2) || !is_prime(p.to_biguint().unwrap()) {
+        return Err(Error::InvalidInput("p must be a prime number".to_string()));
+    }
+    Ok(())
+}
+
+fn valuation(n: &BigInt, p: &BigInt) -> u64 {
+    if n.is_zero() {
+        panic!("The valuation of zero is infinite");
+    }
+    let mut n = n.clone();
+    let mut valuation = 0;
+    while n.is_multiple_of(p) {
+        n /= p;
+        valuation += 1;
+    }
+    valuation
+}
+
+fn prime_divisors(n: &BigInt) -> Vec<BigInt> {
+    if n.is_zero() {
+        return Vec::new();
+    }
+    num_prime::nt_funcs::factorize(n.abs().to_biguint().unwrap()).into_keys().map(BigInt::from).collect()
+}
+
+// positive integers y with y^2 | n
+fn square_divisors(n: &BigInt) -> Vec<BigInt> {
+    let mut divisors = vec![BigInt::one()];
+    if n.is_zero() {
+        return divisors;
+    }
+    for (p, exponent) in num_prime::nt_funcs::factorize(n.abs().to_biguint().unwrap()) {
+        let p = BigInt::from(p);
+        let mut extended = Vec::new();
+        for divisor in divisors.iter() {
+            for k in 0..=(exponent / 2) {
+                extended.push(divisor * p.pow(k as u32));
+            }
+        }
+        divisors = extended;
+    }
+    divisors
+}
+
+/*
+    Integer roots of x^3 + a x + c: the cubic is monotone on (-inf, -k-1], [-k, k], [k+1, inf)
+    with k = floor(sqrt(-a/3)), each piece is searched by bisection within the Cauchy bound
+*/
+fn integer_roots_of_depressed_cubic(a: &BigInt, c: &BigInt) -> Vec<BigInt> {
+    let f = |x: &BigInt| x * x * x + a * x + c;
+    let bound: BigInt = BigInt::one() + a.abs().max(c.abs());
+    let mut intervals: Vec<(BigInt, BigInt, bool)> = Vec::new();
+    if a >= &BigInt::zero() {
+        intervals.push((-&bound, bound.clone(), true));
+    } else {
+        let k: BigInt = (-a / BigInt::from(3)).sqrt();
+        intervals.push((-&bound, -&k - 1, true));
+        intervals.push((-&k, k.clone(), false));
+        intervals.push((&k + 1, bound.clone(), true));
+    }
+
+    let mut roots: Vec<BigInt> = Vec::new();
+    for (mut low, mut high, increasing) in intervals {
+        while low <= high {
+            let middle: BigInt = (&low + &high).div_floor(&BigInt::from(2));
+            let value = f(&middle);
+            if value.is_zero() {
+                if !roots.contains(&middle) {
+                    roots.push(middle);
+                }
+                break;
+            }
+            if (value > BigInt::zero()) == increasing {
+                high = middle - 1;
+            } else {
+                low = middle + 1;
+            }
+        }
+    }
+    roots
+}
+
+// whether a T^2 + b T + c has a root modulo p
+fn quadratic_has_root(a: &BigInt, b: &BigInt, c: &BigInt, p: &BigInt) -> bool {
+    if a.is_multiple_of(p) {
+        return !b.is_multiple_of(p) || c.is_multiple_of(p);
+    }
+    if *p == BigInt::from(2) {
+        return c.is_multiple_of(p) || (a + b + c).is_multiple_of(p);
+    }
+    let discriminant: BigInt = b * b - a * c * 4;
+    legendre_symbol(discriminant.mod_floor(p), p.clone()) != -1
+}
+
+// number of distinct roots of T^3 + b T^2 + c T + d modulo p, the degree of gcd(P, T^p - T)
+fn cubic_root_count(b: &BigInt, c: &BigInt, d: &BigInt, p: &BigInt) -> u64 {
+    let field = Zmod::new(Some(ZZ::new().new_instance(p.clone())));
+    let cubic: Vec<ZmodInstance> = [d, c, b, &BigInt::one()].into_iter().map(|coefficient| field.apply(coefficient.clone())).collect();
+    let x = vec![field.apply(BigInt::zero()), field.apply(BigInt::one())];
+    let frobenius = sub(&powmod(&x, p, &cubic), &x);
+    (gcd(&cubic, &frobenius).len() - 1) as u64
+}
@@ -1,5 +1,6 @@
 //use sagemath::numbers::sets::General_Class;
 
+use crate::error::{unwrap_or_panic, Error, Result};
 use num_bigint::BigInt;
 use num_traits::Num;
 use num_traits::ToPrimitive;
@@ -142,6 +143,27 @@ impl PolynomialRing<ZZinstance> {
     }
 }
 
+/*
+    The quotient by an irreducible polynomial over Zmod p is a field with p^n elements,
+    the other moduli (like x^n + 1 for the NTT) only give a ring
+*/
+impl PolynomialRing<ZmodInstance> {
+    pub fn new_field(irreducible_polynomial: UnivariatePolynomialInstance<ZmodInstance>, fixed_length_coefficients: bool) -> PolynomialRing<ZmodInstance> {
+        unwrap_or_panic(PolynomialRing::try_new_field(irreducible_polynomial, fixed_length_coefficients))
+    }
+
+    pub fn try_new_field(irreducible_polynomial: UnivariatePolynomialInstance<ZmodInstance>, fixed_length_coefficients: bool) -> Result<PolynomialRing<ZmodInstance>> {
+        if !irreducible_polynomial.try_is_irreducible()? {
+            return Err(Error::InvalidInput("The modulus is not irreducible".to_string()));
+        }
+        Ok(PolynomialRing::new(irreducible_polynomial, fixed_length_coefficients))
+    }
+
+    pub fn is_field(&self) -> bool {
+        self.irreducible_polynomial.try_is_irreducible().unwrap_or(false)
+    }
+}
+
 impl<T> PolynomialRing<T> where T: Instance + Operand + Clone + PartialEq + Number + ClassInstance + 'static {
     pub fn new(irreducible_polynomial: UnivariatePolynomialInstance<T>, fixed_length_coefficients: bool) -> PolynomialRing<T> {
         PolynomialRing { irreducible_polynomial: irreducible_polynomial, ntt_enabled: false, ntt_ctxt: None, fixed_length_coefficients: fixed_length_coefficients } 
@@ -247,6 +269,10 @@ impl<T> PolynomialRing<T> where T: Instance + Operand + Clone + PartialEq + Numb
 
 impl PolynomialRing<ZmodInstance> {
     pub fn apply_ntt_ctxt<T>(&self, instance: &PolynomialRingInstance<T>) -> PolynomialRingInstance<ZmodInstance> where T: Instance + Operand + Clone + PartialEq  + Number + ClassInstance + 'static{
+        unwrap_or_panic(self.try_apply_ntt_ctxt(instance))
+    }
+
+    pub fn try_apply_ntt_ctxt<T>(&self, instance: &PolynomialRingInstance<T>) -> Result<PolynomialRingInstance<ZmodInstance>> where T: Instance + Operand + Clone + PartialEq  + Number + ClassInstance + 'static{
         if self.ntt_enabled {
             let mut coefficients = instance.coefficients.clone();
             let generator = coefficients[0].clone().get_class();
@@ -255,9 +281,9 @@ impl PolynomialRing<ZmodInstance> {
                 coefficients.push(generator.zero().as_any().downcast_ref::<T>().unwrap().clone());
             }
             let new_coefficients = self.ntt_ctxt.clone().unwrap().into_inner().to_ntt(coefficients);
-            return PolynomialRing::<ZmodInstance>::new_ntt_instance(self, instance.var.clone(), new_coefficients);
+            Ok(PolynomialRing::<ZmodInstance>::new_ntt_instance(self, instance.var.clone(), new_coefficients))
         } else {
-            panic!("NTT not enabled");
+            Err(Error::InvalidInput("NTT not enabled".to_string()))
         }
     }
 
@@ -278,6 +304,10 @@ impl PolynomialRing<ZmodInstance> {
 
 
     pub fn from_ntt_ctxt(&self, instance: &PolynomialRingInstance<ZmodInstance>, fixed_length_coefficients: bool) -> PolynomialRingInstance<ZZinstance>{
+        unwrap_or_panic(self.try_from_ntt_ctxt(instance, fixed_length_coefficients))
+    }
+
+    pub fn try_from_ntt_ctxt(&self, instance: &PolynomialRingInstance<ZmodInstance>, fixed_length_coefficients: bool) -> Result<PolynomialRingInstance<ZZinstance>>{
         
         if instance.class.clone().into_inner().ntt_enabled && instance.ntt_form {
             let mut irreducible_polynomial: Vec<ZZinstance> = Vec::new();
@@ -296,9 +326,9 @@ impl PolynomialRing<ZmodInstance> {
             //coefficients = clean_coefficients(coefficients);
 
             let class: PolynomialRing<ZZinstance>  = PolynomialRing::new( UnivariatePolynomial::new_instance(irreducible_polynomial, instance.var.clone(), None, false), fixed_length_coefficients);
-            return class.new_instance(instance.var.clone(), coefficients, false);
+            Ok(class.new_instance(instance.var.clone(), coefficients, false))
         } else {
-            panic!("ERROR: Polynomial is not in NTT format");
+            Err(Error::InvalidInput("ERROR: Polynomial is not in NTT format".to_string()))
         }
     }
 }
@@ -306,15 +336,23 @@ impl PolynomialRing<ZmodInstance> {
 
 impl<T> PolynomialRing<T> where T: Instance + Operand + Clone + PartialEq + Number + ClassInstance + 'static + Display{
     pub fn div(&self, x: PolynomialRingInstance<T>, y: PolynomialRingInstance<T>) -> PolynomialRingInstance<T>  {
-        x * y.inverse()
+        unwrap_or_panic(self.try_div(x, y))
+    }
+
+    pub fn try_div(&self, x: PolynomialRingInstance<T>, y: PolynomialRingInstance<T>) -> Result<PolynomialRingInstance<T>>  {
+        Ok(x * self.try_inverse(y)?)
     }
     
     pub fn inverse(&self, x: PolynomialRingInstance<T>) -> PolynomialRingInstance<T> {
+        unwrap_or_panic(self.try_inverse(x))
+    }
+
+    pub fn try_inverse(&self, x: PolynomialRingInstance<T>) -> Result<PolynomialRingInstance<T>> {
         let result: Vec<PolynomialRingInstance<T>> = utils::egcd(x.clone(), self.apply(&self.irreducible_polynomial,false));
         if result[0] != self.clone().one(x.var, &x.coefficients[0].get_class()) {
-            panic!("The inverse does not exist");
+            Err(Error::NotInvertible("The inverse does not exist".to_string()))
         } else {
-            return result[1].clone()
+            Ok(result[1].clone())
         }
     }
 
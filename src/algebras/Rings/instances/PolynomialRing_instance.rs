@@ -17,6 +17,7 @@ use std::fmt::Display;
 use crate::algebras::Rings::classes::PolynomialRing::PolynomialRing;
 use crate::variables::vars::Var;
 use std::cmp::Ordering;
+use crate::error::{unwrap_or_panic, Error, Result};
 /*
     Zmod INSTANCE
 */
@@ -66,7 +67,45 @@ impl<T> PolynomialRingInstance<T> where T: Instance + Clone + PartialEq + Operan
 
 impl<T> PolynomialRingInstance<T> where T: ClassInstance + Instance + 'static + Clone + PartialEq + Operand + Number + Display {
     pub fn inverse(self) -> PolynomialRingInstance<T> {
-        self.class.clone().into_inner().inverse(self)
+        unwrap_or_panic(self.try_inverse())
+    }
+
+    pub fn try_inverse(self) -> Result<PolynomialRingInstance<T>> {
+        self.class.clone().into_inner().try_inverse(self)
+    }
+
+    pub fn try_mul(self, rhs: PolynomialRingInstance<T>) -> Result<PolynomialRingInstance<T>> {
+        if self.class == rhs.class {
+            Ok(self.class.clone().into_inner().mul(self, rhs))
+        } else {
+            Err(Error::IncompatibleOperands("[ERROR] Impossible to multiply polynomials in different polynomial rings".to_string()))
+        }
+    }
+
+    pub fn try_div(self, rhs: PolynomialRingInstance<T>) -> Result<PolynomialRingInstance<T>> {
+        if self.class == rhs.class {
+            self.class.clone().into_inner().try_div(self, rhs)
+        } else {
+            Err(Error::IncompatibleOperands("[ERROR] Impossible to divide polynomials in different polynomial rings".to_string()))
+        }
+    }
+}
+
+impl<T> PolynomialRingInstance<T> where T: Number + Instance + PartialEq + Clone + Operand + ClassInstance + 'static {
+    pub fn try_add(self, rhs: PolynomialRingInstance<T>) -> Result<PolynomialRingInstance<T>> {
+        if self.class == rhs.class {
+            Ok(self.class.clone().into_inner().add(self, rhs))
+        } else {
+            Err(Error::IncompatibleOperands("[ERROR] Impossible to add polynomials in different polynomial rings".to_string()))
+        }
+    }
+
+    pub fn try_sub(self, rhs: PolynomialRingInstance<T>) -> Result<PolynomialRingInstance<T>> {
+        if self.class == rhs.class {
+            Ok(self.class.clone().into_inner().sub(self, rhs))
+        } else {
+            Err(Error::IncompatibleOperands("[ERROR] Impossible to subtract polynomials in different polynomial rings".to_string()))
+        }
     }
 }
 
@@ -96,12 +135,7 @@ impl<T> std::ops::Neg for PolynomialRingInstance<T> where T: Number + Instance +
 impl<T> std::ops::Add<PolynomialRingInstance<T>> for PolynomialRingInstance<T> where T: Number + Instance + PartialEq + Clone + Operand + ClassInstance + 'static{
     type Output = PolynomialRingInstance<T>;
     fn add(self, rhs: PolynomialRingInstance<T>) -> PolynomialRingInstance<T> {
-        if self.class == rhs.class {
-        
-            self.class.clone().into_inner().add(self, rhs)
-        } else {
-            panic!("[ERROR] Impossible to add polynomials in different polynomial rings")
-        }
+        unwrap_or_panic(self.try_add(rhs))
     }
 }
 
@@ -109,11 +143,7 @@ impl<T> std::ops::Add<PolynomialRingInstance<T>> for PolynomialRingInstance<T> w
 impl<T> std::ops::Sub<PolynomialRingInstance<T>> for PolynomialRingInstance<T> where T: Number + Instance + PartialEq  + Clone + Operand + ClassInstance + 'static{
     type Output = PolynomialRingInstance<T>;
     fn sub(self, rhs: PolynomialRingInstance<T>) -> PolynomialRingInstance<T> {
-        if self.class == rhs.class {
-            self.class.clone().into_inner().sub(self, rhs)
-        } else {
-            panic!("[ERROR] Impossible to subtract polynomials in different polynomial rings")
-        }
+        unwrap_or_panic(self.try_sub(rhs))
     }
 }
 
@@ -121,11 +151,7 @@ impl<T> std::ops::Sub<PolynomialRingInstance<T>> for PolynomialRingInstance<T> w
 impl<T> std::ops::Mul<PolynomialRingInstance<T>> for PolynomialRingInstance<T> where T: Display + 'static + Number + Instance + PartialEq  + Clone + Operand + ClassInstance{
     type Output = PolynomialRingInstance<T>;
     fn mul(self, rhs: PolynomialRingInstance<T>) -> PolynomialRingInstance<T> {
-        if self.class == rhs.class {
-            self.class.clone().into_inner().mul(self, rhs)
-        } else {
-            panic!("[ERROR] Impossible to multiply polynomials in different polynomial rings")
-        }
+        unwrap_or_panic(self.try_mul(rhs))
     }
 }
 
@@ -144,11 +170,7 @@ impl<T> std::ops::Mul<T> for PolynomialRingInstance<T> where T: Number + Instanc
 impl<T> std::ops::Div<PolynomialRingInstance<T>> for PolynomialRingInstance<T> where T: 'static + Display + ClassInstance + Number + Instance + PartialEq  + Clone + Operand {
     type Output = PolynomialRingInstance<T>;
     fn div(self, rhs: PolynomialRingInstance<T>) -> PolynomialRingInstance<T> {
-        if self.class == rhs.class {
-            self.class.clone().into_inner().div(self, rhs)
-        } else {
-            panic!("[ERROR] Impossible to multiply polynomials in different polynomial rings")
-        }
+        unwrap_or_panic(self.try_div(rhs))
     }
 }
 
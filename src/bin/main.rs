@@ -12,31 +12,62 @@ use sage_math::arith::random::gen_from_uniform_distribution_with_modulo;
 use sage_math::arith::random::get_random_bigint;
 use sage_math::arith::random::get_random_bigint_with_bounds;
 use sage_math::arith::random::random_byte_array;
-use sage_math::cryptography::asymmetric::interfaces::interfaces::LatticeBased_PKIinterface;
+use sage_math::cryptography::asymmetric::interfaces::interfaces::KEMinterface;
 use sage_math::cryptography::asymmetric::interfaces::interfaces::PKIinterface;
-use sage_math::cryptography::asymmetric::primitives::kyber::Kyber1024;
-use sage_math::cryptography::asymmetric::primitives::kyber::Kyber512;
-use sage_math::cryptography::asymmetric::primitives::kyber::Kyber768;
-use sage_math::cryptography::asymmetric::primitives::rsa::RSA;
+use sage_math::cryptography::asymmetric::interfaces::interfaces::KeyAgreementInterface;
+use sage_math::cryptography::asymmetric::interfaces::interfaces::SignatureInterface;
+use sage_math::cryptography::asymmetric::primitives::ecdh::ECDH;
+use sage_math::cryptography::asymmetric::primitives::ecdsa::ECDSA;
+use sage_math::cryptography::asymmetric::primitives::eddsa::Ed25519;
+use sage_math::cryptography::asymmetric::primitives::kyber::{Kyber, KyberParams, Kyber512, Kyber768, Kyber1024};
+use sage_math::cryptography::asymmetric::primitives::rsa::{RSA, RSAEncryptionScheme, RSASignatureScheme, RSAHashFunction};
+use sage_math::cryptography::asymmetric::primitives::xdh::{MontgomeryCurves, XDH};
 use sage_math::cryptography::homomorphic::interfaces::interfaces::BFV_PKI;
 use sage_math::cryptography::homomorphic::interfaces::interfaces::FHE;
 use sage_math::cryptography::homomorphic::primitives::bfv::BFV;
-use sage_math::cryptography::padding::padding::Paddings;
+use sage_math::algebras::Groups::EllipticCurves::elliptic_curve_factory::{EllipticCurveFactory, NamedCurves};
+use sage_math::cryptography::hash::interfaces::interfaces::{HashInterface, XOFReaderInterface, XOFinterface};
+use sage_math::cryptography::hash::primitives::sha1::SHA1;
+use sage_math::cryptography::hash::primitives::sha2::{SHA256, SHA384, SHA512};
+use sage_math::cryptography::hash::primitives::sha3::{SHA3_224, SHA3_256, SHA3_384, SHA3_512, SHAKE128, SHAKE256};
+use sage_math::cryptography::kdf::primitives::hkdf::HKDF;
+use sage_math::cryptography::kdf::primitives::pbkdf2::{pbkdf2, try_pbkdf2};
+use sage_math::cryptography::padding::padding::{GenericPadding, Pad, PaddingError, Paddings, ANSI_X9_23, ISO_10126, ISO_IEC_7816_4, ISO_IEC_9797_1_M1, ISO_IEC_9797_1_M2, ISO_IEC_9797_1_M3, PKCS7};
 use sage_math::cryptography::symmetric::interfaces::interfaces::AESfactory;
+use sage_math::cryptography::symmetric::interfaces::interfaces::{AEADinterface, AEADError};
+use sage_math::cryptography::symmetric::interfaces::interfaces::MACinterface;
+use sage_math::cryptography::symmetric::interfaces::interfaces::AESinterface;
 use sage_math::cryptography::symmetric::modes::modes::Modes;
+use sage_math::Error;
+use sage_math::cryptography::symmetric::AES::aesni;
+use sage_math::cryptography::symmetric::AES::cipher::{self, AES_BACKEND};
 use sage_math::cryptography::symmetric::primitives::aes::aes_factory;
 use sage_math::cryptography::symmetric::primitives::aes::AES;
 use sage_math::cryptography::symmetric::primitives::aes::AES_KEY_SIZE;
+use sage_math::cryptography::symmetric::primitives::aes::{AES_CBC, AES_GCM};
+use sage_math::cryptography::symmetric::primitives::aes::{AES_CTR, AES_CTR_COUNTER_SIZE};
+use sage_math::cryptography::symmetric::primitives::aes::{AES_OFB, AES_CFB, AES_CFB_SEGMENT_SIZE, AES_XTS};
+use sage_math::cryptography::symmetric::primitives::chacha20::{chacha20_block, hchacha20, ChaCha20, XChaCha20};
+use sage_math::cryptography::symmetric::primitives::chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+use sage_math::cryptography::symmetric::primitives::hmac::HMAC;
+use sage_math::cryptography::symmetric::primitives::poly1305::Poly1305;
 use sage_math::numbers::classes::RR::RR;
 use sage_math::numbers::classes::ZZ::ZZ;
 use sage_math::numbers::instances::RR_instance::RRinstance;
+use sage_math::numbers::instances::QQ_instance::QQinstance;
 use sage_math::numbers::numbers::Class;
 use sage_math::numbers::numbers::Operand;
 use sage_math::poly::classes::monomial::Monomial;
-use sage_math::poly::classes::univariate_polynomial::UnivariatePolynomial;
+use sage_math::poly::classes::polynomial::{MonomialOrder, Polynomial};
+use sage_math::poly::instances::polynomial_instance::PolynomialInstance;
+use sage_math::poly::classes::univariate_polynomial::{PolyMultiplicationAlgorithm, UnivariatePolynomial};
+use sage_math::matrices::matrix::Matrix;
+use sage_math::poly::ideal::{GroebnerAlgorithm, Ideal};
+use sage_math::numbers::classes::QQ::QQ;
 use sage_math::test::test_ZZ;
 use sage_math::test::test_RR;
 use sage_math::test::test_QQ;
+use sage_math::test::test_elliptic_curves;
 use sage_math::transform::ntt::NTTFactory;
 use sage_math::transform::ntt::NTT_Algorithm;
 use sage_math::transform::ntt::NTT;
@@ -128,6 +159,7 @@ fn main() {
     test_ZZ::test();
     test_RR::test();
     test_QQ::test();
+    test_elliptic_curves::test();
     
     // let v: Var = Var::new("x", BigInt::from(3));
     // let w: Var = Var::new("x", BigInt::from(4));
@@ -208,13 +240,35 @@ fn main() {
     
    //test_ntt();
 
-    // test_kyber();
     // test_bfv();
     test_rsa();
+    test_sha2();
+    test_sha3();
+    test_hmac_kdf();
+    test_ecdsa();
+    test_ed25519();
+    test_ecdh();
+    test_xdh();
+    test_kyber();
     simple_aes_test();
+    aes_known_answer_test();
+    aes_backend_test();
     aes_ctr_preprocessing_test();
+    aes_ctr_test();
     aes_cbc_test();
     aes_ecb_test();
+    aes_ofb_cfb_test();
+    aes_xts_test();
+    aes_gcm_test();
+    padding_test();
+    test_chacha20_poly1305();
+    error_test();
+    test_poly_multiplication();
+    test_multivariate_polynomials();
+    test_groebner();
+    test_univariate_gcd();
+    test_factorization();
+    test_roots_and_interpolation();
 }
 
 fn test_bfv() {
@@ -255,27 +309,439 @@ fn test_rsa() {
     println!("RSA test");
 
     let rsa: RSA = RSA::init(1024);
-
-    
     let plain = random_byte_array(12);
     let ciphertext = rsa.encrypt(plain.clone());
-
     let plaintext = rsa.decrypt(ciphertext.clone());
     assert_eq!(plain, plaintext);
-    
+
+    let signature = rsa.sign(plain.clone());
+    assert!(rsa.verify(plain.clone(), signature.clone()));
+    assert!(!rsa.verify(random_byte_array(12), signature));
+
+    // RSAES-OAEP and RSASSA-PSS examples of the PKCS #1 test vectors (oaep-int.txt and pss-int.txt), SHA-1 and MGF1 with SHA-1
+    let p = BigInt::parse_bytes(b"eecfae81b1b9b3c908810b10a1b5600199eb9f44aef4fda493b81a9e3d84f632124ef0236e5d1e3b7e28fae7aa040a2d5b252176459d1f397541ba2a58fb6599", 16).unwrap();
+    let q = BigInt::parse_bytes(b"c97fb1f027f453f6341233eaaad1d9353f6c42d08866b1d05a0f2035028b9d869840b41666b42e92ea0da3b43204b5cfce3352524d0416a5a441e700af461503", 16).unwrap();
+    let mut rsa: RSA = RSA::from_private_key(p, q, BigInt::from(0x11));
+    rsa.hash_function = RSAHashFunction::SHA1;
+    let message = hex_to_bytes("d436e99569fd32a7c8a05bbc90d32c49");
+    let ciphertext = hex_to_bytes("1253e04dc0a5397bb44a7ab87e9bf2a039a33d1e996fc82a94ccd30074c95df763722017069e5268da5d1c0b4f872cf653c11df82314a67968dfeae28def04bb6d84b1c31d654a1970e5783bd6eb96a024c2ca2f4a90fe9f2ef5c9c140e5bb48da9536ad8700c84fc9130adea74e558d51a74ddf85d8b50de96838d6063e0955");
+    assert_eq!(rsa.encrypt_with_seed(message.clone(), hex_to_bytes("aafd12f659cae63489b479e5076ddec2f06cb58f")), ciphertext);
+    assert_eq!(rsa.decrypt(ciphertext), message);
+
+    let p = BigInt::parse_bytes(b"d17f655bf27c8b16d35462c905cc04a26f37e2a67fa9c0ce0dced472394a0df743fe7f929e378efdb368eddff453cf007af6d948e0ade757371f8a711e278f6b", 16).unwrap();
+    let q = BigInt::parse_bytes(b"c6d92b6fee7414d1358ce1546fb62987530b90bd15e0f14963a5e2635adb69347ec0c01b2ab1763fd8ac1a592fb22757463a982425bb97a3a437c5bf86d03f2f", 16).unwrap();
+    let mut rsa: RSA = RSA::from_private_key(p, q, BigInt::from(65537));
+    rsa.hash_function = RSAHashFunction::SHA1;
+    let message = hex_to_bytes("859eef2fd78aca00308bdc471193bf55bf9d78db8f8a672b484634f3c9c26e6478ae10260fe0dd8c082e53a5293af2173cd50c6d5d354febf78b26021c25c02712e78cd4694c9f469777e451e7f8e9e04cd3739c6bbfedae487fb55644e9ca74ff77a53cb729802f6ed4a5ffa8ba159890fc");
+    let signature = hex_to_bytes("8daa627d3de7595d63056c7ec659e54406f10610128baae821c8b2a0f3936d54dc3bdce46689f6b7951bb18e840542769718d5715d210d85efbb596192032c42be4c29972c856275eb6d5a45f05f51876fc6743deddd28caec9bb30ea99e02c3488269604fe497f74ccd7c7fca1671897123cbd30def5d54a2b5536ad90a747e");
+    assert_eq!(rsa.sign_with_salt(message.clone(), hex_to_bytes("e3b5d5d002c1bce50c2b65ef88a188d83bce7e61")), signature);
+    assert!(rsa.verify(message, signature));
+    assert!(matches!(rsa.try_sign_with_salt(vec![1, 2, 3], vec![0u8; 32]), Err(Error::InvalidInput(_))));
+
+    // other hash functions, labels and PKCS #1 v1.5, cross-checked with the pyca/cryptography implementation
+    let p = BigInt::parse_bytes(b"e198521f2f3e905fa1f118dbfaf7847ce5b5313a2342e1c48419cff982f9ed201b3cb6a93173e98765d655a3f9f3d56a1e066c649be92acfe73bac30e024fbbb", 16).unwrap();
+    let q = BigInt::parse_bytes(b"cf646e55208d9fd491414fcb7976a2854ea576dcfcf8a5eb76a42d8c41f9fe11776bea324024a76524442d2f397d4dfa8b220ba784c75a1e141178eee8ad14f9", 16).unwrap();
+    let message = b"RSA with PKCS #1 v2.2 encodings".to_vec();
+    let mut rsa: RSA = RSA::from_private_key(p, q, BigInt::from(65537));
+
+    rsa.hash_function = RSAHashFunction::SHA1;
+    assert_eq!(rsa.decrypt(hex_to_bytes("65b4bdc5db4f37651642d2eea2598f2bd1836566a0baef2999307c8f7d7c473c7e880abe19e1fb6bbd829de9cbd137a6e4ba2ca35e43c12c06adbc4383301b4bffc02c68bbd984747a68c96022c08c11cdc5518c725a4046ffbbbb1d9572046b80df59f7b0c4326e3fe045627e7b1d9cc076a0cbefa32cafbaabdcd337739269")), message);
+    assert!(rsa.verify(message.clone(), hex_to_bytes("4a33e4f994e5a4dd1af6d5dc8db936976691ced0c6112f89500a1f248627adec44343fb2d6b367a9527944c1a749508c2dacf2434b2d3631f440b31873c6300732cd37c723de097deb1d36177fe0c7a7beb63112758dd144a578f176a9636e8b94d07a40f712425c2ac68434011a359c9962be3fb8eb10b6c8211ba8bb02a2cd")));
+
+    rsa.hash_function = RSAHashFunction::SHA256;
+    rsa.label = b"label".to_vec();
+    assert_eq!(rsa.decrypt(hex_to_bytes("a3a6f4091551bfb85e324c8f95e66e56f3e09f83977c3e34ae8e13f5059f362fda3ea3dbe20dbd0ebac11b59823e7dbe5aae12d6afe36b4c34cfd236d98b96a47bca61539185e35e7b20cd511325da061ecf7eefaaede4762aceac99326a4e6cf48853f9c861844fd69c8ff3eff571089396a9abb64fb622ebe231b76284547a")), message);
+    assert!(rsa.verify(message.clone(), hex_to_bytes("53ed8b3e5c1cfe2ea821b7e64b2f91f40b17227855f43932fc3809648578d34c4690841804fc18c9a8b6d106c5ed9fe3f1421da4b5583022deaa2bd8ff21ef8dbdd8d5662c10b95114e6929cc6fb784d5c460bc377eb5504d16ed0495347896af1fac6f2bc304296f14478667076c031219d0aa2d0aa4f46ce4f33695c9dd353")));
+    let ciphertext = rsa.encrypt(message.clone());
+    assert_eq!(rsa.decrypt(ciphertext), message);
+
+    rsa.encryption_scheme = RSAEncryptionScheme::PKCS1v15;
+    rsa.signature_scheme = RSASignatureScheme::PKCS1v15;
+    assert_eq!(rsa.decrypt(hex_to_bytes("6a835368febe07e2736c20676f8722e8a188566d2ab35acec1086781d63b647e082da87fdf61b1ae5a5619b83e930bbae268d0235463649377579228ef01090b92c9522e07b34676e15508a47852569d7d78ec9818c72f82977f9d2c3daa8953ed63b54db554e2f8b98b0ba49f4baf3945ee8c463075cdddb4b3fac9bf2928a5")), message);
+    let signature = hex_to_bytes("0c711b914b7a482b784a690d1cd14cc51c1a44606b86fa8c44658a4c81776fb4638e054b1431d10953a06230718267a25f2bd88d7c599441811ded96ffe74850a3571adae540e42ca61d3ee771fbedfea841d3fffa81c14be4f7307bdefd82f9e0306f4341744bf30d24797f40abe6072f91da655f7eb5b563822a36207f46bf");
+    assert_eq!(rsa.sign(message.clone()), signature);
+
+    let (n, e) = rsa.get_current_public_key();
+    let mut verifier: RSA = RSA::from_public_key(n.value, e.value);
+    verifier.signature_scheme = RSASignatureScheme::PKCS1v15;
+    assert!(verifier.verify(message.clone(), signature.clone()));
+    let mut forged = signature;
+    forged[100] ^= 1;
+    assert!(!verifier.verify(message, forged));
 }
 
 
-fn test_kyber() {
-    println!("Kyber test");
+fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
 
-    let cipher: Kyber512 = Kyber512::init();
-    let plain = random_byte_array(32);
-   
-    let (u, v) = cipher.encrypt(plain.clone());
- 
-    assert_eq!(cipher.decrypt(u, v), plain);
-  
+// the incremental API must agree with the one shot digest, whatever the split of the message
+fn check_incremental_hash<H: HashInterface>() {
+    let message = random_byte_array(1000);
+    for chunk_size in [1, 37, H::BLOCK_SIZE, H::BLOCK_SIZE + 1] {
+        let mut hash = H::new();
+        for chunk in message.chunks(chunk_size) {
+            hash.update(chunk);
+        }
+        assert_eq!(hash.finalize(), H::digest(&message));
+    }
+}
+
+fn test_sha2() {
+    println!("SHA-1 and SHA-2 test");
+
+    // FIPS 180 examples and NIST CAVP messages
+    let message_448 = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    let message_896 = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+    assert_eq!(SHA1::digest(b"abc"), hex_to_bytes("a9993e364706816aba3e25717850c26c9cd0d89d"));
+    assert_eq!(SHA1::digest(message_448), hex_to_bytes("84983e441c3bd26ebaae4aa1f95129e5e54670f1"));
+    assert_eq!(SHA256::digest(b"abc"), hex_to_bytes("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+    assert_eq!(SHA256::digest(b""), hex_to_bytes("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
+    assert_eq!(SHA256::digest(message_896), hex_to_bytes("cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1"));
+    assert_eq!(SHA256::digest(&[b'a'; 1000000]), hex_to_bytes("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"));
+    assert_eq!(SHA384::digest(b"abc"), hex_to_bytes("cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"));
+    assert_eq!(SHA384::digest(message_896), hex_to_bytes("09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712fcc7c71a557e2db966c3e9fa91746039"));
+    assert_eq!(SHA512::digest(b"abc"), hex_to_bytes("ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"));
+    assert_eq!(SHA512::digest(message_896), hex_to_bytes("8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"));
+
+    check_incremental_hash::<SHA1>();
+    check_incremental_hash::<SHA256>();
+    check_incremental_hash::<SHA384>();
+    check_incremental_hash::<SHA512>();
+}
+
+fn test_sha3() {
+    println!("SHA-3 and SHAKE test");
+
+    // FIPS 202 examples: the empty message, "abc" and 200 bytes 0xa3
+    let message_1600 = [0xa3u8; 200];
+    assert_eq!(SHA3_224::digest(b""), hex_to_bytes("6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7"));
+    assert_eq!(SHA3_224::digest(b"abc"), hex_to_bytes("e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf"));
+    assert_eq!(SHA3_224::digest(&message_1600), hex_to_bytes("9376816aba503f72f96ce7eb65ac095deee3be4bf9bbc2a1cb7e11e0"));
+    assert_eq!(SHA3_256::digest(b""), hex_to_bytes("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"));
+    assert_eq!(SHA3_256::digest(b"abc"), hex_to_bytes("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"));
+    assert_eq!(SHA3_256::digest(&message_1600), hex_to_bytes("79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787"));
+    assert_eq!(SHA3_384::digest(b""), hex_to_bytes("0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2ac3713831264adb47fb6bd1e058d5f004"));
+    assert_eq!(SHA3_384::digest(b"abc"), hex_to_bytes("ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"));
+    assert_eq!(SHA3_384::digest(&message_1600), hex_to_bytes("1881de2ca7e41ef95dc4732b8f5f002b189cc1e42b74168ed1732649ce1dbcdd76197a31fd55ee989f2d7050dd473e8f"));
+    assert_eq!(SHA3_512::digest(b""), hex_to_bytes("a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26"));
+    assert_eq!(SHA3_512::digest(b"abc"), hex_to_bytes("b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"));
+    assert_eq!(SHA3_512::digest(&message_1600), hex_to_bytes("e76dfad22084a8b1467fcf2ffa58361bec7628edf5f3fdc0e4805dc48caeeca81b7c13c30adf52a3659584739a2df46be589c51ca1a4a8416df6545a1ce8ba00"));
+
+    assert_eq!(SHAKE128::digest(b"", 32), hex_to_bytes("7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"));
+    assert_eq!(SHAKE128::digest(&message_1600, 64), hex_to_bytes("131ab8d2b594946b9c81333f9bb6e0ce75c3b93104fa3469d3917457385da037cf232ef7164a6d1eb448c8908186ad852d3f85a5cf28da1ab6fe343817197846"));
+    assert_eq!(SHAKE256::digest(b"", 32), hex_to_bytes("46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f"));
+    assert_eq!(SHAKE256::digest(&message_1600, 64), hex_to_bytes("cd8a920ed141aa0407a22d59288652e9d9f1a7ee0c1e7c1ca699424da84a904d2d700caae7396ece96604440577da4f3aa22aeb8857f961c4cd8e06f0ae6610b"));
+
+    // incremental absorbing and squeezing
+    let mut xof = SHAKE256::new();
+    xof.update(b"a");
+    xof.update(b"bc");
+    let mut reader = xof.finalize();
+    let mut output = reader.read(10);
+    output.extend(reader.read(54));
+    assert_eq!(output, hex_to_bytes("483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e4"));
+    let long_output = SHAKE128::digest(&message_1600, 500);
+    let mut reader = SHAKE128::new();
+    reader.update(&message_1600);
+    let mut reader = reader.finalize();
+    let streamed: Vec<u8> = (0..500).flat_map(|_| reader.read(1)).collect();
+    assert_eq!(streamed, long_output);
+
+    check_incremental_hash::<SHA3_224>();
+    check_incremental_hash::<SHA3_256>();
+    check_incremental_hash::<SHA3_384>();
+    check_incremental_hash::<SHA3_512>();
+}
+
+fn test_hmac_kdf() {
+    println!("HMAC, HKDF and PBKDF2 test");
+
+    // RFC 4231 test cases 1, 2 and 6
+    let key = [0x0bu8; 20];
+    assert_eq!(HMAC::<SHA256>::mac(&key, b"Hi There"), hex_to_bytes("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"));
+    assert_eq!(HMAC::<SHA512>::mac(&key, b"Hi There"), hex_to_bytes("87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"));
+    assert_eq!(HMAC::<SHA384>::mac(b"Jefe", b"what do ya want for nothing?"), hex_to_bytes("af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649"));
+    let long_key = [0xaau8; 131];
+    assert_eq!(HMAC::<SHA256>::mac(&long_key, b"Test Using Larger Than Block-Size Key - Hash Key First"), hex_to_bytes("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"));
+    assert_eq!(HMAC::<SHA3_256>::mac(b"key", b"The quick brown fox jumps over the lazy dog"), hex_to_bytes("8c6e0683409427f8931711b10ca92a506eb1fafa48fadd66d76126f47ac2c333"));
+
+    // MACinterface and the incremental API
+    let hmac = HMAC::<SHA256>::new(&key);
+    let tag = hmac.get_authentication_tag(b"Hi There".to_vec());
+    assert!(hmac.verify(b"Hi There".to_vec(), tag.clone()));
+    assert!(!hmac.verify(b"Hi there".to_vec(), tag.clone()));
+    assert!(!hmac.verify(b"Hi There".to_vec(), tag[..16].to_vec()));
+    let mut incremental = HMAC::<SHA256>::new(&key);
+    incremental.update(b"Hi ");
+    incremental.update(b"There");
+    assert_eq!(incremental.finalize(), tag);
+
+    // RFC 5869 test cases 1 and 3
+    let ikm = [0x0bu8; 22];
+    let salt: Vec<u8> = (0x00..=0x0c).collect();
+    let info: Vec<u8> = (0xf0..=0xf9).collect();
+    let hkdf = HKDF::<SHA256>::extract(&salt, &ikm);
+    assert_eq!(hkdf.get_prk(), hex_to_bytes("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5"));
+    assert_eq!(hkdf.expand(&info, 42), hex_to_bytes("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"));
+    assert_eq!(HKDF::<SHA256>::derive(&[], &ikm, &[], 42), hex_to_bytes("8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"));
+    assert_eq!(HKDF::<SHA256>::from_prk(&hkdf.get_prk()).expand(&info, 42), hkdf.expand(&info, 42));
+    assert!(matches!(HKDF::<SHA256>::try_from_prk(&hkdf.get_prk()[..16]), Err(Error::InvalidInput(_))));
+    assert!(matches!(hkdf.try_expand(&info, 255 * 32 + 1), Err(Error::InvalidInput(_))));
+    assert_eq!(hkdf.try_expand(&info, 255 * 32).map(|okm| okm.len()), Ok(255 * 32));
+
+    // RFC 6070 (PBKDF2-HMAC-SHA1) and a PBKDF2-HMAC-SHA256 vector checked against hashlib
+    assert_eq!(pbkdf2::<SHA1>(b"password", b"salt", 1, 20), hex_to_bytes("0c60c80f961f0e71f3a9b524af6012062fe037a6"));
+    assert_eq!(pbkdf2::<SHA1>(b"password", b"salt", 2, 20), hex_to_bytes("ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957"));
+    assert_eq!(pbkdf2::<SHA1>(b"password", b"salt", 4096, 20), hex_to_bytes("4b007901b765489abead49d926f721d065a429c1"));
+    assert_eq!(pbkdf2::<SHA256>(b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, 40), hex_to_bytes("348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9"));
+    assert!(matches!(try_pbkdf2::<SHA256>(b"password", b"salt", 0, 32), Err(Error::InvalidInput(_))));
+
+    // key schedule: AES keys from a Kyber shared secret
+    let alice: Kyber768 = Kyber768::init();
+    let bob: Kyber768 = Kyber768::from_encapsulation_key(alice.get_encapsulation_key());
+    let (shared_secret, ciphertext) = bob.encapsulate();
+    let bob_keys = HKDF::<SHA256>::extract(&[], &shared_secret);
+    let alice_keys = HKDF::<SHA256>::extract(&[], &alice.decapsulate(ciphertext));
+    assert_eq!(alice_keys.expand(b"aes key", 32), bob_keys.expand(b"aes key", 32));
+    assert_ne!(alice_keys.expand(b"aes key", 32), alice_keys.expand(b"mac key", 32));
+}
+
+fn test_ecdsa() {
+    println!("ECDSA test");
+
+    // RFC 6979, appendix A.2.5 and A.2.6
+    let private_key = BigInt::parse_bytes(b"C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721", 16).unwrap();
+    let ecdsa = ECDSA::from_private_key(NamedCurves::P256, private_key.clone(), true);
+    let (x, y) = ecdsa.get_current_public_key().xy();
+    assert_eq!(x.value.value, BigInt::parse_bytes(b"60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6", 16).unwrap());
+    assert_eq!(y.value.value, BigInt::parse_bytes(b"7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299", 16).unwrap());
+    assert_eq!(ecdsa.sign(b"sample".to_vec()), hex_to_bytes("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"));
+    assert_eq!(ecdsa.sign(b"test".to_vec()), hex_to_bytes("f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083"));
+
+    let ecdsa = ECDSA::from_private_key(NamedCurves::P384, BigInt::parse_bytes(b"6B9D3DAD2E1B8C1C05B19875B6659F4DE23C3B667BF297BA9AA47740787137D896D5724E4C70A825F872C9EA60D2EDF5", 16).unwrap(), true);
+    assert_eq!(ecdsa.sign(b"sample".to_vec()), hex_to_bytes("94edbb92a5ecb8aad4736e56c691916b3f88140666ce9fa73d64c4ea95ad133c81a648152e44acf96e36dd1e80fabe4699ef4aeb15f178cea1fe40db2603138f130e740a19624526203b6351d0a3a94fa329c145786e679e7b82c71a38628ac8"));
+    assert_eq!(ecdsa.sign(b"test".to_vec()), hex_to_bytes("8203b63d3c853e8d77227fb377bcf7b7b772e97892a80f36ab775d509d7a5feb0542a7f0812998da8f1dd3ca3cf023dbddd0760448d42d8a43af45af836fce4de8be06b485e9b61b827c2f13173923e06a739f040649a667bf3b828246baa5a5"));
+
+    let ecdsa = ECDSA::from_private_key(NamedCurves::SECP256K1, private_key, true);
+    let signature = ecdsa.sign(b"sample".to_vec());
+    assert_eq!(signature, hex_to_bytes("432310e32cb80eb6503a26ce83cc165c783b870845fb8aad6d970889fcd7a6c8530128b6b81c548874a6305d93ed071ca6e05074d85863d4056ce89b02bfab69"));
+
+    // verification with the public key only
+    let verifier = ECDSA::from_public_key(NamedCurves::SECP256K1, ecdsa.get_current_public_key());
+    assert!(verifier.verify(b"sample".to_vec(), signature.clone()));
+    assert!(!verifier.verify(b"samplE".to_vec(), signature.clone()));
+    let mut tampered = signature.clone();
+    tampered[40] ^= 1;
+    assert!(!verifier.verify(b"sample".to_vec(), tampered));
+    assert!(!verifier.verify(b"sample".to_vec(), signature[..63].to_vec()));
+
+    // random keys and random nonces
+    for curve in [NamedCurves::P256, NamedCurves::P384, NamedCurves::SECP256K1] {
+        let mut ecdsa = ECDSA::init(curve, false);
+        let message = random_byte_array(50);
+        let signature = ecdsa.sign(message.clone());
+        assert!(ecdsa.verify(message.clone(), signature.clone()));
+        assert_ne!(signature, ecdsa.sign(message.clone()));
+
+        ecdsa.refresh_keys();
+        assert!(!ecdsa.verify(message.clone(), signature.clone()));
+        assert!(ecdsa.set_primary_key(0));
+        assert!(ecdsa.verify(message, signature));
+    }
+}
+
+fn test_ed25519() {
+    println!("Ed25519 test");
+
+    // RFC 8032, section 7.1, tests 1 and 2
+    let ed25519 = Ed25519::from_secret_key(hex_to_bytes("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"));
+    assert_eq!(ed25519.get_current_public_key(), hex_to_bytes("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"));
+    assert_eq!(ed25519.sign(vec![]), hex_to_bytes("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"));
+
+    let ed25519 = Ed25519::from_secret_key(hex_to_bytes("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb"));
+    assert_eq!(ed25519.get_current_public_key(), hex_to_bytes("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"));
+    let signature = ed25519.sign(vec![0x72]);
+    assert_eq!(signature, hex_to_bytes("92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"));
+
+    let verifier = Ed25519::from_public_key(ed25519.get_current_public_key());
+    assert!(verifier.verify(vec![0x72], signature.clone()));
+    assert!(!verifier.verify(vec![0x73], signature.clone()));
+    let mut tampered = signature.clone();
+    tampered[10] ^= 1;
+    assert!(!verifier.verify(vec![0x72], tampered));
+
+    let mut ed25519 = Ed25519::init();
+    let message = random_byte_array(100);
+    let signature = ed25519.sign(message.clone());
+    assert!(ed25519.verify(message.clone(), signature.clone()));
+    ed25519.refresh_keys();
+    assert!(!ed25519.verify(message, signature));
+}
+
+
+fn test_ecdh() {
+    println!("ECDH test");
+
+    // the first key is the P-256 key of RFC 6979, appendix A.2.5; the shared secrets and the compressed
+    // public keys of the second one were computed with OpenSSL 3.5 (ECDH derivation of the same keys)
+    let first_key = BigInt::parse_bytes(b"c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721", 16).unwrap();
+    let second_key = BigInt::parse_bytes(b"0f56db78ca460b055c500064824bed999a25aaf48ebb519ac201537b85479813", 16).unwrap();
+    let vectors = [
+        (NamedCurves::P256, "90223373f75e989ab8965d8cc88f01ceb4c622875861771da7bf1a0faccae374", "03e266ddfdc12668db30d4ca3e8f7749432c416044f2d2b8c10bf3d4012aeffa8a"),
+        (NamedCurves::P384, "92c60c92ae2cab7823671343d83935395e750220b2e77643cae012b1efd9cf7b12c6de23ed71df7ee6650b01cc856726", "031ac1f45026e8ebae0306caca806a0fe64dfcf6de8c41b13accfc19e02913ebed5a6586d3dc4bbb371cfa6c02bea1bcc3"),
+        (NamedCurves::SECP256K1, "e9a6859bd005c9c577f3298a6d5a5b95f5fda93e9dcae624f3b32c772d8311f8", "020efc15cf0638b99ca1cef370a74aceccb950d5e0531bc1526ac0fa9ca90c1176")
+    ];
+    for (curve, shared_secret, compressed_key) in vectors {
+        let alice = ECDH::from_private_key(curve, first_key.clone());
+        let bob = ECDH::from_private_key(curve, second_key.clone());
+        assert_eq!(alice.compute_shared_secret(bob.get_public_key()), hex_to_bytes(shared_secret));
+        assert_eq!(bob.compute_shared_secret(alice.get_public_key()), hex_to_bytes(shared_secret));
+        // compressed points are accepted as well
+        assert_eq!(alice.compute_shared_secret(hex_to_bytes(compressed_key)), hex_to_bytes(shared_secret));
+
+        let mut invalid = bob.get_public_key();
+        let last = invalid.len() - 1;
+        invalid[last] ^= 1;
+        assert!(!alice.validate_public_key(invalid));
+        assert!(!alice.validate_public_key(vec![0x00]));
+        assert!(alice.validate_public_key(bob.get_public_key()));
+    }
+
+    // y^2 = x^3 + 2x + 3 over F_97 has 100 points, (3, 6) generates the subgroup of order 5
+    let field = Zmod::new(Some(ZZ::new().new_instance(BigInt::from(97))));
+    let coefficients: Vec<ZmodInstance> = [0, 0, 0, 2, 3].iter().map(|c| field.apply(BigInt::from(*c))).collect();
+    let curve = EllipticCurveFactory::gen_finite_field_curve(coefficients);
+    let generator = curve.point(field.apply(BigInt::from(3)), field.apply(BigInt::from(6)));
+    let outside_subgroup = curve.encode_point(&curve.lift_x(field.apply(BigInt::from(0))).unwrap(), false);
+    let alice = ECDH::from_curve(curve.clone(), generator.clone());
+    let mut bob = ECDH::from_curve(curve, generator);
+    bob.refresh_keys();
+    assert_eq!(alice.compute_shared_secret(bob.get_public_key()), bob.compute_shared_secret(alice.get_public_key()));
+    assert!(!alice.validate_public_key(outside_subgroup));
+}
+
+fn test_xdh() {
+    println!("X25519 and X448 test");
+
+    // RFC 7748, sections 6.1 (X25519) and 6.2 (X448)
+    let vectors = [
+        (MontgomeryCurves::X25519,
+            "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a", "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a",
+            "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb", "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f",
+            "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742"),
+        (MontgomeryCurves::X448,
+            "9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b", "9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0",
+            "1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d", "3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609",
+            "07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936521c24403085d59a449a5037514a879d")
+    ];
+    for (curve, alice_private, alice_public, bob_private, bob_public, shared_secret) in vectors {
+        let alice = XDH::from_private_key(curve, hex_to_bytes(alice_private));
+        let bob = XDH::from_private_key(curve, hex_to_bytes(bob_private));
+        assert!(matches!(XDH::try_from_private_key(curve, hex_to_bytes(alice_private)[1..].to_vec()), Err(Error::InvalidKey(_))));
+        assert_eq!(alice.get_public_key(), hex_to_bytes(alice_public));
+        assert_eq!(bob.get_public_key(), hex_to_bytes(bob_public));
+        assert_eq!(alice.compute_shared_secret(bob.get_public_key()), hex_to_bytes(shared_secret));
+        assert_eq!(bob.compute_shared_secret(alice.get_public_key()), hex_to_bytes(shared_secret));
+
+        // small order points
+        let key_size = alice.get_public_key().len();
+        assert!(!alice.validate_public_key(vec![0u8; key_size]));
+        let mut one = vec![0u8; key_size];
+        one[0] = 1;
+        assert!(!alice.validate_public_key(one));
+        assert!(alice.validate_public_key(bob.get_public_key()));
+
+        let mut alice = XDH::init(curve);
+        let bob = XDH::init(curve);
+        assert_eq!(alice.compute_shared_secret(bob.get_public_key()), bob.compute_shared_secret(alice.get_public_key()));
+        alice.refresh_keys();
+        assert_eq!(alice.public_keys.len(), 2);
+    }
+}
+
+
+struct ResearchKyberParams;
+impl KyberParams for ResearchKyberParams {
+    const K: usize = 5;
+    const ETA1: usize = 2;
+    const ETA2: usize = 2;
+    const DU: usize = 11;
+    const DV: usize = 5;
+}
+
+struct Q7681KyberParams;
+impl KyberParams for Q7681KyberParams {
+    const K: usize = 3;
+    const ETA1: usize = 4;
+    const ETA2: usize = 4;
+    const DU: usize = 11;
+    const DV: usize = 3;
+    const Q: u32 = 7681;
+    const ZETA: u32 = 5685;
+}
+
+fn test_kyber() {
+    println!("ML-KEM test");
+
+    let alice: Kyber768 = Kyber768::init();
+    let bob: Kyber768 = Kyber768::from_encapsulation_key(alice.get_encapsulation_key());
+    let (shared_secret, ciphertext) = bob.encapsulate();
+    assert_eq!(ciphertext.len(), Kyber768::CIPHERTEXT_SIZE);
+    assert_eq!(alice.decapsulate(ciphertext), shared_secret);
+
+    // deterministic vectors with d = 00..1f, z = 20..3f and m = 40..5f, computed with OpenSSL 3.5 (the keys with
+    // `openssl genpkey -algorithm ML-KEM-512 -pkeyopt hexseed:<d || z>`, the encapsulation with its ikme parameter);
+    // keys and ciphertexts are given through their SHA3-256 digest, the last key is the implicit rejection
+    // of the ciphertext with its first bit flipped
+    let d: Vec<u8> = (0..32).collect();
+    let z: Vec<u8> = (32..64).collect();
+    let m: Vec<u8> = (64..96).collect();
+    assert!(matches!(Kyber512::try_from_seed(d[..31].to_vec(), z.clone()), Err(Error::InvalidInput(_))));
+    assert!(matches!(Kyber512::try_from_seed(d.clone(), Vec::new()), Err(Error::InvalidInput(_))));
+    assert!(matches!(Kyber512::from_seed(d.clone(), z.clone()).try_encapsulate_with_seed(m[..16].to_vec()), Err(Error::InvalidInput(_))));
+
+    let kem: Kyber512 = Kyber512::from_seed(d.clone(), z.clone());
+    assert_eq!(SHA3_256::digest(&kem.get_encapsulation_key()), hex_to_bytes("82f101ff648063b376e2bb6c5b7455f655a50c2feadade150efa0e0e6f365aea"));
+    assert_eq!(SHA3_256::digest(&kem.get_decapsulation_key()), hex_to_bytes("0bd3f5df01098ac9c29d687c7f1bd0588a5573feeef8f1e3b4573fa7f6ab57c8"));
+    let (shared_secret, mut ciphertext) = kem.encapsulate_with_seed(m.clone());
+    assert_eq!(SHA3_256::digest(&ciphertext), hex_to_bytes("e3fdddb90255869185c07cdf1c1880b2efe08b6f04da4997b693c0dea61503bd"));
+    assert_eq!(shared_secret, hex_to_bytes("14cace3e48771b316676afad2cfcfe8488daaa4fad954e57236caa3f24a42cf7"));
+    assert_eq!(kem.decapsulate(ciphertext.clone()), shared_secret);
+    ciphertext[0] ^= 1;
+    assert_eq!(kem.decapsulate(ciphertext), hex_to_bytes("32ee1fb3f7bd2915218e9c1b2d0d2da88f0edce6804278bab3a6123c5bb64fc4"));
+
+    let kem: Kyber768 = Kyber768::from_seed(d.clone(), z.clone());
+    assert_eq!(SHA3_256::digest(&kem.get_encapsulation_key()), hex_to_bytes("a24e16d8f8f9383a95b77050f4d9fd2f5733eec1d63ef3c23ebf9918173669a7"));
+    assert_eq!(SHA3_256::digest(&kem.get_decapsulation_key()), hex_to_bytes("1149f17c3c4ac6ab1e3e2d9d8bd0171355ac0fa31bb8855c48ceade874c0864b"));
+    let (shared_secret, mut ciphertext) = kem.encapsulate_with_seed(m.clone());
+    assert_eq!(SHA3_256::digest(&ciphertext), hex_to_bytes("b4cfbd24cef67afd3764276c6980e0f88f8e9ca57f59b7f12fe1a9c1e72f4710"));
+    assert_eq!(shared_secret, hex_to_bytes("9cddd089ffe70e3996e76f7c8d06746df34d07e8657bc0fcf2bb0e1c3084aea1"));
+    assert_eq!(kem.decapsulate(ciphertext.clone()), shared_secret);
+    ciphertext[0] ^= 1;
+    assert_eq!(kem.decapsulate(ciphertext), hex_to_bytes("dcfc80c6db46ff7028e3a4398651c063ae7a42c107a6dc8cb07141861698ab92"));
+
+    let kem: Kyber1024 = Kyber1024::from_seed(d.clone(), z.clone());
+    assert_eq!(SHA3_256::digest(&kem.get_encapsulation_key()), hex_to_bytes("61349e5c131a7e116a0463861d7d18663c5627c38c7147ddaadfd48acd7a4535"));
+    assert_eq!(SHA3_256::digest(&kem.get_decapsulation_key()), hex_to_bytes("f0db5d938027fcd9bad87847d52c14cf0c4abcf0703b749793f212111ffb303b"));
+    let (shared_secret, mut ciphertext) = kem.encapsulate_with_seed(m.clone());
+    assert_eq!(SHA3_256::digest(&ciphertext), hex_to_bytes("c1579fa02c614f3762b2a799b51e41cebb8f820f34fa736af02c56de2460ce3c"));
+    assert_eq!(shared_secret, hex_to_bytes("0ad8d1ea1b8dd788979b4379581218df9321bdce5567eca42ae6be7d395f1a54"));
+    assert_eq!(kem.decapsulate(ciphertext.clone()), shared_secret);
+    ciphertext[0] ^= 1;
+    assert_eq!(kem.decapsulate(ciphertext), hex_to_bytes("8f2c880890996c587aa500cf8b6da03372de706a9f96075744bb0956ea6fbaac"));
+
+    // research parameter sets: a larger module rank, and the q = 7681 of the first Kyber proposal
+    let kem: Kyber<ResearchKyberParams> = Kyber::init();
+    let (shared_secret, ciphertext) = kem.encapsulate();
+    assert_eq!(kem.get_encapsulation_key().len(), Kyber::<ResearchKyberParams>::ENCAPSULATION_KEY_SIZE);
+    assert_eq!(kem.decapsulate(ciphertext), shared_secret);
+
+    let kem: Kyber<Q7681KyberParams> = Kyber::init();
+    let encapsulator: Kyber<Q7681KyberParams> = Kyber::from_encapsulation_key(kem.get_encapsulation_key());
+    let (shared_secret, ciphertext) = encapsulator.encapsulate();
+    assert_eq!(kem.get_decapsulation_key().len(), Kyber::<Q7681KyberParams>::DECAPSULATION_KEY_SIZE);
+    assert_eq!(kem.decapsulate(ciphertext), shared_secret);
 }
 
 fn simple_aes_test() {
@@ -286,7 +752,7 @@ fn simple_aes_test() {
         Simple AES TEST
     */
     let plaintext: Vec<u8> = random_byte_array(16);
-    let mut cipher = aes_factory::init(Modes::GCM, Paddings::PKCS7, AES_KEY_SIZE::AES_256);
+    let mut cipher = aes_factory::init(Modes::NONE, Paddings::PKCS7, AES_KEY_SIZE::AES_256);
 
     let ciphertext = cipher.encrypt(plaintext.clone());
 
@@ -295,8 +761,144 @@ fn simple_aes_test() {
 
 }
 
+fn aes_known_answer_test() {
+    println!("AES known-answer test");
+
+    // FIPS-197 appendices B and C
+    let vectors = [
+        ("2b7e151628aed2a6abf7158809cf4f3c", "3243f6a8885a308d313198a2e0370734", "3925841d02dc09fbdc118597196a0b32"),
+        ("000102030405060708090a0b0c0d0e0f", "00112233445566778899aabbccddeeff", "69c4e0d86a7b0430d8cdb78070b4c55a"),
+        ("000102030405060708090a0b0c0d0e0f1011121314151617", "00112233445566778899aabbccddeeff", "dda97ca4864cdfe06eaf70a0ec0d7191"),
+        ("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", "00112233445566778899aabbccddeeff", "8ea2b7ca516745bfeafc49904b496089"),
+    ];
+    for (key, plaintext, expected) in vectors {
+        let mut cipher = aes_factory::init_with_key(Modes::NONE, Paddings::PKCS7, hex_to_bytes(key));
+        let ciphertext = cipher.encrypt(hex_to_bytes(plaintext));
+        assert_eq!(ciphertext, hex_to_bytes(expected));
+        assert_eq!(cipher.decrypt(ciphertext), hex_to_bytes(plaintext));
+        assert_eq!(cipher.export_key(), hex_to_bytes(key));
+    }
+
+    // NIST SP 800-38A F.1 and F.2, AES-128 and AES-256: PKCS7 appends a full block of padding
+    let plaintext = hex_to_bytes("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710");
+    let iv: Vec<u8> = (0..16).collect();
+    let vectors = [
+        ("2b7e151628aed2a6abf7158809cf4f3c",
+            "3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf43b1cd7f598ece23881b00e3ed0306887b0c785e27e8ad3f8223207104725dd4",
+            "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b273bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7"),
+        ("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+            "f3eed1bdb5d2a03c064b5a7e3db181f8591ccb10d410ed26dc5ba74a31362870b6ed21b99ca6f4f9f153e7b1beafed1d23304b7a39f9f3ff067d8d8f9e24ecc7",
+            "f58c4c04d6e5f1ba779eabfb5f7bfbd69cfc4e967edb808d679f777bc6702c7d39f23369a9d9bacfa530e26304231461b2eb05e2c39be9fcda6c19078c6a9d1b"),
+    ];
+    for (key, ecb, cbc) in vectors {
+        let mut cipher = aes_factory::init_with_key(Modes::ECB, Paddings::PKCS7, hex_to_bytes(key));
+        let ciphertext = cipher.encrypt(plaintext.clone());
+        assert_eq!(ciphertext[..64].to_vec(), hex_to_bytes(ecb));
+        assert_eq!(cipher.decrypt(ciphertext), plaintext);
+
+        let mut cipher = aes_factory::init_with_key_and_iv(Modes::CBC, Paddings::PKCS7, hex_to_bytes(key), iv.clone());
+        let ciphertext = cipher.encrypt(plaintext.clone());
+        assert_eq!(ciphertext[..16].to_vec(), iv);
+        assert_eq!(ciphertext[16..80].to_vec(), hex_to_bytes(cbc));
+        assert_eq!(cipher.decrypt(ciphertext), plaintext);
+    }
+
+    // the same IV through the factory gives the vectors of the other modes
+    let key = hex_to_bytes("2b7e151628aed2a6abf7158809cf4f3c");
+    let mut cipher = aes_factory::init_with_key_and_iv(Modes::CFB128, Paddings::PKCS7, key.clone(), iv.clone());
+    assert_eq!(cipher.encrypt(plaintext[..16].to_vec())[16..].to_vec(), hex_to_bytes("3b3fd92eb72dad20333449f8e83cfb4a"));
+    let nonce = hex_to_bytes("f0f1f2f3f4f5f6f7f8f9fafb");
+    let mut cipher = aes_factory::init_with_key_and_iv(Modes::CTR, Paddings::PKCS7, key.clone(), nonce.clone());
+    let mut expected = plaintext.clone();
+    AES_CTR::from_key(key.clone(), AES_CTR_COUNTER_SIZE::COUNTER_32).apply_keystream(&nonce, 0, 0, &mut expected);
+    assert_eq!(cipher.encrypt(plaintext.clone()), [nonce, expected].concat());
+
+    // a key exported by one instance decrypts in another one
+    for mode in [Modes::CBC, Modes::CTR, Modes::OFB, Modes::CFB8, Modes::GCM, Modes::ECB] {
+        let plaintext: Vec<u8> = random_byte_array(40);
+        let mut sender = aes_factory::init(mode, Paddings::PKCS7, AES_KEY_SIZE::AES_256);
+        let ciphertext = sender.encrypt(plaintext.clone());
+        let mut receiver = aes_factory::init_with_key(mode, Paddings::PKCS7, sender.export_key());
+        assert_eq!(receiver.decrypt(ciphertext), plaintext);
+    }
+    let mut sender = aes_factory::init_with_key_and_iv(Modes::CTR_with_preprocessing, Paddings::PKCS7, key.clone(), vec![7; 12]);
+    let ciphertext = sender.encrypt(plaintext.clone());
+    let mut receiver = aes_factory::init_with_key_and_iv(Modes::CTR_with_preprocessing, Paddings::PKCS7, key.clone(), vec![7; 12]);
+    assert_eq!(receiver.decrypt(ciphertext), plaintext);
+}
+
+fn aes_backend_test() {
+    println!("AES backends test");
+
+    let mut backends = vec![AES_BACKEND::TABLES, AES_BACKEND::BITSLICED];
+    if aesni::is_available() {
+        backends.push(AES_BACKEND::AESNI);
+    }
+
+    // FIPS-197 appendix C with every backend
+    let plaintext = hex_to_bytes("00112233445566778899aabbccddeeff");
+    let vectors = [
+        (16, 10, "69c4e0d86a7b0430d8cdb78070b4c55a"),
+        (24, 12, "dda97ca4864cdfe06eaf70a0ec0d7191"),
+        (32, 14, "8ea2b7ca516745bfeafc49904b496089"),
+    ];
+    for backend in backends.iter() {
+        for (key_length, rounds, expected) in vectors.iter() {
+            let key: Vec<u8> = (0..*key_length as u8).collect();
+            let round_keys = cipher::expand_key(&key, *rounds);
+            let mut block = plaintext.clone();
+            cipher::encrypt_with_backend(*backend, &mut block, &round_keys);
+            assert_eq!(block, hex_to_bytes(expected));
+            cipher::decrypt_with_backend(*backend, &mut block, &round_keys);
+            assert_eq!(block, plaintext);
+        }
+    }
+
+    // the backends agree on random keys and blocks
+    for (key_length, rounds, _) in vectors.iter() {
+        for _ in 0..50 {
+            let round_keys = cipher::expand_key(&random_byte_array(*key_length), *rounds);
+            let block: Vec<u8> = random_byte_array(16);
+            let mut reference = block.clone();
+            cipher::encrypt_with_backend(AES_BACKEND::TABLES, &mut reference, &round_keys);
+            for backend in backends.iter() {
+                let mut ciphertext = block.clone();
+                cipher::encrypt_with_backend(*backend, &mut ciphertext, &round_keys);
+                assert_eq!(ciphertext, reference);
+                cipher::decrypt_with_backend(*backend, &mut ciphertext, &round_keys);
+                assert_eq!(ciphertext, block);
+            }
+        }
+    }
+
+    // each cipher runs on its own backend, chosen when it is built
+    let key = hex_to_bytes("2b7e151628aed2a6abf7158809cf4f3c");
+    let plaintext = hex_to_bytes("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51");
+    for backend in backends.iter() {
+        let mut cipher = aes_factory::init_with_key_and_backend(Modes::ECB, Paddings::PKCS7, key.clone(), *backend);
+        assert_eq!(cipher.get_backend(), *backend);
+        let ciphertext = cipher.encrypt(plaintext.clone());
+        assert_eq!(ciphertext[..32].to_vec(), hex_to_bytes("3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf"));
+        assert_eq!(cipher.decrypt(ciphertext), plaintext);
+        for mode in [Modes::CBC, Modes::CTR, Modes::OFB, Modes::CFB8, Modes::CFB128, Modes::XTS, Modes::GCM] {
+            let key = random_byte_array(if mode == Modes::XTS { 32 } else { 16 });
+            let mut sender = aes_factory::init_with_key_and_backend(mode, Paddings::PKCS7, key.clone(), *backend);
+            let mut receiver = aes_factory::init_with_key(mode, Paddings::PKCS7, key);
+            assert_eq!(receiver.get_backend(), cipher::default_backend());
+            assert_eq!(receiver.decrypt(sender.encrypt(plaintext.clone())), plaintext);
+        }
+    }
+    let mut cipher = aes_factory::init_with_key(Modes::ECB, Paddings::PKCS7, key.clone());
+    if aesni::is_available() {
+        assert!(cipher.try_set_backend(AES_BACKEND::AESNI).is_ok());
+    } else {
+        assert!(matches!(cipher.try_set_backend(AES_BACKEND::AESNI), Err(Error::InvalidInput(_))));
+        assert!(matches!(aes_factory::try_init_with_key_and_backend(Modes::ECB, Paddings::PKCS7, key, AES_BACKEND::AESNI), Err(Error::InvalidInput(_))));
+    }
+}
+
 fn aes_ctr_preprocessing_test() {
-    println!("AES CTR test");
+    println!("AES CTR with preprocessing test");
 
     /*
    
@@ -304,13 +906,76 @@ fn aes_ctr_preprocessing_test() {
     */
 
     let plaintext: Vec<u8> = vec![0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15, 16,17,18,20,21,21,22,33];
-    let mut cipher = aes_factory::init(Modes::CTR, Paddings::PKCS7, AES_KEY_SIZE::AES_256);
+    let mut cipher = aes_factory::init(Modes::CTR_with_preprocessing, Paddings::PKCS7, AES_KEY_SIZE::AES_256);
 
     
     let ciphertext = cipher.encrypt(plaintext.clone());
     assert_eq!(plaintext, cipher.decrypt(ciphertext));
 }
 
+fn aes_ctr_test() {
+    println!("AES CTR test");
+
+    // NIST SP 800-38A F.5.1, F.5.3 and F.5.5: the initial counter block is f0f1...feff
+    let plaintext = hex_to_bytes("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710");
+    let nonce = hex_to_bytes("f0f1f2f3f4f5f6f7f8f9fafb");
+    let vectors = [
+        ("2b7e151628aed2a6abf7158809cf4f3c", "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee"),
+        ("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b", "1abc932417521ca24f2b0459fe7e6e0b090339ec0aa6faefd5ccc2c6f4ce8e941e36b26bd1ebc670d1bd1d665620abf74f78a7f6d29809585a97daec58c6b050"),
+        ("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4", "601ec313775789a5b7a7f504bbf3d228f443e3ca4d62b59aca84e990cacaf5c52b0930daa23de94ce87017ba2d84988ddfc9c58db67aada613c2dd08457941a6"),
+    ];
+    for (key, expected) in vectors {
+        let cipher = AES_CTR::from_key(hex_to_bytes(key), AES_CTR_COUNTER_SIZE::COUNTER_32);
+        let mut data = plaintext.clone();
+        cipher.apply_keystream(&nonce, 0xfcfdfeff, 0, &mut data);
+        assert_eq!(data, hex_to_bytes(expected));
+        cipher.apply_keystream(&nonce, 0xfcfdfeff, 0, &mut data);
+        assert_eq!(data, plaintext);
+
+        // the same stream with a 64-bit counter
+        let cipher = AES_CTR::from_key(hex_to_bytes(key), AES_CTR_COUNTER_SIZE::COUNTER_64);
+        let mut data = plaintext.clone();
+        cipher.apply_keystream(&nonce[..8], 0xf8f9fafbfcfdfeff, 0, &mut data);
+        assert_eq!(data, hex_to_bytes(expected));
+    }
+
+    // seeking: any split of the message gives the same ciphertext
+    let cipher = AES_CTR::from_key(hex_to_bytes("2b7e151628aed2a6abf7158809cf4f3c"), AES_CTR_COUNTER_SIZE::COUNTER_32);
+    let message: Vec<u8> = (0..1000).map(|i| (i % 251) as u8).collect();
+    let mut expected = message.clone();
+    cipher.apply_keystream(&nonce, 7, 0, &mut expected);
+    for split in [1, 5, 16, 17, 500, 999] {
+        let mut stream = cipher.stream(&nonce, 7);
+        let (mut first, mut second) = (message[..split].to_vec(), message[split..].to_vec());
+        stream.apply_keystream(&mut first);
+        stream.apply_keystream(&mut second);
+        assert_eq!([first, second].concat(), expected);
+    }
+    let mut stream = cipher.stream(&nonce, 7);
+    stream.seek(333);
+    let mut middle = message[333..400].to_vec();
+    stream.apply_keystream(&mut middle);
+    assert_eq!(middle, expected[333..400].to_vec());
+    assert_eq!(stream.get_position(), 400);
+
+    // a wrong nonce or an exhausted counter is an error and the position does not move
+    assert!(matches!(cipher.try_stream(&nonce[1..], 0), Err(Error::InvalidInput(_))));
+    let mut stream = cipher.try_stream(&nonce, u32::MAX as u64).unwrap();
+    let mut block = vec![0u8; 16];
+    stream.try_apply_keystream(&mut block).unwrap();
+    let mut next = vec![0u8; 1];
+    assert!(matches!(stream.try_apply_keystream(&mut next), Err(Error::InvalidInput(_))));
+    assert_eq!(next, vec![0u8]);
+    assert_eq!(stream.get_position(), 16);
+
+    // messages longer than the 256 blocks of the preprocessing variant
+    let plaintext: Vec<u8> = random_byte_array(16 * 300 + 5);
+    let mut cipher = aes_factory::init(Modes::CTR, Paddings::PKCS7, AES_KEY_SIZE::AES_256);
+    let ciphertext = cipher.encrypt(plaintext.clone());
+    assert_eq!(ciphertext.len(), 12 + plaintext.len());
+    assert_eq!(plaintext, cipher.decrypt(ciphertext));
+}
+
 
 fn aes_cbc_test() {
     println!("AES CBC test");
@@ -333,4 +998,712 @@ fn aes_ecb_test() {
     
     let ciphertext = cipher.encrypt(plaintext.clone());
     assert_eq!(plaintext, cipher.decrypt(ciphertext));
-}
\ No newline at end of file
+}
+fn aes_ofb_cfb_test() {
+    println!("AES OFB and CFB test");
+
+    // NIST SP 800-38A F.3 and F.4: AES-128, AES-192 and AES-256 with IV 000102...0f
+    let plaintext = hex_to_bytes("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710");
+    let iv: Vec<u8> = (0..16).collect();
+    let keys = [
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+        "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    ];
+    let ofb = [
+        "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed8259740051e9c5fecf64344f7a82260edcc304c6528f659c77866a510d9c1d6ae5e",
+        "cdc80d6fddf18cab34c25909c99a4174fcc28b8d4c63837c09e81700c11004018d9a9aeac0f6596f559c6d4daf59a5f26d9f200857ca6c3e9cac524bd9acc92a",
+        "dc7e84bfda79164b7ecd8486985d38604febdc6740d20b3ac88f6ad82a4fb08d71ab47a086e86eedf39d1c5bba97c4080126141d67f37be8538f5a8be740e484",
+    ];
+    let cfb128 = [
+        "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b26751f67a3cbb140b1808cf187a4f4dfc04b05357c5d1c0eeac4c66f9ff7f2e6",
+        "cdc80d6fddf18cab34c25909c99a417467ce7f7f81173621961a2b70171d3d7a2e1e8a1dd59b88b1c8e60fed1efac4c9c05f9f9ca9834fa042ae8fba584b09ff",
+        "dc7e84bfda79164b7ecd8486985d386039ffed143b28b1c832113c6331e5407bdf10132415e54b92a13ed0a8267ae2f975a385741ab9cef82031623d55b1e471",
+    ];
+    // CFB-8 on the first 18 bytes
+    let cfb8 = ["3b79424c9c0dd436bace9e0ed4586a4f32b9", "cda2521ef0a905ca44cd057cbf0d47a0678a", "dc1f1a8520a64db55fcc8ac554844e889700"];
+    for i in 0..3 {
+        let cipher = AES_OFB::from_key(hex_to_bytes(keys[i]));
+        let ciphertext = cipher.apply_keystream(&iv, &plaintext);
+        assert_eq!(ciphertext, hex_to_bytes(ofb[i]));
+        assert_eq!(cipher.apply_keystream(&iv, &ciphertext), plaintext);
+
+        let cipher = AES_CFB::from_key(hex_to_bytes(keys[i]), AES_CFB_SEGMENT_SIZE::CFB_128);
+        let ciphertext = cipher.encrypt_with_iv(&iv, &plaintext);
+        assert_eq!(ciphertext, hex_to_bytes(cfb128[i]));
+        assert_eq!(cipher.decrypt_with_iv(&iv, &ciphertext), plaintext);
+
+        let cipher = AES_CFB::from_key(hex_to_bytes(keys[i]), AES_CFB_SEGMENT_SIZE::CFB_8);
+        let ciphertext = cipher.encrypt_with_iv(&iv, &plaintext[..18]);
+        assert_eq!(ciphertext, hex_to_bytes(cfb8[i]));
+        assert_eq!(cipher.decrypt_with_iv(&iv, &ciphertext), plaintext[..18].to_vec());
+    }
+
+    // IVs of the wrong length are errors
+    let key = hex_to_bytes(keys[0]);
+    assert!(matches!(AES_OFB::from_key(key.clone()).try_apply_keystream(&iv[..15], &plaintext), Err(Error::InvalidInput(_))));
+    for segment_size in [AES_CFB_SEGMENT_SIZE::CFB_8, AES_CFB_SEGMENT_SIZE::CFB_128] {
+        let cipher = AES_CFB::from_key(key.clone(), segment_size);
+        assert!(matches!(cipher.try_encrypt_with_iv(&[iv.clone(), vec![0]].concat(), &plaintext), Err(Error::InvalidInput(_))));
+        assert!(matches!(cipher.try_decrypt_with_iv(&[], &plaintext), Err(Error::InvalidInput(_))));
+    }
+
+    // partial last segments, through the factory
+    let plaintext: Vec<u8> = random_byte_array(37);
+    for mode in [Modes::OFB, Modes::CFB8, Modes::CFB128] {
+        let mut cipher = aes_factory::init(mode, Paddings::PKCS7, AES_KEY_SIZE::AES_192);
+        let ciphertext = cipher.encrypt(plaintext.clone());
+        assert_eq!(ciphertext.len(), 16 + plaintext.len());
+        assert_eq!(plaintext, cipher.decrypt(ciphertext));
+    }
+}
+
+fn aes_xts_test() {
+    println!("AES XTS test");
+
+    // IEEE 1619 vectors 2, 3 and 15 (ciphertext stealing), and a 21-byte data unit checked with OpenSSL
+    let sector: Vec<u8> = (0..=255).chain(0..=255).collect();
+    let vectors: Vec<(String, u128, Vec<u8>, &str)> = vec![
+        ("11".repeat(16) + &"22".repeat(16), 0x3333333333, vec![0x44; 32], "c454185e6a16936e39334038acef838bfb186fff7480adc4289382ecd6d394f0"),
+        ("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0".to_string() + &"22".repeat(16), 0x3333333333, vec![0x44; 32], "af85336b597afc1a900b2eb21ec949d292df4c047e0b21532186a5971a227a89"),
+        ("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0".to_string(), 0x123456789a, (0..17).collect(), "6c1625db4671522d3d7599601de7ca09ed"),
+        ("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0".to_string(), 0x123456789a, (0..21).collect(), "2cd47e780de4b008d8fde727c1c325f4edbf9dace4"),
+    ];
+    for (key, data_unit, plaintext, expected) in vectors {
+        let cipher = AES_XTS::from_key(hex_to_bytes(&key));
+        let ciphertext = cipher.encrypt_data_unit(data_unit, &plaintext);
+        assert_eq!(ciphertext, hex_to_bytes(expected));
+        assert_eq!(cipher.decrypt_data_unit(data_unit, &ciphertext), plaintext);
+    }
+
+    // 512-byte sectors: IEEE 1619 vectors 4 and 10
+    let cipher = AES_XTS::from_key(hex_to_bytes("2718281828459045235360287471352631415926535897932384626433832795"));
+    let ciphertext = cipher.encrypt_data_unit(0, &sector);
+    assert_eq!(ciphertext[..32].to_vec(), hex_to_bytes("27a7479befa1d476489f308cd4cfa6e2a96e4bbe3208ff25287dd3819616e89c"));
+    assert_eq!(ciphertext[480..].to_vec(), hex_to_bytes("eb4a427d1923ce3ff262735779a418f20a282df920147beabe421ee5319d0568"));
+    assert_eq!(cipher.decrypt_data_unit(0, &ciphertext), sector);
+    let cipher = AES_XTS::from_key(hex_to_bytes("27182818284590452353602874713526624977572470936999595749669676273141592653589793238462643383279502884197169399375105820974944592"));
+    let ciphertext = cipher.encrypt_data_unit(0xff, &sector);
+    assert_eq!(ciphertext[..32].to_vec(), hex_to_bytes("1c3b3a102f770386e4836c99e370cf9bea00803f5e482357a4ae12d414a3e63b"));
+    assert_eq!(ciphertext[480..].to_vec(), hex_to_bytes("773dad38014bd2092fa755c824bb5e54c4f36ffda9fcea70b9c6e693e148c151"));
+    assert_eq!(cipher.decrypt_data_unit(0xff, &ciphertext), sector);
+
+    // through the factory, the ciphertext has the length of the plaintext
+    let plaintext: Vec<u8> = random_byte_array(100);
+    let mut cipher = aes_factory::init(Modes::XTS, Paddings::PKCS7, AES_KEY_SIZE::AES_256);
+    let ciphertext = cipher.encrypt(plaintext.clone());
+    assert_eq!(ciphertext.len(), plaintext.len());
+    assert_eq!(plaintext, cipher.decrypt(ciphertext));
+}
+
+fn aes_gcm_test() {
+    println!("AES GCM test");
+
+    // test cases of the GCM specification (McGrew and Viega) used by NIST, cross-checked with OpenSSL
+    let key = hex_to_bytes("feffe9928665731c6d6a8f9467308308");
+    let plaintext = hex_to_bytes("d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39");
+    let associated_data = hex_to_bytes("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+    let long_iv = hex_to_bytes("9313225df88406e5a55909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39");
+    let vectors = vec![
+        (vec![0; 16], vec![0; 12], vec![], vec![], "", "58e2fccefa7e3061367f1d57a4e7455a"),
+        (vec![0; 16], vec![0; 12], vec![0; 16], vec![], "0388dace60b6a392f328c2b971b2fe78", "ab6e47d42cec13bdf53a67b21257bddf"),
+        (key.clone(), hex_to_bytes("cafebabefacedbaddecaf888"), plaintext.clone(), associated_data.clone(),
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091", "5bc94fbc3221a5db94fae95ae7121a47"),
+        (key.clone(), hex_to_bytes("cafebabefacedbad"), plaintext.clone(), associated_data.clone(),
+            "61353b4c2806934a777ff51fa22a4755699b2a714fcdc6f83766e5f97b6c742373806900e49f24b22b097544d4896b424989b5e1ebac0f07c23f4598", "3612d2e79e3b0785561be14aaca2fccb"),
+        (key.clone(), long_iv.clone(), plaintext.clone(), associated_data.clone(),
+            "d029c111c6bdfdd623b71099d2f95c63da791e92eb85ddb2878f69b77aa33074fd746147c98727f81a71749d7936a41c46428b0dc338adaf27d27fb8", "ab409cef23414822cd79ce54079134f4"),
+        ([key.clone(), key[..8].to_vec()].concat(), hex_to_bytes("cafebabefacedbaddecaf888"), plaintext.clone(), associated_data.clone(),
+            "3980ca0b3c00e841eb06fac4872a2757859e1ceaa6efd984628593b40ca1e19c7d773d00c144c525ac619d18c84a3f4718e2448b2fe324d9ccda2710", "2519498e80f1478f37ba55bd6d27618c"),
+        ([key.clone(), key.clone()].concat(), hex_to_bytes("cafebabefacedbaddecaf888"), plaintext.clone(), associated_data.clone(),
+            "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662", "76fc6ece0f4e1768cddf8853bb2d551b"),
+        ([key.clone(), key.clone()].concat(), long_iv.clone(), plaintext.clone(), associated_data.clone(),
+            "17a8ccd6fbb97ff82bd8a319ce76a0fd588498f02e9f50ce1316ea2feb658593ca12f157aa9f3fc8f7bf9a139abd5ff5c97b789d215468dba5c2c2ec", "d173a613c4f08f605dbfb78a71bde59d"),
+    ];
+    for (key, iv, plaintext, associated_data, expected_ciphertext, expected_tag) in vectors {
+        let gcm = AES_GCM::from_key(key);
+        let (ciphertext, tag) = gcm.encrypt(iv.clone(), plaintext.clone(), associated_data.clone()).unwrap();
+        assert_eq!(ciphertext, hex_to_bytes(expected_ciphertext));
+        assert_eq!(tag, hex_to_bytes(expected_tag));
+        assert_eq!(gcm.decrypt(iv, ciphertext, associated_data, tag), Ok(plaintext));
+    }
+
+    // tampering with the ciphertext, the associated data or the tag is detected
+    let gcm = AES_GCM::from_key(key.clone());
+    let iv = hex_to_bytes("cafebabefacedbaddecaf888");
+    let (ciphertext, tag) = gcm.encrypt(iv.clone(), plaintext.clone(), associated_data.clone()).unwrap();
+    let mut tampered = ciphertext.clone();
+    tampered[0] ^= 1;
+    assert_eq!(gcm.decrypt(iv.clone(), tampered, associated_data.clone(), tag.clone()), Err(AEADError::AuthenticationFailed));
+    assert_eq!(gcm.decrypt(iv.clone(), ciphertext.clone(), vec![], tag.clone()), Err(AEADError::AuthenticationFailed));
+    assert_eq!(gcm.decrypt(iv.clone(), ciphertext.clone(), associated_data.clone(), tag[..12].to_vec()), Err(AEADError::InvalidTag));
+    assert_eq!(gcm.encrypt(vec![], plaintext.clone(), vec![]), Err(AEADError::InvalidNonce));
+
+    // truncated tags are the leftmost bytes of the full tag
+    let mut truncated = AES_GCM::from_key(key.clone());
+    truncated.set_tag_length(12);
+    let (_, short_tag) = truncated.encrypt(iv.clone(), plaintext.clone(), associated_data.clone()).unwrap();
+    assert_eq!(short_tag, tag[..12].to_vec());
+    assert_eq!(truncated.decrypt(iv, ciphertext, associated_data, short_tag), Ok(plaintext.clone()));
+    assert!(matches!(truncated.try_set_tag_length(10), Err(Error::InvalidInput(_))));
+
+    // through the factory the IV is prepended and the tag appended
+    let mut cipher = aes_factory::init(Modes::GCM, Paddings::PKCS7, AES_KEY_SIZE::AES_192);
+    let ciphertext = cipher.encrypt(plaintext.clone());
+    assert_eq!(ciphertext.len(), 12 + plaintext.len() + 16);
+    assert_eq!(plaintext, cipher.decrypt(ciphertext));
+}
+
+fn test_chacha20_poly1305() {
+    println!("ChaCha20-Poly1305 test");
+
+    // RFC 8439 sections 2.3.2, 2.4.2 and 2.5.2
+    let key: Vec<u8> = (0x00..=0x1f).collect();
+    let sunscreen = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.".to_vec();
+    assert_eq!(chacha20_block(&key, 1, &hex_to_bytes("000000090000004a00000000")), hex_to_bytes("10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4ed2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"));
+    let chacha20 = ChaCha20::from_key(key.clone());
+    let mut data = sunscreen.clone();
+    chacha20.apply_keystream(&hex_to_bytes("000000000000004a00000000"), 1, &mut data);
+    assert_eq!(data, hex_to_bytes("6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d"));
+    chacha20.apply_keystream(&hex_to_bytes("000000000000004a00000000"), 1, &mut data);
+    assert_eq!(data, sunscreen);
+
+    let poly1305_key = hex_to_bytes("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b");
+    assert_eq!(Poly1305::mac(&poly1305_key, b"Cryptographic Forum Research Group"), hex_to_bytes("a8061dc1305136c6c22b8baf0c0127a9"));
+    let poly1305 = Poly1305::new(&poly1305_key);
+    let tag = poly1305.get_authentication_tag(b"Cryptographic Forum Research Group".to_vec());
+    assert!(poly1305.verify(b"Cryptographic Forum Research Group".to_vec(), tag.clone()));
+    assert!(!poly1305.verify(b"Cryptographic Forum Research Grouq".to_vec(), tag));
+    assert!(matches!(Poly1305::try_new(&poly1305_key[..16]), Err(Error::InvalidKey(_))));
+    // accumulators close to 2^130 - 5, cross-checked with OpenSSL
+    assert_eq!(Poly1305::mac(&[0xff; 32], &[0xff; 100]), hex_to_bytes("b99c030d7ce939bb6607393e68656f22"));
+    assert_eq!(Poly1305::mac(&[[2u8].as_slice(), &[0; 15], &[0xff; 16]].concat(), &[0xff; 16]), hex_to_bytes("02000000000000000000000000000000"));
+
+    // RFC 8439 section 2.8.2
+    let key: Vec<u8> = (0x80..=0x9f).collect();
+    let associated_data = hex_to_bytes("50515253c0c1c2c3c4c5c6c7");
+    let nonce = hex_to_bytes("070000004041424344454647");
+    let aead = ChaCha20Poly1305::from_key(key.clone());
+    let (ciphertext, tag) = aead.encrypt(nonce.clone(), sunscreen.clone(), associated_data.clone()).unwrap();
+    assert_eq!(ciphertext, hex_to_bytes("d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b6116"));
+    assert_eq!(tag, hex_to_bytes("1ae10b594f09e26a7e902ecbd0600691"));
+    assert_eq!(aead.decrypt(nonce.clone(), ciphertext.clone(), associated_data.clone(), tag.clone()), Ok(sunscreen.clone()));
+
+    // tampering with the ciphertext, the associated data or the tag is detected
+    let mut tampered = ciphertext.clone();
+    tampered[0] ^= 1;
+    assert_eq!(aead.decrypt(nonce.clone(), tampered, associated_data.clone(), tag.clone()), Err(AEADError::AuthenticationFailed));
+    assert_eq!(aead.decrypt(nonce.clone(), ciphertext.clone(), vec![], tag.clone()), Err(AEADError::AuthenticationFailed));
+    assert_eq!(aead.decrypt(nonce.clone(), ciphertext, associated_data.clone(), tag[..12].to_vec()), Err(AEADError::InvalidTag));
+    assert_eq!(aead.encrypt(vec![0; 8], sunscreen.clone(), vec![]), Err(AEADError::InvalidNonce));
+
+    // XChaCha20 (draft-irtf-cfrg-xchacha sections 2.2.1 and A.3.1), cross-checked with libsodium
+    assert_eq!(hchacha20(&(0x00..=0x1f).collect::<Vec<u8>>(), &hex_to_bytes("000000090000004a0000000031415927")), hex_to_bytes("82413b4227b27bfed30e42508a877d73a0f9e4d58a74a853c12ec41326d3ecdc"));
+    let xnonce = hex_to_bytes("404142434445464748494a4b4c4d4e4f5051525354555657");
+    let xaead = XChaCha20Poly1305::from_key(key.clone());
+    let (ciphertext, tag) = xaead.encrypt(xnonce.clone(), sunscreen.clone(), associated_data.clone()).unwrap();
+    assert_eq!(ciphertext, hex_to_bytes("bd6d179d3e83d43b9576579493c0e939572a1700252bfaccbed2902c21396cbb731c7f1b0b4aa6440bf3a82f4eda7e39ae64c6708c54c216cb96b72e1213b4522f8c9ba40db5d945b11b69b982c1bb9e3f3fac2bc369488f76b2383565d3fff921f9664c97637da9768812f615c68b13b52e"));
+    assert_eq!(tag, hex_to_bytes("c0875924c1c7987947deafd8780acf49"));
+    assert_eq!(xaead.decrypt(xnonce.clone(), ciphertext, associated_data.clone(), tag), Ok(sunscreen.clone()));
+    assert_eq!(xaead.encrypt(nonce, sunscreen.clone(), vec![]), Err(AEADError::InvalidNonce));
+
+    let mut data = sunscreen[..64].to_vec();
+    XChaCha20::from_key(key).apply_keystream(&xnonce, 0, &mut data);
+    assert_eq!(data, hex_to_bytes("37787be99612d0f8672b4f0cead7099422a10d1d889dd7b0a91be551e09566a6d2eb485e7b270ba647fc5b16799fa8463ed44c83437c348fd54a350b86253535"));
+
+    // random keys and a long message over many parallel blocks
+    let aead = ChaCha20Poly1305::new();
+    let message = random_byte_array(10000);
+    let (ciphertext, tag) = aead.encrypt(vec![0; 12], message.clone(), vec![]).unwrap();
+    assert_eq!(aead.decrypt(vec![0; 12], ciphertext, vec![], tag), Ok(message));
+}
+
+fn padding_test() {
+    println!("Padding test");
+
+    let message = b"hello".to_vec();
+    assert_eq!(PKCS7::pad(message.clone(), 16), [message.clone(), vec![0x0b; 11]].concat());
+    assert_eq!(ANSI_X9_23::pad(message.clone(), 8), [message.clone(), vec![0, 0, 3]].concat());
+    assert_eq!(ISO_IEC_7816_4::pad(message.clone(), 8), [message.clone(), vec![0x80, 0, 0]].concat());
+    assert_eq!(PKCS7::pad(vec![0; 16], 16), [vec![0; 16], vec![16; 16]].concat());
+
+    // round trips for every length around the block size
+    for padding in [Paddings::ANSI_X9_23, Paddings::ISO_10126, Paddings::PKCS7, Paddings::ISO_IEC_7816_4] {
+        let generic_padding = GenericPadding::init(padding);
+        for length in 0..40 {
+            let message = random_byte_array(length);
+            let padded_message = generic_padding.pad(message.clone(), 16);
+            assert_eq!(padded_message.len() % 16, 0);
+            assert_eq!(generic_padding.unpad(padded_message, 16), Ok(message));
+        }
+    }
+
+    // malformed paddings are errors
+    assert_eq!(PKCS7::unpad(vec![], 16), Err(PaddingError::InvalidLength));
+    assert_eq!(PKCS7::unpad(vec![1; 15], 16), Err(PaddingError::InvalidLength));
+    assert_eq!(PKCS7::unpad([vec![0; 15], vec![0]].concat(), 16), Err(PaddingError::InvalidPadding));
+    assert_eq!(PKCS7::unpad([vec![0; 15], vec![17]].concat(), 16), Err(PaddingError::InvalidPadding));
+    assert_eq!(PKCS7::unpad([vec![0; 13], vec![2, 3, 3]].concat(), 16), Err(PaddingError::InvalidPadding));
+    assert_eq!(ANSI_X9_23::unpad([vec![0; 13], vec![1, 0, 3]].concat(), 16), Err(PaddingError::InvalidPadding));
+    assert_eq!(ISO_10126::unpad([vec![0; 15], vec![0]].concat(), 16), Err(PaddingError::InvalidPadding));
+    assert_eq!(ISO_IEC_7816_4::unpad(vec![0; 16], 16), Err(PaddingError::InvalidPadding));
+    assert_eq!(ISO_IEC_7816_4::unpad([vec![0x80; 14], vec![1, 0]].concat(), 16), Err(PaddingError::InvalidPadding));
+    assert_eq!(ISO_IEC_7816_4::unpad([vec![0x80; 14], vec![0x80, 0]].concat(), 16), Ok(vec![0x80; 14]));
+
+    // CBC reports bad lengths and bad paddings with the same error
+    let key = random_byte_array(16);
+    let mut cbc = AES_CBC::from_key(Paddings::PKCS7, key.clone());
+    let mut cipher = aes_factory::init_with_key(Modes::CBC, Paddings::PKCS7, key);
+    let ciphertext = cipher.encrypt(message.clone());
+    assert_eq!(cbc.try_decrypt(ciphertext.clone()), Ok(message.clone()));
+    assert_eq!(cbc.try_decrypt(ciphertext[..16].to_vec()), Err(Error::Padding(PaddingError::InvalidPadding)));
+    assert_eq!(cbc.try_decrypt(ciphertext[..31].to_vec()), Err(Error::Padding(PaddingError::InvalidPadding)));
+    let mut invalid_padding = 0;
+    for byte in 0..=255u8 {
+        let mut tampered = ciphertext.clone();
+        tampered[15] = byte;
+        match cbc.try_decrypt(tampered) {
+            Ok(_) => (),
+            Err(error) => {
+                assert_eq!(error, Error::Padding(PaddingError::InvalidPadding));
+                invalid_padding += 1;
+            }
+        }
+    }
+    assert!(invalid_padding >= 254);
+
+    // ISO/IEC 9797-1 padding methods 1, 2 and 3
+    assert_eq!(ISO_IEC_9797_1_M1::pad(message.clone(), 8), [message.clone(), vec![0, 0, 0]].concat());
+    assert_eq!(ISO_IEC_9797_1_M1::pad(vec![1; 16], 16), vec![1; 16]);
+    assert_eq!(ISO_IEC_9797_1_M1::pad(vec![], 16), vec![0; 16]);
+    assert_eq!(ISO_IEC_9797_1_M1::unpad([message.clone(), vec![0, 0, 0]].concat(), 8), Ok(message.clone()));
+    assert_eq!(ISO_IEC_9797_1_M2::pad(message.clone(), 8), [message.clone(), vec![0x80, 0, 0]].concat());
+    assert_eq!(ISO_IEC_9797_1_M3::pad(message.clone(), 8), [vec![0, 0, 0, 0, 0, 0, 0, 40], message.clone(), vec![0, 0, 0]].concat());
+    assert_eq!(ISO_IEC_9797_1_M3::pad(vec![], 16), [vec![0; 16]].concat());
+    for length in 0..40 {
+        let message = random_byte_array(length);
+        for padding in [Paddings::ISO_IEC_9797_1_M2, Paddings::ISO_IEC_9797_1_M3] {
+            let generic_padding = GenericPadding::init(padding);
+            assert_eq!(generic_padding.unpad(generic_padding.pad(message.clone(), 16), 16), Ok(message.clone()));
+        }
+    }
+    assert_eq!(ISO_IEC_9797_1_M3::unpad([vec![0, 0, 0, 0, 0, 0, 0, 41], message.clone(), vec![0, 0, 0]].concat(), 8), Err(PaddingError::InvalidPadding));
+    assert_eq!(ISO_IEC_9797_1_M3::unpad([vec![0, 0, 0, 0, 0, 0, 0, 40], message.clone(), vec![0, 0, 1]].concat(), 8), Err(PaddingError::InvalidPadding));
+    assert_eq!(ISO_IEC_9797_1_M3::unpad([vec![0, 0, 0, 0, 0, 0, 0, 128], message.clone(), vec![0, 0, 0]].concat(), 8), Err(PaddingError::InvalidPadding));
+
+    // CBC ciphertext stealing: RFC 3962 test vectors are CBC-CS3 with a zero IV
+    let key = hex_to_bytes("636869636b656e207465726979616b69");
+    let vectors = [
+        ("4920776f756c64206c696b652074686520", "c6353568f2bf8cb4d8a580362da7ff7f97"),
+        ("4920776f756c64206c696b65207468652047656e6572616c20476175277320", "fc00783e0efdb2c1d445d4c8eff7ed2297687268d6ecccc0c07b25e25ecfe5"),
+        ("4920776f756c64206c696b65207468652047656e6572616c2047617527732043", "39312523a78662d5be7fcbcc98ebf5a897687268d6ecccc0c07b25e25ecfe584"),
+        ("4920776f756c64206c696b65207468652047656e6572616c20476175277320436869636b656e2c20706c656173652c", "97687268d6ecccc0c07b25e25ecfe584b3fffd940c16a18c1b5549d2f838029e39312523a78662d5be7fcbcc98ebf5"),
+        ("4920776f756c64206c696b65207468652047656e6572616c20476175277320436869636b656e2c20706c656173652c20616e6420776f6e746f6e20736f75702e", "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a84807efe836ee89a526730dbc2f7bc8409dad8bbb96c4cdc03bc103e1a194bbd8"),
+    ];
+    for (plaintext, expected_ciphertext) in vectors {
+        let mut cipher = aes_factory::init_with_key_and_iv(Modes::CBC, Paddings::CBC_CS3, key.clone(), vec![0; 16]);
+        let ciphertext = cipher.encrypt(hex_to_bytes(plaintext));
+        assert_eq!(ciphertext, [vec![0; 16], hex_to_bytes(expected_ciphertext)].concat());
+        assert_eq!(cipher.decrypt(ciphertext), hex_to_bytes(plaintext));
+    }
+
+    // CBC-CS1 keeps the order of the blocks, CBC-CS2 swaps the last two only when the last one is partial
+    let key: Vec<u8> = (0x00..=0x0f).collect();
+    let iv: Vec<u8> = (0x10..=0x1f).collect();
+    let vectors = [
+        (31, "bbedd27aedb27c515396335ea59cdc5b58aa0e4c9b34a5e0e8fa9693af7e94", "5b58aa0e4c9b34a5e0e8fa9693af7e94bbedd27aedb27c515396335ea59cdc"),
+        (32, "bbedd27aedb27c515396335ea59cdcb7a0441da14b84c8261d8a99271088166f", "bbedd27aedb27c515396335ea59cdcb7a0441da14b84c8261d8a99271088166f"),
+        (47, "bbedd27aedb27c515396335ea59cdcb7a0441da14b84c8261d8a9927108816bcff89f19cf8b5c3b0a49bd699a420fe", "bbedd27aedb27c515396335ea59cdcb7bcff89f19cf8b5c3b0a49bd699a420fea0441da14b84c8261d8a9927108816"),
+    ];
+    for (length, expected_cs1, expected_cs2) in vectors {
+        let plaintext: Vec<u8> = (100..100 + length).collect();
+        for (padding, expected_ciphertext) in [(Paddings::CBC_CS1, expected_cs1), (Paddings::CBC_CS2, expected_cs2)] {
+            let mut cipher = aes_factory::init_with_key_and_iv(Modes::CBC, padding, key.clone(), iv.clone());
+            let ciphertext = cipher.encrypt(plaintext.clone());
+            assert_eq!(ciphertext, [iv.clone(), hex_to_bytes(expected_ciphertext)].concat());
+            assert_eq!(cipher.decrypt(ciphertext), plaintext);
+        }
+    }
+    for length in 16..70 {
+        let plaintext = random_byte_array(length);
+        let mut cipher = aes_factory::init(Modes::CBC, Paddings::CBC_CS2, AES_KEY_SIZE::AES_256);
+        let ciphertext = cipher.encrypt(plaintext.clone());
+        assert_eq!(ciphertext.len(), 16 + length);
+        assert_eq!(cipher.decrypt(ciphertext), plaintext);
+    }
+}
+
+fn error_test() {
+    println!("Error handling test");
+    let zz = ZZ::new();
+
+    // polynomials in different variables
+    let x: Var = Var::new("x", BigInt::from(1));
+    let y: Var = Var::new("y", BigInt::from(1));
+    let f = UnivariatePolynomial::new_instance(vec![zz.new_instance(BigInt::from(1)), zz.new_instance(BigInt::from(2))], x.clone(), None, false);
+    let g = UnivariatePolynomial::new_instance(vec![zz.new_instance(BigInt::from(3)), zz.new_instance(BigInt::from(4))], y, None, false);
+    assert!(matches!(UnivariatePolynomial::try_mul(f.clone(), g.clone()), Err(Error::IncompatibleOperands(_))));
+    assert!(matches!(UnivariatePolynomial::try_add(f.clone(), g), Err(Error::IncompatibleOperands(_))));
+    assert!(UnivariatePolynomial::try_mul(f.clone(), f.clone()).is_ok());
+
+    // matrices
+    let entries = |values: Vec<Vec<i64>>| values.into_iter().map(|row| row.into_iter().map(|v| zz.new_instance(BigInt::from(v))).collect()).collect::<Vec<Vec<ZZinstance>>>();
+    let square: Matrix<ZZinstance> = Matrix::new(entries(vec![vec![1, 2], vec![2, 4]]), 2, 2);
+    let wide: Matrix<ZZinstance> = Matrix::new(entries(vec![vec![1, 2, 3]]), 1, 3);
+    assert!(matches!(square.clone().try_add(wide.clone()), Err(Error::DimensionMismatch(_))));
+    assert!(matches!(square.clone().try_mul(wide.clone()), Err(Error::DimensionMismatch(_))));
+    assert!(matches!(square.try_inverse(), Err(Error::NotInvertible(_))));
+    assert_eq!(wide.try_get_ith_vector(5).err(), Some(Error::IndexOutOfRange));
+
+    // finite fields
+    let field: Zmod = Zmod::new(Some(zz.new_instance(BigInt::from(12))));
+    assert!(matches!(field.clone().try_inverse(field.apply(BigInt::from(4))), Err(Error::NotInvertible(_))));
+    assert_eq!(field.clone().try_inverse(field.apply(BigInt::from(5))).unwrap().value.value, BigInt::from(5));
+    let other_field: Zmod = Zmod::new(Some(zz.new_instance(BigInt::from(7))));
+    assert!(matches!(field.apply(BigInt::from(1)).try_add(other_field.apply(BigInt::from(1))), Err(Error::IncompatibleOperands(_))));
+
+    // symmetric ciphers
+    assert!(matches!(aes_factory::try_init_with_key(Modes::CBC, Paddings::PKCS7, vec![0u8; 15]), Err(Error::InvalidKey(_))));
+    let mut aes = aes_factory::init(Modes::NONE, Paddings::PKCS7, AES_KEY_SIZE::AES_128);
+    assert!(matches!(aes.try_encrypt(vec![0u8; 17]), Err(Error::InvalidInput(_))));
+    assert!(matches!(aes.try_encrypt(vec![1, 2, 3]), Err(Error::InvalidInput(_))));
+    assert!(matches!(aes.try_decrypt(vec![0u8; 15]), Err(Error::InvalidInput(_))));
+    assert!(matches!(aes_factory::try_init(Modes::XTS, Paddings::PKCS7, AES_KEY_SIZE::AES_192), Err(Error::InvalidKey(_))));
+    assert!(matches!(ChaCha20::try_from_key(vec![0u8; 16]), Err(Error::InvalidKey(_))));
+    assert!(matches!(XChaCha20Poly1305::try_from_key(vec![0u8; 31]), Err(Error::InvalidKey(_))));
+    assert!(matches!(Ed25519::try_from_secret_key(vec![0u8; 31]), Err(Error::InvalidKey(_))));
+    assert!(matches!(XChaCha20::new().try_apply_keystream(&[0u8; 12], 0, &mut [0u8; 10]), Err(Error::InvalidInput(_))));
+    let mut ctr = aes_factory::init(Modes::CTR, Paddings::PKCS7, AES_KEY_SIZE::AES_128);
+    assert!(matches!(ctr.try_decrypt(vec![0u8; 3]), Err(Error::InvalidInput(_))));
+
+    // asymmetric ciphers
+    let rsa: RSA = RSA::init(1024);
+    assert_eq!(rsa.try_decrypt(vec![1u8; 5]), Err(Error::DecryptionFailed));
+    let verifier: RSA = RSA::from_public_key(rsa.public_keys[0].0.value.clone(), rsa.public_keys[0].1.value.clone());
+    assert!(matches!(verifier.try_sign(vec![1, 2, 3]), Err(Error::InvalidKey(_))));
+    let x25519: XDH = XDH::init(MontgomeryCurves::X25519);
+    assert!(matches!(x25519.try_compute_shared_secret(vec![0u8; 31]), Err(Error::InvalidKey(_))));
+    assert!(matches!(x25519.try_compute_shared_secret(vec![0u8; 32]), Err(Error::InvalidKey(_))));
+}
+
+fn test_poly_multiplication() {
+    println!("Polynomial multiplication test");
+    let zz = ZZ::new();
+    let x: Var = Var::new("x", BigInt::from(1));
+    let algorithms = [PolyMultiplicationAlgorithm::Karatsuba, PolyMultiplicationAlgorithm::ToomCook, PolyMultiplicationAlgorithm::NTT];
+    let random_integers = |length: usize, bound: i64| (0..length).map(|_| get_random_bigint_with_bounds(BigInt::from(-bound), BigInt::from(bound))).collect::<Vec<BigInt>>();
+
+    // integer coefficients, balanced and unbalanced operands
+    for (length_a, length_b, bound) in [(1, 1, 10), (7, 300, 1000), (150, 150, 1 << 40), (400, 257, 1 << 62), (513, 1000, 5)] {
+        let to_poly = |values: &Vec<BigInt>, algorithm: Option<PolyMultiplicationAlgorithm>| UnivariatePolynomial::new_instance(values.iter().map(|v| zz.new_instance(v.clone())).collect::<Vec<ZZinstance>>(), x.clone(), algorithm, true);
+        let (a, b) = (random_integers(length_a, bound), random_integers(length_b, bound));
+        let expected = to_poly(&a, Some(PolyMultiplicationAlgorithm::Naive)) * to_poly(&b, None);
+        for algorithm in algorithms {
+            assert!(to_poly(&a, Some(algorithm)) * to_poly(&b, None) == expected);
+        }
+        assert!(to_poly(&a, None) * to_poly(&b, None) == expected);
+    }
+
+    // Zmod coefficients, with a prime and a composite modulus
+    for modulus in [3329, 6] {
+        let field: Zmod = Zmod::new(Some(zz.new_instance(BigInt::from(modulus))));
+        let to_poly = |values: &Vec<BigInt>, algorithm: Option<PolyMultiplicationAlgorithm>| UnivariatePolynomial::new_instance(values.iter().map(|v| field.apply(v.clone())).collect::<Vec<ZmodInstance>>(), x.clone(), algorithm, false);
+        let (a, b) = (random_integers(256, modulus), random_integers(256, modulus));
+        let expected = to_poly(&a, Some(PolyMultiplicationAlgorithm::Naive)) * to_poly(&b, None);
+        assert_eq!(expected.coefficients.len(), 511);
+        for algorithm in algorithms {
+            assert!(to_poly(&a, Some(algorithm)) * to_poly(&b, None) == expected);
+        }
+    }
+
+    // degree 4096 products are chosen to use the NTT
+    let field: Zmod = Zmod::new(Some(zz.new_instance(BigInt::from(3329))));
+    let a = UnivariatePolynomial::new_instance(random_integers(4097, 3329).into_iter().map(|v| field.apply(v)).collect::<Vec<ZmodInstance>>(), x.clone(), None, true);
+    let b = UnivariatePolynomial::new_instance(random_integers(4097, 3329).into_iter().map(|v| field.apply(v)).collect::<Vec<ZmodInstance>>(), x.clone(), None, true);
+    let product = a.clone() * b.clone();
+    let point = field.apply(BigInt::from(17));
+    let evaluate = |p: &sage_math::poly::instances::univariate_polynomial_instance::UnivariatePolynomialInstance<ZmodInstance>| p.coefficients.iter().rev().fold(field.zero(), |acc, c| acc * point.clone() + c.clone());
+    assert!(evaluate(&product) == evaluate(&a) * evaluate(&b));
+}
+
+fn test_multivariate_polynomials() {
+    println!("Multivariate polynomials test");
+    let zz = ZZ::new();
+    let term = |coefficient: i64, exponents: &[(&'static str, i64)]| Monomial::new_monomial(exponents.iter().map(|(symbol, e)| Var::new(symbol, BigInt::from(*e))).collect(), zz.new_instance(BigInt::from(coefficient)));
+    let poly = |terms: Vec<MonomialInstance<ZZinstance>>| Polynomial::new(terms);
+    let x = poly(vec![term(1, &[("x", 1)])]);
+    let y = poly(vec![term(1, &[("y", 1)])]);
+
+    // like terms are collected
+    assert!(poly(vec![term(2, &[("x", 1), ("y", 1)]), term(3, &[("y", 1), ("x", 1)]), term(1, &[("x", 2)])]) == poly(vec![term(1, &[("x", 2)]), term(5, &[("x", 1), ("y", 1)])]));
+    assert!((x.clone() + y.clone()) * (x.clone() - y.clone()) == poly(vec![term(1, &[("x", 2)]), term(-1, &[("y", 2)])]));
+    assert!((x.clone() - x.clone()).is_zero());
+    let cube = (x.clone() + y.clone()).pow(3);
+    assert_eq!(cube.monomials.len(), 4);
+    assert!(cube == poly(vec![term(1, &[("x", 3)]), term(3, &[("x", 2), ("y", 1)]), term(3, &[("x", 1), ("y", 2)]), term(1, &[("y", 3)])]));
+    assert_eq!(cube.total_degree(), BigInt::from(3));
+
+    // monomial orders
+    let a = term(1, &[("x", 1), ("y", 5), ("z", 2)]);
+    let b = term(1, &[("x", 4), ("y", 1), ("z", 3)]);
+    assert_eq!(MonomialOrder::Lex.compare(&a, &b, &[]), std::cmp::Ordering::Less);
+    assert_eq!(MonomialOrder::GrLex.compare(&a, &b, &[]), std::cmp::Ordering::Less);
+    assert_eq!(MonomialOrder::GrevLex.compare(&a, &b, &[]), std::cmp::Ordering::Greater);
+    assert_eq!(MonomialOrder::Lex.compare(&a, &b, &["z", "y", "x"]), std::cmp::Ordering::Less);
+    assert_eq!(MonomialOrder::Lex.compare(&term(1, &[("x", 2)]), &term(1, &[("x", 1), ("y", 3)]), &[]), std::cmp::Ordering::Greater);
+    let f: PolynomialInstance<ZZinstance> = poly(vec![term(1, &[("x", 2)]), term(1, &[("x", 1), ("y", 3)])]);
+    assert!(f.leading_monomial().unwrap() == term(1, &[("x", 1), ("y", 3)]));
+    assert!(f.with_order(MonomialOrder::Lex, Vec::new()).leading_monomial().unwrap() == term(1, &[("x", 2)]));
+
+    // derivatives and evaluation
+    let g = poly(vec![term(1, &[("x", 3), ("y", 1)]), term(2, &[("x", 1), ("y", 2)]), term(5, &[])]);
+    assert!(g.derivative("x") == poly(vec![term(3, &[("x", 2), ("y", 1)]), term(2, &[("y", 2)])]));
+    assert!(g.derivative("z").is_zero());
+    let values = [("x", zz.new_instance(BigInt::from(2))), ("y", zz.new_instance(BigInt::from(3)))];
+    assert_eq!(g.evaluate(&values).value, BigInt::from(65));
+    assert!(g.substitute(&values[..1]) == poly(vec![term(8, &[("y", 1)]), term(4, &[("y", 2)]), term(5, &[])]));
+    assert!(matches!(g.try_evaluate(&values[1..]), Err(Error::InvalidInput(_))));
+
+    // division
+    let (q, r) = (x.clone().pow(2) - y.clone().pow(2)).div_rem(&(x.clone() - y.clone()));
+    assert!(q == x.clone() + y.clone() && r.is_zero());
+
+    // Zmod coefficients: (x + 1)^7 = x^7 + 1 mod 7
+    let field: Zmod = Zmod::new(Some(zz.new_instance(BigInt::from(7))));
+    let x_plus_one = Polynomial::new(vec![Monomial::new_monomial(vec![Var::new("x", BigInt::from(1))], field.apply(BigInt::from(1))), Monomial::new_monomial(Vec::new(), field.apply(BigInt::from(1)))]);
+    assert_eq!(x_plus_one.pow(7).monomials.len(), 2);
+    assert_eq!(x_plus_one.pow(7).total_degree(), BigInt::from(7));
+}
+
+fn test_groebner() {
+    println!("Groebner bases test");
+    let qq = QQ::new();
+    let term = |numerator: i64, denominator: i64, exponents: &[(&'static str, i64)]| Monomial::new_monomial(exponents.iter().map(|(symbol, e)| Var::new(symbol, BigInt::from(*e))).collect(), qq.new_instance(BigInt::from(numerator), BigInt::from(denominator)));
+    let poly = |terms| Polynomial::new_with_order(terms, MonomialOrder::GrLex, vec!["x", "y"]);
+
+    // x^3 - 2xy, x^2 y - 2y^2 + x gives x^2, xy, y^2 - x/2
+    let f1 = poly(vec![term(1, 1, &[("x", 3)]), term(-2, 1, &[("x", 1), ("y", 1)])]);
+    let f2 = poly(vec![term(1, 1, &[("x", 2), ("y", 1)]), term(-2, 1, &[("y", 2)]), term(1, 1, &[("x", 1)])]);
+    let ideal = Ideal::new(vec![f1.clone(), f2.clone()]);
+    let expected = vec![poly(vec![term(1, 1, &[("x", 2)])]), poly(vec![term(1, 1, &[("x", 1), ("y", 1)])]), poly(vec![term(1, 1, &[("y", 2)]), term(-1, 2, &[("x", 1)])])];
+    assert!(ideal.groebner_basis(GroebnerAlgorithm::Buchberger) == expected);
+    assert!(ideal.groebner_basis(GroebnerAlgorithm::F4) == expected);
+
+    // ideal membership
+    assert!(ideal.contains(&poly(vec![term(1, 1, &[("x", 2)])])));
+    assert!(ideal.contains(&(f1.clone() * poly(vec![term(3, 1, &[("y", 2)])]) + f2.clone())));
+    assert!(!ideal.contains(&poly(vec![term(1, 1, &[("x", 1)])])));
+    assert!(ideal.reduce(&poly(vec![term(1, 1, &[("y", 3)]), term(1, 1, &[])])) == poly(vec![term(1, 1, &[])]));
+
+    // elimination: x^2 + y + z - 1, x + y^2 + z - 1, x + y + z^2 - 1
+    let g = |terms| Polynomial::new_with_order(terms, MonomialOrder::Lex, vec!["x", "y", "z"]);
+    let system = Ideal::new(vec![
+        g(vec![term(1, 1, &[("x", 2)]), term(1, 1, &[("y", 1)]), term(1, 1, &[("z", 1)]), term(-1, 1, &[])]),
+        g(vec![term(1, 1, &[("x", 1)]), term(1, 1, &[("y", 2)]), term(1, 1, &[("z", 1)]), term(-1, 1, &[])]),
+        g(vec![term(1, 1, &[("x", 1)]), term(1, 1, &[("y", 1)]), term(1, 1, &[("z", 2)]), term(-1, 1, &[])])
+    ]);
+    let eliminated = system.elimination_ideal(&["x", "y"]);
+    assert_eq!(eliminated.generators.len(), 1);
+    assert!(eliminated.generators[0] == g(vec![term(1, 1, &[("z", 6)]), term(-4, 1, &[("z", 4)]), term(4, 1, &[("z", 3)]), term(-1, 1, &[("z", 2)])]));
+    assert!(system.groebner_basis(GroebnerAlgorithm::Buchberger) == system.groebner_basis(GroebnerAlgorithm::F4));
+
+    // Zmod coefficients: the same system mod 7
+    let zz = ZZ::new();
+    let field: Zmod = Zmod::new(Some(zz.new_instance(BigInt::from(7))));
+    let term = |coefficient: i64, exponents: &[(&'static str, i64)]| Monomial::new_monomial(exponents.iter().map(|(symbol, e)| Var::new(symbol, BigInt::from(*e))).collect(), field.apply(BigInt::from(coefficient)));
+    let generators = vec![
+        Polynomial::new(vec![term(1, &[("x", 2)]), term(1, &[("y", 1)]), term(1, &[("z", 1)]), term(-1, &[])]),
+        Polynomial::new(vec![term(1, &[("x", 1)]), term(1, &[("y", 2)]), term(1, &[("z", 1)]), term(-1, &[])]),
+        Polynomial::new(vec![term(1, &[("x", 1)]), term(1, &[("y", 1)]), term(1, &[("z", 2)]), term(-1, &[])])
+    ];
+    let ideal = Ideal::new(generators.clone());
+    let basis = ideal.groebner_basis(GroebnerAlgorithm::F4);
+    assert!(basis == ideal.groebner_basis(GroebnerAlgorithm::Buchberger));
+    assert!(generators.iter().all(|generator| ideal.contains(generator)));
+    assert!(!ideal.contains(&Polynomial::new(vec![term(1, &[("z", 1)])])));
+}
+
+fn test_univariate_gcd() {
+    println!("Univariate gcd test");
+    let x: Var = Var::new("x", BigInt::from(1));
+
+    // integer coefficients, the gcd keeps the content
+    let zz = ZZ::new();
+    let poly = |coefficients: &[i64]| UnivariatePolynomial::new_instance(coefficients.iter().map(|c| zz.new_instance(BigInt::from(*c))).collect::<Vec<ZZinstance>>(), x.clone(), None, true);
+    let a = poly(&[-6, 0, 6]) * poly(&[1, 3, 1]);
+    let b = poly(&[-4, 4]) * poly(&[3, 1]);
+    assert!(a.gcd(&b) == poly(&[-2, 2]));
+    assert!(a.gcd(&poly(&[5, 1])) == poly(&[1]));
+    assert_eq!(poly(&[1, 0, 1]).resultant(&poly(&[-2, 0, 1])).value, BigInt::from(9));
+    assert_eq!(poly(&[-2, 1]).resultant(&poly(&[1, 0, 1])).value, BigInt::from(5));
+    assert_eq!(poly(&[1, 0, 1]).resultant(&poly(&[-2, 1])).value, BigInt::from(5));
+    assert_eq!(poly(&[-1, 0, 1]).resultant(&poly(&[-1, 1])).value, BigInt::from(0));
+    assert_eq!(poly(&[1, -1, 0, 1]).discriminant().value, BigInt::from(-23));
+    assert_eq!(poly(&[1, 3, 2]).discriminant().value, BigInt::from(1));
+    let f = poly(&[1, 1]) * poly(&[1, 1]) * poly(&[1, 1]) * poly(&[-2, 1]) * poly(&[-2, 1]) * poly(&[5, 1]);
+    let decomposition = f.square_free_decomposition();
+    assert_eq!(decomposition.len(), 3);
+    assert!(decomposition[0] == (poly(&[5, 1]), 1) && decomposition[1] == (poly(&[-2, 1]), 2) && decomposition[2] == (poly(&[1, 1]), 3));
+    assert!(matches!(a.try_xgcd(&b), Err(Error::InvalidInput(_))));
+    let y = UnivariatePolynomial::new_instance(vec![zz.new_instance(BigInt::from(1))], Var::new("y", BigInt::from(1)), None, true);
+    assert!(matches!(a.try_gcd(&y), Err(Error::IncompatibleOperands(_))));
+
+    // rational coefficients, the gcd is monic
+    let qq = QQ::new();
+    let poly = |coefficients: &[(i64, i64)]| UnivariatePolynomial::new_instance(coefficients.iter().map(|(n, d)| qq.new_instance(BigInt::from(*n), BigInt::from(*d))).collect::<Vec<_>>(), x.clone(), None, true);
+    let a = poly(&[(-2, 1), (0, 1), (2, 1)]);
+    let b = poly(&[(2, 3), (-1, 1), (1, 3)]);
+    let (d, s, t) = a.xgcd(&b);
+    assert!(d == poly(&[(-1, 1), (1, 1)]));
+    assert!(s * a.clone() + t * b.clone() == d);
+    assert!(a.gcd(&b) == d);
+    assert!(poly(&[(1, 2), (1, 1), (1, 2)]).discriminant() == qq.new_instance(BigInt::from(0), BigInt::from(1)));
+
+    // Zmod coefficients, (x + 1)^7 = x^7 + 1 is a 7-th power
+    let field: Zmod = Zmod::new(Some(zz.new_instance(BigInt::from(7))));
+    let poly = |coefficients: &[i64]| UnivariatePolynomial::new_instance(coefficients.iter().map(|c| field.apply(BigInt::from(*c))).collect::<Vec<ZmodInstance>>(), x.clone(), None, true);
+    let f = poly(&[1, 0, 0, 0, 0, 0, 0, 1]) * poly(&[2, 1]) * poly(&[2, 1]) * poly(&[3, 1]);
+    let decomposition = f.square_free_decomposition();
+    assert_eq!(decomposition.len(), 3);
+    assert!(decomposition[0] == (poly(&[3, 1]), 1) && decomposition[1] == (poly(&[2, 1]), 2) && decomposition[2] == (poly(&[1, 1]), 7));
+    assert!(poly(&[1, 0, 1]).discriminant() == field.apply(BigInt::from(3)));
+    let (d, s, t) = poly(&[6, 0, 1]).xgcd(&poly(&[2, 4, 1]));
+    assert!(d == poly(&[6, 1]));
+    assert!(s * poly(&[6, 0, 1]) + t * poly(&[2, 4, 1]) == d);
+    assert!(poly(&[1, 2, 3]).resultant(&poly(&[1, 2, 3]).derivative()) == (poly(&[1, 2, 3]).discriminant() * field.apply(BigInt::from(-3))));
+
+    // a characteristic that does not fit in a usize
+    let field: Zmod = Zmod::new(Some(zz.new_instance((BigInt::from(1) << 127) - 1)));
+    let poly = |coefficients: &[i64]| UnivariatePolynomial::new_instance(coefficients.iter().map(|c| field.apply(BigInt::from(*c))).collect::<Vec<ZmodInstance>>(), x.clone(), None, true);
+    let decomposition = (poly(&[1, 1]) * poly(&[1, 1]) * poly(&[2, 1])).square_free_decomposition();
+    assert!(decomposition == vec![(poly(&[2, 1]), 1), (poly(&[1, 1]), 2)]);
+}
+
+fn test_factorization() {
+    println!("Factorization test");
+    let zz = ZZ::new();
+    let x: Var = Var::new("x", BigInt::from(1));
+    let field = |p: i64| Zmod::new(Some(zz.new_instance(BigInt::from(p))));
+    let poly = |coefficients: &[i64], p: i64| UnivariatePolynomial::new_instance(coefficients.iter().map(|c| field(p).apply(BigInt::from(*c))).collect::<Vec<ZmodInstance>>(), x.clone(), None, true);
+
+    // x^7 - 1 = (x + 1)(x^3 + x + 1)(x^3 + x^2 + 1) mod 2
+    let factors = poly(&[-1, 0, 0, 0, 0, 0, 0, 1], 2).factor();
+    assert_eq!(factors.len(), 3);
+    assert!(factors[0] == (poly(&[1, 1], 2), 1) && factors[1] == (poly(&[1, 1, 0, 1], 2), 1) && factors[2] == (poly(&[1, 0, 1, 1], 2), 1));
+
+    // 3 (x^2 + 1)^2 (x + 3) (x^3 + x + 1) mod 7
+    let f = poly(&[1, 0, 1], 7) * poly(&[1, 0, 1], 7) * poly(&[3, 1], 7) * poly(&[1, 1, 0, 1], 7) * field(7).apply(BigInt::from(3));
+    let factors = f.factor();
+    assert_eq!(factors.len(), 3);
+    assert!(factors[0] == (poly(&[3, 1], 7), 1) && factors[1] == (poly(&[1, 0, 1], 7), 2));
+    assert_eq!(factors[2].0.degree(), 3);
+    assert_eq!(factors[2].1, 1);
+    // x^5 - x splits into linear factors mod 5
+    let ddf = poly(&[0, -1, 0, 0, 0, 1], 5).distinct_degree_factorization();
+    assert_eq!(ddf.len(), 1);
+    assert_eq!(ddf[0].1, 1);
+    assert_eq!(ddf[0].0.equal_degree_factorization(1).len(), 5);
+    // x^4 + 1 is a product of two quadratics mod 7, (x - 1)^2 is not square-free
+    assert_eq!(poly(&[1, 0, 0, 0, 1], 7).equal_degree_factorization(2).len(), 2);
+    assert!(matches!(poly(&[1, 0, 0, 0, 1], 7).try_equal_degree_factorization(1), Err(Error::InvalidInput(_))));
+    assert!(matches!(poly(&[1, 0, 0, 0, 1], 7).try_equal_degree_factorization(3), Err(Error::InvalidInput(_))));
+    assert!(matches!(poly(&[1, -2, 1], 7).try_equal_degree_factorization(1), Err(Error::InvalidInput(_))));
+    assert!(matches!(poly(&[1, 0, 1], 6).try_factor(), Err(Error::InvalidInput(_))));
+
+    // a random polynomial is the product of its factors
+    let p: i64 = 101;
+    let coefficients: Vec<i64> = (0..40).map(|_| get_random_bigint_with_bounds(BigInt::from(0), BigInt::from(p)).to_i64().unwrap()).chain([1]).collect();
+    let f = poly(&coefficients, p);
+    let mut product = poly(&[1], p);
+    for (factor, multiplicity) in f.factor() {
+        assert!(factor.is_irreducible());
+        for _ in 0..multiplicity {
+            product = product * factor.clone();
+        }
+    }
+    assert!(product == f);
+
+    // irreducibility and extension fields
+    assert!(poly(&[1, 0, 1], 7).is_irreducible());
+    assert!(!poly(&[1, 0, 1], 5).is_irreducible());
+    assert!(!poly(&[1, 0, 0, 0, 1], 3).is_irreducible());
+    assert!(poly(&[1, 1, 0, 1], 2).is_irreducible());
+    // degree 6: x^6 + x + 1 is irreducible, the others have factors of degree 2 and 3 over GF(2)
+    assert!(poly(&[1, 1, 0, 0, 0, 0, 1], 2).is_irreducible());
+    assert!(!poly(&[1, 0, 0, 1, 1, 1, 1], 2).is_irreducible());
+    assert!(!poly(&[1, 1, 1, 1, 1, 1, 1], 2).is_irreducible());
+    assert!(!poly(&[1], 7).is_irreducible());
+    assert!(PolynomialRing::new_field(poly(&[1, 0, 1], 7), false).is_field());
+    assert!(matches!(PolynomialRing::try_new_field(poly(&[1, 0, 1], 5), false), Err(Error::InvalidInput(_))));
+    assert!(!PolynomialRing::new(poly(&[1, 0, 0, 0, 1], 17), false).is_field());
+}
+
+fn test_roots_and_interpolation() {
+    println!("Roots and interpolation test");
+    let zz = ZZ::new();
+    let x: Var = Var::new("x", BigInt::from(1));
+
+    // evaluation: Horner and remainder tree agree
+    let p: BigInt = BigInt::from(2).pow(61u32) - 1;
+    let field: Zmod = Zmod::new(Some(zz.new_instance(p.clone())));
+    let random = || field.apply(get_random_bigint_with_bounds(BigInt::from(0), p.clone()));
+    let f = UnivariatePolynomial::new_instance((0..200).map(|_| random()).collect::<Vec<ZmodInstance>>(), x.clone(), None, true);
+    let points: Vec<ZmodInstance> = (0..100).map(|_| random()).collect();
+    let values = f.evaluate_multipoint(&points);
+    assert!(points.iter().zip(&values).all(|(point, value)| f.evaluate(point) == *value));
+
+    // Shamir secret sharing: 5 shares of a degree 4 polynomial give back the secret f(0)
+    let secret = random();
+    let mut coefficients: Vec<ZmodInstance> = (0..5).map(|_| random()).collect();
+    coefficients[0] = secret.clone();
+    let g = UnivariatePolynomial::new_instance(coefficients, x.clone(), None, true);
+    let shares: Vec<(ZmodInstance, ZmodInstance)> = (1..=5).map(|i| field.apply(BigInt::from(i))).map(|i| (i.clone(), g.evaluate(&i))).collect();
+    let lagrange = UnivariatePolynomial::lagrange_interpolation(&shares, x.clone());
+    assert!(lagrange == g);
+    assert!(lagrange.evaluate(&field.apply(BigInt::from(0))) == secret);
+    assert!(UnivariatePolynomial::newton_interpolation(&shares, x.clone()) == g);
+    let points: Vec<(ZmodInstance, ZmodInstance)> = (0..150).map(|i| (field.apply(BigInt::from(i)), random())).collect();
+    let h = UnivariatePolynomial::lagrange_interpolation(&points, x.clone());
+    assert!(h == UnivariatePolynomial::newton_interpolation(&points, x.clone()));
+    assert!(points.iter().all(|(point, value)| h.evaluate(point) == *value));
+    assert!(matches!(UnivariatePolynomial::try_lagrange_interpolation(&[shares[0].clone(), shares[0].clone()], x.clone()), Err(Error::InvalidInput(_))));
+
+    // roots over Zmod p: (x - 1)(x - 3)^2(x^2 + 1) mod 7
+    let field: Zmod = Zmod::new(Some(zz.new_instance(BigInt::from(7))));
+    let poly = |coefficients: &[i64]| UnivariatePolynomial::new_instance(coefficients.iter().map(|c| field.apply(BigInt::from(*c))).collect::<Vec<ZmodInstance>>(), x.clone(), None, true);
+    let roots = (poly(&[-1, 1]) * poly(&[-3, 1]) * poly(&[-3, 1]) * poly(&[1, 0, 1])).roots();
+    assert!(roots == vec![field.apply(BigInt::from(1)), field.apply(BigInt::from(3))]);
+    assert!(poly(&[1, 0, 1]).roots().is_empty());
+    assert_eq!(poly(&[0, -1, 0, 0, 0, 0, 0, 1]).roots().len(), 7);
+
+    // real roots over QQ: (x - 1)(x - 2)(x + 3)(x^2 - 2)(x - 1)
+    let qq = QQ::new();
+    let rational = |n: i64| qq.new_instance(BigInt::from(n), BigInt::from(1));
+    let poly = |coefficients: &[i64]| UnivariatePolynomial::new_instance(coefficients.iter().map(|c| rational(*c)).collect::<Vec<_>>(), x.clone(), None, true);
+    let f = poly(&[-1, 1]) * poly(&[-2, 1]) * poly(&[3, 1]) * poly(&[-2, 0, 1]) * poly(&[-1, 1]);
+    let intervals = f.real_root_intervals();
+    assert_eq!(intervals.len(), 5);
+    let contains = |(a, b): &(QQinstance, QQinstance), root: &QQinstance| a < root && root <= b;
+    assert!(contains(&intervals[0], &rational(-3)) && contains(&intervals[2], &rational(1)) && contains(&intervals[4], &rational(2)));
+    // the sign of x^2 - 2 changes in the intervals of -sqrt(2) and sqrt(2)
+    let square = poly(&[-2, 0, 1]);
+    for interval in [&intervals[1], &intervals[3]] {
+        assert!(square.evaluate(&interval.0) * square.evaluate(&interval.1) <= rational(0));
+    }
+    assert!(poly(&[1, 0, 1]).real_root_intervals().is_empty());
+
+    // real roots over RR: x^2 - 2 and x^3 - x
+    let rr = RR::new();
+    let real = |n: i64| rr.new_instance(BigDecimal::from(n));
+    let poly = |coefficients: &[i64]| UnivariatePolynomial::new_instance(coefficients.iter().map(|c| real(*c)).collect::<Vec<RRinstance>>(), x.clone(), None, true);
+    let square = poly(&[-2, 0, 1]);
+    let intervals = square.real_root_intervals();
+    assert_eq!(intervals.len(), 2);
+    for (a, b) in &intervals {
+        assert!(square.evaluate(a) * square.evaluate(b) <= real(0));
+    }
+    let intervals = poly(&[0, -1, 0, 1]).real_root_intervals();
+    assert_eq!(intervals.len(), 3);
+    let contains = |(a, b): &(RRinstance, RRinstance), root: &RRinstance| a < root && root <= b;
+    assert!((-1..=1).zip(&intervals).all(|(root, interval)| contains(interval, &real(root))));
+}
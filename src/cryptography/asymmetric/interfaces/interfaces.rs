@@ -1,18 +1,38 @@
-use crate::{algebras::{Rings::instances::PolynomialRing_instance::PolynomialRingInstance, FiniteField::instances::Zmod_instance::ZmodInstance}, matrices::vector::Vector};
+use crate::error::{unwrap_or_panic, Result};
 
 // asymmetric ciphers interfaces
+// the try_ methods report failures as errors, the others panic on them
 pub trait PKIinterface {
-    fn encrypt(&self, plaintext: Vec<u8>) -> Vec<u8>;
-    fn decrypt(&self, ciphertext: Vec<u8>) -> Vec<u8>;
-}
+    fn try_encrypt(&self, plaintext: Vec<u8>) -> Result<Vec<u8>>;
+    fn try_decrypt(&self, ciphertext: Vec<u8>) -> Result<Vec<u8>>;
+
+    fn encrypt(&self, plaintext: Vec<u8>) -> Vec<u8> {
+        unwrap_or_panic(self.try_encrypt(plaintext))
+    }
 
-pub trait LatticeBased_PKIinterface {
-    fn encrypt(&self, plaintext: Vec<u8>) -> (Vector<PolynomialRingInstance<ZmodInstance>>, PolynomialRingInstance<ZmodInstance>);
-    fn decrypt(&self, u: Vector<PolynomialRingInstance<ZmodInstance>>, v: PolynomialRingInstance<ZmodInstance>) -> Vec<u8>;
+    fn decrypt(&self, ciphertext: Vec<u8>) -> Vec<u8> {
+        unwrap_or_panic(self.try_decrypt(ciphertext))
+    }
 }
 
+// key encapsulation: encapsulate returns the shared secret and the ciphertext
 pub trait KEMinterface {
-    fn encapsulate(&self, bytes_length: usize) -> (Vector<PolynomialRingInstance<ZmodInstance>>, PolynomialRingInstance<ZmodInstance>);
+    fn encapsulate(&self) -> (Vec<u8>, Vec<u8>);
+    fn try_decapsulate(&self, ciphertext: Vec<u8>) -> Result<Vec<u8>>;
+
+    fn decapsulate(&self, ciphertext: Vec<u8>) -> Vec<u8> {
+        unwrap_or_panic(self.try_decapsulate(ciphertext))
+    }
+}
+
+pub trait KeyAgreementInterface {
+    fn get_public_key(&self) -> Vec<u8>;
+    fn validate_public_key(&self, public_key: Vec<u8>) -> bool;
+    fn try_compute_shared_secret(&self, public_key: Vec<u8>) -> Result<Vec<u8>>;
+
+    fn compute_shared_secret(&self, public_key: Vec<u8>) -> Vec<u8> {
+        unwrap_or_panic(self.try_compute_shared_secret(public_key))
+    }
 }
 
 pub trait KeyExchange {
@@ -20,6 +40,10 @@ pub trait KeyExchange {
 }
 
 pub trait SignatureInterface {
-    fn sign(&self, message: Vec<u8>) -> Vec<u8>;
+    fn try_sign(&self, message: Vec<u8>) -> Result<Vec<u8>>;
     fn verify(&self, message: Vec<u8>, signature: Vec<u8>) -> bool;
-}
\ No newline at end of file
+
+    fn sign(&self, message: Vec<u8>) -> Vec<u8> {
+        unwrap_or_panic(self.try_sign(message))
+    }
+}
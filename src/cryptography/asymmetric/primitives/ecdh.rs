@@ -0,0 +1,134 @@
+use num_bigint::BigInt;
+
+use crate::algebras::FiniteField::instances::Zmod_instance::ZmodInstance;
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_factory::{EllipticCurveFactory, NamedCurves};
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_finite_field::EllipticCurve_finite_field;
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_point::EllipticCurvePoint;
+use crate::arith::random::get_random_bigint_with_bounds;
+use crate::cryptography::asymmetric::interfaces::interfaces::KeyAgreementInterface;
+use crate::error::{unwrap_or_panic, Error, Result};
+use crate::numbers::classes::ZZ::ZZ;
+use crate::numbers::instances::ZZ_instance::ZZinstance;
+
+/*
+    Elliptic curve Diffie-Hellman (SEC 1, section 3.3.1) over a curve E(F_p) and a generator G of order n.
+    Public keys are exchanged as uncompressed SEC 1 points, the shared secret is the x coordinate of d Q
+    encoded on the byte length of p.
+*/
+pub struct ECDH {
+    pub public_keys: Vec<EllipticCurvePoint<ZmodInstance>>, // Q = d G
+    pub private_keys: Vec<ZZinstance>, // d
+    pub primary_key: usize,
+    elliptic_curve: Box<EllipticCurve_finite_field>,
+    generator: EllipticCurvePoint<ZmodInstance>,
+    generator_order: BigInt
+}
+
+impl KeyAgreementInterface for ECDH {
+    fn get_public_key(&self) -> Vec<u8> {
+        self.elliptic_curve.encode_point(&self.public_keys[self.primary_key], false)
+    }
+
+    /*
+    Full public key validation (SP 800-56A, section 5.6.2.3.3): the point is on the curve,
+    it is not the point at infinity and it lies in the subgroup generated by G
+     */
+    fn validate_public_key(&self, public_key: Vec<u8>) -> bool {
+        match self.elliptic_curve.decode_point(&public_key) {
+            Some(point) => self.is_valid_point(&point),
+            None => false
+        }
+    }
+
+    fn try_compute_shared_secret(&self, public_key: Vec<u8>) -> Result<Vec<u8>> {
+        let point = match self.elliptic_curve.decode_point(&public_key) {
+            Some(point) if self.is_valid_point(&point) => point,
+            _ => return Err(Error::InvalidKey("Invalid public key".to_string()))
+        };
+        let shared_point = point * self.private_keys[self.primary_key].value.clone();
+        if shared_point.is_infinity() {
+            return Err(Error::InvalidKey("The shared secret is the point at infinity".to_string()));
+        }
+        let mut encoded = self.elliptic_curve.encode_point(&shared_point, true);
+        encoded.remove(0);
+        Ok(encoded)
+    }
+}
+
+impl ECDH {
+    pub fn init(named_curve: NamedCurves) -> Self {
+        let (elliptic_curve, generator) = EllipticCurveFactory::gen_named_curve(named_curve);
+        ECDH::from_curve(elliptic_curve, generator)
+    }
+
+    /*
+    Key agreement on any curve built through EllipticCurveFactory, the order of the generator
+    is computed from the order of the curve (which must be set beyond 2^64, see try_order)
+     */
+    pub fn from_curve(elliptic_curve: Box<EllipticCurve_finite_field>, generator: EllipticCurvePoint<ZmodInstance>) -> Self {
+        unwrap_or_panic(ECDH::try_from_curve(elliptic_curve, generator))
+    }
+
+    pub fn try_from_curve(elliptic_curve: Box<EllipticCurve_finite_field>, generator: EllipticCurvePoint<ZmodInstance>) -> Result<Self> {
+        if generator.is_infinity() {
+            return Err(Error::InvalidInput("The generator cannot be the point at infinity".to_string()));
+        }
+        let generator_order = elliptic_curve.try_point_order(&generator)?;
+        let mut ecdh = ECDH { public_keys: Vec::new(), private_keys: Vec::new(), primary_key: 0, elliptic_curve, generator, generator_order };
+        ecdh.refresh_keys();
+        Ok(ecdh)
+    }
+
+    pub fn from_private_key(named_curve: NamedCurves, private_key: BigInt) -> Self {
+        unwrap_or_panic(ECDH::try_from_private_key(named_curve, private_key))
+    }
+
+    pub fn try_from_private_key(named_curve: NamedCurves, private_key: BigInt) -> Result<Self> {
+        let (elliptic_curve, generator) = EllipticCurveFactory::gen_named_curve(named_curve);
+        let generator_order = elliptic_curve.try_point_order(&generator)?;
+        if private_key < BigInt::from(1) || private_key >= generator_order {
+            return Err(Error::InvalidKey("The private key must be in [1, n - 1]".to_string()));
+        }
+        let public_key = generator.clone() * private_key.clone();
+        Ok(ECDH { public_keys: vec![public_key], private_keys: vec![ZZ::new().new_instance(private_key)], primary_key: 0, elliptic_curve, generator, generator_order })
+    }
+
+    pub fn refresh_keys(&mut self) {
+        let d = get_random_bigint_with_bounds(BigInt::from(1), self.generator_order.clone());
+        self.public_keys.push(self.generator.clone() * d.clone());
+        self.private_keys.push(ZZ::new().new_instance(d));
+        self.primary_key = self.private_keys.len() - 1;
+    }
+
+    pub fn get_current_public_key(&self) -> EllipticCurvePoint<ZmodInstance> {
+        self.public_keys[self.primary_key].clone()
+    }
+
+    pub fn get_current_private_key(&self) -> ZZinstance {
+        self.private_keys[self.primary_key].clone()
+    }
+
+    // TRUE: set new primary key
+    // FALSE: operation not permitted
+    pub fn set_primary_key(&mut self, new_primary_key: usize) -> bool {
+        if new_primary_key >= self.public_keys.len() {
+            return false;
+        }
+        self.primary_key = new_primary_key;
+        true
+    }
+
+    pub fn get_curve(&self) -> EllipticCurve_finite_field {
+        *self.elliptic_curve.clone()
+    }
+
+    pub fn get_generator(&self) -> EllipticCurvePoint<ZmodInstance> {
+        self.generator.clone()
+    }
+
+    fn is_valid_point(&self, point: &EllipticCurvePoint<ZmodInstance>) -> bool {
+        point.curve.borrow().clone() == self.elliptic_curve.base_curve()
+            && !point.is_infinity()
+            && (point.clone() * self.generator_order.clone()).is_infinity()
+    }
+}
@@ -0,0 +1,260 @@
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+
+use crate::algebras::FiniteField::classes::Zmod::Zmod;
+use crate::algebras::FiniteField::instances::Zmod_instance::ZmodInstance;
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_factory::{EllipticCurveFactory, NamedCurves};
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_finite_field::EllipticCurve_finite_field;
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_point::EllipticCurvePoint;
+use crate::arith::random::get_random_bigint_with_bounds;
+use crate::cryptography::asymmetric::interfaces::interfaces::SignatureInterface;
+use crate::cryptography::hash::interfaces::interfaces::HashInterface;
+use crate::cryptography::hash::primitives::sha2::{SHA256, SHA384};
+use crate::cryptography::symmetric::primitives::hmac::HMAC;
+use crate::error::{unwrap_or_panic, Error, Result};
+use crate::numbers::classes::ZZ::ZZ;
+use crate::numbers::instances::ZZ_instance::ZZinstance;
+use crate::numbers::numbers::Class;
+
+/*
+    ECDSA (FIPS 186-5) over the named curves, with SHA-256 (P-256, secp256k1) or SHA-384 (P-384).
+    Signatures are encoded as r || s, both big endian on the byte length of the group order.
+    Nonces are either random or derived deterministically as in RFC 6979.
+*/
+pub struct ECDSA {
+    pub named_curve: NamedCurves,
+    pub deterministic: bool,
+    pub public_keys: Vec<EllipticCurvePoint<ZmodInstance>>, // Q = d G
+    pub private_keys: Vec<ZZinstance>, // d
+    pub primary_key: usize,
+    elliptic_curve: Box<EllipticCurve_finite_field>,
+    generator: EllipticCurvePoint<ZmodInstance>
+}
+
+impl SignatureInterface for ECDSA {
+    fn try_sign(&self, message: Vec<u8>) -> Result<Vec<u8>> {
+        if self.primary_key >= self.private_keys.len() {
+            return Err(Error::InvalidKey("No private key available to sign".to_string()));
+        }
+        let d = self.private_keys[self.primary_key].value.clone();
+        let n = self.order();
+        let scalar_field = Zmod::new(Some(ZZ::new().new_instance(n.clone())));
+        let digest = self.hash(&message);
+        let e = self.bits_to_int(&digest);
+
+        let mut nonces = if self.deterministic { Some(RFC6979NonceGenerator::new(self, &d, &digest)) } else { None };
+        loop {
+            let k = match nonces.as_mut() {
+                Some(generator) => generator.next_nonce(),
+                None => get_random_bigint_with_bounds(BigInt::from(1), n.clone()),
+            };
+            let (x, _) = (self.generator.clone() * k.clone()).xy();
+            let r = x.value.value.mod_floor(&n);
+            if r == BigInt::from(0) {
+                continue;
+            }
+            let s: ZmodInstance = scalar_field.apply(k).inverse() * scalar_field.apply(e.clone() + r.clone() * d.clone());
+            if s.value.value == BigInt::from(0) {
+                continue;
+            }
+
+            let mut signature = self.int_to_octets(&r);
+            signature.extend(self.int_to_octets(&s.value.value));
+            return Ok(signature);
+        }
+    }
+
+    fn verify(&self, message: Vec<u8>, signature: Vec<u8>) -> bool {
+        let length = self.scalar_length();
+        if signature.len() != 2 * length {
+            return false;
+        }
+        let n = self.order();
+        let r = BigInt::from_bytes_be(Sign::Plus, &signature[..length]);
+        let s = BigInt::from_bytes_be(Sign::Plus, &signature[length..]);
+        if r < BigInt::from(1) || r >= n || s < BigInt::from(1) || s >= n {
+            return false;
+        }
+
+        let scalar_field = Zmod::new(Some(ZZ::new().new_instance(n.clone())));
+        let e = self.bits_to_int(&self.hash(&message));
+        let w = scalar_field.apply(s).inverse();
+        let u1 = (scalar_field.apply(e) * w.clone()).value.value;
+        let u2 = (scalar_field.apply(r.clone()) * w).value.value;
+
+        let point = self.generator.clone() * u1 + self.public_keys[self.primary_key].clone() * u2;
+        if point.is_infinity() {
+            return false;
+        }
+        let (x, _) = point.xy();
+        x.value.value.mod_floor(&n) == r
+    }
+}
+
+impl ECDSA {
+    pub fn init(named_curve: NamedCurves, deterministic: bool) -> Self {
+        let (elliptic_curve, generator) = EllipticCurveFactory::gen_named_curve(named_curve);
+        let mut ecdsa = ECDSA { named_curve, deterministic, public_keys: Vec::new(), private_keys: Vec::new(), primary_key: 0, elliptic_curve, generator };
+        let d = get_random_bigint_with_bounds(BigInt::from(1), ecdsa.order());
+        ecdsa.add_private_key(d);
+        ecdsa
+    }
+
+    /*
+    Signer from an existing private key 1 <= d < n
+     */
+    pub fn from_private_key(named_curve: NamedCurves, private_key: BigInt, deterministic: bool) -> Self {
+        unwrap_or_panic(ECDSA::try_from_private_key(named_curve, private_key, deterministic))
+    }
+
+    pub fn try_from_private_key(named_curve: NamedCurves, private_key: BigInt, deterministic: bool) -> Result<Self> {
+        let (elliptic_curve, generator) = EllipticCurveFactory::gen_named_curve(named_curve);
+        let mut ecdsa = ECDSA { named_curve, deterministic, public_keys: Vec::new(), private_keys: Vec::new(), primary_key: 0, elliptic_curve, generator };
+        if private_key < BigInt::from(1) || private_key >= ecdsa.order() {
+            return Err(Error::InvalidKey("The private key must be in [1, n - 1]".to_string()));
+        }
+        ecdsa.add_private_key(private_key);
+        Ok(ecdsa)
+    }
+
+    /*
+    Verifier only. The public key must be a point of order n on the curve.
+     */
+    pub fn from_public_key(named_curve: NamedCurves, public_key: EllipticCurvePoint<ZmodInstance>) -> Self {
+        unwrap_or_panic(ECDSA::try_from_public_key(named_curve, public_key))
+    }
+
+    pub fn try_from_public_key(named_curve: NamedCurves, public_key: EllipticCurvePoint<ZmodInstance>) -> Result<Self> {
+        let (elliptic_curve, generator) = EllipticCurveFactory::gen_named_curve(named_curve);
+        if public_key.curve.borrow().clone() != elliptic_curve.base_curve() || public_key.is_infinity() || !(public_key.clone() * elliptic_curve.order()).is_infinity() {
+            return Err(Error::InvalidKey("Invalid public key".to_string()));
+        }
+        Ok(ECDSA { named_curve, deterministic: true, public_keys: vec![public_key], private_keys: Vec::new(), primary_key: 0, elliptic_curve, generator })
+    }
+
+    pub fn refresh_keys(&mut self) {
+        let d = get_random_bigint_with_bounds(BigInt::from(1), self.order());
+        self.add_private_key(d);
+        self.primary_key = self.private_keys.len() - 1;
+    }
+
+    pub fn get_current_public_key(&self) -> EllipticCurvePoint<ZmodInstance> {
+        self.public_keys[self.primary_key].clone()
+    }
+
+    pub fn get_current_private_key(&self) -> ZZinstance {
+        self.private_keys[self.primary_key].clone()
+    }
+
+    // TRUE: set new primary key
+    // FALSE: operation not permitted
+    pub fn set_primary_key(&mut self, new_primary_key: usize) -> bool {
+        if new_primary_key >= self.public_keys.len() {
+            return false;
+        }
+        self.primary_key = new_primary_key;
+        true
+    }
+
+    pub fn get_generator(&self) -> EllipticCurvePoint<ZmodInstance> {
+        self.generator.clone()
+    }
+
+    fn add_private_key(&mut self, d: BigInt) {
+        self.public_keys.push(self.generator.clone() * d.clone());
+        self.private_keys.push(ZZ::new().new_instance(d));
+    }
+
+    fn order(&self) -> BigInt {
+        self.elliptic_curve.order()
+    }
+
+    fn scalar_length(&self) -> usize {
+        self.order().bits().div_ceil(8) as usize
+    }
+
+    fn hash(&self, message: &[u8]) -> Vec<u8> {
+        match self.named_curve {
+            NamedCurves::P384 => SHA384::digest(message),
+            _ => SHA256::digest(message)
+        }
+    }
+
+    fn hmac(&self, key: &[u8], data: &[u8]) -> Vec<u8> {
+        match self.named_curve {
+            NamedCurves::P384 => HMAC::<SHA384>::mac(key, data),
+            _ => HMAC::<SHA256>::mac(key, data)
+        }
+    }
+
+    // leftmost bits of the input, as many as the bit length of n
+    fn bits_to_int(&self, bits: &[u8]) -> BigInt {
+        let value = BigInt::from_bytes_be(Sign::Plus, bits);
+        let (bit_length, order_length) = (bits.len() as u64 * 8, self.order().bits());
+        if bit_length > order_length {
+            value >> (bit_length - order_length)
+        } else {
+            value
+        }
+    }
+
+    fn int_to_octets(&self, value: &BigInt) -> Vec<u8> {
+        let (_, bytes) = value.to_bytes_be();
+        let mut octets = vec![0u8; self.scalar_length() - bytes.len()];
+        octets.extend(bytes);
+        octets
+    }
+}
+
+
+/*
+    RFC 6979, section 3.2: HMAC_DRBG instantiated with the private key and the message digest
+*/
+struct RFC6979NonceGenerator<'a> {
+    ecdsa: &'a ECDSA,
+    k: Vec<u8>,
+    v: Vec<u8>,
+    first: bool
+}
+
+impl<'a> RFC6979NonceGenerator<'a> {
+    fn new(ecdsa: &'a ECDSA, private_key: &BigInt, digest: &[u8]) -> RFC6979NonceGenerator<'a> {
+        let hash_length = digest.len();
+        let mut seed = ecdsa.int_to_octets(private_key);
+        seed.extend(ecdsa.int_to_octets(&ecdsa.bits_to_int(digest).mod_floor(&ecdsa.order())));
+
+        let mut v = vec![0x01u8; hash_length];
+        let mut k = vec![0x00u8; hash_length];
+        for separator in [0x00u8, 0x01u8] {
+            let mut data = v.clone();
+            data.push(separator);
+            data.extend_from_slice(&seed);
+            k = ecdsa.hmac(&k, &data);
+            v = ecdsa.hmac(&k, &v);
+        }
+        RFC6979NonceGenerator { ecdsa, k, v, first: true }
+    }
+
+    fn next_nonce(&mut self) -> BigInt {
+        let n = self.ecdsa.order();
+        loop {
+            if !self.first {
+                let mut data = self.v.clone();
+                data.push(0x00);
+                self.k = self.ecdsa.hmac(&self.k, &data);
+                self.v = self.ecdsa.hmac(&self.k, &self.v);
+            }
+            self.first = false;
+
+            let mut t: Vec<u8> = Vec::new();
+            while (t.len() as u64) * 8 < n.bits() {
+                self.v = self.ecdsa.hmac(&self.k, &self.v);
+                t.extend_from_slice(&self.v);
+            }
+            let k = self.ecdsa.bits_to_int(&t);
+            if k >= BigInt::from(1) && k < n {
+                return k;
+            }
+        }
+    }
+}
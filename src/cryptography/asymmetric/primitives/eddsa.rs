@@ -0,0 +1,290 @@
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{Num, One, Zero};
+
+use crate::arith::random::random_byte_array;
+use crate::cryptography::asymmetric::interfaces::interfaces::SignatureInterface;
+use crate::cryptography::hash::interfaces::interfaces::HashInterface;
+use crate::cryptography::hash::primitives::sha2::SHA512;
+use crate::error::{unwrap_or_panic, Error, Result};
+
+/*
+    Ed25519 (RFC 8032, section 5.1): pure EdDSA over the twisted Edwards curve
+    -x^2 + y^2 = 1 + d x^2 y^2 over GF(2^255 - 19), with SHA-512.
+    Keys are the 32-byte encodings of RFC 8032, signatures are R || S (64 bytes).
+*/
+pub struct Ed25519 {
+    pub public_keys: Vec<Vec<u8>>,
+    pub private_keys: Vec<Vec<u8>>,
+    pub primary_key: usize
+}
+
+impl SignatureInterface for Ed25519 {
+    fn try_sign(&self, message: Vec<u8>) -> Result<Vec<u8>> {
+        if self.primary_key >= self.private_keys.len() {
+            return Err(Error::InvalidKey("No private key available to sign".to_string()));
+        }
+        let curve = Edwards25519::new();
+        let (s, prefix) = expand_secret_key(&self.private_keys[self.primary_key]);
+        let public_key = &self.public_keys[self.primary_key];
+
+        let mut nonce_input = prefix;
+        nonce_input.extend_from_slice(&message);
+        let r = from_le_bytes(&SHA512::digest(&nonce_input)).mod_floor(&curve.l);
+        let big_r = curve.encode(&curve.scalar_mul(&curve.base, &r));
+
+        let k = curve.challenge(&big_r, public_key, &message);
+        let big_s = (r + k * s).mod_floor(&curve.l);
+
+        let mut signature = big_r;
+        signature.extend(to_le_bytes(&big_s));
+        Ok(signature)
+    }
+
+    /*
+    Cofactorless verification: [S]B == R + [k]A
+     */
+    fn verify(&self, message: Vec<u8>, signature: Vec<u8>) -> bool {
+        if signature.len() != 64 {
+            return false;
+        }
+        let curve = Edwards25519::new();
+        let public_key = &self.public_keys[self.primary_key];
+        let (a, r) = match (curve.decode(public_key), curve.decode(&signature[..32])) {
+            (Some(a), Some(r)) => (a, r),
+            _ => return false
+        };
+        let s = from_le_bytes(&signature[32..]);
+        if s >= curve.l {
+            return false;
+        }
+
+        let k = curve.challenge(&signature[..32], public_key, &message);
+        let lhs = curve.scalar_mul(&curve.base, &s);
+        let rhs = curve.add(&r, &curve.scalar_mul(&a, &k));
+        curve.equal(&lhs, &rhs)
+    }
+}
+
+impl Ed25519 {
+    pub const KEY_SIZE: usize = 32;
+    pub const SIGNATURE_SIZE: usize = 64;
+
+    pub fn init() -> Self {
+        Ed25519::from_secret_key(random_byte_array(Ed25519::KEY_SIZE))
+    }
+
+    /*
+    Signer from a 32-byte secret key (the seed of RFC 8032). Panics if the key is not 32 bytes long.
+     */
+    pub fn from_secret_key(secret_key: Vec<u8>) -> Self {
+        unwrap_or_panic(Ed25519::try_from_secret_key(secret_key))
+    }
+
+    pub fn try_from_secret_key(secret_key: Vec<u8>) -> Result<Self> {
+        let mut ed25519 = Ed25519 { public_keys: Vec::new(), private_keys: Vec::new(), primary_key: 0 };
+        ed25519.add_secret_key(secret_key)?;
+        Ok(ed25519)
+    }
+
+    /*
+    Verifier only. Panics if the encoding is not a point of the curve.
+     */
+    pub fn from_public_key(public_key: Vec<u8>) -> Self {
+        unwrap_or_panic(Ed25519::try_from_public_key(public_key))
+    }
+
+    pub fn try_from_public_key(public_key: Vec<u8>) -> Result<Self> {
+        if public_key.len() != Ed25519::KEY_SIZE || Edwards25519::new().decode(&public_key).is_none() {
+            return Err(Error::InvalidKey("Invalid public key".to_string()));
+        }
+        Ok(Ed25519 { public_keys: vec![public_key], private_keys: Vec::new(), primary_key: 0 })
+    }
+
+    pub fn refresh_keys(&mut self) {
+        unwrap_or_panic(self.add_secret_key(random_byte_array(Ed25519::KEY_SIZE)));
+        self.primary_key = self.private_keys.len() - 1;
+    }
+
+    pub fn get_current_public_key(&self) -> Vec<u8> {
+        self.public_keys[self.primary_key].clone()
+    }
+
+    pub fn get_current_private_key(&self) -> Vec<u8> {
+        self.private_keys[self.primary_key].clone()
+    }
+
+    // TRUE: set new primary key
+    // FALSE: operation not permitted
+    pub fn set_primary_key(&mut self, new_primary_key: usize) -> bool {
+        if new_primary_key >= self.public_keys.len() {
+            return false;
+        }
+        self.primary_key = new_primary_key;
+        true
+    }
+
+    fn add_secret_key(&mut self, secret_key: Vec<u8>) -> Result<()> {
+        if secret_key.len() != Ed25519::KEY_SIZE {
+            return Err(Error::InvalidKey("The secret key must be 32 bytes long".to_string()));
+        }
+        let curve = Edwards25519::new();
+        let (s, _) = expand_secret_key(&secret_key);
+        self.public_keys.push(curve.encode(&curve.scalar_mul(&curve.base, &s)));
+        self.private_keys.push(secret_key);
+        Ok(())
+    }
+}
+
+
+// returns the clamped scalar and the prefix used to derive the nonces
+fn expand_secret_key(secret_key: &[u8]) -> (BigInt, Vec<u8>) {
+    let h = SHA512::digest(secret_key);
+    let mut scalar = h[..32].to_vec();
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+    (from_le_bytes(&scalar), h[32..].to_vec())
+}
+
+fn from_le_bytes(bytes: &[u8]) -> BigInt {
+    BigInt::from_bytes_le(Sign::Plus, bytes)
+}
+
+fn to_le_bytes(value: &BigInt) -> Vec<u8> {
+    let (_, mut bytes) = value.to_bytes_le();
+    bytes.resize(32, 0);
+    bytes
+}
+
+/*
+    Extended twisted Edwards coordinates (X : Y : Z : T) with x = X/Z, y = Y/Z, xy = T/Z
+*/
+#[derive(Clone)]
+struct EdwardsPoint {
+    x: BigInt,
+    y: BigInt,
+    z: BigInt,
+    t: BigInt
+}
+
+struct Edwards25519 {
+    p: BigInt,
+    d: BigInt,
+    l: BigInt,
+    base: EdwardsPoint
+}
+
+impl Edwards25519 {
+    fn new() -> Edwards25519 {
+        let p: BigInt = (BigInt::one() << 255) - 19;
+        let d = (BigInt::from(-121665) * BigInt::from(121666).modpow(&(p.clone() - 2), &p)).mod_floor(&p);
+        let l = (BigInt::one() << 252) + BigInt::from_str_radix("27742317777372353535851937790883648493", 10).unwrap();
+        let x = BigInt::from_str_radix("15112221349535400772501151409588531511454012693041857206046113283949847762202", 10).unwrap();
+        let y = BigInt::from_str_radix("46316835694926478169428394003475163141307993866256225615783033603165251855960", 10).unwrap();
+        let t = (x.clone() * y.clone()).mod_floor(&p);
+        Edwards25519 { p, d, l, base: EdwardsPoint { x, y, z: BigInt::one(), t } }
+    }
+
+    fn neutral(&self) -> EdwardsPoint {
+        EdwardsPoint { x: BigInt::zero(), y: BigInt::one(), z: BigInt::one(), t: BigInt::zero() }
+    }
+
+    fn reduce(&self, value: BigInt) -> BigInt {
+        value.mod_floor(&self.p)
+    }
+
+    fn inverse(&self, value: &BigInt) -> BigInt {
+        value.modpow(&(self.p.clone() - 2), &self.p)
+    }
+
+    // RFC 8032, section 5.1.4
+    fn add(&self, first: &EdwardsPoint, second: &EdwardsPoint) -> EdwardsPoint {
+        let a = self.reduce((first.y.clone() - first.x.clone()) * (second.y.clone() - second.x.clone()));
+        let b = self.reduce((first.y.clone() + first.x.clone()) * (second.y.clone() + second.x.clone()));
+        let c = self.reduce(BigInt::from(2) * first.t.clone() * second.t.clone() * self.d.clone());
+        let d = self.reduce(BigInt::from(2) * first.z.clone() * second.z.clone());
+        let (e, f, g, h) = (b.clone() - a.clone(), d.clone() - c.clone(), d + c, b + a);
+        EdwardsPoint {
+            x: self.reduce(e.clone() * f.clone()),
+            y: self.reduce(g.clone() * h.clone()),
+            z: self.reduce(f * g),
+            t: self.reduce(e * h)
+        }
+    }
+
+    fn scalar_mul(&self, point: &EdwardsPoint, scalar: &BigInt) -> EdwardsPoint {
+        let mut result = self.neutral();
+        for i in (0..scalar.bits()).rev() {
+            result = self.add(&result, &result);
+            if scalar.bit(i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+
+    fn equal(&self, first: &EdwardsPoint, second: &EdwardsPoint) -> bool {
+        self.reduce(first.x.clone() * second.z.clone()) == self.reduce(second.x.clone() * first.z.clone())
+            && self.reduce(first.y.clone() * second.z.clone()) == self.reduce(second.y.clone() * first.z.clone())
+    }
+
+    // little endian y with the sign of x in the most significant bit
+    fn encode(&self, point: &EdwardsPoint) -> Vec<u8> {
+        let z_inverse = self.inverse(&point.z);
+        let x = self.reduce(point.x.clone() * z_inverse.clone());
+        let y = self.reduce(point.y.clone() * z_inverse);
+        let mut bytes = to_le_bytes(&y);
+        if x.is_odd() {
+            bytes[31] |= 0x80;
+        }
+        bytes
+    }
+
+    // RFC 8032, section 5.1.3
+    fn decode(&self, bytes: &[u8]) -> Option<EdwardsPoint> {
+        if bytes.len() != 32 {
+            return None;
+        }
+        let mut y_bytes = bytes.to_vec();
+        let sign = y_bytes[31] >> 7 == 1;
+        y_bytes[31] &= 0x7f;
+        let y = from_le_bytes(&y_bytes);
+        if y >= self.p {
+            return None;
+        }
+
+        let u = self.reduce(y.clone() * y.clone() - 1);
+        let v = self.reduce(self.d.clone() * y.clone() * y.clone() + 1);
+        let v3 = self.reduce(v.clone() * v.clone() * v.clone());
+        let exponent: BigInt = (self.p.clone() - 5) / 8;
+        let mut x = self.reduce(u.clone() * v3.clone() * self.reduce(u.clone() * v3 * v.clone() * v.clone() * v.clone() * v.clone()).modpow(&exponent, &self.p));
+
+        let vx2 = self.reduce(v * x.clone() * x.clone());
+        if vx2 != u {
+            if vx2 == self.reduce(-u) {
+                let sqrt_minus_one = BigInt::from(2).modpow(&((self.p.clone() - 1) / 4), &self.p);
+                x = self.reduce(x * sqrt_minus_one);
+            } else {
+                return None;
+            }
+        }
+        if x.is_zero() && sign {
+            return None;
+        }
+        if x.is_odd() != sign {
+            x = self.p.clone() - x;
+        }
+        let t = self.reduce(x.clone() * y.clone());
+        Some(EdwardsPoint { x, y, z: BigInt::one(), t })
+    }
+
+    // k = SHA-512(R || A || M) mod L
+    fn challenge(&self, r: &[u8], public_key: &[u8], message: &[u8]) -> BigInt {
+        let mut hash = SHA512::new();
+        hash.update(r);
+        hash.update(public_key);
+        hash.update(message);
+        from_le_bytes(&hash.finalize()).mod_floor(&self.l)
+    }
+}
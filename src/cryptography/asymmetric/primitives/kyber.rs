@@ -1,565 +1,574 @@
-use std::{cell::RefCell, fmt::Display};
-
-use bigdecimal::BigDecimal;
-use num_bigint::BigInt;
-use num_traits::{ToPrimitive, Pow};
-use crate::{algebras::{Rings::{instances::PolynomialRing_instance::PolynomialRingInstance, classes::PolynomialRing::PolynomialRing}, FiniteField::{instances::Zmod_instance::ZmodInstance, classes::Zmod::Zmod}}, arith::random::{gen_from_range_with_modulo, random_byte_array}, cryptography::asymmetric::interfaces::interfaces::{PKIinterface, KEMinterface, LatticeBased_PKIinterface}, matrices::{matrix::Matrix, vector::Vector}, numbers::{numbers::{Class, Instance, Number, Operand, PrimitiveNumber}, instances::{ZZ_instance::{ZZinstance, self}, RR_instance::RRinstance}, classes::RR::RR}, poly::{instances::univariate_polynomial_instance::UnivariatePolynomialInstance, classes::univariate_polynomial::UnivariatePolynomial}, transform::ntt::{NTTFactory, NTT_Algorithm}, variables::vars::Var};
-use crate::arith::random::gen_from_centered_binomial_distribution;
-use crate::numbers::classes::ZZ::ZZ;
-
-
-// kyber utilities
-fn compress<T>(poly: UnivariatePolynomialInstance<T>, modulo: ZZinstance, d: usize) -> UnivariatePolynomialInstance<ZmodInstance> where T: Display + Instance + Clone + Eq + Operand + Number {
-    let q1: ZZinstance =  ZZ::new().new_instance(BigInt::from(2)).pow(BigInt::from(d));
-    let r_class: RR = RR::new();
-    let factor: RRinstance = r_class.new_instance(BigDecimal::from(q1.value.clone())/BigDecimal::from(modulo.value));
-   
-    let mut new_poly = (r_class.apply_to_univariate_poly(poly) * factor).round(); //% q1; // values are yet modulo q
-    new_poly % q1
+use std::marker::PhantomData;
+
+use crate::arith::random::random_byte_array;
+use crate::cryptography::asymmetric::interfaces::interfaces::KEMinterface;
+use crate::cryptography::hash::interfaces::interfaces::{HashInterface, XOFReaderInterface, XOFinterface};
+use crate::cryptography::hash::primitives::sha3::{SHA3_256, SHA3_512, SHAKE128, SHAKE256};
+use crate::error::{unwrap_or_panic, Error, Result};
+
+/*
+    ML-KEM (FIPS 203), the standardised version of Kyber.
+    Keys and ciphertexts are the byte strings of the standard, so that they interoperate with other implementations.
+*/
+
+const N: usize = 256;
+
+pub const SHARED_SECRET_SIZE: usize = 32;
+
+type Polynomial = [u32; N];
+
+/*
+    Parameter set of ML-KEM. The three parameter sets of FIPS 203 are given below, other sets can be
+    defined for research purposes: q must be a prime below 2^16 with q = 1 mod 256 and zeta a primitive
+    256-th root of unity modulo q, so that the NTT splits X^256 + 1 into 128 quadratic factors.
+    Coefficients are encoded on the bit length of q - 1 (12 bits for q = 3329).
+*/
+pub trait KyberParams {
+    const K: usize;
+    const ETA1: usize;
+    const ETA2: usize;
+    const DU: usize;
+    const DV: usize;
+    const Q: u32 = 3329;
+    const ZETA: u32 = 17;
 }
 
+pub struct Kyber512Params;
+pub struct Kyber768Params;
+pub struct Kyber1024Params;
 
-fn decompress<T>(poly: UnivariatePolynomialInstance<T>, modulo: ZZinstance, d: usize) -> UnivariatePolynomialInstance<ZmodInstance> where T: Instance + Clone + Eq + Operand + Number {
-    let q1: ZZinstance =  ZZ::new().new_instance(BigInt::from(2)).pow(BigInt::from(d));
-    let r_class: RR = RR::new();
-    let factor: RRinstance = r_class.new_instance(BigDecimal::from(modulo.value.clone())/BigDecimal::from(q1.value.clone()));
-    let mut new_poly = (r_class.apply_to_univariate_poly(poly) * factor).round() % modulo; // values are yet modulo q
-    new_poly
+impl KyberParams for Kyber512Params {
+    const K: usize = 2;
+    const ETA1: usize = 3;
+    const ETA2: usize = 2;
+    const DU: usize = 10;
+    const DV: usize = 4;
 }
 
-fn plaintext_to_poly(plaintext: Vec<u8>, size: usize) -> UnivariatePolynomialInstance<ZZinstance> {
-    let class = ZZ::new();
-    let mut coefficients: Vec<ZZinstance> = Vec::new();
-    for el in plaintext.clone() {
-        for bit in 0..8 {
-            coefficients.push(class.new_instance(BigInt::from(el & 1<<bit)>>bit));
-        }    
-    }
-
-    for i in (plaintext.len()*8)..size {
-       
-            coefficients.push(class.new_instance(BigInt::from(0)));
-        
-    }
+impl KyberParams for Kyber768Params {
+    const K: usize = 3;
+    const ETA1: usize = 2;
+    const ETA2: usize = 2;
+    const DU: usize = 10;
+    const DV: usize = 4;
+}
 
-    UnivariatePolynomial::new_instance(coefficients, Var::new("x", BigInt::one()), None, false)
+impl KyberParams for Kyber1024Params {
+    const K: usize = 4;
+    const ETA1: usize = 2;
+    const ETA2: usize = 2;
+    const DU: usize = 11;
+    const DV: usize = 5;
 }
 
+pub type Kyber512 = Kyber<Kyber512Params>;
+pub type Kyber768 = Kyber<Kyber768Params>;
+pub type Kyber1024 = Kyber<Kyber1024Params>;
 
-fn poly_to_plaintext(poly: UnivariatePolynomialInstance<ZmodInstance>) -> Vec<u8> {
-    let number_of_bytes = (poly.degree()+1)/8;
-    let mut plaintext: Vec<u8> = Vec::new();
-    for i in 0..number_of_bytes {
-        let mut accumulator: u8 = 0;
-        for bit in 0..8 {
-            //plaintext.push(poly.coefficients[i*8+bit].value.value.to_u8().unwrap() & 0x1);
-            accumulator = accumulator + ((poly.coefficients[i*8+bit].value.value.to_u8().unwrap() & 0x1) << (bit));
-        }
-        plaintext.push(accumulator);
-    }
-    plaintext
-}
 
-pub struct Kyber512 {
-    n: usize,
-    public_keys: Vec<(Matrix<PolynomialRingInstance<ZmodInstance>>, Vector<PolynomialRingInstance<ZmodInstance>>)>, // n, e
-    private_keys: Vec<Vector<PolynomialRingInstance<ZmodInstance>>>,// p, q, d
-    primary_key: usize,
-    ring: PolynomialRing<ZmodInstance>,
-    field: Zmod,
-    k: usize,
-    eta1: usize,
-    eta2: usize,
-    du: usize,
-    dv: usize
+fn bit_reverse_7(value: usize) -> usize {
+    (0..7).fold(0, |reversed, bit| reversed | (((value >> bit) & 1) << (6 - bit)))
 }
 
-pub struct Kyber768 {
-    n: usize,
-    public_keys: Vec<(Matrix<PolynomialRingInstance<ZmodInstance>>, Vector<PolynomialRingInstance<ZmodInstance>>)>, // n, e
-    private_keys: Vec<Vector<PolynomialRingInstance<ZmodInstance>>>,// p, q, d
-    primary_key: usize,
-    ring: PolynomialRing<ZmodInstance>,
-    field: Zmod,
-    k: usize,
-    eta1: usize,
-    eta2: usize,
-    du: usize,
-    dv: usize
+fn power_mod(base: u32, exponent: u32, modulus: u32) -> u32 {
+    let mut result: u64 = 1;
+    let mut base: u64 = base as u64 % modulus as u64;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus as u64;
+        }
+        base = base * base % modulus as u64;
+        exponent >>= 1;
+    }
+    result as u32
 }
 
-pub struct Kyber1024 {
-    n: usize,
-    public_keys: Vec<(Matrix<PolynomialRingInstance<ZmodInstance>>, Vector<PolynomialRingInstance<ZmodInstance>>)>, // n, e
-    private_keys: Vec<Vector<PolynomialRingInstance<ZmodInstance>>>,// p, q, d
-    primary_key: usize,
-    ring: PolynomialRing<ZmodInstance>,
-    field: Zmod,
+/*
+    Parameters of a KyberParams implementation, with the precomputed NTT constants
+*/
+struct KyberParameters {
     k: usize,
     eta1: usize,
     eta2: usize,
     du: usize,
-    dv: usize
+    dv: usize,
+    q: u32,
+    coefficient_bits: usize,
+    zetas: [u32; 128], // zeta^BitRev7(i) for the NTT layers
+    gammas: [u32; 128], // zeta^(2 BitRev7(i) + 1) for the products in the NTT domain
+    n_inverse: u32 // 128^-1 mod q, the scaling of the inverse NTT
 }
 
-
-impl Kyber512 {
-    pub fn init() -> Kyber512 {
-        let n: usize = 256;
-        let q: ZZinstance = ZZ::new().new_instance(BigInt::from(3329));
-        let k: usize = 2; // number of polynomials per vector
-        let eta_1: usize = 3;
-        let eta_2: usize = 2;
-        let du: usize = 10;
-        let dv: usize = 4;
-        let zeta = BigInt::from(17); // fixed nth_root
-
-        let ntt_ctxt = NTTFactory::init(n.clone(), q.value.clone(), zeta, NTT_Algorithm::NegativeConvolution);
-
-        // initializing the struct
-        let mut public_keys: Vec<(Matrix<PolynomialRingInstance<ZmodInstance>>, Vector<PolynomialRingInstance<ZmodInstance>>)> = Vec::new(); // n, e
-        let mut private_keys: Vec<Vector<PolynomialRingInstance<ZmodInstance>>> = Vec::new();
-        let primary_key: usize = 0;
-
-
-        // creating the RING x^256+1
-        let field: Zmod = Zmod::new(Some(q.clone()));
-        let var: Var = Var::new("x", BigInt::from(1));
-        let mut coefficients: Vec<ZmodInstance> = Vec::new();
-        coefficients.push(field.one());
-        for _i in 1..n {
-            coefficients.push(field.zero());
-        }
-        coefficients.push(field.one());
-
-        let irreducible_polynomial = UnivariatePolynomial::new_instance(coefficients, var, None, false);
-        let ring: PolynomialRing<ZmodInstance> = PolynomialRing::new(irreducible_polynomial.clone(), false);
-        let ntt_ring = ring.get_ntt_enabled_ring(RefCell::new(ntt_ctxt));
-
-        // creating public key, private key couple
-        let mut secret_key: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..k {
-            secret_key.push(
-                ntt_ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(n, eta_1) % q.clone()).quotient(irreducible_polynomial.clone(), true, false))))
-            ;
+impl KyberParameters {
+    fn new<P: KyberParams>() -> KyberParameters {
+        let q = P::Q;
+        let is_prime = q > 2 && (2..).take_while(|d| d * d <= q).all(|d| q % d != 0);
+        if !is_prime || q >= 1 << 16 || q % 256 != 1 || power_mod(P::ZETA, 128, q) != q - 1 {
+            panic!("q must be a prime below 2^16 with a primitive 256-th root of unity zeta");
         }
-        let s: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(secret_key);
-
-        let mut vectors_of_public_key: Vec<Vec<PolynomialRingInstance<ZmodInstance>>> = Vec::new();
-        for _i in 0..k {
-            let mut vect: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-            for _j in 0..k {
-                vect.push(
-                    ntt_ring.apply_ntt_ctxt(&((gen_from_range_with_modulo::<ZZinstance>(BigInt::zero(), q.value.clone(), n-1, q.value.clone()).quotient(irreducible_polynomial.clone(), true, false)))))
-            }
-            vectors_of_public_key.push(vect);
-        } 
-
-        let A: Matrix<PolynomialRingInstance<ZmodInstance>> = Matrix::new(vectors_of_public_key, k, k);
-        
-        let mut error: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..k {
-            error.push((
-                ntt_ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(n, eta_1) % q.clone()).quotient(irreducible_polynomial.clone(), true, false)))));
+        let coefficient_bits = (u32::BITS - (q - 1).leading_zeros()) as usize;
+        if P::K == 0 || P::K > 127 || P::DU >= coefficient_bits || P::DV >= coefficient_bits || P::DU == 0 || P::DV == 0 || P::ETA1 == 0 || P::ETA2 == 0 {
+            panic!("Invalid parameter set");
         }
-        let e: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(error);
 
+        let mut zetas = [0u32; 128];
+        let mut gammas = [0u32; 128];
+        for i in 0..128 {
+            zetas[i] = power_mod(P::ZETA, bit_reverse_7(i) as u32, q);
+            gammas[i] = power_mod(P::ZETA, 2 * bit_reverse_7(i) as u32 + 1, q);
+        }
+        KyberParameters {
+            k: P::K,
+            eta1: P::ETA1,
+            eta2: P::ETA2,
+            du: P::DU,
+            dv: P::DV,
+            q,
+            coefficient_bits,
+            zetas,
+            gammas,
+            n_inverse: power_mod(128, q - 2, q)
+        }
+    }
 
-        let b = A.clone()*s.clone()+ e;
+    fn polynomial_size(&self) -> usize {
+        32 * self.coefficient_bits
+    }
 
-        public_keys.push((A, b));
-        private_keys.push(s);
+    fn encapsulation_key_size(&self) -> usize {
+        self.polynomial_size() * self.k + 32
+    }
 
-        Kyber512 { n: n, public_keys: public_keys, private_keys: private_keys, primary_key: primary_key, ring: ntt_ring, field: field, k: k, eta1: eta_1, eta2: eta_2, du: du, dv: dv }
-        
+    fn ciphertext_size(&self) -> usize {
+        32 * (self.du * self.k + self.dv)
     }
 
-    //fn keygen() {}
-}
+    fn multiply(&self, a: u32, b: u32) -> u32 {
+        (a as u64 * b as u64 % self.q as u64) as u32
+    }
 
 
+    // --------------------- NTT ---------------------
+
+    // FIPS 203, algorithm 9
+    fn ntt(&self, f: &Polynomial) -> Polynomial {
+        let q = self.q;
+        let mut f_hat = *f;
+        let mut i = 1;
+        let mut length = 128;
+        while length >= 2 {
+            for start in (0..N).step_by(2 * length) {
+                let zeta = self.zetas[i];
+                i += 1;
+                for j in start..start + length {
+                    let t = self.multiply(zeta, f_hat[j + length]);
+                    f_hat[j + length] = (f_hat[j] + q - t) % q;
+                    f_hat[j] = (f_hat[j] + t) % q;
+                }
+            }
+            length /= 2;
+        }
+        f_hat
+    }
 
+    // FIPS 203, algorithm 10
+    fn inverse_ntt(&self, f_hat: &Polynomial) -> Polynomial {
+        let q = self.q;
+        let mut f = *f_hat;
+        let mut i = 127;
+        let mut length = 2;
+        while length <= 128 {
+            for start in (0..N).step_by(2 * length) {
+                let zeta = self.zetas[i];
+                i -= 1;
+                for j in start..start + length {
+                    let t = f[j];
+                    f[j] = (t + f[j + length]) % q;
+                    f[j + length] = self.multiply(zeta, (f[j + length] + q - t) % q);
+                }
+            }
+            length *= 2;
+        }
+        f.map(|coefficient| self.multiply(coefficient, self.n_inverse))
+    }
 
-impl LatticeBased_PKIinterface for Kyber512 {
-    fn encrypt(&self, plaintext: Vec<u8>) -> (Vector<PolynomialRingInstance<ZmodInstance>>, PolynomialRingInstance<ZmodInstance>) {
-        if plaintext.len() > 32 {
-            panic!("Plaintext to big. Only 32 bytes can be encrypted");
+    // FIPS 203, algorithms 11 and 12: products in the 128 quadratic factors X^2 - zeta^(2 BitRev7(i) + 1)
+    fn multiply_ntts(&self, f_hat: &Polynomial, g_hat: &Polynomial) -> Polynomial {
+        let mut h_hat = [0u32; N];
+        for i in 0..128 {
+            let (a0, a1, b0, b1) = (f_hat[2 * i], f_hat[2 * i + 1], g_hat[2 * i], g_hat[2 * i + 1]);
+            h_hat[2 * i] = (self.multiply(a0, b0) + self.multiply(self.multiply(a1, b1), self.gammas[i])) % self.q;
+            h_hat[2 * i + 1] = (self.multiply(a0, b1) + self.multiply(a1, b0)) % self.q;
         }
+        h_hat
+    }
 
-        let poly_plaintext: UnivariatePolynomialInstance<ZZinstance> = plaintext_to_poly(plaintext, self.n);
-        let m_scaled: UnivariatePolynomialInstance<ZmodInstance> = decompress(poly_plaintext, self.field.module.as_ref().unwrap().clone(), 1);
-        let vector_m_scaled: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(vec![self.ring.apply_ntt_ctxt(&(m_scaled.quotient(self.ring.irreducible_polynomial.clone(), true, false)))]);
-        let mut random_vector: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..self.k {
-            random_vector.push((
-                self.ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(self.n, self.eta1) % self.field.module.as_ref().unwrap().clone()).quotient(self.ring.irreducible_polynomial.clone(), true, false)))));
+    fn add_polynomials(&self, f: &Polynomial, g: &Polynomial) -> Polynomial {
+        let mut h = [0u32; N];
+        for i in 0..N {
+            h[i] = (f[i] + g[i]) % self.q;
         }
-        let r: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(random_vector);
+        h
+    }
 
-        let mut error_1: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..self.k {
-            error_1.push((
-                self.ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(self.n, self.eta2) % self.field.module.as_ref().unwrap().clone()).quotient(self.ring.irreducible_polynomial.clone(), true, false)))));
+    fn sub_polynomials(&self, f: &Polynomial, g: &Polynomial) -> Polynomial {
+        let mut h = [0u32; N];
+        for i in 0..N {
+            h[i] = (f[i] + self.q - g[i]) % self.q;
         }
-        let e1: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(error_1);
+        h
+    }
 
-        let mut error_2: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        error_2.push((
-            self.ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(self.n, self.eta2) % self.field.module.as_ref().unwrap().clone()).quotient(self.ring.irreducible_polynomial.clone(), true, false))))); 
-        let e2: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(error_2);
+    // sum of the products of two vectors in the NTT domain
+    fn inner_product_ntt(&self, f_hat: &[Polynomial], g_hat: &[Polynomial]) -> Polynomial {
+        f_hat.iter().zip(g_hat).fold([0u32; N], |sum, (f, g)| self.add_polynomials(&sum, &self.multiply_ntts(f, g)))
+    }
 
-        let (mut A, mut b) = self.public_keys[self.primary_key].clone();
 
-        A.inplace_transpose();
-        let mut u =  A*r.clone() + e1;
-        let mut v =  (b.transpose()*r + e2 + vector_m_scaled).values[0].clone();
+    // --------------------- ENCODINGS ---------------------
 
-        
-        
-        for i in 0..u.len {
-            u.values[i] = compress(self.ring.from_ntt_ctxt(&u.values[i], false).unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.du).quotient(self.ring.irreducible_polynomial.clone(), true, false);
+    // FIPS 203, algorithm 5: d bits per coefficient, least significant bit first
+    fn byte_encode(&self, f: &Polynomial, d: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; 32 * d];
+        for (i, coefficient) in f.iter().enumerate() {
+            for j in 0..d {
+                let bit = i * d + j;
+                bytes[bit / 8] |= (((coefficient >> j) & 1) as u8) << (bit % 8);
+            }
         }
-
-        v = compress(self.ring.from_ntt_ctxt(&v, false).unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.dv).quotient(self.ring.irreducible_polynomial.clone(), true, false);
-
-        (u,v)
+        bytes
     }
 
-    fn decrypt(&self, u: Vector<PolynomialRingInstance<ZmodInstance>>, v: PolynomialRingInstance<ZmodInstance>) -> Vec<u8> {
-        let mut decomp_u: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for i in 0..u.len {
-            decomp_u.push(self.ring.apply_ntt_ctxt(&decompress(u.values[i].unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.du).quotient(self.ring.irreducible_polynomial.clone(), true, false)));
+    // FIPS 203, algorithm 6: coefficients are reduced modulo 2^d, or modulo q for full size coefficients
+    fn byte_decode(&self, bytes: &[u8], d: usize) -> Polynomial {
+        let modulus = if d == self.coefficient_bits { self.q } else { 1 << d };
+        let mut f = [0u32; N];
+        for (i, coefficient) in f.iter_mut().enumerate() {
+            let mut value = 0u32;
+            for j in 0..d {
+                let bit = i * d + j;
+                value |= (((bytes[bit / 8] >> (bit % 8)) & 1) as u32) << j;
+            }
+            *coefficient = value % modulus;
         }
-        let U: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(decomp_u);    
-        let mut decomp_v = self.ring.apply_ntt_ctxt(&decompress(v.unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.dv).quotient(self.ring.irreducible_polynomial.clone(), true, false));
-        let mut temp_result = decomp_v - (self.private_keys[self.primary_key].transpose()*U).values[0].clone();
-
-        let val = self.ring.from_ntt_ctxt(&temp_result, false);
-        let compress_result = compress(val.unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), 1);
-
-        poly_to_plaintext(compress_result)
-    
+        f
     }
-}
 
+    fn encode_vector(&self, vector: &[Polynomial], d: usize) -> Vec<u8> {
+        vector.iter().flat_map(|polynomial| self.byte_encode(polynomial, d)).collect()
+    }
 
-impl KEMinterface for Kyber512 {
-    fn encapsulate(&self, bytes_length: usize) -> (Vector<PolynomialRingInstance<ZmodInstance>>, PolynomialRingInstance<ZmodInstance>) {
-        if bytes_length > 32 {
-            panic!("The session key could be at maximum 8 bytes long");
-        }
+    fn decode_vector(&self, bytes: &[u8], d: usize) -> Vec<Polynomial> {
+        bytes.chunks(32 * d).map(|chunk| self.byte_decode(chunk, d)).collect()
+    }
 
-        let ss: Vec<u8> = random_byte_array(bytes_length);
-        self.encrypt(ss)
+    // round(2^d / q * x) mod 2^d
+    fn compress(&self, f: &Polynomial, d: usize) -> Polynomial {
+        let q = self.q as u64;
+        f.map(|x| ((((x as u64) << d) + q / 2) / q) as u32 & ((1 << d) - 1))
     }
-}
 
+    // round(q / 2^d * y)
+    fn decompress(&self, f: &Polynomial, d: usize) -> Polynomial {
+        f.map(|y| ((y as u64 * self.q as u64 + (1 << (d - 1))) >> d) as u32)
+    }
 
 
-/**
- *  KYBER 768
- */
-impl Kyber768 {
-    pub fn init() -> Kyber768 {
-        let n: usize = 256;
-        let q: ZZinstance = ZZ::new().new_instance(BigInt::from(3329));
-        let k: usize = 3; // number of polynomials per vector
-        let eta_1: usize = 2;
-        let eta_2: usize = 2;
-        let du: usize = 10;
-        let dv: usize = 4;
-        let zeta = BigInt::from(17); // fixed nth_root
-
-        let ntt_ctxt = NTTFactory::init(n.clone(), q.value.clone(), zeta, NTT_Algorithm::NegativeConvolution);
-
-        // initializing the struct
-        let mut public_keys: Vec<(Matrix<PolynomialRingInstance<ZmodInstance>>, Vector<PolynomialRingInstance<ZmodInstance>>)> = Vec::new(); // n, e
-        let mut private_keys: Vec<Vector<PolynomialRingInstance<ZmodInstance>>> = Vec::new();
-        let primary_key: usize = 0;
-
-
-        // creating the RING x^256+1
-        let field: Zmod = Zmod::new(Some(q.clone()));
-        let var: Var = Var::new("x", BigInt::from(1));
-        let mut coefficients: Vec<ZmodInstance> = Vec::new();
-        coefficients.push(field.one());
-        for _i in 1..n {
-            coefficients.push(field.zero());
-        }
-        coefficients.push(field.one());
-
-        let irreducible_polynomial = UnivariatePolynomial::new_instance(coefficients, var, None, false);
-        let ring: PolynomialRing<ZmodInstance> = PolynomialRing::new(irreducible_polynomial.clone(), false);
-        let ntt_ring = ring.get_ntt_enabled_ring(RefCell::new(ntt_ctxt));
-
-        // creating public key, private key couple
-        let mut secret_key: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..k {
-            secret_key.push(
-                ntt_ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(n, eta_1) % q.clone()).quotient(irreducible_polynomial.clone(), true, false))))
-            ;
-        }
-        let s: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(secret_key);
-
-        let mut vectors_of_public_key: Vec<Vec<PolynomialRingInstance<ZmodInstance>>> = Vec::new();
-        for _i in 0..k {
-            let mut vect: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-            for _j in 0..k {
-                vect.push(
-                    ntt_ring.apply_ntt_ctxt(&((gen_from_range_with_modulo::<ZZinstance>(BigInt::zero(), q.value.clone(), n-1, q.value.clone()).quotient(irreducible_polynomial.clone(), true, false)))))
+    // --------------------- SAMPLING ---------------------
+
+    /*
+    FIPS 203, algorithm 7: rejection sampling of a polynomial in the NTT domain from SHAKE128(rho || j || i).
+    Candidates are read on the bit length of q - 1, which is the 12 bits of the standard for q = 3329.
+     */
+    fn sample_ntt(&self, rho: &[u8], j: u8, i: u8) -> Polynomial {
+        let mut xof = SHAKE128::new();
+        xof.update(rho);
+        xof.update(&[j, i]);
+        let mut reader = xof.finalize();
+
+        let mut a_hat = [0u32; N];
+        let mut position = 0;
+        let (mut bits, mut available_bits) = (0u64, 0usize);
+        while position < N {
+            while available_bits < self.coefficient_bits {
+                bits |= (reader.read(1)[0] as u64) << available_bits;
+                available_bits += 8;
+            }
+            let candidate = (bits & ((1 << self.coefficient_bits) - 1)) as u32;
+            bits >>= self.coefficient_bits;
+            available_bits -= self.coefficient_bits;
+            if candidate < self.q {
+                a_hat[position] = candidate;
+                position += 1;
             }
-            vectors_of_public_key.push(vect);
-        } 
-
-        let A: Matrix<PolynomialRingInstance<ZmodInstance>> = Matrix::new(vectors_of_public_key, k, k);
-        
-        let mut error: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..k {
-            error.push((
-                ntt_ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(n, eta_1) % q.clone()).quotient(irreducible_polynomial.clone(), true, false)))));
         }
-        let e: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(error);
-
+        a_hat
+    }
 
-        let b = A.clone()*s.clone()+ e;
+    // FIPS 203, algorithm 8: centered binomial distribution from 64 eta bytes
+    fn sample_poly_cbd(&self, bytes: &[u8], eta: usize) -> Polynomial {
+        let q = self.q;
+        let bit = |position: usize| ((bytes[position / 8] >> (position % 8)) & 1) as u32;
+        let mut f = [0u32; N];
+        for (i, coefficient) in f.iter_mut().enumerate() {
+            let x: u32 = (0..eta).map(|j| bit(2 * i * eta + j)).sum();
+            let y: u32 = (0..eta).map(|j| bit(2 * i * eta + eta + j)).sum();
+            *coefficient = (x + q - y) % q;
+        }
+        f
+    }
 
-        public_keys.push((A, b));
-        private_keys.push(s);
+    // A_hat[i][j] = SampleNTT(rho || j || i)
+    fn generate_matrix(&self, rho: &[u8]) -> Vec<Vec<Polynomial>> {
+        (0..self.k).map(|i| (0..self.k).map(|j| self.sample_ntt(rho, j as u8, i as u8)).collect()).collect()
+    }
 
-        Kyber768 { n: n, public_keys: public_keys, private_keys: private_keys, primary_key: primary_key, ring: ntt_ring, field: field, k: k, eta1: eta_1, eta2: eta_2, du: du, dv: dv }
-        
+    // k polynomials sampled with consecutive nonces, starting from nonce
+    fn sample_vector(&self, seed: &[u8], nonce: &mut u8, eta: usize) -> Vec<Polynomial> {
+        let mut vector: Vec<Polynomial> = Vec::new();
+        for _ in 0..self.k {
+            vector.push(self.sample_poly_cbd(&prf(seed, *nonce, eta), eta));
+            *nonce += 1;
+        }
+        vector
     }
+}
 
-    //fn keygen() {}
+// PRF_eta(s, b) = SHAKE256(s || b, 64 eta)
+fn prf(seed: &[u8], nonce: u8, eta: usize) -> Vec<u8> {
+    let mut input = seed.to_vec();
+    input.push(nonce);
+    SHAKE256::digest(&input, 64 * eta)
 }
 
 
+// --------------------- K-PKE ---------------------
 
+// FIPS 203, algorithm 13
+fn k_pke_key_gen(parameters: &KyberParameters, d: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let (k, bits) = (parameters.k, parameters.coefficient_bits);
+    let mut seed = d.to_vec();
+    seed.push(k as u8);
+    let (rho, sigma) = {
+        let g = SHA3_512::digest(&seed);
+        (g[..32].to_vec(), g[32..].to_vec())
+    };
 
-impl LatticeBased_PKIinterface for Kyber768 {
-    fn encrypt(&self, plaintext: Vec<u8>) -> (Vector<PolynomialRingInstance<ZmodInstance>>, PolynomialRingInstance<ZmodInstance>) {
-        if plaintext.len() > 32 {
-            panic!("Plaintext to big. Only 32 bytes can be encrypted");
-        }
+    let a_hat = parameters.generate_matrix(&rho);
+    let mut nonce = 0u8;
+    let s_hat: Vec<Polynomial> = parameters.sample_vector(&sigma, &mut nonce, parameters.eta1).iter().map(|s| parameters.ntt(s)).collect();
+    let e_hat: Vec<Polynomial> = parameters.sample_vector(&sigma, &mut nonce, parameters.eta1).iter().map(|e| parameters.ntt(e)).collect();
+    let t_hat: Vec<Polynomial> = (0..k).map(|i| parameters.add_polynomials(&parameters.inner_product_ntt(&a_hat[i], &s_hat), &e_hat[i])).collect();
 
-        let poly_plaintext: UnivariatePolynomialInstance<ZZinstance> = plaintext_to_poly(plaintext, self.n);
-        let m_scaled: UnivariatePolynomialInstance<ZmodInstance> = decompress(poly_plaintext, self.field.module.as_ref().unwrap().clone(), 1);
-        let vector_m_scaled: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(vec![self.ring.apply_ntt_ctxt(&(m_scaled.quotient(self.ring.irreducible_polynomial.clone(), true, false)))]);
-        let mut random_vector: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..self.k {
-            random_vector.push((
-                self.ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(self.n, self.eta1) % self.field.module.as_ref().unwrap().clone()).quotient(self.ring.irreducible_polynomial.clone(), true, false)))));
-        }
-        let r: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(random_vector);
+    let mut encryption_key = parameters.encode_vector(&t_hat, bits);
+    encryption_key.extend(rho);
+    (encryption_key, parameters.encode_vector(&s_hat, bits))
+}
 
-        let mut error_1: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..self.k {
-            error_1.push((
-                self.ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(self.n, self.eta2) % self.field.module.as_ref().unwrap().clone()).quotient(self.ring.irreducible_polynomial.clone(), true, false)))));
-        }
-        let e1: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(error_1);
+// FIPS 203, algorithm 14
+fn k_pke_encrypt(parameters: &KyberParameters, encryption_key: &[u8], message: &[u8], randomness: &[u8]) -> Vec<u8> {
+    let k = parameters.k;
+    let (t_hat_bytes, rho) = encryption_key.split_at(parameters.polynomial_size() * k);
+    let t_hat = parameters.decode_vector(t_hat_bytes, parameters.coefficient_bits);
+    let a_hat = parameters.generate_matrix(rho);
+
+    let mut nonce = 0u8;
+    let y_hat: Vec<Polynomial> = parameters.sample_vector(randomness, &mut nonce, parameters.eta1).iter().map(|y| parameters.ntt(y)).collect();
+    let e1 = parameters.sample_vector(randomness, &mut nonce, parameters.eta2);
+    let e2 = parameters.sample_poly_cbd(&prf(randomness, nonce, parameters.eta2), parameters.eta2);
+
+    // u = NTT^-1(A_hat^T y_hat) + e1
+    let u: Vec<Polynomial> = (0..k).map(|i| {
+        let column: Vec<Polynomial> = (0..k).map(|j| a_hat[j][i]).collect();
+        parameters.add_polynomials(&parameters.inverse_ntt(&parameters.inner_product_ntt(&column, &y_hat)), &e1[i])
+    }).collect();
+    let mu = parameters.decompress(&parameters.byte_decode(message, 1), 1);
+    let v = parameters.add_polynomials(&parameters.add_polynomials(&parameters.inverse_ntt(&parameters.inner_product_ntt(&t_hat, &y_hat)), &e2), &mu);
+
+    let compressed_u: Vec<Polynomial> = u.iter().map(|polynomial| parameters.compress(polynomial, parameters.du)).collect();
+    let mut ciphertext = parameters.encode_vector(&compressed_u, parameters.du);
+    ciphertext.extend(parameters.byte_encode(&parameters.compress(&v, parameters.dv), parameters.dv));
+    ciphertext
+}
 
-        let mut error_2: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        error_2.push((
-            self.ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(self.n, self.eta2) % self.field.module.as_ref().unwrap().clone()).quotient(self.ring.irreducible_polynomial.clone(), true, false))))); 
-        let e2: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(error_2);
+// FIPS 203, algorithm 15
+fn k_pke_decrypt(parameters: &KyberParameters, decryption_key: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let (c1, c2) = ciphertext.split_at(32 * parameters.du * parameters.k);
+    let u: Vec<Polynomial> = parameters.decode_vector(c1, parameters.du).iter().map(|polynomial| parameters.ntt(&parameters.decompress(polynomial, parameters.du))).collect();
+    let v = parameters.decompress(&parameters.byte_decode(c2, parameters.dv), parameters.dv);
+    let s_hat = parameters.decode_vector(decryption_key, parameters.coefficient_bits);
 
-        let (mut A, mut b) = self.public_keys[self.primary_key].clone();
+    let w = parameters.sub_polynomials(&v, &parameters.inverse_ntt(&parameters.inner_product_ntt(&s_hat, &u)));
+    parameters.byte_encode(&parameters.compress(&w, 1), 1)
+}
 
-        A.inplace_transpose();
-        let mut u =  A*r.clone() + e1;
-        let mut v =  (b.transpose()*r + e2 + vector_m_scaled).values[0].clone();
 
-        
-        
-        for i in 0..u.len {
-            u.values[i] = compress(self.ring.from_ntt_ctxt(&u.values[i], false).unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.du).quotient(self.ring.irreducible_polynomial.clone(), true, false);
-        }
+// --------------------- ML-KEM ---------------------
 
-        v = compress(self.ring.from_ntt_ctxt(&v, false).unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.dv).quotient(self.ring.irreducible_polynomial.clone(), true, false);
+// FIPS 203, algorithm 16: dk = dk_pke || ek || H(ek) || z
+fn ml_kem_key_gen(parameters: &KyberParameters, d: &[u8], z: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    unwrap_or_panic(try_ml_kem_key_gen(parameters, d, z))
+}
 
-        (u,v)
+fn try_ml_kem_key_gen(parameters: &KyberParameters, d: &[u8], z: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    if d.len() != 32 || z.len() != 32 {
+        return Err(Error::InvalidInput("The seeds must be 32 bytes long".to_string()));
     }
+    let (encapsulation_key, decryption_key) = k_pke_key_gen(parameters, d);
+    let mut decapsulation_key = decryption_key;
+    decapsulation_key.extend_from_slice(&encapsulation_key);
+    decapsulation_key.extend(SHA3_256::digest(&encapsulation_key));
+    decapsulation_key.extend_from_slice(z);
+    Ok((encapsulation_key, decapsulation_key))
+}
 
-    fn decrypt(&self, u: Vector<PolynomialRingInstance<ZmodInstance>>, v: PolynomialRingInstance<ZmodInstance>) -> Vec<u8> {
-        let mut decomp_u: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for i in 0..u.len {
-            decomp_u.push(self.ring.apply_ntt_ctxt(&decompress(u.values[i].unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.du).quotient(self.ring.irreducible_polynomial.clone(), true, false)));
-        }
-        let U: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(decomp_u);    
-        let mut decomp_v = self.ring.apply_ntt_ctxt(&decompress(v.unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.dv).quotient(self.ring.irreducible_polynomial.clone(), true, false));
-        let mut temp_result = decomp_v - (self.private_keys[self.primary_key].transpose()*U).values[0].clone();
-
-        let val = self.ring.from_ntt_ctxt(&temp_result, false);
-        let compress_result = compress(val.unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), 1);
+// FIPS 203, section 7.2: length and modulus checks of an encapsulation key
+fn is_valid_encapsulation_key(parameters: &KyberParameters, encapsulation_key: &[u8]) -> bool {
+    if encapsulation_key.len() != parameters.encapsulation_key_size() {
+        return false;
+    }
+    let bits = parameters.coefficient_bits;
+    let t_hat_bytes = &encapsulation_key[..parameters.polynomial_size() * parameters.k];
+    parameters.encode_vector(&parameters.decode_vector(t_hat_bytes, bits), bits) == t_hat_bytes
+}
 
-        poly_to_plaintext(compress_result)
-    
+// FIPS 203, algorithm 17: returns (K, c)
+fn try_ml_kem_encaps(parameters: &KyberParameters, encapsulation_key: &[u8], message: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    if message.len() != 32 {
+        return Err(Error::InvalidInput("The message must be 32 bytes long".to_string()));
     }
+    let mut seed = message.to_vec();
+    seed.extend(SHA3_256::digest(encapsulation_key));
+    let g = SHA3_512::digest(&seed);
+    let (shared_secret, randomness) = g.split_at(SHARED_SECRET_SIZE);
+    Ok((shared_secret.to_vec(), k_pke_encrypt(parameters, encapsulation_key, message, randomness)))
 }
 
+// FIPS 203, algorithm 18: a ciphertext that does not re-encrypt to itself gives the implicit rejection key J(z || c)
+fn ml_kem_decaps(parameters: &KyberParameters, decapsulation_key: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    if ciphertext.len() != parameters.ciphertext_size() {
+        panic!("Invalid ciphertext");
+    }
+    let key_size = parameters.polynomial_size() * parameters.k;
+    let decryption_key = &decapsulation_key[..key_size];
+    let encryption_key = &decapsulation_key[key_size..2 * key_size + 32];
+    let h = &decapsulation_key[2 * key_size + 32..2 * key_size + 64];
+    let z = &decapsulation_key[2 * key_size + 64..];
+
+    let message = k_pke_decrypt(parameters, decryption_key, ciphertext);
+    let mut seed = message.clone();
+    seed.extend_from_slice(h);
+    let g = SHA3_512::digest(&seed);
+    let (shared_secret, randomness) = g.split_at(SHARED_SECRET_SIZE);
+
+    let mut rejection_input = z.to_vec();
+    rejection_input.extend_from_slice(ciphertext);
+    let rejection_key = SHAKE256::digest(&rejection_input, SHARED_SECRET_SIZE);
+
+    let reencrypted = k_pke_encrypt(parameters, encryption_key, &message, randomness);
+    // the comparison and the selection do not branch on secret data
+    let difference = reencrypted.iter().zip(ciphertext).fold(0u8, |accumulator, (a, b)| accumulator | (a ^ b));
+    let mask = ((difference as u16).wrapping_sub(1) >> 8) as u8; // 0xff when the ciphertexts are equal
+    shared_secret.iter().zip(rejection_key).map(|(accepted, rejected)| (accepted & mask) | (rejected & !mask)).collect()
+}
 
-impl KEMinterface for Kyber768 {
-    fn encapsulate(&self, bytes_length: usize) -> (Vector<PolynomialRingInstance<ZmodInstance>>, PolynomialRingInstance<ZmodInstance>) {
-        if bytes_length > 32 {
-            panic!("The session key could be at maximum 8 bytes long");
-        }
 
-        let ss: Vec<u8> = random_byte_array(bytes_length);
-        self.encrypt(ss)
-    }
+/*
+    ML-KEM over the parameter set P: Kyber512, Kyber768 and Kyber1024 are the parameter sets of FIPS 203
+*/
+pub struct Kyber<P: KyberParams> {
+    pub encapsulation_keys: Vec<Vec<u8>>, // ek
+    pub decapsulation_keys: Vec<Vec<u8>>, // dk, empty for an encapsulation only instance
+    pub primary_key: usize,
+    parameters: PhantomData<P>
 }
 
+impl<P: KyberParams> KEMinterface for Kyber<P> {
+    fn encapsulate(&self) -> (Vec<u8>, Vec<u8>) {
+        self.encapsulate_with_seed(random_byte_array(32))
+    }
 
-/**
- * Kyber1024
- */
-impl Kyber1024 {
-    pub fn init() -> Kyber1024 {
-        let n: usize = 256;
-        let q: ZZinstance = ZZ::new().new_instance(BigInt::from(3329));
-        let k: usize = 4; // number of polynomials per vector
-        let eta_1: usize = 2;
-        let eta_2: usize = 2;
-        let du: usize = 11;
-        let dv: usize = 5;
-        let zeta = BigInt::from(17); // fixed nth_root
-
-        let ntt_ctxt = NTTFactory::init(n.clone(), q.value.clone(), zeta, NTT_Algorithm::NegativeConvolution);
-
-        // initializing the struct
-        let mut public_keys: Vec<(Matrix<PolynomialRingInstance<ZmodInstance>>, Vector<PolynomialRingInstance<ZmodInstance>>)> = Vec::new(); // n, e
-        let mut private_keys: Vec<Vector<PolynomialRingInstance<ZmodInstance>>> = Vec::new();
-        let primary_key: usize = 0;
-
-
-        // creating the RING x^256+1
-        let field: Zmod = Zmod::new(Some(q.clone()));
-        let var: Var = Var::new("x", BigInt::from(1));
-        let mut coefficients: Vec<ZmodInstance> = Vec::new();
-        coefficients.push(field.one());
-        for _i in 1..n {
-            coefficients.push(field.zero());
+    fn try_decapsulate(&self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        if self.primary_key >= self.decapsulation_keys.len() {
+            return Err(Error::InvalidKey("No decapsulation key available".to_string()));
         }
-        coefficients.push(field.one());
-
-        let irreducible_polynomial = UnivariatePolynomial::new_instance(coefficients, var, None, false);
-        let ring: PolynomialRing<ZmodInstance> = PolynomialRing::new(irreducible_polynomial.clone(), false);
-        let ntt_ring = ring.get_ntt_enabled_ring(RefCell::new(ntt_ctxt));
-
-        // creating public key, private key couple
-        let mut secret_key: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..k {
-            secret_key.push(
-                ntt_ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(n, eta_1) % q.clone()).quotient(irreducible_polynomial.clone(), true, false))))
-            ;
+        if ciphertext.len() != Self::CIPHERTEXT_SIZE {
+            return Err(Error::InvalidInput("Invalid ciphertext".to_string()));
         }
-        let s: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(secret_key);
-
-        let mut vectors_of_public_key: Vec<Vec<PolynomialRingInstance<ZmodInstance>>> = Vec::new();
-        for _i in 0..k {
-            let mut vect: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-            for _j in 0..k {
-                vect.push(
-                    ntt_ring.apply_ntt_ctxt(&((gen_from_range_with_modulo::<ZZinstance>(BigInt::zero(), q.value.clone(), n-1, q.value.clone()).quotient(irreducible_polynomial.clone(), true, false)))))
-            }
-            vectors_of_public_key.push(vect);
-        } 
-
-        let A: Matrix<PolynomialRingInstance<ZmodInstance>> = Matrix::new(vectors_of_public_key, k, k);
-        
-        let mut error: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..k {
-            error.push((
-                ntt_ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(n, eta_1) % q.clone()).quotient(irreducible_polynomial.clone(), true, false)))));
-        }
-        let e: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(error);
-
-
-        let b = A.clone()*s.clone()+ e;
-
-        public_keys.push((A, b));
-        private_keys.push(s);
-
-        Kyber1024 { n: n, public_keys: public_keys, private_keys: private_keys, primary_key: primary_key, ring: ntt_ring, field: field, k: k, eta1: eta_1, eta2: eta_2, du: du, dv: dv }
-        
+        Ok(ml_kem_decaps(&KyberParameters::new::<P>(), &self.decapsulation_keys[self.primary_key], &ciphertext))
     }
-
-    //fn keygen() {}
 }
 
+impl<P: KyberParams> Kyber<P> {
+    const COEFFICIENT_BITS: usize = (u32::BITS - (P::Q - 1).leading_zeros()) as usize;
+    pub const ENCAPSULATION_KEY_SIZE: usize = 32 * Self::COEFFICIENT_BITS * P::K + 32;
+    pub const DECAPSULATION_KEY_SIZE: usize = 64 * Self::COEFFICIENT_BITS * P::K + 96;
+    pub const CIPHERTEXT_SIZE: usize = 32 * (P::DU * P::K + P::DV);
 
+    pub fn init() -> Self {
+        Kyber::from_seed(random_byte_array(32), random_byte_array(32))
+    }
 
-impl LatticeBased_PKIinterface for Kyber1024 {
-    fn encrypt(&self, plaintext: Vec<u8>) -> (Vector<PolynomialRingInstance<ZmodInstance>>, PolynomialRingInstance<ZmodInstance>) {
-        if plaintext.len() > 32 {
-            panic!("Plaintext to big. Only 32 bytes can be encrypted");
-        }
-
-        let poly_plaintext: UnivariatePolynomialInstance<ZZinstance> = plaintext_to_poly(plaintext, self.n);
-        let m_scaled: UnivariatePolynomialInstance<ZmodInstance> = decompress(poly_plaintext, self.field.module.as_ref().unwrap().clone(), 1);
-        let vector_m_scaled: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(vec![self.ring.apply_ntt_ctxt(&(m_scaled.quotient(self.ring.irreducible_polynomial.clone(), true, false)))]);
-        let mut random_vector: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..self.k {
-            random_vector.push((
-                self.ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(self.n, self.eta1) % self.field.module.as_ref().unwrap().clone()).quotient(self.ring.irreducible_polynomial.clone(), true, false)))));
-        }
-        let r: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(random_vector);
-
-        let mut error_1: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for _i in 0..self.k {
-            error_1.push((
-                self.ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(self.n, self.eta2) % self.field.module.as_ref().unwrap().clone()).quotient(self.ring.irreducible_polynomial.clone(), true, false)))));
-        }
-        let e1: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(error_1);
-
-        let mut error_2: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        error_2.push((
-            self.ring.apply_ntt_ctxt(&((gen_from_centered_binomial_distribution(self.n, self.eta2) % self.field.module.as_ref().unwrap().clone()).quotient(self.ring.irreducible_polynomial.clone(), true, false))))); 
-        let e2: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(error_2);
+    /*
+    Deterministic key generation (ML-KEM.KeyGen_internal) from the 32 bytes seeds d and z
+     */
+    pub fn from_seed(d: Vec<u8>, z: Vec<u8>) -> Self {
+        unwrap_or_panic(Kyber::try_from_seed(d, z))
+    }
 
-        let (mut A, mut b) = self.public_keys[self.primary_key].clone();
+    pub fn try_from_seed(d: Vec<u8>, z: Vec<u8>) -> Result<Self> {
+        let (encapsulation_key, decapsulation_key) = try_ml_kem_key_gen(&KyberParameters::new::<P>(), &d, &z)?;
+        Ok(Kyber { encapsulation_keys: vec![encapsulation_key], decapsulation_keys: vec![decapsulation_key], primary_key: 0, parameters: PhantomData })
+    }
 
-        A.inplace_transpose();
-        let mut u =  A*r.clone() + e1;
-        let mut v =  (b.transpose()*r + e2 + vector_m_scaled).values[0].clone();
+    /*
+    Encapsulation only, the key must pass the checks of FIPS 203, section 7.2
+     */
+    pub fn from_encapsulation_key(encapsulation_key: Vec<u8>) -> Self {
+        unwrap_or_panic(Kyber::try_from_encapsulation_key(encapsulation_key))
+    }
 
-        
-        
-        for i in 0..u.len {
-            u.values[i] = compress(self.ring.from_ntt_ctxt(&u.values[i], false).unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.du).quotient(self.ring.irreducible_polynomial.clone(), true, false);
+    pub fn try_from_encapsulation_key(encapsulation_key: Vec<u8>) -> Result<Self> {
+        if !is_valid_encapsulation_key(&KyberParameters::new::<P>(), &encapsulation_key) {
+            return Err(Error::InvalidKey("Invalid encapsulation key".to_string()));
         }
+        Ok(Kyber { encapsulation_keys: vec![encapsulation_key], decapsulation_keys: Vec::new(), primary_key: 0, parameters: PhantomData })
+    }
 
-        v = compress(self.ring.from_ntt_ctxt(&v, false).unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.dv).quotient(self.ring.irreducible_polynomial.clone(), true, false);
-
-        (u,v)
+    pub fn refresh_keys(&mut self) {
+        let (encapsulation_key, decapsulation_key) = ml_kem_key_gen(&KyberParameters::new::<P>(), &random_byte_array(32), &random_byte_array(32));
+        self.encapsulation_keys.push(encapsulation_key);
+        self.decapsulation_keys.push(decapsulation_key);
+        self.primary_key = self.encapsulation_keys.len() - 1;
     }
 
-    fn decrypt(&self, u: Vector<PolynomialRingInstance<ZmodInstance>>, v: PolynomialRingInstance<ZmodInstance>) -> Vec<u8> {
-        let mut decomp_u: Vec<PolynomialRingInstance<ZmodInstance>> = Vec::new();
-        for i in 0..u.len {
-            decomp_u.push(self.ring.apply_ntt_ctxt(&decompress(u.values[i].unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.du).quotient(self.ring.irreducible_polynomial.clone(), true, false)));
-        }
-        let U: Vector<PolynomialRingInstance<ZmodInstance>> = Vector::new(decomp_u);    
-        let mut decomp_v = self.ring.apply_ntt_ctxt(&decompress(v.unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), self.dv).quotient(self.ring.irreducible_polynomial.clone(), true, false));
-        let mut temp_result = decomp_v - (self.private_keys[self.primary_key].transpose()*U).values[0].clone();
+    /*
+    Deterministic encapsulation (ML-KEM.Encaps_internal) with the 32 bytes message m, returns (K, c)
+     */
+    pub fn encapsulate_with_seed(&self, m: Vec<u8>) -> (Vec<u8>, Vec<u8>) {
+        unwrap_or_panic(self.try_encapsulate_with_seed(m))
+    }
 
-        let val = self.ring.from_ntt_ctxt(&temp_result, false);
-        let compress_result = compress(val.unwrap_from_ring(), self.field.module.as_ref().unwrap().clone(), 1);
+    pub fn try_encapsulate_with_seed(&self, m: Vec<u8>) -> Result<(Vec<u8>, Vec<u8>)> {
+        try_ml_kem_encaps(&KyberParameters::new::<P>(), &self.encapsulation_keys[self.primary_key], &m)
+    }
 
-        poly_to_plaintext(compress_result)
-    
+    pub fn get_encapsulation_key(&self) -> Vec<u8> {
+        self.encapsulation_keys[self.primary_key].clone()
     }
-}
 
+    pub fn get_decapsulation_key(&self) -> Vec<u8> {
+        self.decapsulation_keys[self.primary_key].clone()
+    }
 
-impl KEMinterface for Kyber1024 {
-    fn encapsulate(&self, bytes_length: usize) -> (Vector<PolynomialRingInstance<ZmodInstance>>, PolynomialRingInstance<ZmodInstance>) {
-        if bytes_length > 32 {
-            panic!("The session key could be at maximum 8 bytes long");
+    // TRUE: set new primary key
+    // FALSE: operation not permitted
+    pub fn set_primary_key(&mut self, new_primary_key: usize) -> bool {
+        if new_primary_key >= self.encapsulation_keys.len() {
+            return false;
         }
-
-        let ss: Vec<u8> = random_byte_array(bytes_length);
-        self.encrypt(ss)
+        self.primary_key = new_primary_key;
+        true
     }
-}
\ No newline at end of file
+}
@@ -1,74 +1,231 @@
-use bigdecimal::BigDecimalRef;
 use num_bigint::BigInt;
 use num_bigint::Sign;
+use num_integer::Integer;
 
 use crate::numbers::classes::ZZ::ZZ;
 use crate::numbers::instances::ZZ_instance::ZZinstance;
-use crate::{cryptography::asymmetric::interfaces::interfaces::{PKIinterface}, algebras::FiniteField::{classes::Zmod::Zmod, instances::Zmod_instance::{self, ZmodInstance}}, numbers::numbers::Class, arith::primes::get_strong_prime};
+use crate::arith::random::random_byte_array;
+use crate::cryptography::hash::interfaces::interfaces::HashInterface;
+use crate::cryptography::hash::primitives::sha1::SHA1;
+use crate::cryptography::hash::primitives::sha2::{SHA256, SHA384, SHA512};
+use crate::error::{unwrap_or_panic, Error, Result};
+use crate::{cryptography::asymmetric::interfaces::interfaces::{PKIinterface, SignatureInterface}, algebras::FiniteField::{classes::Zmod::Zmod, instances::Zmod_instance::ZmodInstance}, numbers::numbers::Class, arith::primes::get_strong_prime};
 
+/*
+    Encryption schemes of PKCS #1 v2.2 (RFC 8017, section 7).
+    PKCS1v15 is only meant for interoperability with legacy systems.
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RSAEncryptionScheme {
+    OAEP,
+    PKCS1v15
+}
+
+/*
+    Signature schemes of PKCS #1 v2.2 (RFC 8017, section 8)
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RSASignatureScheme {
+    PSS,
+    PKCS1v15
+}
+
+/*
+    Hash function used by OAEP, PSS (also for MGF1) and by the EMSA-PKCS1-v1_5 DigestInfo
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RSAHashFunction {
+    SHA1,
+    SHA256,
+    SHA384,
+    SHA512
+}
+
+/*
+    RSA with the encodings of PKCS #1 v2.2. Private key operations use the CRT with the stored p and q.
+    Ciphertexts and signatures are big endian on the byte length of n.
+*/
 pub struct RSA {
     pub public_keys: Vec<(ZZinstance, ZZinstance)>, // n, e
     pub private_keys: Vec<(ZZinstance, ZZinstance, ZZinstance)>,// p, q, d
-    pub primary_key: usize
+    pub primary_key: usize,
+    pub encryption_scheme: RSAEncryptionScheme,
+    pub signature_scheme: RSASignatureScheme,
+    pub hash_function: RSAHashFunction,
+    pub label: Vec<u8> // OAEP label
 }
 
 impl PKIinterface for RSA {
-    fn encrypt(&self, plaintext: Vec<u8>) -> Vec<u8> {
-        // convert bytes to BigInt as big endian
-        let value: BigInt = BigInt::from_bytes_be(Sign::Plus, &plaintext);
-        let (n,e) = self.public_keys[self.primary_key].clone();
-        let ciphertext = value.modpow(&e.value, &n.value);
-        let (_s, c) = ciphertext.to_bytes_be();
-        c
-    }
-
-    fn decrypt(&self, ciphertext: Vec<u8>) -> Vec<u8> {
-        let value: BigInt = BigInt::from_bytes_be(Sign::Plus, &ciphertext);
-        let (p,q, d) = self.private_keys[self.primary_key].clone();
-        let (n,e) = self.public_keys[self.primary_key].clone();
-        let plaintext = value.modpow(&d.value, &n.value);
-        let (_s, p) = plaintext.to_bytes_be();
-        p
+    fn try_encrypt(&self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
+        let k = self.modulus_length();
+        let encoded_message = match self.encryption_scheme {
+            RSAEncryptionScheme::OAEP => self.eme_oaep_encode(&plaintext, &random_byte_array(self.hash_length()))?,
+            RSAEncryptionScheme::PKCS1v15 => {
+                if plaintext.len() + 11 > k {
+                    return Err(Error::InvalidInput("Message too long".to_string()));
+                }
+                // EM = 0x00 || 0x02 || PS || 0x00 || M, with PS made of non zero random bytes
+                let mut encoded_message = vec![0x00u8, 0x02u8];
+                while encoded_message.len() < k - plaintext.len() - 1 {
+                    let byte = random_byte_array(1)[0];
+                    if byte != 0 {
+                        encoded_message.push(byte);
+                    }
+                }
+                encoded_message.push(0x00);
+                encoded_message.extend(plaintext);
+                encoded_message
+            }
+        };
+        let m = BigInt::from_bytes_be(Sign::Plus, &encoded_message);
+        Ok(self.i2osp(&self.public_operation(&m)?, k))
+    }
+
+    fn try_decrypt(&self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        let k = self.modulus_length();
+        if ciphertext.len() != k {
+            return Err(Error::DecryptionFailed);
+        }
+        let c = BigInt::from_bytes_be(Sign::Plus, &ciphertext);
+        let encoded_message = self.i2osp(&self.private_operation(&c)?, k);
+        let message = match self.encryption_scheme {
+            RSAEncryptionScheme::OAEP => self.eme_oaep_decode(&encoded_message),
+            RSAEncryptionScheme::PKCS1v15 => {
+                let separator = encoded_message.iter().skip(2).position(|byte| *byte == 0);
+                match separator {
+                    Some(padding_length) if encoded_message[0] == 0x00 && encoded_message[1] == 0x02 && padding_length >= 8 => Some(encoded_message[padding_length + 3..].to_vec()),
+                    _ => None
+                }
+            }
+        };
+        message.ok_or(Error::DecryptionFailed)
+    }
+}
+
+impl SignatureInterface for RSA {
+    fn try_sign(&self, message: Vec<u8>) -> Result<Vec<u8>> {
+        let k = self.modulus_length();
+        let encoded_message = match self.signature_scheme {
+            RSASignatureScheme::PSS => self.emsa_pss_encode(&message, self.modulus_bits() - 1, &random_byte_array(self.hash_length()))?,
+            RSASignatureScheme::PKCS1v15 => self.emsa_pkcs1_v15_encode(&message)?
+        };
+        let m = BigInt::from_bytes_be(Sign::Plus, &encoded_message);
+        Ok(self.i2osp(&self.private_operation(&m)?, k))
+    }
+
+    fn verify(&self, message: Vec<u8>, signature: Vec<u8>) -> bool {
+        let k = self.modulus_length();
+        let (n, _) = self.public_keys[self.primary_key].clone();
+        if signature.len() != k {
+            return false;
+        }
+        let s = BigInt::from_bytes_be(Sign::Plus, &signature);
+        if s >= n.value {
+            return false;
+        }
+        let m = match self.public_operation(&s) {
+            Ok(m) => m,
+            Err(_) => return false
+        };
+        match self.signature_scheme {
+            RSASignatureScheme::PSS => {
+                let em_bits = self.modulus_bits() - 1;
+                let em_length = em_bits.div_ceil(8) as usize;
+                if m.bits() > em_bits {
+                    return false;
+                }
+                self.emsa_pss_verify(&message, &self.i2osp(&m, em_length), em_bits)
+            },
+            RSASignatureScheme::PKCS1v15 => self.emsa_pkcs1_v15_encode(&message).is_ok_and(|encoded_message| encoded_message == self.i2osp(&m, k))
+        }
     }
 }
 
 impl RSA {
+    /*
+    Key generation with two primes of n_bits bits and e = 65537.
+    The default encodings are OAEP and PSS with SHA-256.
+     */
     pub fn init(n_bits: u32) -> Self {
+        let mut rsa = RSA::empty();
+        rsa.refresh_keys(n_bits);
+        rsa
+    }
+
+    /*
+    Private key from its prime factors, d is computed from e
+     */
+    pub fn from_private_key(p: BigInt, q: BigInt, e: BigInt) -> Self {
+        unwrap_or_panic(RSA::try_from_private_key(p, q, e))
+    }
+
+    pub fn try_from_private_key(p: BigInt, q: BigInt, e: BigInt) -> Result<Self> {
+        let one: BigInt = BigInt::from(1);
+        if p == q || (p.clone() - &one).gcd(&e) != one || (q.clone() - &one).gcd(&e) != one {
+            return Err(Error::InvalidKey("Invalid private key".to_string()));
+        }
+        let mut rsa = RSA::empty();
+        rsa.add_key_pair(p, q, e);
+        Ok(rsa)
+    }
+
+    /*
+    Encryption and signature verification only
+     */
+    pub fn from_public_key(n: BigInt, e: BigInt) -> Self {
+        unwrap_or_panic(RSA::try_from_public_key(n, e))
+    }
+
+    pub fn try_from_public_key(n: BigInt, e: BigInt) -> Result<Self> {
+        if n <= BigInt::from(0) || e <= BigInt::from(1) || e >= n {
+            return Err(Error::InvalidKey("Invalid public key".to_string()));
+        }
         let zz: ZZ = ZZ::new();
-        let p: ZZinstance = zz.new_instance(get_strong_prime(n_bits, false));
-        let q: ZZinstance = p.next_prime();//zz.new_instance(get_strong_prime(n_bits, false));
-        let n: ZZinstance = p.clone()*q.clone();
-        let phin: ZZinstance = (p.clone()-1)*(q.clone()-1);
+        let mut rsa = RSA::empty();
+        rsa.public_keys.push( (zz.new_instance(n), zz.new_instance(e)) );
+        Ok(rsa)
+    }
 
-        let phin_field: Zmod = Zmod::new(Some(phin));
-        let e: ZmodInstance = phin_field.apply(BigInt::from(65537));
-        let d: ZmodInstance = e.clone().inverse();
+    /*
+    OAEP encryption and PSS signature with a given seed or salt, as long as the hash. Only meant
+    for known-answer tests: the seed and the salt must be random and never reused
+     */
+    pub fn encrypt_with_seed(&self, plaintext: Vec<u8>, seed: Vec<u8>) -> Vec<u8> {
+        unwrap_or_panic(self.try_encrypt_with_seed(plaintext, seed))
+    }
 
+    pub fn try_encrypt_with_seed(&self, plaintext: Vec<u8>, seed: Vec<u8>) -> Result<Vec<u8>> {
+        if self.encryption_scheme != RSAEncryptionScheme::OAEP || seed.len() != self.hash_length() {
+            return Err(Error::InvalidInput("Only OAEP takes a seed, as long as the hash".to_string()));
+        }
+        let encoded_message = self.eme_oaep_encode(&plaintext, &seed)?;
+        let m = BigInt::from_bytes_be(Sign::Plus, &encoded_message);
+        Ok(self.i2osp(&self.public_operation(&m)?, self.modulus_length()))
+    }
 
-        let mut public_keys: Vec<(ZZinstance, ZZinstance)> = Vec::new();        
-        public_keys.push( (n, e.get_bigint_value()) );
-        let mut private_keys: Vec<(ZZinstance, ZZinstance, ZZinstance)> = Vec::new();        
-        private_keys.push( (p, q, d.get_bigint_value()) );
+    pub fn sign_with_salt(&self, message: Vec<u8>, salt: Vec<u8>) -> Vec<u8> {
+        unwrap_or_panic(self.try_sign_with_salt(message, salt))
+    }
 
-        RSA { public_keys: public_keys, private_keys: private_keys, primary_key: 0}
+    pub fn try_sign_with_salt(&self, message: Vec<u8>, salt: Vec<u8>) -> Result<Vec<u8>> {
+        if self.signature_scheme != RSASignatureScheme::PSS || salt.len() != self.hash_length() {
+            return Err(Error::InvalidInput("Only PSS takes a salt, as long as the hash".to_string()));
+        }
+        let encoded_message = self.emsa_pss_encode(&message, self.modulus_bits() - 1, &salt)?;
+        let m = BigInt::from_bytes_be(Sign::Plus, &encoded_message);
+        Ok(self.i2osp(&self.private_operation(&m)?, self.modulus_length()))
     }
 
     pub fn refresh_keys(&mut self, n_bits: u32) {
-        let zz: ZZ = ZZ::new();
-        let p: ZZinstance = zz.new_instance(get_strong_prime(n_bits, false));
-        
-        let q: ZZinstance = p.next_prime();//zz.new_instance(get_strong_prime(n_bits, false));
-        let n: ZZinstance = p.clone()*q.clone();
-        let phin: ZZinstance = (p.clone()-1)*(q.clone()-1);
-        let phin_field: Zmod = Zmod::new(Some(phin));
-        let e: ZmodInstance = phin_field.apply(BigInt::from(65537));
-        let d: ZmodInstance = e.clone().inverse();
-
-               
-        self.public_keys.push( (n, e.get_bigint_value()) );
-        self.private_keys.push( (p, q, d.get_bigint_value()) );
-        self.primary_key = self.primary_key + 1
-    } 
+        let e: BigInt = BigInt::from(65537);
+        let p: BigInt = RSA::generate_prime(n_bits, &e);
+        let mut q: BigInt = RSA::generate_prime(n_bits, &e);
+        while q == p {
+            q = RSA::generate_prime(n_bits, &e);
+        }
+        self.add_key_pair(p, q, e);
+        self.primary_key = self.public_keys.len() - 1;
+    }
 
     pub fn get_current_public_key(&self) -> (ZZinstance, ZZinstance) {
         self.public_keys[self.primary_key].clone()
@@ -82,12 +239,276 @@ impl RSA {
     // TRUE: set new primary key
     // FALSE: operation not permitted
     pub fn set_primary_key(&mut self, new_primary_key: usize) -> bool {
-        if new_primary_key > self.private_keys.len() {
+        if new_primary_key >= self.public_keys.len() {
             return false;
         }
 
         self.primary_key = new_primary_key;
-        return true;
+        true
+    }
+
+    fn empty() -> Self {
+        RSA {
+            public_keys: Vec::new(),
+            private_keys: Vec::new(),
+            primary_key: 0,
+            encryption_scheme: RSAEncryptionScheme::OAEP,
+            signature_scheme: RSASignatureScheme::PSS,
+            hash_function: RSAHashFunction::SHA256,
+            label: Vec::new()
+        }
     }
 
-}
\ No newline at end of file
+    // e must be invertible modulo p - 1
+    fn generate_prime(n_bits: u32, e: &BigInt) -> BigInt {
+        loop {
+            let p: BigInt = get_strong_prime(n_bits, false);
+            let p_minus_one: BigInt = p.clone() - 1;
+            if p_minus_one.gcd(e) == BigInt::from(1) {
+                return p;
+            }
+        }
+    }
+
+    fn add_key_pair(&mut self, p: BigInt, q: BigInt, e: BigInt) {
+        let zz: ZZ = ZZ::new();
+        let p: ZZinstance = zz.new_instance(p);
+        let q: ZZinstance = zz.new_instance(q);
+        let n: ZZinstance = p.clone()*q.clone();
+        let phin: ZZinstance = (p.clone()-1)*(q.clone()-1);
+
+        let phin_field: Zmod = Zmod::new(Some(phin));
+        let e: ZmodInstance = phin_field.apply(e);
+        let d: ZmodInstance = e.clone().inverse();
+
+        self.public_keys.push( (n, e.get_bigint_value()) );
+        self.private_keys.push( (p, q, d.get_bigint_value()) );
+    }
+
+    fn modulus_bits(&self) -> u64 {
+        self.public_keys[self.primary_key].0.value.bits()
+    }
+
+    fn modulus_length(&self) -> usize {
+        self.modulus_bits().div_ceil(8) as usize
+    }
+
+    // RSAEP / RSAVP1
+    fn public_operation(&self, m: &BigInt) -> Result<BigInt> {
+        let (n, e) = self.public_keys[self.primary_key].clone();
+        if *m >= n.value {
+            return Err(Error::InvalidInput("Message representative out of range".to_string()));
+        }
+        Ok(m.modpow(&e.value, &n.value))
+    }
+
+    /*
+    RSADP / RSASP1 with the CRT (RFC 8017, section 5.1.2). The result is checked
+    against the public key, so that a faulty computation never leaks a factor of n.
+     */
+    fn private_operation(&self, c: &BigInt) -> Result<BigInt> {
+        if self.primary_key >= self.private_keys.len() {
+            return Err(Error::InvalidKey("No private key available".to_string()));
+        }
+        let (n, e) = self.public_keys[self.primary_key].clone();
+        let (p, q, d) = self.private_keys[self.primary_key].clone();
+        let (p, q, d) = (p.value, q.value, d.value);
+        if *c >= n.value {
+            return Err(Error::DecryptionFailed);
+        }
+
+        let dp: BigInt = d.mod_floor(&(p.clone() - 1));
+        let dq: BigInt = d.mod_floor(&(q.clone() - 1));
+        let q_inverse: BigInt = q.modpow(&(p.clone() - 2), &p);
+        let m1: BigInt = c.modpow(&dp, &p);
+        let m2: BigInt = c.modpow(&dq, &q);
+        let h: BigInt = (q_inverse * (m1 - m2.clone())).mod_floor(&p);
+        let m: BigInt = m2 + h * q;
+
+        if m.modpow(&e.value, &n.value) != *c {
+            return Err(Error::DecryptionFailed);
+        }
+        Ok(m)
+    }
+
+    fn i2osp(&self, value: &BigInt, length: usize) -> Vec<u8> {
+        let (_, bytes) = value.to_bytes_be();
+        if bytes.len() > length {
+            panic!("Integer too large");
+        }
+        let mut octets = vec![0u8; length - bytes.len()];
+        octets.extend(bytes);
+        octets
+    }
+
+    fn hash(&self, data: &[u8]) -> Vec<u8> {
+        match self.hash_function {
+            RSAHashFunction::SHA1 => SHA1::digest(data),
+            RSAHashFunction::SHA256 => SHA256::digest(data),
+            RSAHashFunction::SHA384 => SHA384::digest(data),
+            RSAHashFunction::SHA512 => SHA512::digest(data)
+        }
+    }
+
+    fn hash_length(&self) -> usize {
+        match self.hash_function {
+            RSAHashFunction::SHA1 => SHA1::OUTPUT_SIZE,
+            RSAHashFunction::SHA256 => SHA256::OUTPUT_SIZE,
+            RSAHashFunction::SHA384 => SHA384::OUTPUT_SIZE,
+            RSAHashFunction::SHA512 => SHA512::OUTPUT_SIZE
+        }
+    }
+
+    // MGF1 (RFC 8017, appendix B.2.1)
+    fn mgf1(&self, seed: &[u8], mask_length: usize) -> Vec<u8> {
+        let mut mask: Vec<u8> = Vec::new();
+        let mut counter: u32 = 0;
+        while mask.len() < mask_length {
+            let mut data = seed.to_vec();
+            data.extend_from_slice(&counter.to_be_bytes());
+            mask.extend(self.hash(&data));
+            counter += 1;
+        }
+        mask.truncate(mask_length);
+        mask
+    }
+
+    // EME-OAEP encoding (RFC 8017, section 7.1.1)
+    fn eme_oaep_encode(&self, message: &[u8], seed: &[u8]) -> Result<Vec<u8>> {
+        let (k, h_length) = (self.modulus_length(), self.hash_length());
+        if message.len() + 2 * h_length + 2 > k {
+            return Err(Error::InvalidInput("Message too long".to_string()));
+        }
+        // DB = lHash || PS || 0x01 || M
+        let mut data_block = self.hash(&self.label);
+        data_block.resize(k - message.len() - h_length - 2, 0);
+        data_block.push(0x01);
+        data_block.extend_from_slice(message);
+
+        let masked_data_block: Vec<u8> = data_block.iter().zip(self.mgf1(&seed, k - h_length - 1)).map(|(a, b)| a ^ b).collect();
+        let masked_seed: Vec<u8> = seed.iter().zip(self.mgf1(&masked_data_block, h_length)).map(|(a, b)| a ^ b).collect();
+
+        let mut encoded_message = vec![0x00u8];
+        encoded_message.extend(masked_seed);
+        encoded_message.extend(masked_data_block);
+        Ok(encoded_message)
+    }
+
+    /*
+    EME-OAEP decoding, every failure is reported in the same way. The whole data block is scanned
+    with masks and the checks are folded into one flag: the time does not depend on the padding
+     */
+    fn eme_oaep_decode(&self, encoded_message: &[u8]) -> Option<Vec<u8>> {
+        let (k, h_length) = (self.modulus_length(), self.hash_length());
+        if k < 2 * h_length + 2 {
+            return None;
+        }
+        let (masked_seed, masked_data_block) = encoded_message[1..].split_at(h_length);
+        let seed: Vec<u8> = masked_seed.iter().zip(self.mgf1(masked_data_block, h_length)).map(|(a, b)| a ^ b).collect();
+        let data_block: Vec<u8> = masked_data_block.iter().zip(self.mgf1(&seed, k - h_length - 1)).map(|(a, b)| a ^ b).collect();
+
+        let label_hash = self.hash(&self.label);
+        let mut invalid: u8 = encoded_message[0];
+        for (byte, expected) in data_block[..h_length].iter().zip(label_hash.iter()) {
+            invalid |= byte ^ expected;
+        }
+        // PS || 0x01 || M: the first non-zero byte must be 0x01, the message follows it
+        let mut found: u8 = 0;
+        let mut message_start: usize = 0;
+        for (i, byte) in data_block.iter().enumerate().skip(h_length) {
+            let first = !found & !zero_mask(*byte);
+            invalid |= first & (byte ^ 0x01);
+            message_start |= usize::from(first & 1).wrapping_neg() & (i + 1);
+            found |= first;
+        }
+        invalid |= !found;
+
+        if invalid == 0 {
+            Some(data_block[message_start..].to_vec())
+        } else {
+            None
+        }
+    }
+
+    // EMSA-PSS encoding (RFC 8017, section 9.1.1), with a salt as long as the hash
+    fn emsa_pss_encode(&self, message: &[u8], em_bits: u64, salt: &[u8]) -> Result<Vec<u8>> {
+        let h_length = self.hash_length();
+        let salt_length = h_length;
+        let em_length = em_bits.div_ceil(8) as usize;
+        if em_length < h_length + salt_length + 2 {
+            return Err(Error::InvalidKey("Encoding error".to_string()));
+        }
+        let h = self.pss_hash(message, salt);
+
+        // DB = PS || 0x01 || salt
+        let mut data_block = vec![0u8; em_length - salt_length - h_length - 2];
+        data_block.push(0x01);
+        data_block.extend_from_slice(salt);
+        let mut masked_data_block: Vec<u8> = data_block.iter().zip(self.mgf1(&h, em_length - h_length - 1)).map(|(a, b)| a ^ b).collect();
+        masked_data_block[0] &= 0xff >> (8 * em_length as u64 - em_bits);
+
+        let mut encoded_message = masked_data_block;
+        encoded_message.extend(h);
+        encoded_message.push(0xbc);
+        Ok(encoded_message)
+    }
+
+    // EMSA-PSS verification (RFC 8017, section 9.1.2)
+    fn emsa_pss_verify(&self, message: &[u8], encoded_message: &[u8], em_bits: u64) -> bool {
+        let h_length = self.hash_length();
+        let salt_length = h_length;
+        let em_length = encoded_message.len();
+        if em_length < h_length + salt_length + 2 || encoded_message[em_length - 1] != 0xbc {
+            return false;
+        }
+        let (masked_data_block, h) = encoded_message[..em_length - 1].split_at(em_length - h_length - 1);
+        let unused_bits_mask: u8 = !(0xffu8 >> (8 * em_length as u64 - em_bits));
+        if masked_data_block[0] & unused_bits_mask != 0 {
+            return false;
+        }
+        let mut data_block: Vec<u8> = masked_data_block.iter().zip(self.mgf1(h, em_length - h_length - 1)).map(|(a, b)| a ^ b).collect();
+        data_block[0] &= !unused_bits_mask;
+
+        let padding_length = em_length - h_length - salt_length - 2;
+        if data_block[..padding_length].iter().any(|byte| *byte != 0) || data_block[padding_length] != 0x01 {
+            return false;
+        }
+        let salt = &data_block[data_block.len() - salt_length..];
+        self.pss_hash(message, salt) == h
+    }
+
+    // H = Hash(0x00 * 8 || mHash || salt)
+    fn pss_hash(&self, message: &[u8], salt: &[u8]) -> Vec<u8> {
+        let mut m_prime = vec![0u8; 8];
+        m_prime.extend(self.hash(message));
+        m_prime.extend_from_slice(salt);
+        self.hash(&m_prime)
+    }
+
+    // EMSA-PKCS1-v1_5 encoding (RFC 8017, section 9.2): 0x00 || 0x01 || PS || 0x00 || DigestInfo
+    fn emsa_pkcs1_v15_encode(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let k = self.modulus_length();
+        let digest_info_prefix: &[u8] = match self.hash_function {
+            RSAHashFunction::SHA1 => &[0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14],
+            RSAHashFunction::SHA256 => &[0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20],
+            RSAHashFunction::SHA384 => &[0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02, 0x05, 0x00, 0x04, 0x30],
+            RSAHashFunction::SHA512 => &[0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03, 0x05, 0x00, 0x04, 0x40]
+        };
+        let mut digest_info = digest_info_prefix.to_vec();
+        digest_info.extend(self.hash(message));
+        if k < digest_info.len() + 11 {
+            return Err(Error::InvalidKey("Intended encoded message length too short".to_string()));
+        }
+
+        let mut encoded_message = vec![0x00u8, 0x01u8];
+        encoded_message.resize(k - digest_info.len() - 1, 0xff);
+        encoded_message.push(0x00);
+        encoded_message.extend(digest_info);
+        Ok(encoded_message)
+    }
+}
+
+// 0xff when the byte is zero, 0x00 otherwise, without branches
+fn zero_mask(byte: u8) -> u8 {
+    ((byte as u16).wrapping_sub(1) >> 8) as u8
+}
@@ -0,0 +1,207 @@
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use crate::arith::random::random_byte_array;
+use crate::cryptography::asymmetric::interfaces::interfaces::KeyAgreementInterface;
+use crate::error::{unwrap_or_panic, Error, Result};
+
+/*
+    Montgomery curves of RFC 7748
+    Curve25519: v^2 = u^3 + 486662 u^2 + u over GF(2^255 - 19)
+    Curve448: v^2 = u^3 + 156326 u^2 + u over GF(2^448 - 2^224 - 1)
+*/
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MontgomeryCurves {
+    X25519,
+    X448
+}
+
+struct MontgomeryParameters {
+    p: BigInt,
+    a24: BigInt,
+    bits: u64,
+    key_size: usize,
+    base_u: BigInt,
+    cofactor: BigInt
+}
+
+fn montgomery_parameters(curve: MontgomeryCurves) -> MontgomeryParameters {
+    match curve {
+        MontgomeryCurves::X25519 => MontgomeryParameters {
+            p: (BigInt::one() << 255) - 19,
+            a24: BigInt::from(121665),
+            bits: 255,
+            key_size: 32,
+            base_u: BigInt::from(9),
+            cofactor: BigInt::from(8)
+        },
+        MontgomeryCurves::X448 => MontgomeryParameters {
+            p: (BigInt::one() << 448) - (BigInt::one() << 224) - 1,
+            a24: BigInt::from(39081),
+            bits: 448,
+            key_size: 56,
+            base_u: BigInt::from(5),
+            cofactor: BigInt::from(4)
+        }
+    }
+}
+
+/*
+    X25519 and X448 key agreement (RFC 7748). Keys are the little endian byte strings of the RFC.
+*/
+pub struct XDH {
+    pub curve: MontgomeryCurves,
+    pub public_keys: Vec<Vec<u8>>,
+    pub private_keys: Vec<Vec<u8>>,
+    pub primary_key: usize
+}
+
+impl KeyAgreementInterface for XDH {
+    fn get_public_key(&self) -> Vec<u8> {
+        self.public_keys[self.primary_key].clone()
+    }
+
+    /*
+    Rejects keys of the wrong length and u-coordinates of small order points,
+    which would force an all-zero shared secret (RFC 7748, section 6)
+     */
+    fn validate_public_key(&self, public_key: Vec<u8>) -> bool {
+        let parameters = montgomery_parameters(self.curve);
+        if public_key.len() != parameters.key_size {
+            return false;
+        }
+        let u = decode_u_coordinate(&parameters, &public_key);
+        !montgomery_ladder(&parameters, &parameters.cofactor, &u).is_zero()
+    }
+
+    fn try_compute_shared_secret(&self, public_key: Vec<u8>) -> Result<Vec<u8>> {
+        let parameters = montgomery_parameters(self.curve);
+        if public_key.len() != parameters.key_size {
+            return Err(Error::InvalidKey("Invalid public key".to_string()));
+        }
+        let shared_secret = x_function(&parameters, &self.private_keys[self.primary_key], &public_key);
+        if shared_secret.iter().all(|byte| *byte == 0) {
+            return Err(Error::InvalidKey("The shared secret is zero".to_string()));
+        }
+        Ok(shared_secret)
+    }
+}
+
+impl XDH {
+    pub fn init(curve: MontgomeryCurves) -> Self {
+        let key_size = montgomery_parameters(curve).key_size;
+        XDH::from_private_key(curve, random_byte_array(key_size))
+    }
+
+    pub fn from_private_key(curve: MontgomeryCurves, private_key: Vec<u8>) -> Self {
+        unwrap_or_panic(XDH::try_from_private_key(curve, private_key))
+    }
+
+    pub fn try_from_private_key(curve: MontgomeryCurves, private_key: Vec<u8>) -> Result<Self> {
+        let mut xdh = XDH { curve, public_keys: Vec::new(), private_keys: Vec::new(), primary_key: 0 };
+        xdh.add_private_key(private_key)?;
+        Ok(xdh)
+    }
+
+    pub fn refresh_keys(&mut self) {
+        let key_size = montgomery_parameters(self.curve).key_size;
+        unwrap_or_panic(self.add_private_key(random_byte_array(key_size)));
+        self.primary_key = self.private_keys.len() - 1;
+    }
+
+    pub fn get_current_private_key(&self) -> Vec<u8> {
+        self.private_keys[self.primary_key].clone()
+    }
+
+    // TRUE: set new primary key
+    // FALSE: operation not permitted
+    pub fn set_primary_key(&mut self, new_primary_key: usize) -> bool {
+        if new_primary_key >= self.public_keys.len() {
+            return false;
+        }
+        self.primary_key = new_primary_key;
+        true
+    }
+
+    fn add_private_key(&mut self, private_key: Vec<u8>) -> Result<()> {
+        let parameters = montgomery_parameters(self.curve);
+        if private_key.len() != parameters.key_size {
+            return Err(Error::InvalidKey("Wrong private key length".to_string()));
+        }
+        let base = encode_u_coordinate(&parameters, &parameters.base_u);
+        self.public_keys.push(x_function(&parameters, &private_key, &base));
+        self.private_keys.push(private_key);
+        Ok(())
+    }
+}
+
+
+// RFC 7748, section 5: clamp the scalar, decode u and run the ladder
+fn x_function(parameters: &MontgomeryParameters, scalar: &[u8], u: &[u8]) -> Vec<u8> {
+    let mut k = scalar.to_vec();
+    if parameters.key_size == 32 {
+        k[0] &= 248;
+        k[31] &= 127;
+        k[31] |= 64;
+    } else {
+        k[0] &= 252;
+        k[55] |= 128;
+    }
+    let k = BigInt::from_bytes_le(Sign::Plus, &k);
+    let u = decode_u_coordinate(parameters, u);
+    encode_u_coordinate(parameters, &montgomery_ladder(parameters, &k, &u))
+}
+
+fn decode_u_coordinate(parameters: &MontgomeryParameters, bytes: &[u8]) -> BigInt {
+    let mut u = bytes.to_vec();
+    // the most significant bit is ignored for X25519
+    if !parameters.bits.is_multiple_of(8) {
+        let last = u.len() - 1;
+        u[last] &= (1 << (parameters.bits % 8)) - 1;
+    }
+    BigInt::from_bytes_le(Sign::Plus, &u).mod_floor(&parameters.p)
+}
+
+fn encode_u_coordinate(parameters: &MontgomeryParameters, u: &BigInt) -> Vec<u8> {
+    let (_, mut bytes) = u.to_bytes_le();
+    bytes.resize(parameters.key_size, 0);
+    bytes
+}
+
+// u-coordinate of [k] P where u is the u-coordinate of P, 0 for the point at infinity
+fn montgomery_ladder(parameters: &MontgomeryParameters, k: &BigInt, u: &BigInt) -> BigInt {
+    let p = &parameters.p;
+    let x1 = u.clone();
+    let (mut x2, mut z2) = (BigInt::one(), BigInt::zero());
+    let (mut x3, mut z3) = (u.clone(), BigInt::one());
+    let mut swap = false;
+
+    for t in (0..parameters.bits).rev() {
+        let bit = k.bit(t);
+        if swap != bit {
+            std::mem::swap(&mut x2, &mut x3);
+            std::mem::swap(&mut z2, &mut z3);
+        }
+        swap = bit;
+
+        let a = (x2.clone() + z2.clone()).mod_floor(p);
+        let aa = (a.clone() * a.clone()).mod_floor(p);
+        let b = (x2.clone() - z2.clone()).mod_floor(p);
+        let bb = (b.clone() * b.clone()).mod_floor(p);
+        let e = (aa.clone() - bb.clone()).mod_floor(p);
+        let c = (x3.clone() + z3.clone()).mod_floor(p);
+        let d = (x3.clone() - z3.clone()).mod_floor(p);
+        let da = (d * a).mod_floor(p);
+        let cb = (c * b).mod_floor(p);
+        x3 = (da.clone() + cb.clone()).pow(2).mod_floor(p);
+        z3 = (x1.clone() * (da - cb).pow(2)).mod_floor(p);
+        x2 = (aa.clone() * bb).mod_floor(p);
+        z2 = (e.clone() * (aa + parameters.a24.clone() * e)).mod_floor(p);
+    }
+    if swap {
+        std::mem::swap(&mut x2, &mut x3);
+        std::mem::swap(&mut z2, &mut z3);
+    }
+    (x2 * z2.modpow(&(p.clone() - 2), p)).mod_floor(p)
+}
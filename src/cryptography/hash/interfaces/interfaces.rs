@@ -0,0 +1,38 @@
+// hash functions interfaces
+
+// fixed length hash functions with an incremental API
+pub trait HashInterface: Clone {
+    const BLOCK_SIZE: usize;
+    const OUTPUT_SIZE: usize;
+
+    fn new() -> Self;
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self) -> Vec<u8>;
+
+    fn digest(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+// output stream of an extendable-output function, read in chunks of any length
+pub trait XOFReaderInterface {
+    fn read(&mut self, length: usize) -> Vec<u8>;
+}
+
+// extendable-output functions: finalize returns a stream of any length
+pub trait XOFinterface: Clone {
+    const BLOCK_SIZE: usize;
+    type Reader: XOFReaderInterface;
+
+    fn new() -> Self;
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self) -> Self::Reader;
+
+    fn digest(data: &[u8], output_size: usize) -> Vec<u8> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize().read(output_size)
+    }
+}
@@ -0,0 +1,88 @@
+// SHA-1 (FIPS 180-4). It is broken for collision resistance and kept only for
+// interoperability with legacy encodings, such as the PKCS #1 test vectors.
+
+use crate::cryptography::hash::interfaces::interfaces::HashInterface;
+
+const SHA1_INITIAL_STATE: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+
+/*
+    SHA-1
+*/
+#[derive(Clone)]
+pub struct SHA1 {
+    state: [u32; 5],
+    buffer: Vec<u8>,
+    length: u64
+}
+
+impl HashInterface for SHA1 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 20;
+
+    fn new() -> SHA1 {
+        SHA1 { state: SHA1_INITIAL_STATE, buffer: Vec::new(), length: 0 }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.buffer.extend_from_slice(data);
+        let full_blocks = self.buffer.len() / SHA1::BLOCK_SIZE;
+        for i in 0..full_blocks {
+            let block: [u8; 64] = self.buffer[i * 64..(i + 1) * 64].try_into().unwrap();
+            sha1_compress(&mut self.state, &block);
+        }
+        self.buffer.drain(..full_blocks * SHA1::BLOCK_SIZE);
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        let bit_length = self.length.wrapping_mul(8);
+        let mut padding = vec![0x80u8];
+        while (self.buffer.len() + padding.len()) % SHA1::BLOCK_SIZE != 56 {
+            padding.push(0);
+        }
+        padding.extend_from_slice(&bit_length.to_be_bytes());
+        // the length must not be counted twice
+        let length = self.length;
+        self.update(&padding);
+        self.length = length;
+
+        self.state.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+}
+
+impl Default for SHA1 {
+    fn default() -> Self {
+        SHA1::new()
+    }
+}
+
+fn sha1_compress(state: &mut [u32; 5], block: &[u8; 64]) {
+    let mut w = [0u32; 80];
+    for i in 0..16 {
+        w[i] = u32::from_be_bytes(block[i * 4..(i + 1) * 4].try_into().unwrap());
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    for (i, word) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5a827999),
+            20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6)
+        };
+        let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(*word);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e]) {
+        *word = word.wrapping_add(value);
+    }
+}
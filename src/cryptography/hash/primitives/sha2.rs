@@ -0,0 +1,251 @@
+// SHA-2 family (FIPS 180-4)
+
+use crate::cryptography::hash::interfaces::interfaces::HashInterface;
+
+const SHA256_ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2
+];
+
+const SHA256_INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19
+];
+
+const SHA512_ROUND_CONSTANTS: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc, 0x3956c25bf348b538,
+    0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118, 0xd807aa98a3030242, 0x12835b0145706fbe,
+    0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2, 0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235,
+    0xc19bf174cf692694, 0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5, 0x983e5152ee66dfab,
+    0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4, 0xc6e00bf33da88fc2, 0xd5a79147930aa725,
+    0x06ca6351e003826f, 0x142929670a0e6e70, 0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df, 0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30, 0xd192e819d6ef5218,
+    0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8, 0x19a4c116b8d2d0c8, 0x1e376c085141ab53,
+    0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8, 0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3, 0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b, 0xca273eceea26619c,
+    0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178, 0x06f067aa72176fba, 0x0a637dc5a2c898a6,
+    0x113f9804bef90dae, 0x1b710b35131c471b, 0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c, 0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817
+];
+
+const SHA512_INITIAL_STATE: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179
+];
+
+const SHA384_INITIAL_STATE: [u64; 8] = [
+    0xcbbb9d5dc1059ed8, 0x629a292a367cd507, 0x9159015a3070dd17, 0x152fecd8f70e5939,
+    0x67332667ffc00b31, 0x8eb44a8768581511, 0xdb0c2e0d64f98fa7, 0x47b5481dbefa4fa4
+];
+
+
+/*
+    SHA-256
+*/
+#[derive(Clone)]
+pub struct SHA256 {
+    state: [u32; 8],
+    buffer: Vec<u8>,
+    length: u64
+}
+
+impl HashInterface for SHA256 {
+    const BLOCK_SIZE: usize = 64;
+    const OUTPUT_SIZE: usize = 32;
+
+    fn new() -> SHA256 {
+        SHA256 { state: SHA256_INITIAL_STATE, buffer: Vec::new(), length: 0 }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.buffer.extend_from_slice(data);
+        let full_blocks = self.buffer.len() / SHA256::BLOCK_SIZE;
+        for i in 0..full_blocks {
+            let block: [u8; 64] = self.buffer[i * 64..(i + 1) * 64].try_into().unwrap();
+            sha256_compress(&mut self.state, &block);
+        }
+        self.buffer.drain(..full_blocks * SHA256::BLOCK_SIZE);
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        let bit_length = self.length.wrapping_mul(8);
+        let mut padding = vec![0x80u8];
+        while (self.buffer.len() + padding.len()) % SHA256::BLOCK_SIZE != 56 {
+            padding.push(0);
+        }
+        padding.extend_from_slice(&bit_length.to_be_bytes());
+        // the length must not be counted twice
+        let length = self.length;
+        self.update(&padding);
+        self.length = length;
+
+        self.state.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+}
+
+impl Default for SHA256 {
+    fn default() -> Self {
+        SHA256::new()
+    }
+}
+
+fn sha256_compress(state: &mut [u32; 8], block: &[u8; 64]) {
+    let mut w = [0u32; 64];
+    for i in 0..16 {
+        w[i] = u32::from_be_bytes(block[i * 4..(i + 1) * 4].try_into().unwrap());
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+        let temp1 = h.wrapping_add(s1).wrapping_add(choice).wrapping_add(SHA256_ROUND_CONSTANTS[i]).wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(majority);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+
+/*
+    SHA-512 (SHA-384 shares the compression function with a different initial state and a truncated output)
+*/
+#[derive(Clone)]
+pub struct SHA512 {
+    state: [u64; 8],
+    buffer: Vec<u8>,
+    length: u128,
+    output_size: usize
+}
+
+impl HashInterface for SHA512 {
+    const BLOCK_SIZE: usize = 128;
+    const OUTPUT_SIZE: usize = 64;
+
+    fn new() -> SHA512 {
+        SHA512 { state: SHA512_INITIAL_STATE, buffer: Vec::new(), length: 0, output_size: SHA512::OUTPUT_SIZE }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u128;
+        self.buffer.extend_from_slice(data);
+        let full_blocks = self.buffer.len() / SHA512::BLOCK_SIZE;
+        for i in 0..full_blocks {
+            let block: [u8; 128] = self.buffer[i * 128..(i + 1) * 128].try_into().unwrap();
+            sha512_compress(&mut self.state, &block);
+        }
+        self.buffer.drain(..full_blocks * SHA512::BLOCK_SIZE);
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        let bit_length = self.length.wrapping_mul(8);
+        let mut padding = vec![0x80u8];
+        while (self.buffer.len() + padding.len()) % SHA512::BLOCK_SIZE != 112 {
+            padding.push(0);
+        }
+        padding.extend_from_slice(&bit_length.to_be_bytes());
+        let length = self.length;
+        self.update(&padding);
+        self.length = length;
+
+        let mut output: Vec<u8> = self.state.iter().flat_map(|word| word.to_be_bytes()).collect();
+        output.truncate(self.output_size);
+        output
+    }
+}
+
+impl Default for SHA512 {
+    fn default() -> Self {
+        SHA512::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct SHA384 {
+    inner: SHA512
+}
+
+impl HashInterface for SHA384 {
+    const BLOCK_SIZE: usize = 128;
+    const OUTPUT_SIZE: usize = 48;
+
+    fn new() -> SHA384 {
+        SHA384 { inner: SHA512 { state: SHA384_INITIAL_STATE, buffer: Vec::new(), length: 0, output_size: SHA384::OUTPUT_SIZE } }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.inner.finalize()
+    }
+}
+
+impl Default for SHA384 {
+    fn default() -> Self {
+        SHA384::new()
+    }
+}
+
+fn sha512_compress(state: &mut [u64; 8], block: &[u8; 128]) {
+    let mut w = [0u64; 80];
+    for i in 0..16 {
+        w[i] = u64::from_be_bytes(block[i * 8..(i + 1) * 8].try_into().unwrap());
+    }
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..80 {
+        let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+        let choice = (e & f) ^ (!e & g);
+        let temp1 = h.wrapping_add(s1).wrapping_add(choice).wrapping_add(SHA512_ROUND_CONSTANTS[i]).wrapping_add(w[i]);
+        let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(majority);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
@@ -0,0 +1,216 @@
+// SHA-3 family and SHAKE extendable-output functions (FIPS 202)
+
+use crate::cryptography::hash::interfaces::interfaces::{HashInterface, XOFReaderInterface, XOFinterface};
+
+const KECCAK_ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008
+];
+
+// rotation offsets of the rho step, indexed by x + 5 y
+const KECCAK_ROTATION_OFFSETS: [u32; 25] = [
+    0, 1, 62, 28, 27,
+    36, 44, 6, 55, 20,
+    3, 10, 43, 25, 39,
+    41, 45, 15, 21, 8,
+    18, 2, 61, 56, 14
+];
+
+// domain separation bits, already followed by the first bit of the pad10*1 padding
+const SHA3_SUFFIX: u8 = 0x06;
+const SHAKE_SUFFIX: u8 = 0x1f;
+
+
+fn keccak_f1600(state: &mut [u64; 25]) {
+    for round_constant in KECCAK_ROUND_CONSTANTS {
+        // theta
+        let mut c = [0u64; 5];
+        for x in 0..5 {
+            c[x] = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+
+        // rho and pi
+        let mut b = [0u64; 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                b[y + 5 * ((2 * x + 3 * y) % 5)] = state[x + 5 * y].rotate_left(KECCAK_ROTATION_OFFSETS[x + 5 * y]);
+            }
+        }
+
+        // chi
+        for x in 0..5 {
+            for y in 0..5 {
+                state[x + 5 * y] = b[x + 5 * y] ^ (!b[(x + 1) % 5 + 5 * y] & b[(x + 2) % 5 + 5 * y]);
+            }
+        }
+
+        // iota
+        state[0] ^= round_constant;
+    }
+}
+
+/*
+    Keccak sponge over Keccak-f[1600]. The rate is given in bytes.
+*/
+#[derive(Clone)]
+struct KeccakSponge {
+    state: [u64; 25],
+    rate: usize,
+    buffer: Vec<u8>,
+    suffix: u8
+}
+
+impl KeccakSponge {
+    fn new(rate: usize, suffix: u8) -> KeccakSponge {
+        KeccakSponge { state: [0u64; 25], rate, buffer: Vec::new(), suffix }
+    }
+
+    fn absorb_block(&mut self, block: &[u8]) {
+        for (i, lane) in block.chunks(8).enumerate() {
+            self.state[i] ^= u64::from_le_bytes(lane.try_into().unwrap());
+        }
+        keccak_f1600(&mut self.state);
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+        let full_blocks = self.buffer.len() / self.rate;
+        for i in 0..full_blocks {
+            let block = self.buffer[i * self.rate..(i + 1) * self.rate].to_vec();
+            self.absorb_block(&block);
+        }
+        self.buffer.drain(..full_blocks * self.rate);
+    }
+
+    fn rate_bytes(&self) -> Vec<u8> {
+        self.state.iter().flat_map(|lane| lane.to_le_bytes()).take(self.rate).collect()
+    }
+}
+
+/*
+    Output stream of an extendable-output function, obtained from SHAKE128::finalize or SHAKE256::finalize
+*/
+#[derive(Clone)]
+pub struct XOFReader {
+    sponge: KeccakSponge,
+    output: Vec<u8>,
+    position: usize
+}
+
+impl XOFReader {
+    // pads the last block and switches the sponge to the squeezing phase
+    fn new(mut sponge: KeccakSponge) -> XOFReader {
+        let suffix_position = sponge.buffer.len();
+        let mut block = std::mem::take(&mut sponge.buffer);
+        block.resize(sponge.rate, 0);
+        block[suffix_position] ^= sponge.suffix;
+        block[sponge.rate - 1] ^= 0x80;
+        sponge.absorb_block(&block);
+        let output = sponge.rate_bytes();
+        XOFReader { sponge, output, position: 0 }
+    }
+}
+
+impl XOFReaderInterface for XOFReader {
+    fn read(&mut self, length: usize) -> Vec<u8> {
+        let mut result: Vec<u8> = Vec::with_capacity(length);
+        while result.len() < length {
+            if self.position == self.output.len() {
+                keccak_f1600(&mut self.sponge.state);
+                self.output = self.sponge.rate_bytes();
+                self.position = 0;
+            }
+            let available = (self.output.len() - self.position).min(length - result.len());
+            result.extend_from_slice(&self.output[self.position..self.position + available]);
+            self.position += available;
+        }
+        result
+    }
+}
+
+/*
+    SHA3-224, SHA3-256, SHA3-384 and SHA3-512: the capacity is twice the output size
+*/
+macro_rules! sha3 {
+    ($name: ident, $rate: expr, $output_size: expr) => {
+        #[derive(Clone)]
+        pub struct $name {
+            sponge: KeccakSponge
+        }
+
+        impl HashInterface for $name {
+            const BLOCK_SIZE: usize = $rate;
+            const OUTPUT_SIZE: usize = $output_size;
+
+            fn new() -> $name {
+                $name { sponge: KeccakSponge::new($rate, SHA3_SUFFIX) }
+            }
+
+            fn update(&mut self, data: &[u8]) {
+                self.sponge.absorb(data);
+            }
+
+            fn finalize(self) -> Vec<u8> {
+                XOFReader::new(self.sponge).read($output_size)
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name::new()
+            }
+        }
+    };
+}
+
+/*
+    SHAKE128 and SHAKE256
+*/
+macro_rules! shake {
+    ($name: ident, $rate: expr) => {
+        #[derive(Clone)]
+        pub struct $name {
+            sponge: KeccakSponge
+        }
+
+        impl XOFinterface for $name {
+            const BLOCK_SIZE: usize = $rate;
+            type Reader = XOFReader;
+
+            fn new() -> $name {
+                $name { sponge: KeccakSponge::new($rate, SHAKE_SUFFIX) }
+            }
+
+            fn update(&mut self, data: &[u8]) {
+                self.sponge.absorb(data);
+            }
+
+            fn finalize(self) -> XOFReader {
+                XOFReader::new(self.sponge)
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name::new()
+            }
+        }
+    };
+}
+
+sha3!(SHA3_224, 144, 28);
+sha3!(SHA3_256, 136, 32);
+sha3!(SHA3_384, 104, 48);
+sha3!(SHA3_512, 72, 64);
+shake!(SHAKE128, 168);
+shake!(SHAKE256, 136);
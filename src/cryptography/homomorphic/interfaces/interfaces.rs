@@ -1,6 +1,7 @@
 use num_bigint::BigInt;
 
 use crate::{algebras::{FiniteField::instances::Zmod_instance::ZmodInstance, Rings::instances::PolynomialRing_instance::PolynomialRingInstance}, matrices::vector::Vector, numbers::instances::ZZ_instance::ZZinstance};
+use crate::error::{unwrap_or_panic, Result};
 
 pub trait FHE<T> {
     fn homomorphic_addition(&self, C1: T, C2: T) -> T;
@@ -9,6 +10,14 @@ pub trait FHE<T> {
 
 pub trait BFV_PKI {
     fn key_gen(&mut self);
-    fn encrypt(&self, plaintext: BigInt) -> (PolynomialRingInstance<ZmodInstance>, PolynomialRingInstance<ZmodInstance>);
-    fn decrypt(&self, ciphertext: Vec<PolynomialRingInstance<ZmodInstance>>) -> BigInt;
+    fn try_encrypt(&self, plaintext: BigInt) -> Result<(PolynomialRingInstance<ZmodInstance>, PolynomialRingInstance<ZmodInstance>)>;
+    fn try_decrypt(&self, ciphertext: Vec<PolynomialRingInstance<ZmodInstance>>) -> Result<BigInt>;
+
+    fn encrypt(&self, plaintext: BigInt) -> (PolynomialRingInstance<ZmodInstance>, PolynomialRingInstance<ZmodInstance>) {
+        unwrap_or_panic(self.try_encrypt(plaintext))
+    }
+
+    fn decrypt(&self, ciphertext: Vec<PolynomialRingInstance<ZmodInstance>>) -> BigInt {
+        unwrap_or_panic(self.try_decrypt(ciphertext))
+    }
 }
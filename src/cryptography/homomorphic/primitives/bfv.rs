@@ -3,7 +3,8 @@ use std::{cell::RefCell, fmt::Display, rc};
 use bigdecimal::BigDecimal;
 use num_bigint::{BigInt, BigUint, ToBigUint};
 use num_traits::{Pow, Signed, ToPrimitive};
-use crate::{algebras::{Rings::{instances::PolynomialRing_instance::{self, PolynomialRingInstance}, classes::PolynomialRing::PolynomialRing}, FiniteField::{instances::Zmod_instance::ZmodInstance, classes::Zmod::Zmod}}, arith::random::{gen_from_gaussian_distribution_with_modulo, gen_from_range_with_modulo, gen_from_uniform_distribution_with_modulo, random_byte_array}, cryptography::{asymmetric::interfaces::interfaces::{PKIinterface, KEMinterface, LatticeBased_PKIinterface}, homomorphic::interfaces::interfaces::{ BFV_PKI, FHE}}, matrices::{matrix::Matrix, vector::Vector}, numbers::{numbers::{Class, ClassInstance, Instance, Number, Operand, PrimitiveNumber, StatefulClass}, instances::{ZZ_instance::{ZZinstance, self}, RR_instance::RRinstance}, classes::RR::RR}, poly::{instances::univariate_polynomial_instance::UnivariatePolynomialInstance, classes::univariate_polynomial::UnivariatePolynomial}, transform::ntt::{NTTFactory, NTT_Algorithm, NTT}, variables::vars::Var};
+use crate::{algebras::{Rings::{instances::PolynomialRing_instance::{self, PolynomialRingInstance}, classes::PolynomialRing::PolynomialRing}, FiniteField::{instances::Zmod_instance::ZmodInstance, classes::Zmod::Zmod}}, arith::random::{gen_from_gaussian_distribution_with_modulo, gen_from_range_with_modulo, gen_from_uniform_distribution_with_modulo, random_byte_array}, cryptography::{asymmetric::interfaces::interfaces::PKIinterface, homomorphic::interfaces::interfaces::{ BFV_PKI, FHE}}, matrices::{matrix::Matrix, vector::Vector}, numbers::{numbers::{Class, ClassInstance, Instance, Number, Operand, PrimitiveNumber, StatefulClass}, instances::{ZZ_instance::{ZZinstance, self}, RR_instance::RRinstance}, classes::RR::RR}, poly::{instances::univariate_polynomial_instance::UnivariatePolynomialInstance, classes::univariate_polynomial::UnivariatePolynomial}, transform::ntt::{NTTFactory, NTT_Algorithm, NTT}, variables::vars::Var};
+use crate::error::{Error, Result};
 use crate::arith::random::gen_from_centered_binomial_distribution;
 use crate::numbers::classes::ZZ::ZZ;
 
@@ -319,7 +320,7 @@ impl BFV_PKI for BFV {
     
     }
 
-    fn decrypt(&self, ciphertext: Vec<PolynomialRingInstance<ZmodInstance>>) -> BigInt {
+    fn try_decrypt(&self, ciphertext: Vec<PolynomialRingInstance<ZmodInstance>>) -> Result<BigInt> {
         // receives values not in ntt context
         let q: BigInt = self.q.clone().module.unwrap().value.clone();
         let p: BigInt = self.p.clone().module.unwrap().value.clone();
@@ -336,7 +337,7 @@ impl BFV_PKI for BFV {
             let mut new_poly = (r_class.apply_to_poly_ring(tmp2)* delta).round(); //% q1; // values are yet modulo q
             let tmp_ciphertext = (new_poly % ZZ::new().new_instance(p.clone()));
             // return poly_to_plaintext(tmp_ciphertext, p.clone());
-            return poly_to_bigint(tmp_ciphertext, p.clone());
+            return Ok(poly_to_bigint(tmp_ciphertext, p.clone()));
 
 
         } else if ciphertext.len() == 3 { // naive decryption
@@ -355,21 +356,21 @@ impl BFV_PKI for BFV {
             let tmp_ciphertext = (new_poly % ZZ::new().new_instance(p.clone()));
             // return poly_to_plaintext(tmp_ciphertext, p.clone());
             
-            return poly_to_bigint(tmp_ciphertext, p.clone());
+            return Ok(poly_to_bigint(tmp_ciphertext, p.clone()));
 
         } else {
-            panic!("Wrong number of values in the ciphertext");
+            return Err(Error::InvalidInput("Wrong number of values in the ciphertext".to_string()));
         }
     }
 
-    fn encrypt(&self, plaintext: BigInt) -> (PolynomialRingInstance<ZmodInstance>, PolynomialRingInstance<ZmodInstance>) {
+    fn try_encrypt(&self, plaintext: BigInt) -> Result<(PolynomialRingInstance<ZmodInstance>, PolynomialRingInstance<ZmodInstance>)> {
         let q: BigInt = self.q.clone().module.unwrap().value.clone();
         let p: BigInt = self.p.clone().module.unwrap().value.clone();
 
 
 
         if plaintext.bits() > p.clone().to_u64().unwrap() {
-            panic!("Plaintext to big. Only {} bytes can be encrypted", self.n/8);
+            return Err(Error::InvalidInput(format!("Plaintext to big. Only {} bytes can be encrypted", self.n/8)));
         }
 
       
@@ -389,7 +390,7 @@ impl BFV_PKI for BFV {
         let a = PK1*u.clone() + e1 + self.ring.apply_ntt_ctxt(&(M*self.q.apply(delta)));
         let b = PK2*u + e2;
       
-        (self.ring.from_ntt_ctxt(&a, self.ring.fixed_length_coefficients) % ZZ::new().new_instance(q.clone()) , self.ring.from_ntt_ctxt(&b, self.ring.fixed_length_coefficients) % ZZ::new().new_instance(q.clone()))
+        Ok((self.ring.from_ntt_ctxt(&a, self.ring.fixed_length_coefficients) % ZZ::new().new_instance(q.clone()) , self.ring.from_ntt_ctxt(&b, self.ring.fixed_length_coefficients) % ZZ::new().new_instance(q.clone())))
     }
 }
 
@@ -0,0 +1,76 @@
+// HKDF, the HMAC-based extract-and-expand key derivation function (RFC 5869)
+
+use std::marker::PhantomData;
+
+use crate::cryptography::hash::interfaces::interfaces::HashInterface;
+use crate::cryptography::symmetric::primitives::hmac::HMAC;
+use crate::error::{unwrap_or_panic, Error, Result};
+
+
+/*
+    HKDF holding the pseudorandom key of the extract step, from which any number of keys
+    can be expanded with different info strings
+*/
+#[derive(Clone)]
+pub struct HKDF<H: HashInterface> {
+    prk: Vec<u8>,
+    hash_function: PhantomData<H>
+}
+
+impl<H: HashInterface> HKDF<H> {
+    // an empty salt stands for a string of HashLen zeros
+    pub fn extract(salt: &[u8], input_key_material: &[u8]) -> HKDF<H> {
+        let salt = if salt.is_empty() { vec![0u8; H::OUTPUT_SIZE] } else { salt.to_vec() };
+        HKDF { prk: HMAC::<H>::mac(&salt, input_key_material), hash_function: PhantomData }
+    }
+
+    // skips the extract step when the input is already a uniformly random key
+    pub fn from_prk(prk: &[u8]) -> HKDF<H> {
+        unwrap_or_panic(HKDF::<H>::try_from_prk(prk))
+    }
+
+    pub fn try_from_prk(prk: &[u8]) -> Result<HKDF<H>> {
+        if prk.len() < H::OUTPUT_SIZE {
+            return Err(Error::InvalidInput(format!("The pseudorandom key must be at least {} bytes long", H::OUTPUT_SIZE)));
+        }
+        Ok(HKDF { prk: prk.to_vec(), hash_function: PhantomData })
+    }
+
+    pub fn expand(&self, info: &[u8], length: usize) -> Vec<u8> {
+        unwrap_or_panic(self.try_expand(info, length))
+    }
+
+    pub fn try_expand(&self, info: &[u8], length: usize) -> Result<Vec<u8>> {
+        if length > 255 * H::OUTPUT_SIZE {
+            return Err(Error::InvalidInput(format!("HKDF cannot output more than {} bytes", 255 * H::OUTPUT_SIZE)));
+        }
+        let mut output_key_material: Vec<u8> = Vec::with_capacity(length);
+        let mut block: Vec<u8> = Vec::new();
+        let mut counter = 1u8;
+        while output_key_material.len() < length {
+            // T(i) = HMAC(PRK, T(i - 1) || info || i)
+            let mut hmac = HMAC::<H>::new(&self.prk);
+            hmac.update(&block);
+            hmac.update(info);
+            hmac.update(&[counter]);
+            block = hmac.finalize();
+            output_key_material.extend_from_slice(&block);
+            counter = counter.wrapping_add(1);
+        }
+        output_key_material.truncate(length);
+        Ok(output_key_material)
+    }
+
+    // extract followed by a single expand
+    pub fn derive(salt: &[u8], input_key_material: &[u8], info: &[u8], length: usize) -> Vec<u8> {
+        HKDF::<H>::extract(salt, input_key_material).expand(info, length)
+    }
+
+    pub fn try_derive(salt: &[u8], input_key_material: &[u8], info: &[u8], length: usize) -> Result<Vec<u8>> {
+        HKDF::<H>::extract(salt, input_key_material).try_expand(info, length)
+    }
+
+    pub fn get_prk(&self) -> Vec<u8> {
+        self.prk.clone()
+    }
+}
@@ -0,0 +1,44 @@
+// PBKDF2 with HMAC as pseudorandom function (RFC 8018, section 5.2)
+
+use crate::cryptography::hash::interfaces::interfaces::HashInterface;
+use crate::cryptography::symmetric::primitives::hmac::HMAC;
+use crate::error::{unwrap_or_panic, Error, Result};
+
+
+pub fn pbkdf2<H: HashInterface>(password: &[u8], salt: &[u8], iterations: u32, length: usize) -> Vec<u8> {
+    unwrap_or_panic(try_pbkdf2::<H>(password, salt, iterations, length))
+}
+
+pub fn try_pbkdf2<H: HashInterface>(password: &[u8], salt: &[u8], iterations: u32, length: usize) -> Result<Vec<u8>> {
+    if iterations == 0 {
+        return Err(Error::InvalidInput("PBKDF2 needs at least one iteration".to_string()));
+    }
+    if length as u64 > u32::MAX as u64 * H::OUTPUT_SIZE as u64 {
+        return Err(Error::InvalidInput("Derived key too long".to_string()));
+    }
+
+    // the password is the HMAC key of every iteration, its padded state is computed once
+    let prf = HMAC::<H>::new(password);
+    let mut derived_key: Vec<u8> = Vec::with_capacity(length);
+    let mut block_index = 1u32;
+    while derived_key.len() < length {
+        // U_1 = PRF(P, S || INT(i)), U_j = PRF(P, U_{j-1}), T_i = U_1 xor ... xor U_c
+        let mut hmac = prf.clone();
+        hmac.update(salt);
+        hmac.update(&block_index.to_be_bytes());
+        let mut u = hmac.finalize();
+        let mut block = u.clone();
+        for _ in 1..iterations {
+            let mut hmac = prf.clone();
+            hmac.update(&u);
+            u = hmac.finalize();
+            for (t, byte) in block.iter_mut().zip(u.iter()) {
+                *t ^= byte;
+            }
+        }
+        derived_key.extend_from_slice(&block);
+        block_index += 1;
+    }
+    derived_key.truncate(length);
+    Ok(derived_key)
+}
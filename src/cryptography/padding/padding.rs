@@ -1,30 +1,88 @@
 use rand::Rng;
 
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Paddings {
     ANSI_X9_23,
     ISO_10126,
     PKCS7,
-    ISO_IEC_7816_4
+    ISO_IEC_7816_4,
+    // zero padding
+    ISO_IEC_9797_1_M1,
+    // 0x80 then zeros
+    ISO_IEC_9797_1_M2,
+    // block with the bit length of the message, then zero padding
+    ISO_IEC_9797_1_M3,
+    // ciphertext stealing (NIST SP 800-38A addendum), only with CBC: the ciphertext is as long as the plaintext
+    CBC_CS1,
+    CBC_CS2,
+    CBC_CS3
 }
 
 
 
 
 
+// Padding errors do not tell where the padding is malformed: unpad checks every byte of the last block
+// without early exits, so the running time depends only on the length of the input (no padding oracle).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PaddingError {
+    // empty input or not a multiple of the block size
+    InvalidLength,
+    InvalidPadding
+}
+
 pub trait Pad {
     fn pad(message: Vec<u8>, block_size: usize) -> Vec<u8>;
-    fn unpad(padded_message: Vec<u8>, consistency_checking: bool) -> Vec<u8>;
+    fn unpad(padded_message: Vec<u8>, block_size: usize) -> Result<Vec<u8>, PaddingError>;
+}
+
+// constant-time comparisons: 0xff when the condition holds, 0 otherwise
+fn ct_eq(a: u8, b: u8) -> u8 {
+    (((a ^ b) as u16).wrapping_sub(1) >> 8) as u8
+}
+
+fn ct_le(a: u8, b: u8) -> u8 {
+    !(((b as u16).wrapping_sub(a as u16) >> 8) as u8)
+}
+
+fn padding_length(message_length: usize, block_size: usize) -> usize {
+    if block_size == 0 || block_size > 255 {
+        panic!("The block size must be between 1 and 255 bytes");
+    }
+    block_size - message_length % block_size
+}
+
+fn check_length(padded_message: &[u8], block_size: usize) -> Result<(), PaddingError> {
+    if block_size == 0 || block_size > 255 || padded_message.is_empty() || !padded_message.len().is_multiple_of(block_size) {
+        return Err(PaddingError::InvalidLength);
+    }
+    Ok(())
+}
+
+// the last byte gives the padding length, between 1 and the block size; each byte of the last block is
+// checked with byte_is_valid(byte, padding_length, distance from the end) when it belongs to the padding
+fn unpad_with_length_byte(padded_message: Vec<u8>, block_size: usize, byte_is_valid: impl Fn(u8, u8, u8) -> u8) -> Result<Vec<u8>, PaddingError> {
+    check_length(&padded_message, block_size)?;
+    let length = padded_message.len();
+    let padding_length = padded_message[length - 1];
+
+    let mut valid = ct_le(1, padding_length) & ct_le(padding_length, block_size as u8);
+    for distance in 1..=block_size as u8 {
+        let byte = padded_message[length - distance as usize];
+        let in_padding = ct_le(distance, padding_length);
+        valid &= !in_padding | byte_is_valid(byte, padding_length, distance);
+    }
+
+    if valid != 0xff {
+        return Err(PaddingError::InvalidPadding);
+    }
+    Ok(padded_message[0..(length - padding_length as usize)].to_vec())
 }
 
 pub struct ANSI_X9_23 {}
 impl Pad for ANSI_X9_23 {
     fn pad(message: Vec<u8>,block_size: usize) -> Vec<u8> {
-        let length: usize = message.len();
-        let mut padding_length = length % block_size;
-
-        if padding_length == 0 {
-            padding_length = block_size;
-        }
+        let padding_length = padding_length(message.len(), block_size);
 
         let mut padded_message: Vec<u8> = message.clone();
         for _i in 0..padding_length-1 {
@@ -36,34 +94,16 @@ impl Pad for ANSI_X9_23 {
         padded_message
     }
 
-    fn unpad(padded_message: Vec<u8>, consistency_checking: bool) -> Vec<u8> {
-        let length: usize = padded_message.len();
-        let padding_length: u8 = padded_message[length-1];
-
-        if consistency_checking {
-            let message: Vec<u8> = padded_message[0..(length-(padding_length as usize))].to_vec();
-            let padding_is_valid: bool = padded_message[(length-(padding_length as usize))..length].to_vec().iter().all(|&x| x == 0);
-            if padding_is_valid {
-                return message;
-            } else {
-                panic!("The input message is not correctly padded");
-            }
-        } else {
-            let message: Vec<u8> = padded_message[0..(length-(padding_length as usize))].to_vec();
-            return message;
-        }
+    // zeros, then the length
+    fn unpad(padded_message: Vec<u8>, block_size: usize) -> Result<Vec<u8>, PaddingError> {
+        unpad_with_length_byte(padded_message, block_size, |byte, _, distance| ct_eq(distance, 1) | ct_eq(byte, 0))
     }
 }
 
 pub struct ISO_10126 {}
 impl Pad for ISO_10126 {
     fn pad(message: Vec<u8>, block_size: usize) -> Vec<u8> {
-        let length: usize = message.len();
-        let mut padding_length = length % block_size;
-
-        if padding_length == 0 {
-            padding_length = block_size;
-        }
+        let padding_length = padding_length(message.len(), block_size);
 
         let mut padded_message: Vec<u8> = message.clone();
         for _i in 0..padding_length-1 {
@@ -75,27 +115,18 @@ impl Pad for ISO_10126 {
         padded_message
     }
 
-    fn unpad(padded_message: Vec<u8>, _consistency_checking: bool) -> Vec<u8> {
-        let length: usize = padded_message.len();
-        let padding_length: u8 = padded_message[length-1];
-
-        let message: Vec<u8> = padded_message[0..(length-(padding_length as usize))].to_vec();
-        message
-        
+    // random bytes, only the length can be checked
+    fn unpad(padded_message: Vec<u8>, block_size: usize) -> Result<Vec<u8>, PaddingError> {
+        unpad_with_length_byte(padded_message, block_size, |_, _, _| 0xff)
     }
 }
 pub struct ISO_IEC_7816_4 {}
 impl Pad for ISO_IEC_7816_4 {
     fn pad(message: Vec<u8>, block_size: usize) -> Vec<u8> {
-        let length: usize = message.len();
-        let mut padding_length = length % block_size;
-
-        if padding_length == 0 {
-            padding_length = block_size;
-        }
+        let padding_length = padding_length(message.len(), block_size);
 
         let mut padded_message: Vec<u8> = message.clone();
-        padded_message.push(0x80 as u8);
+        padded_message.push(0x80_u8);
 
         for _i in 0..padding_length-1 {
             padded_message.push(0u8);
@@ -104,43 +135,33 @@ impl Pad for ISO_IEC_7816_4 {
         padded_message
     }
 
-    fn unpad(padded_message: Vec<u8>, consistency_checking: bool) -> Vec<u8> {
-        let length: usize = padded_message.len();
-        let mut padding_length: u8 = 0;
+    // 0x80 followed by zeros: the last block is scanned from the end, remembering the first 0x80 found
+    fn unpad(padded_message: Vec<u8>, block_size: usize) -> Result<Vec<u8>, PaddingError> {
+        check_length(&padded_message, block_size)?;
+        let length = padded_message.len();
 
-        // computing padding length
-        for i in (0..length).rev() {
-            if padded_message[i] != 0x80 {
-                padding_length += 1;
-            } else {
-                padding_length += 1;
-                break;
-            }
+        let mut found: u8 = 0;
+        let mut invalid: u8 = 0;
+        let mut padding_length: u8 = 0;
+        for distance in 1..=block_size as u8 {
+            let byte = padded_message[length - distance as usize];
+            let is_marker = ct_eq(byte, 0x80);
+            let not_found = !found;
+            padding_length |= distance & not_found & is_marker;
+            invalid |= not_found & !is_marker & !ct_eq(byte, 0);
+            found |= not_found & is_marker;
         }
-        
-        if consistency_checking {
-            let message: Vec<u8> = padded_message[0..(length-(padding_length as usize))].to_vec();
-            let padding_is_valid: bool = padded_message[(length-(padding_length as usize)+1)..].to_vec().iter().all(|&x| x == 0);
-            if padding_is_valid {
-                return message;
-            } else {
-                panic!("The input message is not correctly padded");
-            }
-        } else {
-            let message: Vec<u8> = padded_message[0..(length-(padding_length as usize))].to_vec();
-            return message;
+
+        if found & !invalid != 0xff {
+            return Err(PaddingError::InvalidPadding);
         }
+        Ok(padded_message[0..(length - padding_length as usize)].to_vec())
     }
 }
 pub struct PKCS7 {}
 impl Pad for PKCS7 {
     fn pad(message: Vec<u8>, block_size: usize) -> Vec<u8> {
-        let length: usize = message.len();
-        let mut padding_length = length % block_size;
-
-        if padding_length == 0 {
-            padding_length = block_size;
-        }
+        let padding_length = padding_length(message.len(), block_size);
 
         let mut padded_message: Vec<u8> = message.clone();
         for _i in 0..padding_length {
@@ -150,26 +171,102 @@ impl Pad for PKCS7 {
         padded_message
     }
 
-    fn unpad(padded_message: Vec<u8>, consistency_checking: bool) -> Vec<u8> {
-        let length: usize = padded_message.len();
-        let padding_length: u8 = padded_message[length-1];
+    // every byte of the padding is the length
+    fn unpad(padded_message: Vec<u8>, block_size: usize) -> Result<Vec<u8>, PaddingError> {
+        unpad_with_length_byte(padded_message, block_size, |byte, padding_length, _| ct_eq(byte, padding_length))
+    }
+}
+
 
-        if consistency_checking {
-            let message: Vec<u8> = padded_message[0..(length-(padding_length as usize))].to_vec();
-            let padding_is_valid: bool = padded_message[(length-(padding_length as usize))..].to_vec().iter().all(|&x| x == padding_length);
-            if padding_is_valid {
-                return message;
-            } else {
-                panic!("The input message is not correctly padded");
-            }
+
+
+// ISO/IEC 9797-1 padding method 1: zeros up to a multiple of the block size, none if the message is already
+// aligned (an empty message becomes one block of zeros). Unpadding is ambiguous: trailing zero bytes of the
+// message are removed as well.
+pub struct ISO_IEC_9797_1_M1 {}
+impl Pad for ISO_IEC_9797_1_M1 {
+    fn pad(message: Vec<u8>, block_size: usize) -> Vec<u8> {
+        let mut padded_message: Vec<u8> = message.clone();
+        if message.is_empty() {
+            padded_message.resize(block_size, 0);
         } else {
-            let message: Vec<u8> = padded_message[0..(length-(padding_length as usize))].to_vec();
-            return message;
+            padded_message.resize(message.len().div_ceil(block_size) * block_size, 0);
+        }
+
+        padded_message
+    }
+
+    fn unpad(padded_message: Vec<u8>, block_size: usize) -> Result<Vec<u8>, PaddingError> {
+        check_length(&padded_message, block_size)?;
+        let length = padded_message.len();
+
+        // trailing zeros of the last block
+        let mut only_zeros: u8 = 0xff;
+        let mut padding_length: usize = 0;
+        for distance in 1..=block_size {
+            only_zeros &= ct_eq(padded_message[length - distance], 0);
+            padding_length += (only_zeros & 1) as usize;
         }
+        Ok(padded_message[0..(length - padding_length)].to_vec())
     }
 }
 
+// ISO/IEC 9797-1 padding method 2: the same format as ISO/IEC 7816-4
+pub struct ISO_IEC_9797_1_M2 {}
+impl Pad for ISO_IEC_9797_1_M2 {
+    fn pad(message: Vec<u8>, block_size: usize) -> Vec<u8> {
+        ISO_IEC_7816_4::pad(message, block_size)
+    }
+
+    fn unpad(padded_message: Vec<u8>, block_size: usize) -> Result<Vec<u8>, PaddingError> {
+        ISO_IEC_7816_4::unpad(padded_message, block_size)
+    }
+}
+
+// ISO/IEC 9797-1 padding method 3: a first block with the length of the message in bits (big endian),
+// then the message padded with as few zeros as possible
+pub struct ISO_IEC_9797_1_M3 {}
+impl Pad for ISO_IEC_9797_1_M3 {
+    fn pad(message: Vec<u8>, block_size: usize) -> Vec<u8> {
+        if block_size == 0 || block_size > 255 {
+            panic!("The block size must be between 1 and 255 bytes");
+        }
+        let bit_length = (message.len() as u128 * 8).to_be_bytes();
+        let mut padded_message: Vec<u8> = vec![0; block_size.saturating_sub(16)];
+        padded_message.extend_from_slice(&bit_length[16usize.saturating_sub(block_size)..]);
+
+        padded_message.extend_from_slice(&message);
+        padded_message.resize(block_size + message.len().div_ceil(block_size) * block_size, 0);
+
+        padded_message
+    }
+
+    fn unpad(padded_message: Vec<u8>, block_size: usize) -> Result<Vec<u8>, PaddingError> {
+        check_length(&padded_message, block_size)?;
+        let length = padded_message.len();
+
+        // the length block: bytes above the 128 least significant bits must be zero
+        let high_bytes = block_size.saturating_sub(16);
+        let mut valid: u8 = padded_message[0..high_bytes].iter().fold(0xff, |valid, byte| valid & ct_eq(*byte, 0));
+        let bit_length = padded_message[high_bytes..block_size].iter().fold(0u128, |bit_length, byte| (bit_length << 8) | *byte as u128);
+
+        let data_length = (length - block_size) as u128;
+        let message_length = bit_length / 8;
+        let padding_length = data_length.wrapping_sub(message_length);
+        valid &= ((bit_length % 8 == 0) & (message_length <= data_length) & (padding_length < block_size as u128)) as u8 * 0xff;
+
+        let padding_length = (padding_length as u8) & valid;
+        for distance in 1..=block_size as u8 {
+            let byte = padded_message[length - distance as usize];
+            valid &= !ct_le(distance, padding_length) | ct_eq(byte, 0);
+        }
 
+        if valid != 0xff {
+            return Err(PaddingError::InvalidPadding);
+        }
+        Ok(padded_message[block_size..(length - padding_length as usize)].to_vec())
+    }
+}
 
 
 pub struct GenericPadding {
@@ -180,23 +277,41 @@ impl GenericPadding {
     pub fn init(pad_type: Paddings) -> Self{
         GenericPadding {padding_method: pad_type}
     }
+
+    pub fn get_padding_method(&self) -> Paddings {
+        self.padding_method
+    }
+
+    // with ciphertext stealing the message is not padded: the mode of operation handles the last partial block
+    pub fn is_ciphertext_stealing(&self) -> bool {
+        matches!(self.padding_method, Paddings::CBC_CS1 | Paddings::CBC_CS2 | Paddings::CBC_CS3)
+    }
+
     pub fn pad(&self, message: Vec<u8>, block_size: usize) -> Vec<u8> {
         let result: Vec<u8> = match self.padding_method { 
             Paddings::ANSI_X9_23=>ANSI_X9_23::pad(message, block_size),
             Paddings::ISO_10126=>ISO_10126::pad(message, block_size),
             Paddings::ISO_IEC_7816_4=>ISO_IEC_7816_4::pad(message, block_size),
             Paddings::PKCS7=>PKCS7::pad(message, block_size),
+            Paddings::ISO_IEC_9797_1_M1=>ISO_IEC_9797_1_M1::pad(message, block_size),
+            Paddings::ISO_IEC_9797_1_M2=>ISO_IEC_9797_1_M2::pad(message, block_size),
+            Paddings::ISO_IEC_9797_1_M3=>ISO_IEC_9797_1_M3::pad(message, block_size),
+            Paddings::CBC_CS1 | Paddings::CBC_CS2 | Paddings::CBC_CS3=>message,
             _=>panic!("Method not found")        
           };
 
           result
     }
-    pub fn unpad(&self, padded_message: Vec<u8>, consistency_checking: bool) -> Vec<u8> {
-        let result: Vec<u8> = match self.padding_method { 
-            Paddings::ANSI_X9_23=>ANSI_X9_23::unpad(padded_message, consistency_checking),
-            Paddings::ISO_10126=>ISO_10126::unpad(padded_message, consistency_checking),
-            Paddings::ISO_IEC_7816_4=>ISO_IEC_7816_4::unpad(padded_message, consistency_checking),
-            Paddings::PKCS7=>PKCS7::unpad(padded_message, consistency_checking),
+    pub fn unpad(&self, padded_message: Vec<u8>, block_size: usize) -> Result<Vec<u8>, PaddingError> {
+        let result: Result<Vec<u8>, PaddingError> = match self.padding_method { 
+            Paddings::ANSI_X9_23=>ANSI_X9_23::unpad(padded_message, block_size),
+            Paddings::ISO_10126=>ISO_10126::unpad(padded_message, block_size),
+            Paddings::ISO_IEC_7816_4=>ISO_IEC_7816_4::unpad(padded_message, block_size),
+            Paddings::PKCS7=>PKCS7::unpad(padded_message, block_size),
+            Paddings::ISO_IEC_9797_1_M1=>ISO_IEC_9797_1_M1::unpad(padded_message, block_size),
+            Paddings::ISO_IEC_9797_1_M2=>ISO_IEC_9797_1_M2::unpad(padded_message, block_size),
+            Paddings::ISO_IEC_9797_1_M3=>ISO_IEC_9797_1_M3::unpad(padded_message, block_size),
+            Paddings::CBC_CS1 | Paddings::CBC_CS2 | Paddings::CBC_CS3=>Ok(padded_message),
             _=>panic!("Method not found")        
           };
 
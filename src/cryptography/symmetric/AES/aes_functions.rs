@@ -112,7 +112,7 @@ pub fn inverse_mix_columns(current_state: &mut Vec<u8>) {
 }
 
 
-pub fn add(current_state: &mut Vec<u8>, key: &Vec<u8>) {
+pub fn add(current_state: &mut Vec<u8>, key: &[u8]) {
     for i in 0..current_state.len() {
         current_state[i] ^= key[i];
     }
@@ -179,6 +179,9 @@ impl AES_KEY_EXPANDER for AES_256_KEY_EXPANDER {
                 add(&mut column, &columns[i-8]);
                 column[0] ^= RCON[index_rcon];
                 index_rcon += 1;
+            } else if i % 8 == 4 { // AES-256 applies the SBOX also in the middle of each series
+                sub_bytes(&mut column);
+                add(&mut column, &columns[i-8]);
             } else { // use simple byte array xor
                 add(&mut column, &columns[i-8]);
             }
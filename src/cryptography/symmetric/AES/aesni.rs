@@ -0,0 +1,64 @@
+// AES with the AES-NI instructions of x86_64 processors, detected at runtime.
+// The round keys come from the table-free key schedule of the bitsliced backend.
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+pub fn is_available() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        is_x86_feature_detected!("aes") && is_x86_feature_detected!("sse2")
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        false
+    }
+}
+
+pub fn encrypt_block(block: &mut [u8], round_keys: &[[u8; 16]]) {
+    if !is_available() || block.len() != 16 {
+        panic!("AES-NI encrypts 16-byte blocks on processors supporting it");
+    }
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        encrypt_block_aesni(block, round_keys)
+    }
+}
+
+pub fn decrypt_block(block: &mut [u8], round_keys: &[[u8; 16]]) {
+    if !is_available() || block.len() != 16 {
+        panic!("AES-NI decrypts 16-byte blocks on processors supporting it");
+    }
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        decrypt_block_aesni(block, round_keys)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "aes,sse2")]
+unsafe fn encrypt_block_aesni(block: &mut [u8], round_keys: &[[u8; 16]]) {
+    let rounds = round_keys.len() - 1;
+    let mut state = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    state = _mm_xor_si128(state, _mm_loadu_si128(round_keys[0].as_ptr() as *const __m128i));
+    for round_key in &round_keys[1..rounds] {
+        state = _mm_aesenc_si128(state, _mm_loadu_si128(round_key.as_ptr() as *const __m128i));
+    }
+    state = _mm_aesenclast_si128(state, _mm_loadu_si128(round_keys[rounds].as_ptr() as *const __m128i));
+    _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, state);
+}
+
+// equivalent inverse cipher: the middle round keys go through InvMixColumns
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "aes,sse2")]
+unsafe fn decrypt_block_aesni(block: &mut [u8], round_keys: &[[u8; 16]]) {
+    let rounds = round_keys.len() - 1;
+    let mut state = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    state = _mm_xor_si128(state, _mm_loadu_si128(round_keys[rounds].as_ptr() as *const __m128i));
+    for round_key in round_keys[1..rounds].iter().rev() {
+        let key = _mm_aesimc_si128(_mm_loadu_si128(round_key.as_ptr() as *const __m128i));
+        state = _mm_aesdec_si128(state, key);
+    }
+    state = _mm_aesdeclast_si128(state, _mm_loadu_si128(round_keys[0].as_ptr() as *const __m128i));
+    _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, state);
+}
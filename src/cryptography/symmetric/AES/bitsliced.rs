@@ -0,0 +1,190 @@
+// Table-free constant-time AES. The state is kept as 8 bit planes of 16 bits: bit k of plane i is bit i of the
+// byte k of the block. SubBytes computes the inverse in GF(2^8) as x^254 with AND and XOR on the planes, so all the
+// 16 bytes are substituted at once and no memory access depends on secret data. ShiftRows and MixColumns are
+// fixed bit permutations and XORs of the planes.
+
+use crate::cryptography::symmetric::AES::data::RCON;
+
+type State = [u16; 8];
+
+fn pack(bytes: &[u8]) -> State {
+    let mut planes: State = [0; 8];
+    for (k, byte) in bytes.iter().enumerate() {
+        for (i, plane) in planes.iter_mut().enumerate() {
+            *plane |= (((byte >> i) & 1) as u16) << k;
+        }
+    }
+    planes
+}
+
+fn unpack(planes: &State, bytes: &mut [u8]) {
+    for (k, byte) in bytes.iter_mut().enumerate() {
+        *byte = 0;
+        for (i, plane) in planes.iter().enumerate() {
+            *byte |= (((plane >> k) & 1) as u8) << i;
+        }
+    }
+}
+
+// multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1, byte by byte on the planes
+fn gf_mul(a: &State, b: &State) -> State {
+    let mut product = [0u16; 15];
+    for i in 0..8 {
+        for j in 0..8 {
+            product[i + j] ^= a[i] & b[j];
+        }
+    }
+    // x^k = x^(k-4) + x^(k-5) + x^(k-7) + x^(k-8) for k >= 8
+    for k in (8..15).rev() {
+        let high = product[k];
+        product[k - 4] ^= high;
+        product[k - 5] ^= high;
+        product[k - 7] ^= high;
+        product[k - 8] ^= high;
+    }
+    product[..8].try_into().unwrap()
+}
+
+// x^254, which is the inverse of x for x != 0 and maps 0 to 0
+fn gf_inverse(x: &State) -> State {
+    let x2 = gf_mul(x, x);
+    let x3 = gf_mul(&x2, x);
+    let x6 = gf_mul(&x3, &x3);
+    let x12 = gf_mul(&x6, &x6);
+    let x15 = gf_mul(&x12, &x3);
+    let x30 = gf_mul(&x15, &x15);
+    let x60 = gf_mul(&x30, &x30);
+    let x120 = gf_mul(&x60, &x60);
+    let x240 = gf_mul(&x120, &x120);
+    let x252 = gf_mul(&x240, &x12);
+    gf_mul(&x252, &x2)
+}
+
+// the constant is added to every byte: unused bytes of the planes are never unpacked
+fn constant_planes(constant: u8) -> State {
+    let mut planes: State = [0; 8];
+    for (i, plane) in planes.iter_mut().enumerate() {
+        *plane = if (constant >> i) & 1 == 1 { u16::MAX } else { 0 };
+    }
+    planes
+}
+
+fn sub_bytes(state: &State) -> State {
+    let inverse = gf_inverse(state);
+    let constant = constant_planes(0x63);
+    let mut result: State = [0; 8];
+    for i in 0..8 {
+        result[i] = inverse[i] ^ inverse[(i + 4) % 8] ^ inverse[(i + 5) % 8] ^ inverse[(i + 6) % 8] ^ inverse[(i + 7) % 8] ^ constant[i];
+    }
+    result
+}
+
+fn inverse_sub_bytes(state: &State) -> State {
+    let constant = constant_planes(0x05);
+    let mut affine: State = [0; 8];
+    for i in 0..8 {
+        affine[i] = state[(i + 2) % 8] ^ state[(i + 5) % 8] ^ state[(i + 7) % 8] ^ constant[i];
+    }
+    gf_inverse(&affine)
+}
+
+// byte k of the result is byte source(k) of the input, in every plane
+fn permute_bytes(state: &State, source: impl Fn(usize) -> usize) -> State {
+    let mut result: State = [0; 8];
+    for (result_plane, plane) in result.iter_mut().zip(state.iter()) {
+        for k in 0..16 {
+            *result_plane |= ((plane >> source(k)) & 1) << k;
+        }
+    }
+    result
+}
+
+// the byte k is in row k % 4 and column k / 4
+fn shift_rows(state: &State) -> State {
+    permute_bytes(state, |k| 4 * ((k / 4 + k % 4) % 4) + k % 4)
+}
+
+fn inverse_shift_rows(state: &State) -> State {
+    permute_bytes(state, |k| 4 * ((k / 4 + 4 - k % 4) % 4) + k % 4)
+}
+
+// each byte takes the value of the byte `rows` rows below in the same column
+fn rotate_columns(state: &State, rows: usize) -> State {
+    permute_bytes(state, |k| 4 * (k / 4) + (k % 4 + rows) % 4)
+}
+
+fn xtime(state: &State) -> State {
+    [state[7], state[0] ^ state[7], state[1], state[2] ^ state[7], state[3] ^ state[7], state[4], state[5], state[6]]
+}
+
+fn xor(a: &State, b: &State) -> State {
+    let mut result: State = [0; 8];
+    for i in 0..8 {
+        result[i] = a[i] ^ b[i];
+    }
+    result
+}
+
+// b_r = 2 a_r + 3 a_(r+1) + a_(r+2) + a_(r+3) = xtime(a_r + a_(r+1)) + a_(r+1) + a_(r+2) + a_(r+3)
+fn mix_columns(state: &State) -> State {
+    let rotated_1 = rotate_columns(state, 1);
+    let rotated_2 = rotate_columns(state, 2);
+    let rotated_3 = rotate_columns(state, 3);
+    let doubled = xtime(&xor(state, &rotated_1));
+    xor(&xor(&doubled, &rotated_1), &xor(&rotated_2, &rotated_3))
+}
+
+// InvMixColumns is MixColumns after adding 4 (a_r + a_(r+2)) to each byte
+fn inverse_mix_columns(state: &State) -> State {
+    let quadrupled = xtime(&xtime(&xor(state, &rotate_columns(state, 2))));
+    mix_columns(&xor(state, &quadrupled))
+}
+
+fn sub_word(word: &mut [u8; 4]) {
+    let substituted = sub_bytes(&pack(word));
+    unpack(&substituted, word);
+}
+
+// key schedule with the table-free S-box: round keys as 16-byte blocks, also used by the AES-NI backend
+pub fn expand_key(key: &[u8], rounds: usize) -> Vec<[u8; 16]> {
+    let key_words = key.len() / 4;
+    let mut words: Vec<[u8; 4]> = key.chunks(4).map(|chunk| chunk.try_into().unwrap()).collect();
+    for i in key_words..4 * (rounds + 1) {
+        let mut temp = words[i - 1];
+        if i % key_words == 0 {
+            temp.rotate_left(1);
+            sub_word(&mut temp);
+            temp[0] ^= RCON[i / key_words];
+        } else if key_words > 6 && i % key_words == 4 {
+            sub_word(&mut temp);
+        }
+        let previous = words[i - key_words];
+        words.push([previous[0] ^ temp[0], previous[1] ^ temp[1], previous[2] ^ temp[2], previous[3] ^ temp[3]]);
+    }
+
+    words.chunks(4).map(|round_key| round_key.concat().try_into().unwrap()).collect()
+}
+
+pub fn encrypt_block(block: &mut [u8], round_keys: &[[u8; 16]]) {
+    let rounds = round_keys.len() - 1;
+    let keys: Vec<State> = round_keys.iter().map(|round_key| pack(round_key)).collect();
+
+    let mut state = xor(&pack(block), &keys[0]);
+    for key in keys.iter().take(rounds).skip(1) {
+        state = xor(&mix_columns(&shift_rows(&sub_bytes(&state))), key);
+    }
+    state = xor(&shift_rows(&sub_bytes(&state)), &keys[rounds]);
+    unpack(&state, block);
+}
+
+pub fn decrypt_block(block: &mut [u8], round_keys: &[[u8; 16]]) {
+    let rounds = round_keys.len() - 1;
+    let keys: Vec<State> = round_keys.iter().map(|round_key| pack(round_key)).collect();
+
+    let mut state = inverse_sub_bytes(&inverse_shift_rows(&xor(&pack(block), &keys[rounds])));
+    for key in keys.iter().take(rounds).skip(1).rev() {
+        state = inverse_sub_bytes(&inverse_shift_rows(&inverse_mix_columns(&xor(&state, key))));
+    }
+    state = xor(&state, &keys[0]);
+    unpack(&state, block);
+}
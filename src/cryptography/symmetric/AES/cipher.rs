@@ -1,36 +1,82 @@
-use crate::cryptography::symmetric::{primitives::aes::AES_KEY_SIZE, AES::aes_functions::*};
+use crate::cryptography::symmetric::AES::{aes_functions::*, aesni, bitsliced};
+use crate::error::{Error, Result};
 
-pub fn encrypt(message: &mut Vec<u8>, key: Vec<u8>, rounds: usize, key_size: AES_KEY_SIZE) {
+// Implementations of the block cipher. TABLES is the original one, with S-box and multiplication tables:
+// its memory accesses depend on the key and on the data, so it leaks timing through the cache.
+// Each cipher keeps its own backend, see AESinterface::try_set_backend.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AES_BACKEND {
+    TABLES,
+    BITSLICED,
+    AESNI
+}
+
+// AES-NI when the processor supports it, otherwise the bitsliced backend
+pub fn default_backend() -> AES_BACKEND {
+    if aesni::is_available() { AES_BACKEND::AESNI } else { AES_BACKEND::BITSLICED }
+}
+
+pub fn check_backend(backend: AES_BACKEND) -> Result<()> {
+    if backend == AES_BACKEND::AESNI && !aesni::is_available() {
+        return Err(Error::InvalidInput("AES-NI is not supported by this processor".to_string()));
+    }
+    Ok(())
+}
+
+// round keys as 16-byte blocks: the modes of operation expand their key once and keep them
+pub fn expand_key(key: &[u8], rounds: usize) -> Vec<[u8; 16]> {
+    bitsliced::expand_key(key, rounds)
+}
+
+pub fn encrypt_with_backend(backend: AES_BACKEND, message: &mut Vec<u8>, round_keys: &[[u8; 16]]) {
+    if message.len() != 16 {
+        panic!("Block length should be 16");
+    }
+    match backend {
+        AES_BACKEND::TABLES => encrypt_with_tables(message, round_keys),
+        AES_BACKEND::BITSLICED => bitsliced::encrypt_block(message, round_keys),
+        AES_BACKEND::AESNI => aesni::encrypt_block(message, round_keys)
+    }
+}
 
-    let keys: Vec<Vec<u8>> = match key_size {
-        AES_KEY_SIZE::AES_128 => AES_128_KEY_EXPANDER::get_n_rounds_keys(rounds, key),
-        AES_KEY_SIZE::AES_192 => AES_192_KEY_EXPANDER::get_n_rounds_keys(rounds, key),
-        AES_KEY_SIZE::AES_256 => AES_256_KEY_EXPANDER::get_n_rounds_keys(rounds, key),
-        _ => AES_128_KEY_EXPANDER::get_n_rounds_keys(rounds, key),
-    };
+pub fn decrypt_with_backend(backend: AES_BACKEND, message: &mut Vec<u8>, round_keys: &[[u8; 16]]) {
+    if message.len() != 16 {
+        panic!("Block length should be 16");
+    }
+    match backend {
+        AES_BACKEND::TABLES => decrypt_with_tables(message, round_keys),
+        AES_BACKEND::BITSLICED => bitsliced::decrypt_block(message, round_keys),
+        AES_BACKEND::AESNI => aesni::decrypt_block(message, round_keys)
+    }
+}
+
+fn encrypt_with_tables(message: &mut Vec<u8>, keys: &[[u8; 16]]) {
+    let rounds = keys.len() - 1;
     // add Key_0 to the message
     add(message, &keys[0]);
-    
-    for i in 0..rounds {
+
+    for key in keys.iter().take(rounds).skip(1) {
         forward_round(message);
-        add(message, &keys[i+1] );
+        add(message, key);
     }
+
+    // the last round has no MixColumns
+    sub_bytes(message);
+    shift_rows(message);
+    add(message, &keys[rounds]);
 }
 
 
-pub fn decrypt(message: &mut Vec<u8>, key: Vec<u8>, rounds: usize, key_size: AES_KEY_SIZE) {
-    let keys: Vec<Vec<u8>> = match key_size {
-        AES_KEY_SIZE::AES_128 => AES_128_KEY_EXPANDER::get_n_rounds_keys(rounds, key),
-        AES_KEY_SIZE::AES_192 => AES_192_KEY_EXPANDER::get_n_rounds_keys(rounds, key),
-        AES_KEY_SIZE::AES_256 => AES_256_KEY_EXPANDER::get_n_rounds_keys(rounds, key),
-        _ => AES_128_KEY_EXPANDER::get_n_rounds_keys(rounds, key),
-    };
-  
+fn decrypt_with_tables(message: &mut Vec<u8>, keys: &[[u8; 16]]) {
+    let rounds = keys.len() - 1;
+
     add(message, &keys[rounds]);
-    
+    inverse_shift_rows(message);
+    inverse_sub_bytes(message);
 
-    for i in (0..rounds).rev() {
-        backward_round(message);
+    for i in (1..rounds).rev() {
         add(message, &keys[i] );
+        backward_round(message);
     }
-}
\ No newline at end of file
+    add(message, &keys[0]);
+}
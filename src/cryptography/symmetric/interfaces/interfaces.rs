@@ -1,22 +1,75 @@
 // symmetric ciphers interfaces
 
-use crate::cryptography::{symmetric::{modes::modes::Modes, primitives::aes::AES_KEY_SIZE}, padding::padding::Paddings};
+use crate::cryptography::{symmetric::{modes::modes::Modes, primitives::aes::AES_KEY_SIZE, AES::cipher::AES_BACKEND}, padding::padding::Paddings};
+use crate::error::{unwrap_or_panic, Result};
 
 // refactoring with ! 
 pub trait AESfactory {
-    fn init(mode: Modes, padding: Paddings, key_size: AES_KEY_SIZE) -> Box<dyn AESinterface>;
+    fn try_init(mode: Modes, padding: Paddings, key_size: AES_KEY_SIZE) -> Result<Box<dyn AESinterface>>;
+    fn try_init_with_key(mode: Modes, padding: Paddings, key: Vec<u8>) -> Result<Box<dyn AESinterface>>;
+    fn try_init_with_key_and_iv(mode: Modes, padding: Paddings, key: Vec<u8>, iv: Vec<u8>) -> Result<Box<dyn AESinterface>>;
+
+    fn init(mode: Modes, padding: Paddings, key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
+        unwrap_or_panic(Self::try_init(mode, padding, key_size))
+    }
+
+    fn init_with_key(mode: Modes, padding: Paddings, key: Vec<u8>) -> Box<dyn AESinterface> {
+        unwrap_or_panic(Self::try_init_with_key(mode, padding, key))
+    }
+
+    fn init_with_key_and_iv(mode: Modes, padding: Paddings, key: Vec<u8>, iv: Vec<u8>) -> Box<dyn AESinterface> {
+        unwrap_or_panic(Self::try_init_with_key_and_iv(mode, padding, key, iv))
+    }
+
+    // the block cipher implementation is chosen when the cipher is built instead of the detected default
+    fn try_init_with_key_and_backend(mode: Modes, padding: Paddings, key: Vec<u8>, backend: AES_BACKEND) -> Result<Box<dyn AESinterface>> {
+        let mut cipher = Self::try_init_with_key(mode, padding, key)?;
+        cipher.try_set_backend(backend)?;
+        Ok(cipher)
+    }
+
+    fn init_with_key_and_backend(mode: Modes, padding: Paddings, key: Vec<u8>, backend: AES_BACKEND) -> Box<dyn AESinterface> {
+        unwrap_or_panic(Self::try_init_with_key_and_backend(mode, padding, key, backend))
+    }
 }
 
+// encrypt and decrypt panic on the errors returned by try_encrypt and try_decrypt
 pub trait AESinterface {
-    fn encrypt(&mut self, plaintext: Vec<u8>) -> Vec<u8>;
-    fn decrypt(&mut self, ciphertext: Vec<u8>) -> Vec<u8>;
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>>;
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>>;
+    // current key, to store it or to share it
+    fn export_key(&self) -> Vec<u8>;
+    // block cipher implementation of this instance, cipher::default_backend() unless it has been set
+    fn get_backend(&self) -> AES_BACKEND;
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()>;
+
+    fn set_backend(&mut self, backend: AES_BACKEND) {
+        unwrap_or_panic(self.try_set_backend(backend))
+    }
+
+    fn encrypt(&mut self, plaintext: Vec<u8>) -> Vec<u8> {
+        unwrap_or_panic(self.try_encrypt(plaintext))
+    }
+
+    fn decrypt(&mut self, ciphertext: Vec<u8>) -> Vec<u8> {
+        unwrap_or_panic(self.try_decrypt(ciphertext))
+    }
 }
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AEADError {
+    InvalidNonce,
+    InvalidTag,
+    MessageTooLong,
+    AuthenticationFailed
+}
+
+// authenticated encryption with associated data: encrypt returns (ciphertext, tag)
 pub trait AEADinterface {
-    fn encrypt(&self, plaintext: Vec<u8>, associated_data: Vec<u8>);
-    fn decrypt(&self, ciphertext: Vec<u8>, associated_data: Vec<u8>);
+    fn encrypt(&self, nonce: Vec<u8>, plaintext: Vec<u8>, associated_data: Vec<u8>) -> Result<(Vec<u8>, Vec<u8>), AEADError>;
+    fn decrypt(&self, nonce: Vec<u8>, ciphertext: Vec<u8>, associated_data: Vec<u8>, tag: Vec<u8>) -> Result<Vec<u8>, AEADError>;
 }
 
 pub trait MACinterface {
-    fn get_authentication_tag(&self, plaintext: Vec<u8>);
-    fn verify(&self, plaintext: Vec<u8>, authentication_tag: Vec<u8>);
+    fn get_authentication_tag(&self, plaintext: Vec<u8>) -> Vec<u8>;
+    fn verify(&self, plaintext: Vec<u8>, authentication_tag: Vec<u8>) -> bool;
 }
@@ -1,8 +1,13 @@
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Modes {
     ECB,
     CTR,
+    CTR_with_preprocessing,
     CBC,
     OFB,
+    CFB8,
+    CFB128,
+    XTS,
     GCM,
     NONE
 }
\ No newline at end of file
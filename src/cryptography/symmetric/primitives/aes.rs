@@ -4,7 +4,8 @@ use num_traits::ToBytes;
 use rand::Rng;
 use rayon::prelude::*;
 
-use crate::cryptography::{symmetric::{modes::modes::Modes, interfaces::interfaces::{AESinterface, AESfactory}, AES::{aes_functions, cipher::{encrypt, decrypt, self}}}, padding::padding::{GenericPadding, Pad, Paddings}};
+use crate::cryptography::{symmetric::{modes::modes::Modes, interfaces::interfaces::{AESinterface, AESfactory, AEADinterface, AEADError}, AES::{aes_functions, cipher::{encrypt_with_backend, decrypt_with_backend, expand_key, self, AES_BACKEND}}}, padding::padding::{GenericPadding, Pad, PaddingError, Paddings}};
+use crate::error::{unwrap_or_panic, Error, Result};
 
 #[derive(Clone)]
 pub enum AES_KEY_SIZE {
@@ -22,6 +23,25 @@ fn random_byte_array(length: usize) -> Vec<u8> {
     byte_array
 }
 
+// number of bytes of the key
+fn key_length(key_size: &AES_KEY_SIZE) -> usize {
+    match key_size {
+        AES_KEY_SIZE::AES_128 => 16,
+        AES_KEY_SIZE::AES_192 => 24,
+        AES_KEY_SIZE::AES_256 => 32
+    }
+}
+
+// number of rounds from the length of a caller-supplied key
+fn try_number_of_rounds(key_length: usize) -> Result<usize> {
+    match key_length {
+        16 => Ok(10),
+        24 => Ok(12),
+        32 => Ok(14),
+        _ => Err(Error::InvalidKey("AES keys are 16, 24 or 32 bytes long".to_string()))
+    }
+}
+
 fn inplace_byte_array_xor(m1: &mut Vec<u8>, m2: &Vec<u8>) {
     
     for i in 0..m1.len() {
@@ -38,17 +58,56 @@ pub struct aes_factory {
 }
 
 impl AESfactory for aes_factory {
-    fn init(mode: Modes, padding: Paddings, key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
+    fn try_init(mode: Modes, padding: Paddings, key_size: AES_KEY_SIZE) -> Result<Box<dyn AESinterface>> {
         let result: Box<dyn AESinterface> = match mode { 
             Modes::CBC=>AES_CBC::init(padding, key_size),
-            Modes::CTR=>AES_CTR_with_preprocessing::init(key_size),
-            // Modes::OFB=>AES_OFB::init(),
-            // Modes::GCM=>AES_GCM::init(),
+            Modes::CTR=>AES_CTR::init(key_size),
+            Modes::CTR_with_preprocessing=>AES_CTR_with_preprocessing::init(key_size),
+            Modes::OFB=>AES_OFB::init(key_size),
+            Modes::CFB8=>AES_CFB::init(key_size, AES_CFB_SEGMENT_SIZE::CFB_8),
+            Modes::CFB128=>AES_CFB::init(key_size, AES_CFB_SEGMENT_SIZE::CFB_128),
+            Modes::XTS=>AES_XTS::try_init(key_size)?,
+            Modes::GCM=>AES_GCM::init(key_size),
             Modes::ECB=>AES_ECB::init(padding, key_size),
             _=>AES::init(key_size)           
           };
 
-          result
+          Ok(result)
+    }
+
+    fn try_init_with_key(mode: Modes, padding: Paddings, key: Vec<u8>) -> Result<Box<dyn AESinterface>> {
+        let result: Box<dyn AESinterface> = match mode { 
+            Modes::CBC=>Box::new(AES_CBC::try_from_key(padding, key)?),
+            Modes::CTR=>Box::new(AES_CTR::try_from_key(key, AES_CTR_COUNTER_SIZE::COUNTER_32)?),
+            Modes::CTR_with_preprocessing=>Box::new(AES_CTR_with_preprocessing::try_from_key(key)?),
+            Modes::OFB=>Box::new(AES_OFB::try_from_key(key)?),
+            Modes::CFB8=>Box::new(AES_CFB::try_from_key(key, AES_CFB_SEGMENT_SIZE::CFB_8)?),
+            Modes::CFB128=>Box::new(AES_CFB::try_from_key(key, AES_CFB_SEGMENT_SIZE::CFB_128)?),
+            Modes::XTS=>Box::new(AES_XTS::try_from_key(key)?),
+            Modes::GCM=>Box::new(AES_GCM::try_from_key(key)?),
+            Modes::ECB=>Box::new(AES_ECB::try_from_key(padding, key)?),
+            Modes::NONE=>Box::new(AES::try_from_key(key)?)
+          };
+
+          Ok(result)
+    }
+
+    // the IV is used for every encryption: reusing it with the same key is only meant for known-answer tests
+    // and interoperability, and it is catastrophic for CTR, OFB and GCM
+    fn try_init_with_key_and_iv(mode: Modes, padding: Paddings, key: Vec<u8>, iv: Vec<u8>) -> Result<Box<dyn AESinterface>> {
+        let result: Box<dyn AESinterface> = match mode { 
+            Modes::CBC=>{ let mut cipher = AES_CBC::try_from_key(padding, key)?; cipher.try_set_iv(iv)?; Box::new(cipher) },
+            Modes::CTR=>{ let mut cipher = AES_CTR::try_from_key(key, AES_CTR_COUNTER_SIZE::COUNTER_32)?; cipher.try_set_iv(iv)?; Box::new(cipher) },
+            Modes::CTR_with_preprocessing=>{ let mut cipher = AES_CTR_with_preprocessing::try_from_key(key)?; cipher.try_set_iv(iv)?; Box::new(cipher) },
+            Modes::OFB=>{ let mut cipher = AES_OFB::try_from_key(key)?; cipher.try_set_iv(iv)?; Box::new(cipher) },
+            Modes::CFB8=>{ let mut cipher = AES_CFB::try_from_key(key, AES_CFB_SEGMENT_SIZE::CFB_8)?; cipher.try_set_iv(iv)?; Box::new(cipher) },
+            Modes::CFB128=>{ let mut cipher = AES_CFB::try_from_key(key, AES_CFB_SEGMENT_SIZE::CFB_128)?; cipher.try_set_iv(iv)?; Box::new(cipher) },
+            Modes::XTS=>{ let mut cipher = AES_XTS::try_from_key(key)?; cipher.try_set_iv(iv)?; Box::new(cipher) },
+            Modes::GCM=>{ let mut cipher = AES_GCM::try_from_key(key)?; cipher.try_set_iv(iv)?; Box::new(cipher) },
+            Modes::ECB | Modes::NONE=>return Err(Error::InvalidInput("This mode of operation has no IV".to_string()))
+          };
+
+          Ok(result)
     }
 }
 
@@ -57,64 +116,72 @@ impl AESfactory for aes_factory {
 pub struct AES {
     keys: Vec<Vec<u8>>, 
     primary_key: usize,
-    key_size: AES_KEY_SIZE,
+    round_keys: Vec<[u8; 16]>, // expanded once from the primary key
+    backend: AES_BACKEND,
     n_rounds: usize,
     number_of_key_bytes: usize
 
 }
 
 impl AESinterface for AES {
-    fn encrypt(&mut self, plaintext: Vec<u8>) -> Vec<u8> {
-        if plaintext.len() > 16 {
-            panic!("Simple AES performs one block encryption at a time");
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
+        if plaintext.len() != 16 {
+            return Err(Error::InvalidInput("Simple AES encrypts exactly one block of 16 bytes".to_string()));
         }
 
         let mut ciphertext = plaintext.clone();
         
-        encrypt( &mut ciphertext, self.keys[self.primary_key].clone(), self.n_rounds, self.key_size.clone());
-        ciphertext
+        encrypt_with_backend(self.backend, &mut ciphertext, &self.round_keys);
+        Ok(ciphertext)
     }
 
-    fn decrypt(&mut self, ciphertext: Vec<u8>) -> Vec<u8> {
-        if ciphertext.len() > 16 {
-            panic!("Simple AES performs one block encryption at a time");
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        if ciphertext.len() != 16 {
+            return Err(Error::InvalidInput("Simple AES encrypts exactly one block of 16 bytes".to_string()));
         }
 
         let mut padded_plaintext = ciphertext.clone();
-        decrypt(&mut padded_plaintext, self.keys[self.primary_key].clone(), self.n_rounds, self.key_size.clone());
-        padded_plaintext
+        decrypt_with_backend(self.backend, &mut padded_plaintext, &self.round_keys);
+        Ok(padded_plaintext)
+    }
+
+    fn export_key(&self) -> Vec<u8> {
+        self.keys[self.primary_key].clone()
+    }
+
+    fn get_backend(&self) -> AES_BACKEND {
+        self.backend
+    }
+
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()> {
+        cipher::check_backend(backend)?;
+        self.backend = backend;
+        Ok(())
     }
 }
 
 impl AES {
     pub fn init(key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
-        
-
-        let n_rounds: usize = match key_size {
-            AES_KEY_SIZE::AES_128 => 10,
-            AES_KEY_SIZE::AES_192 => 12,
-            AES_KEY_SIZE::AES_256 => 14,
-            _ => 10
-        };
-
-        let number_of_key_bytes: usize = match key_size {
-            AES_KEY_SIZE::AES_128 => 16,
-            AES_KEY_SIZE::AES_192 => 24,
-            AES_KEY_SIZE::AES_256 => 32,
-            _ => 16
-        };
+        Box::new(AES::from_key(random_byte_array(key_length(&key_size))))
+    }
 
-        let key: Vec<u8> = random_byte_array(number_of_key_bytes);
-        let mut keys: Vec<Vec<u8>> = Vec::new();
-        keys.push(key);
+    // the key size is given by the length of the key
+    pub fn from_key(key: Vec<u8>) -> AES {
+        unwrap_or_panic(AES::try_from_key(key))
+    }
 
-        Box::new(AES { keys: keys, primary_key: 0, key_size: key_size, n_rounds: n_rounds, number_of_key_bytes: number_of_key_bytes})
+    pub fn try_from_key(key: Vec<u8>) -> Result<AES> {
+        let n_rounds = try_number_of_rounds(key.len())?;
+        let round_keys = expand_key(&key, n_rounds);
+        let number_of_key_bytes = key.len();
+        Ok(AES { keys: vec![key], primary_key: 0, backend: cipher::default_backend(), round_keys, n_rounds, number_of_key_bytes })
     }
 
     pub fn refresh_key(&mut self) {
         let key: Vec<u8> = random_byte_array(self.number_of_key_bytes);
         self.keys.push(key);
         self.primary_key = self.primary_key + 1;
+        self.round_keys = expand_key(&self.keys[self.primary_key], self.n_rounds);
     }
 }
 //end simple AES
@@ -127,14 +194,19 @@ pub struct AES_CTR_with_preprocessing {
     current_byte_stream: Vec<Vec<u8>>, 
     current_iv: Vec<u8>, // unique for each encryption
     nonce: Vec<u8>, // unique for each security association (chosen at init at random)
-    key_size: AES_KEY_SIZE,
+    round_keys: Vec<[u8; 16]>, // expanded once from the primary key
+    backend: AES_BACKEND,
     n_rounds: usize,
-    number_of_key_bytes: usize
+    number_of_key_bytes: usize,
+    fixed_iv: bool // set by set_iv: the IV is not refreshed after each encryption
 
 }
 
 impl AESinterface for AES_CTR_with_preprocessing {
-    fn encrypt(&mut self, plaintext: Vec<u8>) -> Vec<u8> {
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
+        if plaintext.len() > 256 * 16 {
+            return Err(Error::InvalidInput("At most 256 blocks can be encrypted with the same IV".to_string()));
+        }
         // divide plaintext into blocks
         let blocks;
         if plaintext.len() % 16 == 0 {
@@ -160,11 +232,14 @@ impl AESinterface for AES_CTR_with_preprocessing {
         blocks_plaintext.par_iter_mut().enumerate().for_each(|(i, x)| inplace_byte_array_xor(x, &self.current_byte_stream[i]));
         blocks_plaintext.insert(0, self.current_iv.clone());
         self.refresh_byte_stream();
-        blocks_plaintext.concat()
+        Ok(blocks_plaintext.concat())
 
     }
 
-    fn decrypt(&mut self, ciphertext: Vec<u8>) -> Vec<u8> {
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        if ciphertext.len() < 8 || ciphertext.len() > 8 + 256 * 16 {
+            return Err(Error::InvalidInput("The ciphertext must be between 8 and 8 + 256 * 16 bytes long".to_string()));
+        }
         let iv: Vec<u8> = ciphertext[0..8].to_vec();
         let ciphertext_container: Vec<u8> = ciphertext[8..].to_vec();
         let mut blocks = 0;
@@ -193,58 +268,85 @@ impl AESinterface for AES_CTR_with_preprocessing {
         }
 
         
-        let byte_stream = AES_CTR_with_preprocessing::get_byte_stream(self.keys[self.primary_key].clone(), iv.clone(), self.nonce.clone(), self.n_rounds, self.key_size.clone());
+        let byte_stream = AES_CTR_with_preprocessing::get_byte_stream(self.backend, &self.round_keys, iv.clone(), self.nonce.clone());
 
         blocks_ciphertext.par_iter_mut().enumerate().for_each(|(i, x)| inplace_byte_array_xor(x, &byte_stream[i]));
-        blocks_ciphertext.concat()
+        Ok(blocks_ciphertext.concat())
+    }
+
+    fn export_key(&self) -> Vec<u8> {
+        self.keys[self.primary_key].clone()
+    }
+
+    fn get_backend(&self) -> AES_BACKEND {
+        self.backend
+    }
+
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()> {
+        cipher::check_backend(backend)?;
+        self.backend = backend;
+        Ok(())
     }
 }
 
 impl AES_CTR_with_preprocessing {
     pub fn init(key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
-       
-
-        let n_rounds: usize = match key_size {
-            AES_KEY_SIZE::AES_128 => 10,
-            AES_KEY_SIZE::AES_192 => 12,
-            AES_KEY_SIZE::AES_256 => 14,
-            _ => 10
-        };
+        Box::new(AES_CTR_with_preprocessing::from_key(random_byte_array(key_length(&key_size))))
+    }
 
-        let number_of_key_bytes: usize = match key_size {
-            AES_KEY_SIZE::AES_128 => 16,
-            AES_KEY_SIZE::AES_192 => 24,
-            AES_KEY_SIZE::AES_256 => 32,
-            _ => 16
-        };
+    // the key size is given by the length of the key, nonce and IV are random
+    pub fn from_key(key: Vec<u8>) -> AES_CTR_with_preprocessing {
+        unwrap_or_panic(AES_CTR_with_preprocessing::try_from_key(key))
+    }
 
-        let key: Vec<u8> = random_byte_array(number_of_key_bytes);
-        let mut keys: Vec<Vec<u8>> = Vec::new();
-        keys.push(key.clone());
+    pub fn try_from_key(key: Vec<u8>) -> Result<AES_CTR_with_preprocessing> {
+        let n_rounds = try_number_of_rounds(key.len())?;
+        let round_keys = expand_key(&key, n_rounds);
+        let number_of_key_bytes = key.len();
 
         let iv: Vec<u8> = random_byte_array(8);
         let nonce: Vec<u8> = random_byte_array(4);
-        let byte_stream: Vec<Vec<u8>> = AES_CTR_with_preprocessing::get_byte_stream(key, iv.clone(), nonce.clone(), n_rounds, key_size.clone());
-        
+        let backend = cipher::default_backend();
+        let byte_stream: Vec<Vec<u8>> = AES_CTR_with_preprocessing::get_byte_stream(backend, &round_keys, iv.clone(), nonce.clone());
         
-        Box::new(AES_CTR_with_preprocessing { keys: keys, primary_key: 0, current_byte_stream: byte_stream, current_iv: iv, nonce: nonce, key_size: key_size.clone(), n_rounds: n_rounds, number_of_key_bytes: number_of_key_bytes})
+        Ok(AES_CTR_with_preprocessing { keys: vec![key], primary_key: 0, backend, current_byte_stream: byte_stream, current_iv: iv, nonce, round_keys, n_rounds, number_of_key_bytes, fixed_iv: false })
+    }
+
+    // nonce (4 bytes) || IV (8 bytes)
+    pub fn set_iv(&mut self, iv: Vec<u8>) {
+        unwrap_or_panic(self.try_set_iv(iv))
+    }
+
+    pub fn try_set_iv(&mut self, iv: Vec<u8>) -> Result<()> {
+        if iv.len() != 12 {
+            return Err(Error::InvalidInput("The IV must be 12 bytes long: 4 bytes of nonce and 8 bytes of IV".to_string()));
+        }
+        self.nonce = iv[0..4].to_vec();
+        self.current_iv = iv[4..12].to_vec();
+        self.current_byte_stream = AES_CTR_with_preprocessing::get_byte_stream(self.backend, &self.round_keys, self.current_iv.clone(), self.nonce.clone());
+        self.fixed_iv = true;
+        Ok(())
     }
 
     pub fn refresh_key(&mut self) {
         let key: Vec<u8> = random_byte_array(self.number_of_key_bytes);
         self.keys.push(key);
         self.primary_key = self.primary_key + 1;
+        self.round_keys = expand_key(&self.keys[self.primary_key], self.n_rounds);
     }
 
     fn refresh_byte_stream(&mut self) {
+        if self.fixed_iv {
+            return;
+        }
         let iv: Vec<u8> = random_byte_array(8);
-        let byte_stream: Vec<Vec<u8>> = AES_CTR_with_preprocessing::get_byte_stream(self.keys[self.primary_key].clone(), iv.clone(), self.nonce.clone(), self.n_rounds, self.key_size.clone());
+        let byte_stream: Vec<Vec<u8>> = AES_CTR_with_preprocessing::get_byte_stream(self.backend, &self.round_keys, iv.clone(), self.nonce.clone());
         self.current_byte_stream = byte_stream;
         self.current_iv = iv.clone();
     }
 
     // the computation of the byte_stream is parallelized
-    pub fn get_byte_stream(key: Vec<u8>, iv: Vec<u8>, nonce: Vec<u8>, n_rounds: usize, key_size: AES_KEY_SIZE) -> Vec<Vec<u8>> {
+    pub fn get_byte_stream(backend: AES_BACKEND, round_keys: &[[u8; 16]], iv: Vec<u8>, nonce: Vec<u8>) -> Vec<Vec<u8>> {
         let mut byte_stream_container: Vec<Vec<u8>> = Vec::new();
         let mut counter: u32 = 0;
         for _i in 0..256 {
@@ -255,32 +357,241 @@ impl AES_CTR_with_preprocessing {
             counter += 1;
         }
 
-        byte_stream_container.par_iter_mut().for_each(|x| encrypt(x, key.clone(), n_rounds, key_size.clone()));
+        byte_stream_container.par_iter_mut().for_each(|x| encrypt_with_backend(backend, x, round_keys));
         byte_stream_container
     }
 }
 // end AES_CTR
 
+// AES_CTR without pre-processing (NIST SP 800-38A): the counter block is nonce || counter,
+// with a 32-bit or 64-bit big endian counter. The key stream is generated on demand, in parallel,
+// from any offset, so there is no limit on the length of the message other than the counter size.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AES_CTR_COUNTER_SIZE {
+    COUNTER_32,
+    COUNTER_64
+}
+
+pub struct AES_CTR {
+    keys: Vec<Vec<u8>>, 
+    primary_key: usize,
+    round_keys: Vec<[u8; 16]>, // expanded once from the primary key
+    backend: AES_BACKEND,
+    n_rounds: usize,
+    number_of_key_bytes: usize,
+    counter_size: AES_CTR_COUNTER_SIZE,
+    nonce: Option<Vec<u8>> // random for each encryption unless set by set_iv
+}
+
+// through AESinterface a random nonce is generated, the counter starts from 0 and the output is nonce || ciphertext
+impl AESinterface for AES_CTR {
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
+        let nonce: Vec<u8> = self.nonce.clone().unwrap_or_else(|| random_byte_array(self.nonce_length()));
+        let mut ciphertext: Vec<u8> = plaintext.clone();
+        self.try_apply_keystream(&nonce, 0, 0, &mut ciphertext)?;
+        Ok([nonce, ciphertext].concat())
+    }
+
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        if ciphertext.len() < self.nonce_length() {
+            return Err(Error::InvalidInput("The ciphertext is too short".to_string()));
+        }
+        let nonce: Vec<u8> = ciphertext[0..self.nonce_length()].to_vec();
+        let mut plaintext: Vec<u8> = ciphertext[self.nonce_length()..].to_vec();
+        self.try_apply_keystream(&nonce, 0, 0, &mut plaintext)?;
+        Ok(plaintext)
+    }
+
+    fn export_key(&self) -> Vec<u8> {
+        self.keys[self.primary_key].clone()
+    }
+
+    fn get_backend(&self) -> AES_BACKEND {
+        self.backend
+    }
+
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()> {
+        cipher::check_backend(backend)?;
+        self.backend = backend;
+        Ok(())
+    }
+}
+
+impl AES_CTR {
+    pub fn init(key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
+        Box::new(AES_CTR::new(key_size, AES_CTR_COUNTER_SIZE::COUNTER_32))
+    }
+
+    // random key
+    pub fn new(key_size: AES_KEY_SIZE, counter_size: AES_CTR_COUNTER_SIZE) -> AES_CTR {
+        let number_of_key_bytes: usize = key_length(&key_size);
+        AES_CTR::from_key(random_byte_array(number_of_key_bytes), counter_size)
+    }
+
+    // the key size is given by the length of the key
+    pub fn from_key(key: Vec<u8>, counter_size: AES_CTR_COUNTER_SIZE) -> AES_CTR {
+        unwrap_or_panic(AES_CTR::try_from_key(key, counter_size))
+    }
+
+    pub fn try_from_key(key: Vec<u8>, counter_size: AES_CTR_COUNTER_SIZE) -> Result<AES_CTR> {
+        let n_rounds = try_number_of_rounds(key.len())?;
+        let round_keys = expand_key(&key, n_rounds);
+
+        let number_of_key_bytes = key.len();
+        Ok(AES_CTR { keys: vec![key], primary_key: 0, backend: cipher::default_backend(), round_keys, n_rounds, number_of_key_bytes, counter_size, nonce: None })
+    }
+
+    // nonce used by AESinterface::encrypt
+    pub fn set_iv(&mut self, nonce: Vec<u8>) {
+        unwrap_or_panic(self.try_set_iv(nonce))
+    }
+
+    pub fn try_set_iv(&mut self, nonce: Vec<u8>) -> Result<()> {
+        if nonce.len() != self.nonce_length() {
+            return Err(Error::InvalidInput(format!("The nonce must be {} bytes long", self.nonce_length())));
+        }
+        self.nonce = Some(nonce);
+        Ok(())
+    }
+
+    pub fn refresh_key(&mut self) {
+        let key: Vec<u8> = random_byte_array(self.number_of_key_bytes);
+        self.keys.push(key);
+        self.primary_key += 1;
+        self.round_keys = expand_key(&self.keys[self.primary_key], self.n_rounds);
+    }
+
+    // 12 bytes with a 32-bit counter, 8 bytes with a 64-bit counter
+    pub fn nonce_length(&self) -> usize {
+        16 - self.counter_length()
+    }
+
+    fn counter_length(&self) -> usize {
+        match self.counter_size {
+            AES_CTR_COUNTER_SIZE::COUNTER_32 => 4,
+            AES_CTR_COUNTER_SIZE::COUNTER_64 => 8
+        }
+    }
+
+    // XORs data with the key stream starting at byte offset of the stream defined by nonce and initial counter.
+    // Encryption and decryption are the same operation.
+    pub fn apply_keystream(&self, nonce: &[u8], initial_counter: u64, offset: u64, data: &mut [u8]) {
+        unwrap_or_panic(self.try_apply_keystream(nonce, initial_counter, offset, data))
+    }
+
+    pub fn try_apply_keystream(&self, nonce: &[u8], initial_counter: u64, offset: u64, data: &mut [u8]) -> Result<()> {
+        if nonce.len() != self.nonce_length() {
+            return Err(Error::InvalidInput(format!("The nonce must be {} bytes long", self.nonce_length())));
+        }
+
+        // the counter must not wrap around: the last block needs initial_counter + last_block < 2^(8 * counter_length)
+        let end = offset.checked_add(data.len() as u64).ok_or_else(|| Error::InvalidInput("Offset too large".to_string()))?;
+        let counter_limit: u128 = 1u128 << (8 * self.counter_length());
+        if end > 0 && initial_counter as u128 + ((end - 1) / 16) as u128 >= counter_limit {
+            return Err(Error::InvalidInput("The counter space is exhausted, a new nonce is needed".to_string()));
+        }
+
+        // leading bytes up to the next block boundary
+        let first_block = offset / 16;
+        let skip = (offset % 16) as usize;
+        let head = data.len().min((16 - skip) % 16);
+        if head > 0 {
+            let key_stream = self.keystream_block(nonce, initial_counter + first_block);
+            inplace_byte_slice_xor(&mut data[..head], &key_stream[skip..skip + head]);
+        }
+
+        // then whole blocks, each encrypting its own counter block
+        let aligned_block = first_block + if skip == 0 { 0 } else { 1 };
+        data[head..].par_chunks_mut(16).enumerate().for_each(|(i, chunk)| {
+            let key_stream = self.keystream_block(nonce, initial_counter + aligned_block + i as u64);
+            inplace_byte_slice_xor(chunk, &key_stream[..chunk.len()]);
+        });
+        Ok(())
+    }
+
+    // stream positioned at the beginning, which remembers how many bytes have been processed
+    pub fn stream<'a>(&'a self, nonce: &[u8], initial_counter: u64) -> AES_CTR_STREAM<'a> {
+        unwrap_or_panic(self.try_stream(nonce, initial_counter))
+    }
+
+    pub fn try_stream<'a>(&'a self, nonce: &[u8], initial_counter: u64) -> Result<AES_CTR_STREAM<'a>> {
+        if nonce.len() != self.nonce_length() {
+            return Err(Error::InvalidInput(format!("The nonce must be {} bytes long", self.nonce_length())));
+        }
+        Ok(AES_CTR_STREAM { cipher: self, nonce: nonce.to_vec(), initial_counter, position: 0 })
+    }
+
+    fn keystream_block(&self, nonce: &[u8], counter: u64) -> Vec<u8> {
+        let mut counter_block: Vec<u8> = nonce.to_vec();
+        counter_block.extend_from_slice(&counter.to_be_bytes()[8 - self.counter_length()..]);
+        encrypt_with_backend(self.backend, &mut counter_block, &self.round_keys);
+        counter_block
+    }
+}
+
+pub struct AES_CTR_STREAM<'a> {
+    cipher: &'a AES_CTR,
+    nonce: Vec<u8>,
+    initial_counter: u64,
+    position: u64
+}
+
+impl AES_CTR_STREAM<'_> {
+    // encrypts or decrypts the next data.len() bytes of the stream
+    pub fn apply_keystream(&mut self, data: &mut [u8]) {
+        unwrap_or_panic(self.try_apply_keystream(data))
+    }
+
+    // the position only moves when the data has been processed
+    pub fn try_apply_keystream(&mut self, data: &mut [u8]) -> Result<()> {
+        self.cipher.try_apply_keystream(&self.nonce, self.initial_counter, self.position, data)?;
+        self.position += data.len() as u64;
+        Ok(())
+    }
+
+    pub fn seek(&mut self, offset: u64) {
+        self.position = offset;
+    }
+
+    pub fn get_position(&self) -> u64 {
+        self.position
+    }
+}
+
+fn inplace_byte_slice_xor(m1: &mut [u8], m2: &[u8]) {
+    for (byte, key_byte) in m1.iter_mut().zip(m2.iter()) {
+        *byte ^= key_byte;
+    }
+}
+// end AES_CTR without pre-processing
+
 
 pub struct AES_CBC {
     keys: Vec<Vec<u8>>, 
     primary_key: usize,
     padding: GenericPadding,
-    key_size: AES_KEY_SIZE,
+    round_keys: Vec<[u8; 16]>, // expanded once from the primary key
+    backend: AES_BACKEND,
     n_rounds: usize,
-    number_of_key_bytes: usize
+    number_of_key_bytes: usize,
+    iv: Option<Vec<u8>> // random for each encryption unless set by set_iv
 
 
 }
 
 impl AESinterface for AES_CBC {
-    fn encrypt(&mut self, plaintext: Vec<u8>) -> Vec<u8> {
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
         // random iv
-        let iv: Vec<u8> = random_byte_array(16);
+        let iv: Vec<u8> = self.iv.clone().unwrap_or_else(|| random_byte_array(16));
         
         // pad plaintext 
-        
-        let padded_plaintext: Vec<u8> = self.padding.pad(plaintext, 16);
+        let message_length = plaintext.len();
+        if self.padding.is_ciphertext_stealing() && message_length < 16 {
+            return Err(Error::InvalidInput("Ciphertext stealing needs at least one block of plaintext".to_string()));
+        }
+        let mut padded_plaintext: Vec<u8> = self.padding.pad(plaintext, 16);
+        // with ciphertext stealing the last partial block is completed with zeros
+        padded_plaintext.resize(padded_plaintext.len().div_ceil(16) * 16, 0);
 
         // dividing plaintext into blocks
         let mut blocks_padded_plaintext: Vec<Vec<u8>> = Vec::new();
@@ -296,7 +607,7 @@ impl AESinterface for AES_CBC {
         let mut current_iv = iv.clone();
         for r in 0..blocks {
             inplace_byte_array_xor(&mut blocks_padded_plaintext[r], &current_iv);
-            encrypt(&mut blocks_padded_plaintext[r], self.keys[self.primary_key].clone(), self.n_rounds, self.key_size.clone());
+            encrypt_with_backend(self.backend, &mut blocks_padded_plaintext[r], &self.round_keys);
             current_iv = blocks_padded_plaintext[r].clone();
         }
 
@@ -315,15 +626,80 @@ impl AESinterface for AES_CBC {
             }
         }
 
-        ciphertext_container
+        if self.padding.is_ciphertext_stealing() {
+            let blocks_ciphertext = ciphertext_container[16..].to_vec();
+            ciphertext_container.truncate(16);
+            ciphertext_container.extend(self.steal_ciphertext(blocks_ciphertext, message_length));
+        }
+
+        Ok(ciphertext_container)
 
 
         
     }
 
-    fn decrypt(&mut self, ciphertext: Vec<u8>) -> Vec<u8> {
+    // IV || ciphertext. A wrong length and a wrong padding give the same error, PaddingError::InvalidPadding,
+    // so that a padding oracle cannot tell them apart
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        self.decrypt_padded(ciphertext).map_err(|_| Error::Padding(PaddingError::InvalidPadding))
+    }
+
+    fn export_key(&self) -> Vec<u8> {
+        self.keys[self.primary_key].clone()
+    }
+
+    fn get_backend(&self) -> AES_BACKEND {
+        self.backend
+    }
+
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()> {
+        cipher::check_backend(backend)?;
+        self.backend = backend;
+        Ok(())
+    }
+}
+
+impl AES_CBC {
+    pub fn init(padding: Paddings, key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
+        Box::new(AES_CBC::from_key(padding, random_byte_array(key_length(&key_size))))
+    }
+
+    // the key size is given by the length of the key
+    pub fn from_key(padding: Paddings, key: Vec<u8>) -> AES_CBC {
+        unwrap_or_panic(AES_CBC::try_from_key(padding, key))
+    }
+
+    pub fn try_from_key(padding: Paddings, key: Vec<u8>) -> Result<AES_CBC> {
+        let n_rounds = try_number_of_rounds(key.len())?;
+        let round_keys = expand_key(&key, n_rounds);
+        let number_of_key_bytes = key.len();
+        Ok(AES_CBC { keys: vec![key], primary_key: 0, backend: cipher::default_backend(), padding: GenericPadding::init(padding), round_keys, n_rounds, number_of_key_bytes, iv: None })
+    }
+
+    pub fn set_iv(&mut self, iv: Vec<u8>) {
+        unwrap_or_panic(self.try_set_iv(iv))
+    }
+
+    pub fn try_set_iv(&mut self, iv: Vec<u8>) -> Result<()> {
+        if iv.len() != 16 {
+            return Err(Error::InvalidInput("The IV must be 16 bytes long".to_string()));
+        }
+        self.iv = Some(iv);
+        Ok(())
+    }
+
+    fn decrypt_padded(&self, ciphertext: Vec<u8>) -> Result<Vec<u8>, PaddingError> {
+        let stealing = self.padding.is_ciphertext_stealing();
+        if ciphertext.len() < 32 || (!stealing && !ciphertext.len().is_multiple_of(16)) {
+            return Err(PaddingError::InvalidPadding);
+        }
         let iv: Vec<u8> = ciphertext[0..16].to_vec();
-        let ciphertext_container: Vec<u8> = ciphertext[16..].to_vec();
+        let message_length = ciphertext.len() - 16;
+        let ciphertext_container: Vec<u8> = if stealing {
+            self.restore_stolen_ciphertext(ciphertext[16..].to_vec())
+        } else {
+            ciphertext[16..].to_vec()
+        };
         
         // dividing ciphertext into blocks
         let mut blocks_ciphertext: Vec<Vec<u8>> = Vec::new();
@@ -339,7 +715,7 @@ impl AESinterface for AES_CBC {
         let mut current_iv = iv.clone();
         for r in 0..blocks {
             let temp_iv: Vec<u8> = blocks_ciphertext[r].clone();
-            decrypt(&mut blocks_ciphertext[r], self.keys[self.primary_key].clone(), self.n_rounds, self.key_size.clone());
+            decrypt_with_backend(self.backend, &mut blocks_ciphertext[r], &self.round_keys);
             inplace_byte_array_xor(&mut blocks_ciphertext[r], &current_iv);
             current_iv = temp_iv;
         }
@@ -347,42 +723,75 @@ impl AESinterface for AES_CBC {
 
         // put encrypted blocks into one vector
         
-        self.padding.unpad(blocks_ciphertext.concat(), true)
+        let mut padded_plaintext = blocks_ciphertext.concat();
+        padded_plaintext.truncate(message_length);
+        self.padding.unpad(padded_plaintext, 16).map_err(|_| PaddingError::InvalidPadding)
 
 
 
     }
-}
 
-impl AES_CBC {
-    pub fn init(padding: Paddings, key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
-        
+    // CBC-CS1 keeps the d leftmost bytes of the second to last block C_(n-1) before the last block C_n;
+    // CBC-CS3 always swaps them, CBC-CS2 only when the last block is partial
+    fn swaps_last_blocks(&self, message_length: usize) -> bool {
+        match self.padding.get_padding_method() {
+            Paddings::CBC_CS3 => true,
+            Paddings::CBC_CS2 => !message_length.is_multiple_of(16),
+            _ => false
+        }
+    }
 
-        let n_rounds: usize = match key_size {
-            AES_KEY_SIZE::AES_128 => 10,
-            AES_KEY_SIZE::AES_192 => 12,
-            AES_KEY_SIZE::AES_256 => 14,
-            _ => 10
-        };
+    // full CBC ciphertext of the zero-padded message -> ciphertext as long as the message
+    fn steal_ciphertext(&self, ciphertext: Vec<u8>, message_length: usize) -> Vec<u8> {
+        let blocks = message_length.div_ceil(16);
+        if blocks < 2 {
+            return ciphertext;
+        }
+        let last_block_length = message_length - 16 * (blocks - 1);
+        let second_to_last = 16 * (blocks - 2);
+        let mut stolen: Vec<u8> = ciphertext[0..second_to_last].to_vec();
+        let partial_block = &ciphertext[second_to_last..second_to_last + last_block_length];
+        let last_block = &ciphertext[second_to_last + 16..second_to_last + 32];
+        if self.swaps_last_blocks(message_length) {
+            stolen.extend_from_slice(last_block);
+            stolen.extend_from_slice(partial_block);
+        } else {
+            stolen.extend_from_slice(partial_block);
+            stolen.extend_from_slice(last_block);
+        }
+        stolen
+    }
 
-        let number_of_key_bytes: usize = match key_size {
-            AES_KEY_SIZE::AES_128 => 16,
-            AES_KEY_SIZE::AES_192 => 24,
-            AES_KEY_SIZE::AES_256 => 32,
-            _ => 16
+    // inverse of steal_ciphertext: decrypting C_n gives P_n xor C_(n-1), whose rightmost bytes are
+    // the missing bytes of C_(n-1) since P_n ends with zeros
+    fn restore_stolen_ciphertext(&self, ciphertext: Vec<u8>) -> Vec<u8> {
+        let message_length = ciphertext.len();
+        let blocks = message_length.div_ceil(16);
+        if blocks < 2 {
+            return ciphertext;
+        }
+        let last_block_length = message_length - 16 * (blocks - 1);
+        let second_to_last = 16 * (blocks - 2);
+        let (partial_block, last_block) = if self.swaps_last_blocks(message_length) {
+            (ciphertext[second_to_last + 16..].to_vec(), ciphertext[second_to_last..second_to_last + 16].to_vec())
+        } else {
+            (ciphertext[second_to_last..second_to_last + last_block_length].to_vec(), ciphertext[second_to_last + last_block_length..].to_vec())
         };
 
-        let key: Vec<u8> = random_byte_array(number_of_key_bytes);
-        let mut keys: Vec<Vec<u8>> = Vec::new();
-        keys.push(key);
-
-        Box::new(AES_CBC { keys: keys, primary_key: 0, padding: GenericPadding::init(padding), key_size: key_size, n_rounds: n_rounds, number_of_key_bytes: number_of_key_bytes})
+        let mut decrypted_last_block = last_block.clone();
+        decrypt_with_backend(self.backend, &mut decrypted_last_block, &self.round_keys);
+        let mut restored: Vec<u8> = ciphertext[0..second_to_last].to_vec();
+        restored.extend_from_slice(&partial_block);
+        restored.extend_from_slice(&decrypted_last_block[last_block_length..]);
+        restored.extend_from_slice(&last_block);
+        restored
     }
 
     pub fn refresh_key(&mut self) {
         let key: Vec<u8> = random_byte_array(self.number_of_key_bytes);
         self.keys.push(key);
         self.primary_key = self.primary_key + 1;
+        self.round_keys = expand_key(&self.keys[self.primary_key], self.n_rounds);
     }
 }
 //end AES_CBC
@@ -391,7 +800,8 @@ pub struct AES_ECB {
     keys: Vec<Vec<u8>>, 
     primary_key: usize,
     padding: GenericPadding,
-    key_size: AES_KEY_SIZE,
+    round_keys: Vec<[u8; 16]>, // expanded once from the primary key
+    backend: AES_BACKEND,
     n_rounds: usize,
     number_of_key_bytes: usize
 
@@ -399,7 +809,7 @@ pub struct AES_ECB {
 }
 
 impl AESinterface for AES_ECB {
-    fn encrypt(&mut self, plaintext: Vec<u8>) -> Vec<u8> {
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
         // compute for how many block we should encrypt
         let mut padded_plaintext_blocks: Vec<Vec<u8>> = Vec::new();
         let padded_plaintext: Vec<u8> = self.padding.pad(plaintext, 16);
@@ -413,12 +823,15 @@ impl AESinterface for AES_ECB {
             padded_plaintext_blocks.push(temp_vector);
         }
 
-        padded_plaintext_blocks.par_iter_mut().for_each(|x| encrypt(x, self.keys[self.primary_key].clone(), self.n_rounds, self.key_size.clone()));
+        padded_plaintext_blocks.par_iter_mut().for_each(|x| encrypt_with_backend(self.backend, x, &self.round_keys));
 
-        padded_plaintext_blocks.concat()
+        Ok(padded_plaintext_blocks.concat())
     }
 
-    fn decrypt(&mut self, ciphertext: Vec<u8>) -> Vec<u8> {
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(16) {
+            return Err(Error::Padding(PaddingError::InvalidPadding));
+        }
         //dividing ciphertexts in blocks
         let mut ciphertext_container: Vec<Vec<u8>> = Vec::new();
         let blocks = ciphertext.len() / 16;
@@ -430,8 +843,22 @@ impl AESinterface for AES_ECB {
             ciphertext_container.push(temp_vector);
         }
 
-        ciphertext_container.par_iter_mut().for_each(|x| decrypt(x, self.keys[self.primary_key].clone(), self.n_rounds, self.key_size.clone()));
-        self.padding.unpad(ciphertext_container.concat(), true)
+        ciphertext_container.par_iter_mut().for_each(|x| decrypt_with_backend(self.backend, x, &self.round_keys));
+        self.padding.unpad(ciphertext_container.concat(), 16).map_err(|_| Error::Padding(PaddingError::InvalidPadding))
+    }
+
+    fn export_key(&self) -> Vec<u8> {
+        self.keys[self.primary_key].clone()
+    }
+
+    fn get_backend(&self) -> AES_BACKEND {
+        self.backend
+    }
+
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()> {
+        cipher::check_backend(backend)?;
+        self.backend = backend;
+        Ok(())
     }
 }
 
@@ -439,77 +866,672 @@ impl AESinterface for AES_ECB {
 // that implementation is parallelized
 impl AES_ECB {
     pub fn init(padding: Paddings, key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
-        
-
-        let n_rounds: usize = match key_size {
-            AES_KEY_SIZE::AES_128 => 10,
-            AES_KEY_SIZE::AES_192 => 12,
-            AES_KEY_SIZE::AES_256 => 14,
-            _ => 10
-        };
+        Box::new(AES_ECB::from_key(padding, random_byte_array(key_length(&key_size))))
+    }
 
-        let number_of_key_bytes: usize = match key_size {
-            AES_KEY_SIZE::AES_128 => 16,
-            AES_KEY_SIZE::AES_192 => 24,
-            AES_KEY_SIZE::AES_256 => 32,
-            _ => 16
-        };
+    // the key size is given by the length of the key
+    pub fn from_key(padding: Paddings, key: Vec<u8>) -> AES_ECB {
+        unwrap_or_panic(AES_ECB::try_from_key(padding, key))
+    }
 
-        let key: Vec<u8> = random_byte_array(number_of_key_bytes);
-        let mut keys: Vec<Vec<u8>> = Vec::new();
-        keys.push(key);
-        
-        Box::new(AES_ECB { keys: keys, primary_key: 0, padding: GenericPadding::init(padding), key_size: key_size, n_rounds: n_rounds, number_of_key_bytes: number_of_key_bytes})
+    pub fn try_from_key(padding: Paddings, key: Vec<u8>) -> Result<AES_ECB> {
+        if GenericPadding::init(padding).is_ciphertext_stealing() {
+            return Err(Error::InvalidInput("Ciphertext stealing is only supported by CBC".to_string()));
+        }
+        let n_rounds = try_number_of_rounds(key.len())?;
+        let round_keys = expand_key(&key, n_rounds);
+        let number_of_key_bytes = key.len();
+        Ok(AES_ECB { keys: vec![key], primary_key: 0, backend: cipher::default_backend(), padding: GenericPadding::init(padding), round_keys, n_rounds, number_of_key_bytes })
     }
 
     pub fn refresh_key(&mut self) {
         let key: Vec<u8> = random_byte_array(self.number_of_key_bytes);
         self.keys.push(key);
         self.primary_key = self.primary_key + 1;
+        self.round_keys = expand_key(&self.keys[self.primary_key], self.n_rounds);
     }
 }
 // end AES ECB
 
-// pub struct AES_GCM {
-//     keys: Vec<Vec<u8>>, 
-//     primary_key: usize
-// }
+// AES_GCM (NIST SP 800-38D): counter mode encryption authenticated by GHASH.
+// 96-bit IVs are used directly as counter block, IVs of any other length are hashed with GHASH.
+pub struct AES_GCM {
+    keys: Vec<Vec<u8>>, 
+    primary_key: usize,
+    round_keys: Vec<[u8; 16]>, // expanded once from the primary key
+    backend: AES_BACKEND,
+    n_rounds: usize,
+    number_of_key_bytes: usize,
+    tag_length: usize,
+    iv: Option<Vec<u8>> // random for each encryption unless set by set_iv
+}
 
-// impl AESinterface for AES_GCM {
-//     fn encrypt(&mut self, plaintext: Vec<u8>) -> Vec<u8> {
-        
-//     }
+// through AESinterface a random 96-bit IV is generated and the output is IV || ciphertext || tag
+impl AESinterface for AES_GCM {
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
+        let iv: Vec<u8> = self.iv.clone().unwrap_or_else(|| random_byte_array(12));
+        let (ciphertext, tag) = AEADinterface::encrypt(self, iv.clone(), plaintext, Vec::new())?;
+        Ok([iv, ciphertext, tag].concat())
+    }
 
-//     fn decrypt(&mut self, ciphertext: Vec<u8>) -> Vec<u8> {
-        
-//     }
-// }
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        if ciphertext.len() < 12 + self.tag_length {
+            return Err(Error::InvalidInput("The ciphertext is too short".to_string()));
+        }
+        let iv: Vec<u8> = ciphertext[0..12].to_vec();
+        let tag: Vec<u8> = ciphertext[ciphertext.len() - self.tag_length..].to_vec();
+        let ciphertext_container: Vec<u8> = ciphertext[12..ciphertext.len() - self.tag_length].to_vec();
+        Ok(AEADinterface::decrypt(self, iv, ciphertext_container, Vec::new(), tag)?)
+    }
 
-// impl AES_GCM {
-//     fn init() {}
-// }
-// // end AES_GCM
+    fn export_key(&self) -> Vec<u8> {
+        self.keys[self.primary_key].clone()
+    }
 
-// pub struct AES_OFB {
-//     keys: Vec<Vec<u8>>, 
-//     primary_key: usize
-// }
+    fn get_backend(&self) -> AES_BACKEND {
+        self.backend
+    }
 
-// impl AESinterface for AES_OFB {
-//     fn encrypt(&mut self, plaintext: Vec<u8>) -> Vec<u8> {
-        
-//     }
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()> {
+        cipher::check_backend(backend)?;
+        self.backend = backend;
+        Ok(())
+    }
+}
 
-//     fn decrypt(&mut self, ciphertext: Vec<u8>) -> Vec<u8> {
-        
-//     }
-// }
+impl AEADinterface for AES_GCM {
+    fn encrypt(&self, nonce: Vec<u8>, plaintext: Vec<u8>, associated_data: Vec<u8>) -> Result<(Vec<u8>, Vec<u8>), AEADError> {
+        AES_GCM::check_lengths(&nonce, plaintext.len())?;
 
-// impl AES_OFB {
-//     fn init() {}
-// }
-// // end AES OFB
+        let hash_subkey = self.hash_subkey();
+        let j0 = self.pre_counter_block(hash_subkey, &nonce);
+        let ciphertext = self.gctr(inc32(j0), &plaintext);
+        let tag = self.compute_tag(hash_subkey, j0, &associated_data, &ciphertext);
+        Ok((ciphertext, tag))
+    }
 
+    fn decrypt(&self, nonce: Vec<u8>, ciphertext: Vec<u8>, associated_data: Vec<u8>, tag: Vec<u8>) -> Result<Vec<u8>, AEADError> {
+        AES_GCM::check_lengths(&nonce, ciphertext.len())?;
+        if tag.len() != self.tag_length {
+            return Err(AEADError::InvalidTag);
+        }
 
+        // the tag is verified before decrypting, comparing all the bytes
+        let hash_subkey = self.hash_subkey();
+        let j0 = self.pre_counter_block(hash_subkey, &nonce);
+        let expected_tag = self.compute_tag(hash_subkey, j0, &associated_data, &ciphertext);
+        let difference = expected_tag.iter().zip(tag.iter()).fold(0u8, |difference, (a, b)| difference | (a ^ b));
+        if difference != 0 {
+            return Err(AEADError::AuthenticationFailed);
+        }
 
+        Ok(self.gctr(inc32(j0), &ciphertext))
+    }
+}
+
+impl AES_GCM {
+    pub fn init(key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
+        Box::new(AES_GCM::new(key_size))
+    }
+
+    // random key
+    pub fn new(key_size: AES_KEY_SIZE) -> AES_GCM {
+        let number_of_key_bytes: usize = key_length(&key_size);
+        AES_GCM::from_key(random_byte_array(number_of_key_bytes))
+    }
 
+    // the key size is given by the length of the key
+    pub fn from_key(key: Vec<u8>) -> AES_GCM {
+        unwrap_or_panic(AES_GCM::try_from_key(key))
+    }
+
+    pub fn try_from_key(key: Vec<u8>) -> Result<AES_GCM> {
+        let n_rounds = try_number_of_rounds(key.len())?;
+        let round_keys = expand_key(&key, n_rounds);
+
+        let number_of_key_bytes = key.len();
+        Ok(AES_GCM { keys: vec![key], primary_key: 0, backend: cipher::default_backend(), round_keys, n_rounds, number_of_key_bytes, tag_length: 16, iv: None })
+    }
+
+    // 96-bit IV used by AESinterface::encrypt, which prepends 12 bytes of IV to the ciphertext
+    pub fn set_iv(&mut self, iv: Vec<u8>) {
+        unwrap_or_panic(self.try_set_iv(iv))
+    }
+
+    pub fn try_set_iv(&mut self, iv: Vec<u8>) -> Result<()> {
+        if iv.len() != 12 {
+            return Err(Error::InvalidInput("The IV must be 12 bytes long".to_string()));
+        }
+        self.iv = Some(iv);
+        Ok(())
+    }
+
+    // truncated tags allowed by SP 800-38D
+    pub fn set_tag_length(&mut self, tag_length: usize) {
+        unwrap_or_panic(self.try_set_tag_length(tag_length))
+    }
+
+    pub fn try_set_tag_length(&mut self, tag_length: usize) -> Result<()> {
+        if ![4, 8, 12, 13, 14, 15, 16].contains(&tag_length) {
+            return Err(Error::InvalidInput("Tag length not supported".to_string()));
+        }
+        self.tag_length = tag_length;
+        Ok(())
+    }
+
+    pub fn refresh_key(&mut self) {
+        let key: Vec<u8> = random_byte_array(self.number_of_key_bytes);
+        self.keys.push(key);
+        self.primary_key += 1;
+        self.round_keys = expand_key(&self.keys[self.primary_key], self.n_rounds);
+    }
+
+    fn check_lengths(nonce: &[u8], text_length: usize) -> Result<(), AEADError> {
+        if nonce.is_empty() {
+            return Err(AEADError::InvalidNonce);
+        }
+        // at most 2^32 - 2 blocks, so that the 32-bit counter does not wrap
+        if text_length as u64 > (1u64 << 36) - 32 {
+            return Err(AEADError::MessageTooLong);
+        }
+        Ok(())
+    }
+
+    fn encrypt_block(&self, block: u128) -> u128 {
+        let mut state: Vec<u8> = block.to_be_bytes().to_vec();
+        encrypt_with_backend(self.backend, &mut state, &self.round_keys);
+        u128::from_be_bytes(state.try_into().unwrap())
+    }
+
+    // H = E(K, 0^128)
+    fn hash_subkey(&self) -> u128 {
+        self.encrypt_block(0)
+    }
+
+    fn pre_counter_block(&self, hash_subkey: u128, iv: &[u8]) -> u128 {
+        if iv.len() == 12 {
+            let mut block: Vec<u8> = iv.to_vec();
+            block.extend_from_slice(&1u32.to_be_bytes());
+            u128::from_be_bytes(block.try_into().unwrap())
+        } else {
+            ghash(hash_subkey, &[], iv)
+        }
+    }
+
+    // counter blocks are encrypted in parallel
+    fn gctr(&self, initial_counter_block: u128, data: &[u8]) -> Vec<u8> {
+        let mut counter_blocks: Vec<Vec<u8>> = Vec::new();
+        let mut counter_block = initial_counter_block;
+        for _i in 0..data.len().div_ceil(16) {
+            counter_blocks.push(counter_block.to_be_bytes().to_vec());
+            counter_block = inc32(counter_block);
+        }
+
+        counter_blocks.par_iter_mut().for_each(|x| encrypt_with_backend(self.backend, x, &self.round_keys));
+
+        data.iter().zip(counter_blocks.concat()).map(|(byte, key_byte)| byte ^ key_byte).collect()
+    }
+
+    fn compute_tag(&self, hash_subkey: u128, j0: u128, associated_data: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let s = ghash(hash_subkey, associated_data, ciphertext);
+        let mut tag = (self.encrypt_block(j0) ^ s).to_be_bytes().to_vec();
+        tag.truncate(self.tag_length);
+        tag
+    }
+}
+
+// increments the rightmost 32 bits of the counter block modulo 2^32
+fn inc32(block: u128) -> u128 {
+    let counter = (block as u32).wrapping_add(1);
+    (block & !(u32::MAX as u128)) | counter as u128
+}
+
+// multiplication in GF(2^128) with the bit ordering of GCM, without branches on secret data
+fn gf128_mul(x: u128, y: u128) -> u128 {
+    const R: u128 = 0xe1 << 120;
+    let mut z: u128 = 0;
+    let mut v: u128 = y;
+    for i in 0..128 {
+        let bit = (x >> (127 - i)) & 1;
+        z ^= v & bit.wrapping_neg();
+        let lsb = v & 1;
+        v = (v >> 1) ^ (R & lsb.wrapping_neg());
+    }
+    z
+}
+
+// GHASH of A || 0^v || C || 0^u || [len(A)]_64 || [len(C)]_64
+fn ghash(hash_subkey: u128, associated_data: &[u8], ciphertext: &[u8]) -> u128 {
+    let mut y: u128 = 0;
+    for data in [associated_data, ciphertext] {
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            y = gf128_mul(y ^ u128::from_be_bytes(block), hash_subkey);
+        }
+    }
+    let lengths: u128 = ((associated_data.len() as u128 * 8) << 64) | (ciphertext.len() as u128 * 8);
+    gf128_mul(y ^ lengths, hash_subkey)
+}
+// end AES_GCM
+
+// AES_OFB (NIST SP 800-38A): the key stream is obtained by encrypting the IV over and over,
+// encryption and decryption are the same operation
+pub struct AES_OFB {
+    keys: Vec<Vec<u8>>, 
+    primary_key: usize,
+    round_keys: Vec<[u8; 16]>, // expanded once from the primary key
+    backend: AES_BACKEND,
+    n_rounds: usize,
+    number_of_key_bytes: usize,
+    iv: Option<Vec<u8>> // random for each encryption unless set by set_iv
+}
+
+// through AESinterface a random IV is generated and the output is IV || ciphertext
+impl AESinterface for AES_OFB {
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
+        let iv: Vec<u8> = self.iv.clone().unwrap_or_else(|| random_byte_array(16));
+        let ciphertext = self.try_apply_keystream(&iv, &plaintext)?;
+        Ok([iv, ciphertext].concat())
+    }
+
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        if ciphertext.len() < 16 {
+            return Err(Error::InvalidInput("The ciphertext is too short".to_string()));
+        }
+        self.try_apply_keystream(&ciphertext[0..16], &ciphertext[16..])
+    }
+
+    fn export_key(&self) -> Vec<u8> {
+        self.keys[self.primary_key].clone()
+    }
+
+    fn get_backend(&self) -> AES_BACKEND {
+        self.backend
+    }
+
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()> {
+        cipher::check_backend(backend)?;
+        self.backend = backend;
+        Ok(())
+    }
+}
+
+impl AES_OFB {
+    pub fn init(key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
+        Box::new(AES_OFB::new(key_size))
+    }
+
+    // random key
+    pub fn new(key_size: AES_KEY_SIZE) -> AES_OFB {
+        AES_OFB::from_key(random_byte_array(key_length(&key_size)))
+    }
+
+    // the key size is given by the length of the key
+    pub fn from_key(key: Vec<u8>) -> AES_OFB {
+        unwrap_or_panic(AES_OFB::try_from_key(key))
+    }
+
+    pub fn try_from_key(key: Vec<u8>) -> Result<AES_OFB> {
+        let n_rounds = try_number_of_rounds(key.len())?;
+        let round_keys = expand_key(&key, n_rounds);
+        let number_of_key_bytes = key.len();
+        Ok(AES_OFB { keys: vec![key], primary_key: 0, backend: cipher::default_backend(), round_keys, n_rounds, number_of_key_bytes, iv: None })
+    }
+
+    // IV used by AESinterface::encrypt
+    pub fn set_iv(&mut self, iv: Vec<u8>) {
+        unwrap_or_panic(self.try_set_iv(iv))
+    }
+
+    pub fn try_set_iv(&mut self, iv: Vec<u8>) -> Result<()> {
+        if iv.len() != 16 {
+            return Err(Error::InvalidInput("The IV must be 16 bytes long".to_string()));
+        }
+        self.iv = Some(iv);
+        Ok(())
+    }
+
+    pub fn refresh_key(&mut self) {
+        let key: Vec<u8> = random_byte_array(self.number_of_key_bytes);
+        self.keys.push(key);
+        self.primary_key += 1;
+        self.round_keys = expand_key(&self.keys[self.primary_key], self.n_rounds);
+    }
+
+    // the IV must be unique for each message encrypted with the same key
+    pub fn apply_keystream(&self, iv: &[u8], data: &[u8]) -> Vec<u8> {
+        unwrap_or_panic(self.try_apply_keystream(iv, data))
+    }
+
+    pub fn try_apply_keystream(&self, iv: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+        if iv.len() != 16 {
+            return Err(Error::InvalidInput("The IV must be 16 bytes long".to_string()));
+        }
+
+        let mut output_block: Vec<u8> = iv.to_vec();
+        let mut result: Vec<u8> = Vec::with_capacity(data.len());
+        for chunk in data.chunks(16) {
+            encrypt_with_backend(self.backend, &mut output_block, &self.round_keys);
+            result.extend(chunk.iter().zip(output_block.iter()).map(|(byte, key_byte)| byte ^ key_byte));
+        }
+        Ok(result)
+    }
+}
+// end AES OFB
+
+// AES_CFB (NIST SP 800-38A) with 8-bit or 128-bit segments. The last segment of CFB-128 may be partial.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AES_CFB_SEGMENT_SIZE {
+    CFB_8,
+    CFB_128
+}
+
+pub struct AES_CFB {
+    keys: Vec<Vec<u8>>, 
+    primary_key: usize,
+    round_keys: Vec<[u8; 16]>, // expanded once from the primary key
+    backend: AES_BACKEND,
+    n_rounds: usize,
+    number_of_key_bytes: usize,
+    segment_size: AES_CFB_SEGMENT_SIZE,
+    iv: Option<Vec<u8>> // random for each encryption unless set by set_iv
+}
+
+// through AESinterface a random IV is generated and the output is IV || ciphertext
+impl AESinterface for AES_CFB {
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
+        let iv: Vec<u8> = self.iv.clone().unwrap_or_else(|| random_byte_array(16));
+        let ciphertext = self.try_encrypt_with_iv(&iv, &plaintext)?;
+        Ok([iv, ciphertext].concat())
+    }
+
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        if ciphertext.len() < 16 {
+            return Err(Error::InvalidInput("The ciphertext is too short".to_string()));
+        }
+        self.try_decrypt_with_iv(&ciphertext[0..16], &ciphertext[16..])
+    }
+
+    fn export_key(&self) -> Vec<u8> {
+        self.keys[self.primary_key].clone()
+    }
+
+    fn get_backend(&self) -> AES_BACKEND {
+        self.backend
+    }
+
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()> {
+        cipher::check_backend(backend)?;
+        self.backend = backend;
+        Ok(())
+    }
+}
+
+impl AES_CFB {
+    pub fn init(key_size: AES_KEY_SIZE, segment_size: AES_CFB_SEGMENT_SIZE) -> Box<dyn AESinterface> {
+        Box::new(AES_CFB::new(key_size, segment_size))
+    }
+
+    // random key
+    pub fn new(key_size: AES_KEY_SIZE, segment_size: AES_CFB_SEGMENT_SIZE) -> AES_CFB {
+        AES_CFB::from_key(random_byte_array(key_length(&key_size)), segment_size)
+    }
+
+    // the key size is given by the length of the key
+    pub fn from_key(key: Vec<u8>, segment_size: AES_CFB_SEGMENT_SIZE) -> AES_CFB {
+        unwrap_or_panic(AES_CFB::try_from_key(key, segment_size))
+    }
+
+    pub fn try_from_key(key: Vec<u8>, segment_size: AES_CFB_SEGMENT_SIZE) -> Result<AES_CFB> {
+        let n_rounds = try_number_of_rounds(key.len())?;
+        let round_keys = expand_key(&key, n_rounds);
+        let number_of_key_bytes = key.len();
+        Ok(AES_CFB { keys: vec![key], primary_key: 0, backend: cipher::default_backend(), round_keys, n_rounds, number_of_key_bytes, segment_size, iv: None })
+    }
+
+    // IV used by AESinterface::encrypt
+    pub fn set_iv(&mut self, iv: Vec<u8>) {
+        unwrap_or_panic(self.try_set_iv(iv))
+    }
+
+    pub fn try_set_iv(&mut self, iv: Vec<u8>) -> Result<()> {
+        if iv.len() != 16 {
+            return Err(Error::InvalidInput("The IV must be 16 bytes long".to_string()));
+        }
+        self.iv = Some(iv);
+        Ok(())
+    }
+
+    pub fn refresh_key(&mut self) {
+        let key: Vec<u8> = random_byte_array(self.number_of_key_bytes);
+        self.keys.push(key);
+        self.primary_key += 1;
+        self.round_keys = expand_key(&self.keys[self.primary_key], self.n_rounds);
+    }
+
+    pub fn encrypt_with_iv(&self, iv: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        unwrap_or_panic(self.try_encrypt_with_iv(iv, plaintext))
+    }
+
+    pub fn try_encrypt_with_iv(&self, iv: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        self.process(iv, plaintext, true)
+    }
+
+    pub fn decrypt_with_iv(&self, iv: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        unwrap_or_panic(self.try_decrypt_with_iv(iv, ciphertext))
+    }
+
+    pub fn try_decrypt_with_iv(&self, iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        self.process(iv, ciphertext, false)
+    }
+
+    // the shift register is fed with the ciphertext segments
+    fn process(&self, iv: &[u8], data: &[u8], encryption: bool) -> Result<Vec<u8>> {
+        if iv.len() != 16 {
+            return Err(Error::InvalidInput("The IV must be 16 bytes long".to_string()));
+        }
+        let segment_length = match self.segment_size {
+            AES_CFB_SEGMENT_SIZE::CFB_8 => 1,
+            AES_CFB_SEGMENT_SIZE::CFB_128 => 16
+        };
+
+        let mut shift_register: Vec<u8> = iv.to_vec();
+        let mut result: Vec<u8> = Vec::with_capacity(data.len());
+        for segment in data.chunks(segment_length) {
+            let mut output_block: Vec<u8> = shift_register.clone();
+            encrypt_with_backend(self.backend, &mut output_block, &self.round_keys);
+            let output_segment: Vec<u8> = segment.iter().zip(output_block.iter()).map(|(byte, key_byte)| byte ^ key_byte).collect();
+            let ciphertext_segment: &[u8] = if encryption { &output_segment } else { segment };
+            shift_register.drain(..ciphertext_segment.len());
+            shift_register.extend_from_slice(ciphertext_segment);
+            result.extend(output_segment);
+        }
+        Ok(result)
+    }
+}
+// end AES CFB
+
+// XTS-AES (IEEE 1619, NIST SP 800-38E) for the encryption of data units such as disk sectors.
+// The key is the concatenation of two AES keys (32 bytes for XTS-AES-128, 64 bytes for XTS-AES-256):
+// the first one encrypts the data, the second one the tweak, i.e. the data unit number.
+// Data units which are not a multiple of 16 bytes are handled with ciphertext stealing.
+pub struct AES_XTS {
+    keys: Vec<Vec<u8>>, 
+    primary_key: usize,
+    round_keys: Vec<[u8; 16]>, // of key1, expanded once from the primary key
+    backend: AES_BACKEND,
+    tweak_round_keys: Vec<[u8; 16]>, // of key2
+    n_rounds: usize,
+    number_of_key_bytes: usize,
+    data_unit: u128
+}
+
+// through AESinterface the current data unit number is used as tweak, see set_data_unit
+impl AESinterface for AES_XTS {
+    fn try_encrypt(&mut self, plaintext: Vec<u8>) -> Result<Vec<u8>> {
+        self.process(self.data_unit, &plaintext, true)
+    }
+
+    fn try_decrypt(&mut self, ciphertext: Vec<u8>) -> Result<Vec<u8>> {
+        self.process(self.data_unit, &ciphertext, false)
+    }
+
+    // key1 || key2
+    fn export_key(&self) -> Vec<u8> {
+        self.keys[self.primary_key].clone()
+    }
+
+    fn get_backend(&self) -> AES_BACKEND {
+        self.backend
+    }
+
+    fn try_set_backend(&mut self, backend: AES_BACKEND) -> Result<()> {
+        cipher::check_backend(backend)?;
+        self.backend = backend;
+        Ok(())
+    }
+}
+
+impl AES_XTS {
+    pub fn init(key_size: AES_KEY_SIZE) -> Box<dyn AESinterface> {
+        unwrap_or_panic(AES_XTS::try_init(key_size))
+    }
+
+    pub fn try_init(key_size: AES_KEY_SIZE) -> Result<Box<dyn AESinterface>> {
+        Ok(Box::new(AES_XTS::try_new(key_size)?))
+    }
+
+    // random keys
+    pub fn new(key_size: AES_KEY_SIZE) -> AES_XTS {
+        unwrap_or_panic(AES_XTS::try_new(key_size))
+    }
+
+    pub fn try_new(key_size: AES_KEY_SIZE) -> Result<AES_XTS> {
+        if let AES_KEY_SIZE::AES_192 = key_size {
+            return Err(Error::InvalidKey("XTS-AES is defined for AES-128 and AES-256 only".to_string()));
+        }
+        AES_XTS::try_from_key(random_byte_array(2 * key_length(&key_size)))
+    }
+
+    // key1 || key2, which must differ
+    pub fn from_key(key: Vec<u8>) -> AES_XTS {
+        unwrap_or_panic(AES_XTS::try_from_key(key))
+    }
+
+    pub fn try_from_key(key: Vec<u8>) -> Result<AES_XTS> {
+        if key.len() != 32 && key.len() != 64 {
+            return Err(Error::InvalidKey("XTS-AES keys are 32 or 64 bytes long".to_string()));
+        }
+        let n_rounds = try_number_of_rounds(key.len() / 2)?;
+        let (key1, key2) = key.split_at(key.len() / 2);
+        if key1 == key2 {
+            return Err(Error::InvalidKey("The two halves of an XTS-AES key must be different".to_string()));
+        }
+        let (round_keys, tweak_round_keys) = (expand_key(key1, n_rounds), expand_key(key2, n_rounds));
+
+        let number_of_key_bytes = key.len();
+        Ok(AES_XTS { keys: vec![key], primary_key: 0, backend: cipher::default_backend(), round_keys, tweak_round_keys, n_rounds, number_of_key_bytes, data_unit: 0 })
+    }
+
+    pub fn refresh_key(&mut self) {
+        let mut key: Vec<u8> = random_byte_array(self.number_of_key_bytes);
+        while key[..self.number_of_key_bytes / 2] == key[self.number_of_key_bytes / 2..] {
+            key = random_byte_array(self.number_of_key_bytes);
+        }
+        let (key1, key2) = key.split_at(self.number_of_key_bytes / 2);
+        self.round_keys = expand_key(key1, self.n_rounds);
+        self.tweak_round_keys = expand_key(key2, self.n_rounds);
+        self.keys.push(key);
+        self.primary_key += 1;
+    }
+
+    pub fn set_data_unit(&mut self, data_unit: u128) {
+        self.data_unit = data_unit;
+    }
+
+    // the tweak as 16 bytes, i.e. the data unit number in little endian
+    pub fn set_iv(&mut self, iv: Vec<u8>) {
+        unwrap_or_panic(self.try_set_iv(iv))
+    }
+
+    pub fn try_set_iv(&mut self, iv: Vec<u8>) -> Result<()> {
+        if iv.len() != 16 {
+            return Err(Error::InvalidInput("The tweak must be 16 bytes long".to_string()));
+        }
+        self.data_unit = u128::from_le_bytes(iv.try_into().unwrap());
+        Ok(())
+    }
+
+    pub fn encrypt_data_unit(&self, data_unit: u128, plaintext: &[u8]) -> Vec<u8> {
+        unwrap_or_panic(self.process(data_unit, plaintext, true))
+    }
+
+    pub fn decrypt_data_unit(&self, data_unit: u128, ciphertext: &[u8]) -> Vec<u8> {
+        unwrap_or_panic(self.process(data_unit, ciphertext, false))
+    }
+
+    // C = E(K1, P xor T) xor T, or the inverse
+    fn process_block(&self, block: &mut Vec<u8>, tweak: &[u8], encryption: bool) {
+        inplace_byte_slice_xor(block, tweak);
+        if encryption {
+            encrypt_with_backend(self.backend, block, &self.round_keys);
+        } else {
+            decrypt_with_backend(self.backend, block, &self.round_keys);
+        }
+        inplace_byte_slice_xor(block, tweak);
+    }
+
+    fn process(&self, data_unit: u128, data: &[u8], encryption: bool) -> Result<Vec<u8>> {
+        if data.len() < 16 {
+            return Err(Error::InvalidInput("XTS-AES data units are at least 16 bytes long".to_string()));
+        }
+        if data.len() > 16 << 20 {
+            return Err(Error::InvalidInput("XTS-AES data units are at most 2^20 blocks long".to_string()));
+        }
+
+        // tweak of block j: E(K2, data unit number) multiplied by alpha^j
+        let blocks = data.len().div_ceil(16);
+        let mut tweaks: Vec<Vec<u8>> = Vec::with_capacity(blocks);
+        let mut tweak: Vec<u8> = data_unit.to_le_bytes().to_vec();
+        encrypt_with_backend(self.backend, &mut tweak, &self.tweak_round_keys);
+        for _i in 0..blocks {
+            tweaks.push(tweak.clone());
+            tweak = multiply_by_alpha(&tweak);
+        }
+
+        let full_blocks = data.len() / 16;
+        let remainder = data.len() % 16;
+        let mut data_blocks: Vec<Vec<u8>> = data.chunks(16).map(|chunk| chunk.to_vec()).collect();
+
+        if remainder == 0 {
+            data_blocks.par_iter_mut().enumerate().for_each(|(j, block)| self.process_block(block, &tweaks[j], encryption));
+            return Ok(data_blocks.concat());
+        }
+
+        // ciphertext stealing on the last full block and the partial one
+        let last_full = full_blocks - 1;
+        data_blocks[..last_full].par_iter_mut().enumerate().for_each(|(j, block)| self.process_block(block, &tweaks[j], encryption));
+
+        // when decrypting, the tweaks of the last two blocks are swapped
+        let (first_tweak, second_tweak) = if encryption { (&tweaks[last_full], &tweaks[full_blocks]) } else { (&tweaks[full_blocks], &tweaks[last_full]) };
+        let mut stolen_block: Vec<u8> = data_blocks[last_full].clone();
+        self.process_block(&mut stolen_block, first_tweak, encryption);
+        let partial_block: Vec<u8> = stolen_block[..remainder].to_vec();
+        let mut last_block: Vec<u8> = data_blocks[full_blocks].clone();
+        last_block.extend_from_slice(&stolen_block[remainder..]);
+        self.process_block(&mut last_block, second_tweak, encryption);
+
+        data_blocks[last_full] = last_block;
+        data_blocks[full_blocks] = partial_block;
+        Ok(data_blocks.concat())
+    }
+}
+
+// multiplication by the primitive element of GF(2^128), in the little endian convention of XTS
+fn multiply_by_alpha(tweak: &[u8]) -> Vec<u8> {
+    let value = u128::from_le_bytes(tweak.try_into().unwrap());
+    let carry = value >> 127;
+    ((value << 1) ^ (0x87 * carry)).to_le_bytes().to_vec()
+}
+// end AES XTS
@@ -0,0 +1,190 @@
+// ChaCha20 stream cipher (RFC 8439) and XChaCha20, its variant with 192-bit nonces (draft-irtf-cfrg-xchacha).
+// Only additions, rotations and XORs on 32-bit words: no tables and no secret-dependent branches.
+
+use rayon::prelude::*;
+
+use crate::arith::random::random_byte_array;
+use crate::error::{unwrap_or_panic, Error, Result};
+
+// "expand 32-byte k"
+const CHACHA20_CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+// 20 rounds: 10 column rounds alternated with 10 diagonal rounds
+fn chacha20_rounds(state: &mut [u32; 16]) {
+    for _i in 0..10 {
+        quarter_round(state, 0, 4, 8, 12);
+        quarter_round(state, 1, 5, 9, 13);
+        quarter_round(state, 2, 6, 10, 14);
+        quarter_round(state, 3, 7, 11, 15);
+        quarter_round(state, 0, 5, 10, 15);
+        quarter_round(state, 1, 6, 11, 12);
+        quarter_round(state, 2, 7, 8, 13);
+        quarter_round(state, 3, 4, 9, 14);
+    }
+}
+
+fn words(bytes: &[u8]) -> Vec<u32> {
+    bytes.chunks(4).map(|word| u32::from_le_bytes(word.try_into().unwrap())).collect()
+}
+
+fn initial_state(key: &[u8], input: &[u32]) -> [u32; 16] {
+    let mut state = [0u32; 16];
+    state[0..4].copy_from_slice(&CHACHA20_CONSTANTS);
+    state[4..12].copy_from_slice(&words(key));
+    state[12..16].copy_from_slice(input);
+    state
+}
+
+// 64 bytes of key stream for a 32-byte key, a block counter and a 12-byte nonce
+pub fn chacha20_block(key: &[u8], counter: u32, nonce: &[u8]) -> Vec<u8> {
+    let mut input: Vec<u32> = vec![counter];
+    input.extend(words(nonce));
+    let initial = initial_state(key, &input);
+    let mut state = initial;
+    chacha20_rounds(&mut state);
+    state.iter().zip(initial.iter()).flat_map(|(word, initial_word)| word.wrapping_add(*initial_word).to_le_bytes()).collect()
+}
+
+// HChaCha20: a 32-byte subkey from a 32-byte key and a 16-byte nonce
+pub fn hchacha20(key: &[u8], nonce: &[u8]) -> Vec<u8> {
+    unwrap_or_panic(try_hchacha20(key, nonce))
+}
+
+pub fn try_hchacha20(key: &[u8], nonce: &[u8]) -> Result<Vec<u8>> {
+    if key.len() != 32 {
+        return Err(Error::InvalidKey("HChaCha20 keys are 32 bytes long".to_string()));
+    }
+    if nonce.len() != 16 {
+        return Err(Error::InvalidInput("The HChaCha20 nonce must be 16 bytes long".to_string()));
+    }
+    let mut state = initial_state(key, &words(nonce));
+    chacha20_rounds(&mut state);
+    Ok(state[0..4].iter().chain(state[12..16].iter()).flat_map(|word| word.to_le_bytes()).collect())
+}
+
+
+/*
+    ChaCha20 with a 32-bit block counter and a 96-bit nonce
+*/
+pub struct ChaCha20 {
+    key: Vec<u8>
+}
+
+impl ChaCha20 {
+    // random key
+    pub fn new() -> ChaCha20 {
+        ChaCha20 { key: random_byte_array(32) }
+    }
+
+    pub fn from_key(key: Vec<u8>) -> ChaCha20 {
+        unwrap_or_panic(ChaCha20::try_from_key(key))
+    }
+
+    pub fn try_from_key(key: Vec<u8>) -> Result<ChaCha20> {
+        if key.len() != 32 {
+            return Err(Error::InvalidKey("ChaCha20 keys are 32 bytes long".to_string()));
+        }
+        Ok(ChaCha20 { key })
+    }
+
+    pub fn export_key(&self) -> Vec<u8> {
+        self.key.clone()
+    }
+
+    // XORs data with the key stream starting at the block counter, the blocks are generated in parallel.
+    // Encryption and decryption are the same operation.
+    pub fn apply_keystream(&self, nonce: &[u8], counter: u32, data: &mut [u8]) {
+        unwrap_or_panic(self.try_apply_keystream(nonce, counter, data))
+    }
+
+    pub fn try_apply_keystream(&self, nonce: &[u8], counter: u32, data: &mut [u8]) -> Result<()> {
+        if nonce.len() != 12 {
+            return Err(Error::InvalidInput("The nonce must be 12 bytes long".to_string()));
+        }
+        let blocks = data.len().div_ceil(64) as u64;
+        if counter as u64 + blocks > 1 << 32 {
+            return Err(Error::InvalidInput("The block counter would wrap around".to_string()));
+        }
+
+        data.par_chunks_mut(64).enumerate().for_each(|(i, chunk)| {
+            let key_stream = chacha20_block(&self.key, counter + i as u32, nonce);
+            for (byte, key_byte) in chunk.iter_mut().zip(key_stream.iter()) {
+                *byte ^= key_byte;
+            }
+        });
+        Ok(())
+    }
+}
+
+impl Default for ChaCha20 {
+    fn default() -> Self {
+        ChaCha20::new()
+    }
+}
+
+/*
+    XChaCha20: HChaCha20 derives a subkey from the first 16 bytes of the 24-byte nonce,
+    then ChaCha20 runs with the nonce 0^32 || last 8 bytes. Random nonces are safe.
+*/
+pub struct XChaCha20 {
+    key: Vec<u8>
+}
+
+impl XChaCha20 {
+    // random key
+    pub fn new() -> XChaCha20 {
+        XChaCha20 { key: random_byte_array(32) }
+    }
+
+    pub fn from_key(key: Vec<u8>) -> XChaCha20 {
+        unwrap_or_panic(XChaCha20::try_from_key(key))
+    }
+
+    pub fn try_from_key(key: Vec<u8>) -> Result<XChaCha20> {
+        if key.len() != 32 {
+            return Err(Error::InvalidKey("XChaCha20 keys are 32 bytes long".to_string()));
+        }
+        Ok(XChaCha20 { key })
+    }
+
+    pub fn export_key(&self) -> Vec<u8> {
+        self.key.clone()
+    }
+
+    pub fn apply_keystream(&self, nonce: &[u8], counter: u32, data: &mut [u8]) {
+        unwrap_or_panic(self.try_apply_keystream(nonce, counter, data))
+    }
+
+    pub fn try_apply_keystream(&self, nonce: &[u8], counter: u32, data: &mut [u8]) -> Result<()> {
+        let (subkey, chacha20_nonce) = xchacha20_subkey_and_nonce(&self.key, nonce)?;
+        ChaCha20::try_from_key(subkey)?.try_apply_keystream(&chacha20_nonce, counter, data)
+    }
+}
+
+impl Default for XChaCha20 {
+    fn default() -> Self {
+        XChaCha20::new()
+    }
+}
+
+pub(crate) fn xchacha20_subkey_and_nonce(key: &[u8], nonce: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    if nonce.len() != 24 {
+        return Err(Error::InvalidInput("The nonce must be 24 bytes long".to_string()));
+    }
+    let subkey = try_hchacha20(key, &nonce[0..16])?;
+    let mut chacha20_nonce: Vec<u8> = vec![0; 4];
+    chacha20_nonce.extend_from_slice(&nonce[16..24]);
+    Ok((subkey, chacha20_nonce))
+}
@@ -0,0 +1,152 @@
+// ChaCha20-Poly1305 AEAD (RFC 8439) and XChaCha20-Poly1305, with the same interface as AES-GCM
+
+use crate::cryptography::symmetric::interfaces::interfaces::{AEADError, AEADinterface};
+use crate::cryptography::symmetric::primitives::chacha20::{chacha20_block, xchacha20_subkey_and_nonce, ChaCha20};
+use crate::cryptography::symmetric::primitives::poly1305::Poly1305;
+use crate::arith::random::random_byte_array;
+use crate::error::{unwrap_or_panic, Error, Result};
+
+// the 32-bit block counter starts from 1 for the data: at most 2^32 - 1 blocks of 64 bytes
+const MAX_PLAINTEXT_LENGTH: u64 = ((1 << 32) - 1) * 64;
+
+
+/*
+    ChaCha20-Poly1305 with a 32-byte key, 12-byte nonces and 16-byte tags
+*/
+pub struct ChaCha20Poly1305 {
+    key: Vec<u8>
+}
+
+impl ChaCha20Poly1305 {
+    // random key
+    pub fn new() -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::from_key(random_byte_array(32))
+    }
+
+    pub fn from_key(key: Vec<u8>) -> ChaCha20Poly1305 {
+        unwrap_or_panic(ChaCha20Poly1305::try_from_key(key))
+    }
+
+    pub fn try_from_key(key: Vec<u8>) -> Result<ChaCha20Poly1305> {
+        if key.len() != 32 {
+            return Err(Error::InvalidKey("ChaCha20-Poly1305 keys are 32 bytes long".to_string()));
+        }
+        Ok(ChaCha20Poly1305 { key })
+    }
+
+    pub fn export_key(&self) -> Vec<u8> {
+        self.key.clone()
+    }
+
+    fn check_lengths(nonce: &[u8], data_length: usize) -> Result<(), AEADError> {
+        if nonce.len() != 12 {
+            return Err(AEADError::InvalidNonce);
+        }
+        if data_length as u64 > MAX_PLAINTEXT_LENGTH {
+            return Err(AEADError::MessageTooLong);
+        }
+        Ok(())
+    }
+
+    // the one-time Poly1305 key is the first half of the key stream block 0
+    fn compute_tag(&self, nonce: &[u8], associated_data: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let one_time_key = chacha20_block(&self.key, 0, nonce);
+        let mut poly1305 = Poly1305::new(&one_time_key[0..32]);
+        poly1305.update(associated_data);
+        poly1305.update(&vec![0; (16 - associated_data.len() % 16) % 16]);
+        poly1305.update(ciphertext);
+        poly1305.update(&vec![0; (16 - ciphertext.len() % 16) % 16]);
+        poly1305.update(&(associated_data.len() as u64).to_le_bytes());
+        poly1305.update(&(ciphertext.len() as u64).to_le_bytes());
+        poly1305.finalize()
+    }
+}
+
+impl Default for ChaCha20Poly1305 {
+    fn default() -> Self {
+        ChaCha20Poly1305::new()
+    }
+}
+
+impl AEADinterface for ChaCha20Poly1305 {
+    fn encrypt(&self, nonce: Vec<u8>, plaintext: Vec<u8>, associated_data: Vec<u8>) -> Result<(Vec<u8>, Vec<u8>), AEADError> {
+        ChaCha20Poly1305::check_lengths(&nonce, plaintext.len())?;
+
+        let mut ciphertext = plaintext;
+        ChaCha20::from_key(self.key.clone()).apply_keystream(&nonce, 1, &mut ciphertext);
+        let tag = self.compute_tag(&nonce, &associated_data, &ciphertext);
+        Ok((ciphertext, tag))
+    }
+
+    fn decrypt(&self, nonce: Vec<u8>, ciphertext: Vec<u8>, associated_data: Vec<u8>, tag: Vec<u8>) -> Result<Vec<u8>, AEADError> {
+        ChaCha20Poly1305::check_lengths(&nonce, ciphertext.len())?;
+        if tag.len() != 16 {
+            return Err(AEADError::InvalidTag);
+        }
+
+        // the tag is verified before decrypting, comparing all the bytes
+        let expected_tag = self.compute_tag(&nonce, &associated_data, &ciphertext);
+        let difference = expected_tag.iter().zip(tag.iter()).fold(0u8, |difference, (a, b)| difference | (a ^ b));
+        if difference != 0 {
+            return Err(AEADError::AuthenticationFailed);
+        }
+
+        let mut plaintext = ciphertext;
+        ChaCha20::from_key(self.key.clone()).apply_keystream(&nonce, 1, &mut plaintext);
+        Ok(plaintext)
+    }
+}
+
+
+/*
+    XChaCha20-Poly1305: 24-byte nonces, which can be picked at random.
+    ChaCha20-Poly1305 runs with the HChaCha20 subkey and the nonce 0^32 || last 8 bytes.
+*/
+pub struct XChaCha20Poly1305 {
+    key: Vec<u8>
+}
+
+impl XChaCha20Poly1305 {
+    // random key
+    pub fn new() -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::from_key(random_byte_array(32))
+    }
+
+    pub fn from_key(key: Vec<u8>) -> XChaCha20Poly1305 {
+        unwrap_or_panic(XChaCha20Poly1305::try_from_key(key))
+    }
+
+    pub fn try_from_key(key: Vec<u8>) -> Result<XChaCha20Poly1305> {
+        if key.len() != 32 {
+            return Err(Error::InvalidKey("XChaCha20-Poly1305 keys are 32 bytes long".to_string()));
+        }
+        Ok(XChaCha20Poly1305 { key })
+    }
+
+    pub fn export_key(&self) -> Vec<u8> {
+        self.key.clone()
+    }
+
+    fn inner_cipher(&self, nonce: &[u8]) -> Result<(ChaCha20Poly1305, Vec<u8>), AEADError> {
+        let (subkey, chacha20_nonce) = xchacha20_subkey_and_nonce(&self.key, nonce).map_err(|_| AEADError::InvalidNonce)?;
+        Ok((ChaCha20Poly1305::from_key(subkey), chacha20_nonce))
+    }
+}
+
+impl Default for XChaCha20Poly1305 {
+    fn default() -> Self {
+        XChaCha20Poly1305::new()
+    }
+}
+
+impl AEADinterface for XChaCha20Poly1305 {
+    fn encrypt(&self, nonce: Vec<u8>, plaintext: Vec<u8>, associated_data: Vec<u8>) -> Result<(Vec<u8>, Vec<u8>), AEADError> {
+        let (cipher, chacha20_nonce) = self.inner_cipher(&nonce)?;
+        cipher.encrypt(chacha20_nonce, plaintext, associated_data)
+    }
+
+    fn decrypt(&self, nonce: Vec<u8>, ciphertext: Vec<u8>, associated_data: Vec<u8>, tag: Vec<u8>) -> Result<Vec<u8>, AEADError> {
+        let (cipher, chacha20_nonce) = self.inner_cipher(&nonce)?;
+        cipher.decrypt(chacha20_nonce, ciphertext, associated_data, tag)
+    }
+}
@@ -0,0 +1,67 @@
+// HMAC (RFC 2104, FIPS 198-1) over any hash function implementing HashInterface
+
+use crate::cryptography::hash::interfaces::interfaces::HashInterface;
+use crate::cryptography::symmetric::interfaces::interfaces::MACinterface;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+
+/*
+    HMAC keyed with a fixed key. The inner and outer hash states are computed once,
+    after absorbing the padded key, and cloned for every message.
+*/
+#[derive(Clone)]
+pub struct HMAC<H: HashInterface> {
+    inner: H,
+    outer: H
+}
+
+impl<H: HashInterface> HMAC<H> {
+    pub fn new(key: &[u8]) -> HMAC<H> {
+        // keys longer than the block size are hashed first
+        let mut padded_key = if key.len() > H::BLOCK_SIZE { H::digest(key) } else { key.to_vec() };
+        padded_key.resize(H::BLOCK_SIZE, 0);
+
+        let mut inner = H::new();
+        inner.update(&padded_key.iter().map(|byte| byte ^ IPAD).collect::<Vec<u8>>());
+        let mut outer = H::new();
+        outer.update(&padded_key.iter().map(|byte| byte ^ OPAD).collect::<Vec<u8>>());
+        HMAC { inner, outer }
+    }
+
+    // incremental API: the message can be given in several parts
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+
+    // one shot
+    pub fn mac(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut hmac = HMAC::<H>::new(key);
+        hmac.update(data);
+        hmac.finalize()
+    }
+}
+
+impl<H: HashInterface> MACinterface for HMAC<H> {
+    fn get_authentication_tag(&self, plaintext: Vec<u8>) -> Vec<u8> {
+        let mut hmac = self.clone();
+        hmac.update(&plaintext);
+        hmac.finalize()
+    }
+
+    // the comparison runs in constant time
+    fn verify(&self, plaintext: Vec<u8>, authentication_tag: Vec<u8>) -> bool {
+        let tag = self.get_authentication_tag(plaintext);
+        if authentication_tag.len() != tag.len() {
+            return false;
+        }
+        tag.iter().zip(authentication_tag.iter()).fold(0u8, |difference, (a, b)| difference | (a ^ b)) == 0
+    }
+}
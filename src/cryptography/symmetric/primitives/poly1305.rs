@@ -0,0 +1,172 @@
+// Poly1305 one-time authenticator (RFC 8439). The accumulator is kept in five 26-bit limbs,
+// the arithmetic modulo 2^130 - 5 runs in constant time.
+
+use crate::cryptography::symmetric::interfaces::interfaces::MACinterface;
+use crate::error::{unwrap_or_panic, Error, Result};
+
+const LIMB_MASK: u32 = 0x3ffffff;
+
+
+/*
+    Poly1305 keyed with a 32-byte one-time key r || s: a key must never authenticate two messages
+*/
+#[derive(Clone)]
+pub struct Poly1305 {
+    r: [u32; 5],
+    s: [u32; 4],
+    accumulator: [u32; 5],
+    buffer: Vec<u8>
+}
+
+fn le_words(bytes: &[u8]) -> [u32; 4] {
+    let mut words = [0u32; 4];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = u32::from_le_bytes(bytes[i * 4..(i + 1) * 4].try_into().unwrap());
+    }
+    words
+}
+
+impl Poly1305 {
+    pub fn new(key: &[u8]) -> Poly1305 {
+        unwrap_or_panic(Poly1305::try_new(key))
+    }
+
+    pub fn try_new(key: &[u8]) -> Result<Poly1305> {
+        if key.len() != 32 {
+            return Err(Error::InvalidKey("Poly1305 keys are 32 bytes long".to_string()));
+        }
+
+        // r is clamped, then split in 26-bit limbs
+        let t = le_words(&key[0..16]);
+        let r = [
+            t[0] & 0x3ffffff,
+            ((t[0] >> 26) | (t[1] << 6)) & 0x3ffff03,
+            ((t[1] >> 20) | (t[2] << 12)) & 0x3ffc0ff,
+            ((t[2] >> 14) | (t[3] << 18)) & 0x3f03fff,
+            (t[3] >> 8) & 0x00fffff
+        ];
+        Ok(Poly1305 { r, s: le_words(&key[16..32]), accumulator: [0; 5], buffer: Vec::new() })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+        let full_blocks = self.buffer.len() / 16;
+        for i in 0..full_blocks {
+            let block: [u8; 16] = self.buffer[i * 16..(i + 1) * 16].try_into().unwrap();
+            self.process_block(&block, 1 << 24);
+        }
+        self.buffer.drain(..full_blocks * 16);
+    }
+
+    pub fn finalize(mut self) -> Vec<u8> {
+        // the last partial block is padded with 0x01 and zeros, without the 2^128 bit
+        if !self.buffer.is_empty() {
+            let mut block = [0u8; 16];
+            block[..self.buffer.len()].copy_from_slice(&self.buffer);
+            block[self.buffer.len()] = 1;
+            self.process_block(&block, 0);
+        }
+
+        let mut h = self.accumulator;
+        let mut carry = h[1] >> 26;
+        h[1] &= LIMB_MASK;
+        for limb in h.iter_mut().skip(2) {
+            *limb += carry;
+            carry = *limb >> 26;
+            *limb &= LIMB_MASK;
+        }
+        h[0] += carry * 5;
+        carry = h[0] >> 26;
+        h[0] &= LIMB_MASK;
+        h[1] += carry;
+
+        // g = h - (2^130 - 5), selected without branches when it is not negative
+        let mut g = [0u32; 5];
+        g[0] = h[0] + 5;
+        carry = g[0] >> 26;
+        g[0] &= LIMB_MASK;
+        for i in 1..4 {
+            g[i] = h[i] + carry;
+            carry = g[i] >> 26;
+            g[i] &= LIMB_MASK;
+        }
+        g[4] = (h[4] + carry).wrapping_sub(1 << 26);
+        let select_g = (g[4] >> 31).wrapping_sub(1);
+        for (h_limb, g_limb) in h.iter_mut().zip(g.iter()) {
+            *h_limb = (*h_limb & !select_g) | (g_limb & select_g);
+        }
+
+        // h mod 2^128, plus s
+        let words = [
+            h[0] | (h[1] << 26),
+            (h[1] >> 6) | (h[2] << 20),
+            (h[2] >> 12) | (h[3] << 14),
+            (h[3] >> 18) | (h[4] << 8)
+        ];
+        let mut tag: Vec<u8> = Vec::with_capacity(16);
+        let mut sum: u64 = 0;
+        for (word, s_word) in words.iter().zip(self.s.iter()) {
+            sum = *word as u64 + *s_word as u64 + (sum >> 32);
+            tag.extend_from_slice(&(sum as u32).to_le_bytes());
+        }
+        tag
+    }
+
+    // one shot
+    pub fn mac(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut poly1305 = Poly1305::new(key);
+        poly1305.update(data);
+        poly1305.finalize()
+    }
+
+    // h = (h + block) * r mod 2^130 - 5
+    fn process_block(&mut self, block: &[u8; 16], high_bit: u32) {
+        let t = le_words(block);
+        let h = &mut self.accumulator;
+        h[0] += t[0] & LIMB_MASK;
+        h[1] += ((t[0] >> 26) | (t[1] << 6)) & LIMB_MASK;
+        h[2] += ((t[1] >> 20) | (t[2] << 12)) & LIMB_MASK;
+        h[3] += ((t[2] >> 14) | (t[3] << 18)) & LIMB_MASK;
+        h[4] += (t[3] >> 8) | high_bit;
+
+        // 2^130 = 5 mod p, so the limbs above the fifth wrap around multiplied by 5
+        let r = self.r.map(|limb| limb as u64);
+        let s = [0, r[1] * 5, r[2] * 5, r[3] * 5, r[4] * 5];
+        let h64 = h.map(|limb| limb as u64);
+        let d = [
+            h64[0] * r[0] + h64[1] * s[4] + h64[2] * s[3] + h64[3] * s[2] + h64[4] * s[1],
+            h64[0] * r[1] + h64[1] * r[0] + h64[2] * s[4] + h64[3] * s[3] + h64[4] * s[2],
+            h64[0] * r[2] + h64[1] * r[1] + h64[2] * r[0] + h64[3] * s[4] + h64[4] * s[3],
+            h64[0] * r[3] + h64[1] * r[2] + h64[2] * r[1] + h64[3] * r[0] + h64[4] * s[4],
+            h64[0] * r[4] + h64[1] * r[3] + h64[2] * r[2] + h64[3] * r[1] + h64[4] * r[0]
+        ];
+
+        let mut carry: u64 = 0;
+        for i in 0..5 {
+            let limb = d[i] + carry;
+            carry = limb >> 26;
+            h[i] = (limb as u32) & LIMB_MASK;
+        }
+        h[0] += (carry * 5) as u32;
+        let carry = h[0] >> 26;
+        h[0] &= LIMB_MASK;
+        h[1] += carry;
+    }
+}
+
+impl MACinterface for Poly1305 {
+    fn get_authentication_tag(&self, plaintext: Vec<u8>) -> Vec<u8> {
+        let mut poly1305 = self.clone();
+        poly1305.update(&plaintext);
+        poly1305.finalize()
+    }
+
+    // the comparison runs in constant time
+    fn verify(&self, plaintext: Vec<u8>, authentication_tag: Vec<u8>) -> bool {
+        let tag = self.get_authentication_tag(plaintext);
+        if authentication_tag.len() != tag.len() {
+            return false;
+        }
+        tag.iter().zip(authentication_tag.iter()).fold(0u8, |difference, (a, b)| difference | (a ^ b)) == 0
+    }
+}
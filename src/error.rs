@@ -0,0 +1,63 @@
+use crate::cryptography::padding::padding::PaddingError;
+use crate::cryptography::symmetric::interfaces::interfaces::AEADError;
+
+/*
+    Errors of the try_* functions. The functions without the try_ prefix are wrappers
+    which panic with the message of the error.
+*/
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    // operands in different rings or fields, or with different variables
+    IncompatibleOperands(String),
+    // sizes of vectors and matrices
+    DimensionMismatch(String),
+    IndexOutOfRange,
+    DivisionByZero,
+    // elements without an inverse, singular matrices
+    NotInvertible(String),
+    // lengths of messages, blocks, nonces and IVs, unsupported parameters
+    InvalidInput(String),
+    // malformed or missing keys
+    InvalidKey(String),
+    // failed decryption or decapsulation, without details on purpose
+    DecryptionFailed,
+    Padding(PaddingError),
+    AEAD(AEADError)
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::IncompatibleOperands(message) | Error::DimensionMismatch(message) | Error::NotInvertible(message) | Error::InvalidInput(message) | Error::InvalidKey(message) => write!(f, "{message}"),
+            Error::IndexOutOfRange => write!(f, "Index out of range"),
+            Error::DivisionByZero => write!(f, "Cannot divide by 0"),
+            Error::DecryptionFailed => write!(f, "Decryption error"),
+            Error::Padding(error) => write!(f, "Invalid padding: {error:?}"),
+            Error::AEAD(error) => write!(f, "Authenticated encryption error: {error:?}")
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<PaddingError> for Error {
+    fn from(error: PaddingError) -> Self {
+        Error::Padding(error)
+    }
+}
+
+impl From<AEADError> for Error {
+    fn from(error: AEADError) -> Self {
+        Error::AEAD(error)
+    }
+}
+
+// used by the panicking wrappers of the try_* functions
+pub(crate) fn unwrap_or_panic<T>(result: Result<T>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic!("{}", error)
+    }
+}
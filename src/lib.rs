@@ -1,4 +1,7 @@
 #![recursion_limit = "2048"]
+pub mod error;
+pub use error::Error;
+
 pub mod poly {
     pub mod classes {
         pub mod polynomial;
@@ -11,6 +14,12 @@ pub mod poly {
     pub mod monomial_instance;
     pub mod univariate_polynomial_instance;
     }
+
+    pub mod multiplication;
+    pub mod gcd;
+    pub mod factorization;
+    pub mod evaluation;
+    pub mod ideal;
 }
 pub mod numbers {
     pub mod sets {
@@ -49,6 +58,7 @@ pub mod test {
     pub mod test_ZZ;
     pub mod test_RR;
     pub mod test_QQ;
+    pub mod test_elliptic_curves;
 }
 
 pub mod algebras {
@@ -100,7 +110,7 @@ pub mod arith {
 pub mod cryptography {
     pub mod asymmetric {
         pub mod interfaces { pub mod interfaces; }
-        pub mod primitives { pub mod rsa; pub mod kyber;}
+        pub mod primitives { pub mod rsa; pub mod kyber; pub mod ecdsa; pub mod eddsa; pub mod ecdh; pub mod xdh; }
     }
 
 
@@ -117,6 +127,8 @@ pub mod cryptography {
     pub mod symmetric {
         pub mod AES {
             pub mod aes_functions;
+            pub mod aesni;
+            pub mod bitsliced;
             pub mod cipher;
             pub mod data;
         }
@@ -125,11 +137,20 @@ pub mod cryptography {
         }
 
         pub mod interfaces { pub mod interfaces; }
-        pub mod primitives { pub mod aes; }
+        pub mod primitives { pub mod aes; pub mod chacha20; pub mod chacha20poly1305; pub mod hmac; pub mod poly1305; }
     }
 
     pub mod padding {
         pub mod padding;
     }
+
+    pub mod hash {
+        pub mod interfaces { pub mod interfaces; }
+        pub mod primitives { pub mod sha1; pub mod sha2; pub mod sha3; }
+    }
+
+    pub mod kdf {
+        pub mod primitives { pub mod hkdf; pub mod pbkdf2; }
+    }
 }
 
@@ -12,6 +12,7 @@ use crate::numbers::sets::Class::ClassTypes;
 use crate::poly::classes::univariate_polynomial::UnivariatePolynomial;
 use crate::poly::instances::univariate_polynomial_instance::UnivariatePolynomialInstance;
 use crate::variables::vars::Var;
+use crate::error::{unwrap_or_panic, Error, Result};
 
 /*
 Notes on the implementation
@@ -50,6 +51,12 @@ impl<T> Matrix<T> where T: Instance + Clone + PartialEq {
 impl<T> std::ops::Add for Matrix<T> where T: Instance + Clone + Operand + PartialEq {
     type Output = Matrix<T>;
     fn add(self, rhs: Self) -> Self::Output {
+        unwrap_or_panic(self.try_add(rhs))
+    }
+}
+
+impl<T> Matrix<T> where T: Instance + Clone + Operand + PartialEq {
+    pub fn try_add(self, rhs: Matrix<T>) -> Result<Matrix<T>> {
         if self.columns == rhs.columns && self.rows == rhs.rows {
             let mut result = self.clone();
             for i in 0..self.columns {
@@ -58,9 +65,9 @@ impl<T> std::ops::Add for Matrix<T> where T: Instance + Clone + Operand + Partia
                 }
             }
 
-            result
+            Ok(result)
         } else {
-            panic!("Cannot add matrix with different sizes");
+            Err(Error::DimensionMismatch("Cannot add matrix with different sizes".to_string()))
         }
     }
 }
@@ -68,6 +75,12 @@ impl<T> std::ops::Add for Matrix<T> where T: Instance + Clone + Operand + Partia
 impl<T> std::ops::Sub for Matrix<T> where T: Instance + Clone + Operand + PartialEq {
     type Output = Matrix<T>;
     fn sub(self, rhs: Self) -> Self::Output {
+        unwrap_or_panic(self.try_sub(rhs))
+    }
+}
+
+impl<T> Matrix<T> where T: Instance + Clone + Operand + PartialEq {
+    pub fn try_sub(self, rhs: Matrix<T>) -> Result<Matrix<T>> {
         if self.columns == rhs.columns && self.rows == rhs.rows {
             let mut result = self.clone();
             for i in 0..self.columns {
@@ -76,9 +89,9 @@ impl<T> std::ops::Sub for Matrix<T> where T: Instance + Clone + Operand + Partia
                 }
             }
 
-            result
+            Ok(result)
         } else {
-            panic!("Cannot subtract matrix with different sizes");
+            Err(Error::DimensionMismatch("Cannot subtract matrix with different sizes".to_string()))
         }
     }
 }
@@ -87,6 +100,12 @@ impl<T> std::ops::Sub for Matrix<T> where T: Instance + Clone + Operand + Partia
 impl<T> std::ops::Mul for Matrix<PolynomialRingInstance<T>> where T: Display + 'static + Instance + Clone + Operand + ClassInstance + PartialEq + Number {
     type Output = Either<Matrix<PolynomialRingInstance<T>>, Vector<PolynomialRingInstance<T>>>;
     fn mul(self, rhs: Self) -> Self::Output {
+        unwrap_or_panic(self.try_mul(rhs))
+    }
+}
+
+impl<T> Matrix<PolynomialRingInstance<T>> where T: Display + 'static + Instance + Clone + Operand + ClassInstance + PartialEq + Number {
+    pub fn try_mul(self, rhs: Matrix<PolynomialRingInstance<T>>) -> Result<Either<Matrix<PolynomialRingInstance<T>>, Vector<PolynomialRingInstance<T>>>> {
         if self.columns == rhs.rows {
             let generator: PolynomialRing<T> = self.values[0][0].clone().class.into_inner().clone();
             let generator_values: Box<dyn StatefulClass> = self.values[0][0].clone().coefficients[0].get_class();
@@ -102,7 +121,7 @@ impl<T> std::ops::Mul for Matrix<PolynomialRingInstance<T>> where T: Display + '
 
                     for old_columns in 0..self.columns {
                         for old_rows in 0..rhs.rows {
-                            accumulator = accumulator.add(&(self.values[old_columns][i].clone().mul(rhs.values[j][old_rows].clone())));
+                            accumulator = accumulator.add(&(self.values[old_columns][i].clone() * rhs.values[j][old_rows].clone()));
                         }
                     }
 
@@ -113,12 +132,12 @@ impl<T> std::ops::Mul for Matrix<PolynomialRingInstance<T>> where T: Display + '
             }
 
             if rhs.columns == 1 {
-                return Right(Vector::new(result_vectors[0].clone()));
+                return Ok(Right(Vector::new(result_vectors[0].clone())));
             } else {
-                return Left(Matrix::new(result_vectors, self.rows, rhs.columns));
+                return Ok(Left(Matrix::new(result_vectors, self.rows, rhs.columns)));
             }
         } else {
-            panic!("Cannot multiplicate those matrices");
+            Err(Error::DimensionMismatch("Cannot multiplicate those matrices".to_string()))
         }
     }
 }
@@ -126,6 +145,12 @@ impl<T> std::ops::Mul for Matrix<PolynomialRingInstance<T>> where T: Display + '
 impl<T> std::ops::Mul for Matrix<T> where T: Instance +  Clone + Operand + PartialEq + Number {
     type Output = Either<Matrix<T>, Vector<T>>;
     fn mul(self, rhs: Self) -> Self::Output {
+        unwrap_or_panic(self.try_mul(rhs))
+    }
+}
+
+impl<T> Matrix<T> where T: Instance +  Clone + Operand + PartialEq + Number {
+    pub fn try_mul(self, rhs: Matrix<T>) -> Result<Either<Matrix<T>, Vector<T>>> {
         if self.columns == rhs.rows {
             let mut result_vectors: Vec<Vec<T>> = Vec::new();
             
@@ -148,12 +173,12 @@ impl<T> std::ops::Mul for Matrix<T> where T: Instance +  Clone + Operand + Parti
             }
 
             if rhs.columns == 1 {
-                return Right(Vector::new(result_vectors[0].clone()));
+                return Ok(Right(Vector::new(result_vectors[0].clone())));
             } else {
-                return Left(Matrix::new(result_vectors, self.rows, rhs.columns));
+                return Ok(Left(Matrix::new(result_vectors, self.rows, rhs.columns)));
             }
         } else {
-            panic!("Cannot multiplicate those matrices");
+            Err(Error::DimensionMismatch("Cannot multiplicate those matrices".to_string()))
         }
     }
 }
@@ -162,6 +187,12 @@ impl<T> std::ops::Mul for Matrix<T> where T: Instance +  Clone + Operand + Parti
 impl<T> std::ops::Mul<Vector<PolynomialRingInstance<T>>> for Matrix<PolynomialRingInstance<T>> where T: Display + 'static + ClassInstance + Instance + Clone + Operand + PartialEq + Number {
     type Output = Vector<PolynomialRingInstance<T>>;
     fn mul(self, rhs: Vector<PolynomialRingInstance<T>>) -> Self::Output {
+        unwrap_or_panic(self.try_mul_vector(rhs))
+    }
+}
+
+impl<T> Matrix<PolynomialRingInstance<T>> where T: Display + 'static + ClassInstance + Instance + Clone + Operand + PartialEq + Number {
+    pub fn try_mul_vector(self, rhs: Vector<PolynomialRingInstance<T>>) -> Result<Vector<PolynomialRingInstance<T>>> {
         if self.columns == rhs.len {
             let variable: Var = self.values[0][0].var.clone();
             let generator: PolynomialRing<T> = self.values[0][0].clone().class.into_inner().clone();
@@ -173,23 +204,29 @@ impl<T> std::ops::Mul<Vector<PolynomialRingInstance<T>>> for Matrix<PolynomialRi
 
                 for old_columns in 0..self.columns {
                     for old_rows in 0..rhs.len {
-                        accumulator = accumulator.add(&(self.values[old_columns][i].clone().mul(rhs.values[old_rows].clone())));
+                        accumulator = accumulator.add(&(self.values[old_columns][i].clone() * rhs.values[old_rows].clone()));
                     }
                 }
 
                 temp_vector.push(accumulator);
             }
 
-            Vector::new(temp_vector)
+            Ok(Vector::new(temp_vector))
             
         } else {
-            panic!("Cannot multiplicate those matrices");
+            Err(Error::DimensionMismatch("Cannot multiplicate those matrices".to_string()))
         }
     }
 }
 impl<T> std::ops::Mul<Vector<T>> for Matrix<T> where T: Instance + Clone + Operand + PartialEq + Number {
     type Output = Vector<T>;
     fn mul(self, rhs: Vector<T>) -> Self::Output {
+        unwrap_or_panic(self.try_mul_vector(rhs))
+    }
+}
+
+impl<T> Matrix<T> where T: Instance + Clone + Operand + PartialEq + Number {
+    pub fn try_mul_vector(self, rhs: Vector<T>) -> Result<Vector<T>> {
         if self.columns == rhs.len {
                  
             let mut temp_vector: Vec<T> = Vec::new();
@@ -205,10 +242,10 @@ impl<T> std::ops::Mul<Vector<T>> for Matrix<T> where T: Instance + Clone + Opera
                 temp_vector.push(accumulator);
             }
 
-            Vector::new(temp_vector)
+            Ok(Vector::new(temp_vector))
             
         } else {
-            panic!("Cannot multiplicate those matrices");
+            Err(Error::DimensionMismatch("Cannot multiplicate those matrices".to_string()))
         }
     }
 }
@@ -217,10 +254,16 @@ impl<T> std::ops::Mul<Vector<T>> for Matrix<T> where T: Instance + Clone + Opera
 impl<T> std::ops::Div for Matrix<T> where T: Instance + Clone + Operand + PartialEq + Number {
     type Output = Either<Matrix<T>, Vector<T>>;
     fn div(self, rhs: Self) -> Self::Output {
+        unwrap_or_panic(self.try_div(rhs))
+    }
+}
+
+impl<T> Matrix<T> where T: Instance + Clone + Operand + PartialEq + Number {
+    pub fn try_div(self, rhs: Matrix<T>) -> Result<Either<Matrix<T>, Vector<T>>> {
         if rhs.rows == rhs.columns {
-            self * (rhs.inverse())
+            self.try_mul(rhs.try_inverse()?)
         } else {
-            panic!("Cannot compute the inverse of a non square matrix");
+            Err(Error::DimensionMismatch("Cannot compute the inverse of a non square matrix".to_string()))
         }
     }
 }
@@ -287,8 +330,12 @@ impl<T> Matrix<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
     }
 
     pub fn prepend_row(&mut self, row: Vec<UnivariatePolynomialInstance<T>>) {
+        unwrap_or_panic(self.try_prepend_row(row))
+    }
+
+    pub fn try_prepend_row(&mut self, row: Vec<UnivariatePolynomialInstance<T>>) -> Result<()> {
         if row.len() != self.columns {
-            panic!("Cannot prepend row with different number of columns");
+            return Err(Error::DimensionMismatch("Cannot prepend row with different number of columns".to_string()));
         }
 
         for i in 0..row.len() {
@@ -296,11 +343,17 @@ impl<T> Matrix<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
         }
 
         self.rows = self.rows + 1;
+
+        Ok(())
     }
 
     pub fn append_row(&mut self,row: Vec<UnivariatePolynomialInstance<T>>) {
+        unwrap_or_panic(self.try_append_row(row))
+    }
+
+    pub fn try_append_row(&mut self,row: Vec<UnivariatePolynomialInstance<T>>) -> Result<()> {
         if row.len() != self.columns {
-            panic!("Cannot appned row with different number of columns");
+            return Err(Error::DimensionMismatch("Cannot appned row with different number of columns".to_string()));
         }
 
         for i in 0..row.len() {
@@ -308,33 +361,51 @@ impl<T> Matrix<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
         }
 
         self.rows = self.rows + 1;
+
+        Ok(())
     }
 
     pub fn prepend_column(&mut self, column: Vec<UnivariatePolynomialInstance<T>>) {
+        unwrap_or_panic(self.try_prepend_column(column))
+    }
+
+    pub fn try_prepend_column(&mut self, column: Vec<UnivariatePolynomialInstance<T>>) -> Result<()> {
         if column.len() != self.rows {
-            panic!("Cannot prepend column with different number of rows");
+            return Err(Error::DimensionMismatch("Cannot prepend column with different number of rows".to_string()));
         }
 
         self.values.insert(0, column);
         self.columns = self.columns + 1;
+
+        Ok(())
     }
 
     pub fn append_column(&mut self, column: Vec<UnivariatePolynomialInstance<T>>) {
+        unwrap_or_panic(self.try_append_column(column))
+    }
+
+    pub fn try_append_column(&mut self, column: Vec<UnivariatePolynomialInstance<T>>) -> Result<()> {
         if column.len() != self.rows {
-            panic!("Cannot prepend column with different number of rows");
+            return Err(Error::DimensionMismatch("Cannot prepend column with different number of rows".to_string()));
         }
 
         self.values.push(column);
         self.columns = self.columns + 1;
+
+        Ok(())
     }
 
 
     pub fn get_ith_vector(&self, index: usize) -> Vector<UnivariatePolynomialInstance<T>> {
+        unwrap_or_panic(self.try_get_ith_vector(index))
+    }
+
+    pub fn try_get_ith_vector(&self, index: usize) -> Result<Vector<UnivariatePolynomialInstance<T>>> {
         if index < self.columns {
             let vector: Vec<UnivariatePolynomialInstance<T>> = self.values[index].clone();
-            Vector::new(vector)
+            Ok(Vector::new(vector))
         } else {
-            panic!("Index out of range");
+            Err(Error::IndexOutOfRange)
         }
     }
 
@@ -353,12 +424,16 @@ impl<T> Matrix<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
 
 
     pub fn determinant(&self) -> UnivariatePolynomialInstance<T> {
+        unwrap_or_panic(self.try_determinant())
+    }
+
+    pub fn try_determinant(&self) -> Result<UnivariatePolynomialInstance<T>> {
         if self.rows != self.columns {
-            panic!("Determinant cannot be computed for non square matrices");
+            return Err(Error::DimensionMismatch("Determinant cannot be computed for non square matrices".to_string()));
         }
 
         if self.rows == 2 && self.columns == 2 {
-            return (self.values[0][0].mul(&self.values[1][1])).sub(&(self.values[0][1].mul(&self.values[1][0])));
+            return Ok((self.values[0][0].mul(&self.values[1][1])).sub(&(self.values[0][1].mul(&self.values[1][0]))));
         } else {
             let variable: Var = self.values[0][0].var.clone();
             let generator: Box<dyn StatefulClass> = self.values[0][0].coefficients[0].get_class();
@@ -372,13 +447,17 @@ impl<T> Matrix<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
                 }
             }
 
-            return determinant;
+            return Ok(determinant);
         }
     }
 
-    pub fn inverse(&self) -> Matrix<UnivariatePolynomialInstance<T>>{
+    pub fn inverse(&self) -> Matrix<UnivariatePolynomialInstance<T>> {
+        unwrap_or_panic(self.try_inverse())
+    }
+
+    pub fn try_inverse(&self) -> Result<Matrix<UnivariatePolynomialInstance<T>>> {
         if self.rows != self.columns {
-            panic!("Cannot compute inverse of non square matrices");
+            return Err(Error::DimensionMismatch("Cannot compute inverse of non square matrices".to_string()));
         }
         let variable: Var = self.values[0][0].var.clone();
 
@@ -386,7 +465,7 @@ impl<T> Matrix<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
         let generator: Box<dyn StatefulClass> = self.values[0][0].coefficients[0].get_class();
 
         if determinant == UnivariatePolynomial::zero(variable, &generator) {
-            panic!("[ERROR] Determinant of matrix is zero");
+            return Err(Error::NotInvertible("[ERROR] Determinant of matrix is zero".to_string()));
         }
 
         // special case for 2x2 matrices
@@ -401,7 +480,7 @@ impl<T> Matrix<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
 
             container.push(vector1);
             container.push(vector2);
-            return Matrix::new(container, 2, 2)
+            return Ok(Matrix::new(container, 2, 2));
         }
 
         // find matrix of cofactors
@@ -430,7 +509,7 @@ impl<T> Matrix<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
             }
         }
 
-        cofactors
+        Ok(cofactors)
     }
 
 
@@ -526,8 +605,12 @@ impl<T> Matrix<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
     }
 
     pub fn prepend_row(&mut self, row: Vec<PolynomialRingInstance<T>>) {
+        unwrap_or_panic(self.try_prepend_row(row))
+    }
+
+    pub fn try_prepend_row(&mut self, row: Vec<PolynomialRingInstance<T>>) -> Result<()> {
         if row.len() != self.columns {
-            panic!("Cannot prepend row with different number of columns");
+            return Err(Error::DimensionMismatch("Cannot prepend row with different number of columns".to_string()));
         }
 
         for i in 0..row.len() {
@@ -535,11 +618,17 @@ impl<T> Matrix<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
         }
 
         self.rows = self.rows + 1;
+
+        Ok(())
     }
 
     pub fn append_row(&mut self,row: Vec<PolynomialRingInstance<T>>) {
+        unwrap_or_panic(self.try_append_row(row))
+    }
+
+    pub fn try_append_row(&mut self,row: Vec<PolynomialRingInstance<T>>) -> Result<()> {
         if row.len() != self.columns {
-            panic!("Cannot appned row with different number of columns");
+            return Err(Error::DimensionMismatch("Cannot appned row with different number of columns".to_string()));
         }
 
         for i in 0..row.len() {
@@ -547,33 +636,51 @@ impl<T> Matrix<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
         }
 
         self.rows = self.rows + 1;
+
+        Ok(())
     }
 
     pub fn prepend_column(&mut self, column: Vec<PolynomialRingInstance<T>>) {
+        unwrap_or_panic(self.try_prepend_column(column))
+    }
+
+    pub fn try_prepend_column(&mut self, column: Vec<PolynomialRingInstance<T>>) -> Result<()> {
         if column.len() != self.rows {
-            panic!("Cannot prepend column with different number of rows");
+            return Err(Error::DimensionMismatch("Cannot prepend column with different number of rows".to_string()));
         }
 
         self.values.insert(0, column);
         self.columns = self.columns + 1;
+
+        Ok(())
     }
 
     pub fn append_column(&mut self, column: Vec<PolynomialRingInstance<T>>) {
+        unwrap_or_panic(self.try_append_column(column))
+    }
+
+    pub fn try_append_column(&mut self, column: Vec<PolynomialRingInstance<T>>) -> Result<()> {
         if column.len() != self.rows {
-            panic!("Cannot prepend column with different number of rows");
+            return Err(Error::DimensionMismatch("Cannot prepend column with different number of rows".to_string()));
         }
 
         self.values.push(column);
         self.columns = self.columns + 1;
+
+        Ok(())
     }
 
 
     pub fn get_ith_vector(&self, index: usize) -> Vector<PolynomialRingInstance<T>> {
+        unwrap_or_panic(self.try_get_ith_vector(index))
+    }
+
+    pub fn try_get_ith_vector(&self, index: usize) -> Result<Vector<PolynomialRingInstance<T>>> {
         if index < self.columns {
             let vector: Vec<PolynomialRingInstance<T>> = self.values[index].clone();
-            Vector::new(vector)
+            Ok(Vector::new(vector))
         } else {
-            panic!("Index out of range");
+            Err(Error::IndexOutOfRange)
         }
     }
 
@@ -592,12 +699,16 @@ impl<T> Matrix<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
 
 
     pub fn determinant(&self) -> PolynomialRingInstance<T> {
+        unwrap_or_panic(self.try_determinant())
+    }
+
+    pub fn try_determinant(&self) -> Result<PolynomialRingInstance<T>> {
         if self.rows != self.columns {
-            panic!("Determinant cannot be computed for non square matrices");
+            return Err(Error::DimensionMismatch("Determinant cannot be computed for non square matrices".to_string()));
         }
 
         if self.rows == 2 && self.columns == 2 {
-            return (self.values[0][0].mul(&self.values[1][1])).sub(&(self.values[0][1].mul(&self.values[1][0])));
+            return Ok((self.values[0][0].mul(&self.values[1][1])).sub(&(self.values[0][1].mul(&self.values[1][0]))));
         } else {
             let variable: Var = self.values[0][0].var.clone();
             let generator: PolynomialRing<T> = self.values[0][0].clone().class.into_inner().clone();
@@ -613,13 +724,17 @@ impl<T> Matrix<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
                 }
             }
 
-            return determinant;
+            return Ok(determinant);
         }
     }
 
-    pub fn inverse(&self) -> Matrix<PolynomialRingInstance<T>>{
+    pub fn inverse(&self) -> Matrix<PolynomialRingInstance<T>> {
+        unwrap_or_panic(self.try_inverse())
+    }
+
+    pub fn try_inverse(&self) -> Result<Matrix<PolynomialRingInstance<T>>> {
         if self.rows != self.columns {
-            panic!("Cannot compute inverse of non square matrices");
+            return Err(Error::DimensionMismatch("Cannot compute inverse of non square matrices".to_string()));
         }
         let variable: Var = self.values[0][0].var.clone();
         let generator: PolynomialRing<T> = self.values[0][0].clone().class.into_inner().clone();
@@ -627,7 +742,7 @@ impl<T> Matrix<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
 
         let determinant = self.determinant();
         if determinant == generator.zero(variable, &generator_values) {
-            panic!("[ERROR] Determinant of matrix is zero");
+            return Err(Error::NotInvertible("[ERROR] Determinant of matrix is zero".to_string()));
         }
 
         // special case for 2x2 matrices
@@ -642,7 +757,7 @@ impl<T> Matrix<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
 
             container.push(vector1);
             container.push(vector2);
-            return Matrix::new(container, 2, 2)
+            return Ok(Matrix::new(container, 2, 2));
         }
 
         // find matrix of cofactors
@@ -671,7 +786,7 @@ impl<T> Matrix<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
             }
         }
 
-        cofactors
+        Ok(cofactors)
     }
 
 
@@ -740,8 +855,12 @@ impl<T> Matrix<T> where T: Instance + Clone + PartialEq + Operand + Number {
     }
 
     pub fn prepend_row(&mut self, row: Vec<T>) {
+        unwrap_or_panic(self.try_prepend_row(row))
+    }
+
+    pub fn try_prepend_row(&mut self, row: Vec<T>) -> Result<()> {
         if row.len() != self.columns {
-            panic!("Cannot prepend row with different number of columns");
+            return Err(Error::DimensionMismatch("Cannot prepend row with different number of columns".to_string()));
         }
 
         for i in 0..row.len() {
@@ -749,11 +868,17 @@ impl<T> Matrix<T> where T: Instance + Clone + PartialEq + Operand + Number {
         }
 
         self.rows = self.rows + 1;
+
+        Ok(())
     }
 
     pub fn append_row(&mut self,row: Vec<T>) {
+        unwrap_or_panic(self.try_append_row(row))
+    }
+
+    pub fn try_append_row(&mut self,row: Vec<T>) -> Result<()> {
         if row.len() != self.columns {
-            panic!("Cannot appned row with different number of columns");
+            return Err(Error::DimensionMismatch("Cannot appned row with different number of columns".to_string()));
         }
 
         for i in 0..row.len() {
@@ -761,33 +886,51 @@ impl<T> Matrix<T> where T: Instance + Clone + PartialEq + Operand + Number {
         }
 
         self.rows = self.rows + 1;
+
+        Ok(())
     }
 
     pub fn prepend_column(&mut self, column: Vec<T>) {
+        unwrap_or_panic(self.try_prepend_column(column))
+    }
+
+    pub fn try_prepend_column(&mut self, column: Vec<T>) -> Result<()> {
         if column.len() != self.rows {
-            panic!("Cannot prepend column with different number of rows");
+            return Err(Error::DimensionMismatch("Cannot prepend column with different number of rows".to_string()));
         }
 
         self.values.insert(0, column);
         self.columns = self.columns + 1;
+
+        Ok(())
     }
 
     pub fn append_column(&mut self, column: Vec<T>) {
+        unwrap_or_panic(self.try_append_column(column))
+    }
+
+    pub fn try_append_column(&mut self, column: Vec<T>) -> Result<()> {
         if column.len() != self.rows {
-            panic!("Cannot prepend column with different number of rows");
+            return Err(Error::DimensionMismatch("Cannot prepend column with different number of rows".to_string()));
         }
 
         self.values.push(column);
         self.columns = self.columns + 1;
+
+        Ok(())
     }
 
 
     pub fn get_ith_vector(&self, index: usize) -> Vector<T> {
+        unwrap_or_panic(self.try_get_ith_vector(index))
+    }
+
+    pub fn try_get_ith_vector(&self, index: usize) -> Result<Vector<T>> {
         if index < self.columns {
             let vector: Vec<T> = self.values[index].clone();
-            Vector::new(vector)
+            Ok(Vector::new(vector))
         } else {
-            panic!("Index out of range");
+            Err(Error::IndexOutOfRange)
         }
     }
 
@@ -806,12 +949,16 @@ impl<T> Matrix<T> where T: Instance + Clone + PartialEq + Operand + Number {
 
 
     pub fn determinant(&self) -> T {
+        unwrap_or_panic(self.try_determinant())
+    }
+
+    pub fn try_determinant(&self) -> Result<T> {
         if self.rows != self.columns {
-            panic!("Determinant cannot be computed for non square matrices");
+            return Err(Error::DimensionMismatch("Determinant cannot be computed for non square matrices".to_string()));
         }
 
         if self.rows == 2 && self.columns == 2 {
-            return (self.values[0][0].mul(&self.values[1][1])).sub(&(self.values[0][1].mul(&self.values[1][0])));
+            return Ok((self.values[0][0].mul(&self.values[1][1])).sub(&(self.values[0][1].mul(&self.values[1][0]))));
         } else {
 
             let mut determinant: T = T::zero();
@@ -824,18 +971,22 @@ impl<T> Matrix<T> where T: Instance + Clone + PartialEq + Operand + Number {
                 }
             }
 
-            return determinant;
+            return Ok(determinant);
         }
     }
 
-    pub fn inverse(&self) -> Matrix<T>{
+    pub fn inverse(&self) -> Matrix<T> {
+        unwrap_or_panic(self.try_inverse())
+    }
+
+    pub fn try_inverse(&self) -> Result<Matrix<T>> {
         if self.rows != self.columns {
-            panic!("Cannot compute inverse of non square matrices");
+            return Err(Error::DimensionMismatch("Cannot compute inverse of non square matrices".to_string()));
         }
 
         let determinant = self.determinant();
         if determinant == T::zero() {
-            panic!("[ERROR] Determinant of matrix is zero");
+            return Err(Error::NotInvertible("[ERROR] Determinant of matrix is zero".to_string()));
         }
 
         // special case for 2x2 matrices
@@ -850,7 +1001,7 @@ impl<T> Matrix<T> where T: Instance + Clone + PartialEq + Operand + Number {
 
             container.push(vector1);
             container.push(vector2);
-            return Matrix::new(container, 2, 2)
+            return Ok(Matrix::new(container, 2, 2));
         }
 
         // find matrix of cofactors
@@ -879,7 +1030,7 @@ impl<T> Matrix<T> where T: Instance + Clone + PartialEq + Operand + Number {
             }
         }
 
-        cofactors
+        Ok(cofactors)
     }
 
 
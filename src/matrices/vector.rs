@@ -3,6 +3,7 @@ use num_bigint::BigInt;
 use crate::{algebras::Rings::{classes::PolynomialRing::PolynomialRing, instances::PolynomialRing_instance::PolynomialRingInstance}, numbers::numbers::{ClassInstance, Instance, Number, Operand, StatefulClass}, poly::{classes::univariate_polynomial::UnivariatePolynomial, instances::univariate_polynomial_instance::UnivariatePolynomialInstance}, variables::vars::Var};
 
 use super::matrix::Matrix;
+use crate::error::{unwrap_or_panic, Error, Result};
 
 
 #[derive(Clone, Debug)]
@@ -15,51 +16,67 @@ pub struct Vector<T> {
 impl<T> std::ops::Add for Vector<T> where T: Instance + Clone + PartialEq + Operand + Number {
     type Output = Vector<T>;
     fn add(self, rhs: Self) -> Self::Output {
+        unwrap_or_panic(self.try_add(rhs))
+    }
+}
+
+impl<T> Vector<T> where T: Instance + Clone + PartialEq + Operand + Number {
+    pub fn try_add(self, rhs: Vector<T>) -> Result<Vector<T>> {
         if self.len == rhs.len {
             let mut temp_values = self.values.clone();
             for i in 0..self.len {
                 temp_values[i] = temp_values[i].add(&rhs.values[i]);
             }
 
-            Vector::new(temp_values)
+            Ok(Vector::new(temp_values))
         } else {
-            panic!("Cannot sum different size vectors");
+            Err(Error::DimensionMismatch("Cannot sum different size vectors".to_string()))
         }
     }
-    
 }
 
 impl<T> std::ops::Add<Vector<PolynomialRingInstance<T>>> for Vector<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq + Operand + Number + ClassInstance + 'static {
     type Output = Vector<PolynomialRingInstance<T>>;
     fn add(self, rhs: Self) -> Self::Output {
+        unwrap_or_panic(self.try_add(rhs))
+    }
+}
+
+impl<T> Vector<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq + Operand + Number + ClassInstance + 'static {
+    pub fn try_add(self, rhs: Vector<PolynomialRingInstance<T>>) -> Result<Vector<PolynomialRingInstance<T>>> {
         if self.len == rhs.len {
             let mut temp_values = self.values.clone();
             for i in 0..self.len {
-                temp_values[i] = temp_values[i].clone().add(rhs.values[i].clone());
+                temp_values[i] = temp_values[i].clone() + rhs.values[i].clone();
             }
 
-            Vector::new(temp_values)
+            Ok(Vector::new(temp_values))
         } else {
-            panic!("Cannot sum different size vectors");
+            Err(Error::DimensionMismatch("Cannot sum different size vectors".to_string()))
         }
     }
-    
 }
 
 impl<T> std::ops::Sub for Vector<T> where T: Instance + Clone + PartialEq + Operand + Number {
     type Output = Vector<T>;
     fn sub(self, rhs: Self) -> Self::Output {
+        unwrap_or_panic(self.try_sub(rhs))
+    }
+}
+
+impl<T> Vector<T> where T: Instance + Clone + PartialEq + Operand + Number {
+    pub fn try_sub(self, rhs: Vector<T>) -> Result<Vector<T>> {
         if self.len == rhs.len {
             let mut temp_values = self.values.clone();
             for i in 0..self.len {
                 temp_values[i] = temp_values[i].sub(&rhs.values[i]);
             }
 
-            Vector::new(temp_values)
+            Ok(Vector::new(temp_values))
         } else {
-            panic!("Cannot sum different size vectors");
+            Err(Error::DimensionMismatch("Cannot sum different size vectors".to_string()))
         }
-    } 
+    }
 }
 
 
@@ -126,8 +143,12 @@ impl<T> Vector<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
     }
 
     pub fn element_wise_product(&self, m: Vector<UnivariatePolynomialInstance<T>>) -> UnivariatePolynomialInstance<T> {
+        unwrap_or_panic(self.try_element_wise_product(m))
+    }
+
+    pub fn try_element_wise_product(&self, m: Vector<UnivariatePolynomialInstance<T>>) -> Result<UnivariatePolynomialInstance<T>> {
         if self.len != m.len {
-            panic!("Cannot perform element-wise product with different size vectors");
+            return Err(Error::DimensionMismatch("Cannot perform element-wise product with different size vectors".to_string()));
         }
         let generator: Box<dyn StatefulClass> = self.values[0].coefficients[0].get_class();
 
@@ -136,7 +157,7 @@ impl<T> Vector<UnivariatePolynomialInstance<T>> where T: Instance + Clone + Part
             sum = sum.add(&(self.values[i].mul(&m.values[i])));
         }
 
-        sum
+        Ok(sum)
     }
 
 }
@@ -204,8 +225,12 @@ impl<T> Vector<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
     }
 
     pub fn element_wise_product(&self, m: Vector<PolynomialRingInstance<T>>) -> PolynomialRingInstance<T> {
+        unwrap_or_panic(self.try_element_wise_product(m))
+    }
+
+    pub fn try_element_wise_product(&self, m: Vector<PolynomialRingInstance<T>>) -> Result<PolynomialRingInstance<T>> {
         if self.len != m.len {
-            panic!("Cannot perform element-wise product with different size vectors");
+            return Err(Error::DimensionMismatch("Cannot perform element-wise product with different size vectors".to_string()));
         }
 
         let variable: Var = self.values[0].var.clone();
@@ -217,7 +242,7 @@ impl<T> Vector<PolynomialRingInstance<T>> where T: Instance + Clone + PartialEq
             sum = sum.add(&(self.values[i].mul(&m.values[i])));
         }
 
-        sum
+        Ok(sum)
     }
 
 }
@@ -272,15 +297,19 @@ impl<T> Vector<T> where T: Instance + Clone + PartialEq + Operand + Number {
     }
 
     pub fn element_wise_product(&self, m: Vector<T>) -> T {
+        unwrap_or_panic(self.try_element_wise_product(m))
+    }
+
+    pub fn try_element_wise_product(&self, m: Vector<T>) -> Result<T> {
         if self.len != m.len {
-            panic!("Cannot perform element-wise product with different size vectors");
+            return Err(Error::DimensionMismatch("Cannot perform element-wise product with different size vectors".to_string()));
         }
         let mut sum: T = T::zero();
         for i in 0..self.len {
             sum = sum.add(&(self.values[i].mul(&m.values[i])));
         }
 
-        sum
+        Ok(sum)
     }
 
 }
\ No newline at end of file
@@ -41,6 +41,10 @@ impl Eq for QQinstance {}
 impl QQinstance {
 
     pub fn simplify(self) -> QQinstance {
+        // the sign is kept on the numerator, so that equality and comparisons work on the reduced form
+        if self.denominator < BigInt::from(0) {
+            return self.class.clone().into_inner().new_instance(-self.numerator, -self.denominator);
+        }
         let gcd_numerator_denominator: BigInt = (self.numerator).gcd(&self.denominator);
         if gcd_numerator_denominator == BigInt::from(1) {
             return self.class.clone().into_inner().create_instance(self.numerator, self.denominator);
@@ -425,6 +425,31 @@ pub fn generic_pow<T: Clone+Number+Operand+std::ops::Mul<T, Output = T>>(value:
 }
 
 
+/*
+    Builds the integer n inside the class of `generator` (i.e. n * 1) by double and add.
+    Useful for generic code that needs small constants (2, 3, 27, ...) without knowing the concrete class.
+*/
+pub fn integer_in_class<T>(generator: &T, n: BigInt) -> T where T: ClassInstance + Operand + Clone + 'static {
+    let class = generator.get_class();
+    let one: T = class.one().as_any().downcast_ref::<T>().unwrap().clone();
+    let mut result: T = class.zero().as_any().downcast_ref::<T>().unwrap().clone();
+
+    let magnitude = n.magnitude().clone();
+    for i in (0..magnitude.bits()).rev() {
+        result = result.add(&result);
+        if magnitude.bit(i) {
+            result = result.add(&one);
+        }
+    }
+
+    if n < BigInt::from(0) {
+        result.neg()
+    } else {
+        result
+    }
+}
+
+
 pub fn ring_poly_pow<T>(value: PolynomialRingInstance<T>, exponent: BigInt) -> PolynomialRingInstance<T> where T: Display + 'static + ClassInstance + Instance + Number + Clone + PartialEq + Operand {
     let mut base = value.clone();
     let mut exp = exponent.clone();
@@ -454,7 +479,7 @@ pub fn ring_poly_pow<T>(value: PolynomialRingInstance<T>, exponent: BigInt) -> P
     acc
 }
 
-pub fn poly_pow<T>(value: UnivariatePolynomialInstance<T>, exponent: BigInt) -> UnivariatePolynomialInstance<T> where T: Display + Number + Instance + Clone + PartialEq + Operand {
+pub fn poly_pow<T>(value: UnivariatePolynomialInstance<T>, exponent: BigInt) -> UnivariatePolynomialInstance<T> where T: Display + Number + Instance + Clone + PartialEq + Operand + 'static {
     let mut base = value.clone();
     let mut exp = exponent.clone();
 
@@ -25,7 +25,7 @@ impl Monomial {
         Monomial {class: ClassTypes::Monomial}
     }
     pub fn new_monomial<T>(vars: Vec<Var>, coefficient: T) -> MonomialInstance<T> where T: Instance + Operand + Clone + Number {
-        MonomialInstance { class: RefCell::new(Monomial::new()), variables: vars, coefficient: coefficient }
+        MonomialInstance { class: RefCell::new(Monomial::new()), variables: normalize_variables(vars), coefficient: coefficient }
     }
 
     pub fn new_from_var<T>(a: Var) -> MonomialInstance<T> where T: Instance + Operand + Clone + Number{
@@ -80,42 +80,30 @@ impl Monomial {
 
     pub fn mul<T>(x: MonomialInstance<T>, y: MonomialInstance<T>) -> MonomialInstance<T> where T: Instance + Operand + Clone + Number{
         let k: T = y.coefficient;
-        let mut variables_clone = x.variables.clone();
-        for i in y.variables {
-            let mut new: bool = true;
-            for j in &mut variables_clone {
-                if i == *j {
-                    new = false;
-                    *j = (*j).clone()*i.clone();
-                    break;
-                } else {}
-            }
-            if new == true {
-                variables_clone.push(i)
-            } else {}
-        }
-        Monomial::new_monomial(variables_clone, x.coefficient.mul(&k))
+        let mut variables = x.variables.clone();
+        variables.extend(y.variables);
+        Monomial::new_monomial(variables, x.coefficient.mul(&k))
     }
 
     pub fn div<T>(x: MonomialInstance<T>, y: MonomialInstance<T>) -> MonomialInstance<T> where T: Instance + Operand + Clone + Number{
         let k: T = y.coefficient;
-        let mut variables_clone = x.variables.clone();
-        for i in y.variables {
-            let mut new: bool = true;
-            for j in &mut variables_clone {
-                if i == *j {
-                    new = false;
-                    *j = (*j).clone()/i.clone();
-                    break;
-                } else {}
-            }
-            if new == true {
-                variables_clone.push(i.pow(BigInt::from(-1)))
-            } else {}
-        }
-        Monomial::new_monomial(variables_clone, x.coefficient.div(&k))
+        let mut variables = x.variables.clone();
+        variables.extend(y.variables.into_iter().map(|i| i.pow(BigInt::from(-1))));
+        Monomial::new_monomial(variables, x.coefficient.div(&k))
     }
 }
 
 
-
+// one variable per symbol, sorted by symbol, without the zero exponents
+fn normalize_variables(vars: Vec<Var>) -> Vec<Var> {
+    let mut variables: Vec<Var> = Vec::new();
+    for var in vars {
+        match variables.iter_mut().find(|v| v.get_symbol() == var.get_symbol()) {
+            Some(v) => *v = v.clone() * var,
+            None => variables.push(var)
+        }
+    }
+    variables.retain(|v| v.get_exponent() != BigInt::from(0));
+    variables.sort_by_key(|v| v.get_symbol());
+    variables
+}
@@ -1,28 +1,83 @@
 use std::cell::RefCell;
+use std::cmp::Ordering;
 
 use num_bigint::BigInt;
 
-use crate::numbers::numbers::Class;
+use crate::error::{Error, Result};
 use crate::numbers::numbers::Instance;
 use crate::numbers::numbers::Number;
 use crate::numbers::numbers::Operand;
 use crate::numbers::sets::Class::ClassTypes;
-use crate::poly::instances::monomial_instance;
+use crate::poly::classes::monomial::Monomial;
 use crate::poly::instances::monomial_instance::MonomialInstance;
 use crate::poly::instances::polynomial_instance::PolynomialInstance;
 use crate::variables::vars::Var;
 
 
+/*
+    Monomial orderings. The variables are ordered by the list of the polynomial
+    (from the largest one), the others follow in alphabetical order: x > y > z
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MonomialOrder {
+    Lex,
+    GrLex,
+    GrevLex
+}
+
+impl MonomialOrder {
+    pub fn compare<T>(&self, a: &MonomialInstance<T>, b: &MonomialInstance<T>, variables: &[&'static str]) -> Ordering where T: Instance + Operand + Clone + Number {
+        let mut symbols: Vec<&'static str> = variables.to_vec();
+        let mut others: Vec<&'static str> = a.variables.iter().chain(&b.variables).map(|v| v.get_symbol()).filter(|symbol| !variables.contains(symbol)).collect();
+        others.sort();
+        others.dedup();
+        symbols.extend(others);
+
+        let exponents = |m: &MonomialInstance<T>| symbols.iter().map(|symbol| m.degree(symbol)).collect::<Vec<BigInt>>();
+        let (a_exponents, b_exponents) = (exponents(a), exponents(b));
+        let lex = a_exponents.cmp(&b_exponents);
+        match self {
+            MonomialOrder::Lex => lex,
+            MonomialOrder::GrLex => a.total_degree().cmp(&b.total_degree()).then(lex),
+            // the smallest variable with a different exponent decides, the smaller exponent wins
+            MonomialOrder::GrevLex => a.total_degree().cmp(&b.total_degree()).then_with(|| {
+                a_exponents.iter().zip(&b_exponents).rev().find(|(x, y)| x != y).map_or(Ordering::Equal, |(x, y)| y.cmp(x))
+            })
+        }
+    }
+}
+
 
 // POLYNOMIAL
 #[derive(Clone)]
 pub struct Polynomial {
-    class: ClassTypes
+    class: ClassTypes,
+    pub order: MonomialOrder,
+    pub variables: Vec<&'static str>
 }
 
 impl Polynomial {
-    pub fn new<T>(monomials: Vec<MonomialInstance<T>>) -> PolynomialInstance<T> where T: Instance + Operand + Clone {
-        PolynomialInstance::<T> { class: RefCell::new(Polynomial {class: ClassTypes::Polynomial}), monomials: monomials }
+    pub fn new<T>(monomials: Vec<MonomialInstance<T>>) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        Polynomial::new_with_order(monomials, MonomialOrder::GrevLex, Vec::new())
+    }
+
+    /*
+    Like terms are collected and the monomials are sorted from the leading one
+     */
+    pub fn new_with_order<T>(monomials: Vec<MonomialInstance<T>>, order: MonomialOrder, variables: Vec<&'static str>) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        let mut monomials = monomials;
+        monomials.sort_by(|a, b| order.compare(b, a, &variables));
+
+        let mut collected: Vec<MonomialInstance<T>> = Vec::new();
+        for monomial in monomials {
+            match collected.last_mut() {
+                Some(last) if last.variables == monomial.variables => last.coefficient = last.coefficient.add(&monomial.coefficient),
+                _ => collected.push(monomial)
+            }
+        }
+        collected.retain(|monomial| !monomial.coefficient.clone().is_zero());
+
+        PolynomialInstance::<T> { class: RefCell::new(Polynomial {class: ClassTypes::Polynomial, order, variables}), monomials: collected }
     }
 
     // pub fn ToUnivariatePolynomial(self) -> UnivariatePolynomial<T> {
@@ -33,20 +88,162 @@ impl Polynomial {
 impl PartialEq for Polynomial {
     fn eq(&self, other: &Self) -> bool {
         self.class == other.class
-        
+
     }
 }
 impl Eq for Polynomial {}
 
 
-// add function
+// operations, the result takes the monomial order of the first operand
+impl Polynomial {
+    fn from_monomials<T>(x: &PolynomialInstance<T>, monomials: Vec<MonomialInstance<T>>) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        let class = x.class.borrow();
+        Polynomial::new_with_order(monomials, class.order, class.variables.clone())
+    }
 
-// mul function
+    pub fn neg<T>(x: PolynomialInstance<T>) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        let monomials = x.monomials.iter().map(|monomial| Monomial::neg(monomial.clone())).collect();
+        Polynomial::from_monomials(&x, monomials)
+    }
 
-// div function
+    pub fn add<T>(x: PolynomialInstance<T>, y: PolynomialInstance<T>) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        let mut monomials = x.monomials.clone();
+        monomials.extend(y.monomials);
+        Polynomial::from_monomials(&x, monomials)
+    }
 
-// sub function
+    pub fn sub<T>(x: PolynomialInstance<T>, y: PolynomialInstance<T>) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        let mut monomials = x.monomials.clone();
+        monomials.extend(y.monomials.into_iter().map(Monomial::neg));
+        Polynomial::from_monomials(&x, monomials)
+    }
 
-// pow to BigInt
+    pub fn mul<T>(x: PolynomialInstance<T>, y: PolynomialInstance<T>) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        let mut monomials = Vec::new();
+        for a in &x.monomials {
+            for b in &y.monomials {
+                monomials.push(Monomial::mul(a.clone(), b.clone()));
+            }
+        }
+        Polynomial::from_monomials(&x, monomials)
+    }
 
+    pub fn mul_by_monomial<T>(x: PolynomialInstance<T>, y: MonomialInstance<T>) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        let monomials = x.monomials.iter().map(|monomial| Monomial::mul(monomial.clone(), y.clone())).collect();
+        Polynomial::from_monomials(&x, monomials)
+    }
 
+    pub fn pow<T>(x: PolynomialInstance<T>, exponent: u64) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        if exponent == 0 {
+            // c / c gives the one of the coefficient ring of x
+            let one = match x.monomials.first() {
+                Some(monomial) => monomial.coefficient.div(&monomial.coefficient),
+                None => T::one()
+            };
+            return Polynomial::from_monomials(&x, vec![Monomial::new_monomial(Vec::new(), one)]);
+        }
+        let mut result = x.clone();
+        for bit in (0..63 - exponent.leading_zeros()).rev() {
+            result = Polynomial::mul(result.clone(), result);
+            if (exponent >> bit) & 1 == 1 {
+                result = Polynomial::mul(result, x.clone());
+            }
+        }
+        result
+    }
+
+    /*
+    Division by one polynomial with respect to the monomial order: x = q y + r where
+    no monomial of r is divisible by the leading monomial of y
+     */
+    pub fn div<T>(x: PolynomialInstance<T>, y: PolynomialInstance<T>) -> Result<(PolynomialInstance<T>, PolynomialInstance<T>)> where T: Instance + Operand + Clone + Number {
+        let y = Polynomial::from_monomials(&x, y.monomials);
+        let leading = match y.monomials.first() {
+            Some(monomial) => monomial.clone(),
+            None => return Err(Error::DivisionByZero)
+        };
+        let mut quotient: Vec<MonomialInstance<T>> = Vec::new();
+        let mut remainder: Vec<MonomialInstance<T>> = Vec::new();
+        let mut rest = x.clone();
+        while let Some(term) = rest.monomials.first().cloned() {
+            let factor = Monomial::div(term.clone(), leading.clone());
+            // over the integers the leading coefficient may not divide the one of the term
+            let divisible = leading.divides(&term) && factor.coefficient.mul(&leading.coefficient).equal(&term.coefficient);
+            if divisible {
+                rest = Polynomial::sub(rest, Polynomial::mul_by_monomial(y.clone(), factor.clone()));
+                quotient.push(factor);
+            } else {
+                rest.monomials.remove(0);
+                remainder.push(term);
+            }
+        }
+        Ok((Polynomial::from_monomials(&x, quotient), Polynomial::from_monomials(&x, remainder)))
+    }
+
+    pub fn derivative<T>(x: PolynomialInstance<T>, symbol: &'static str) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        let monomials = x.monomials.iter().filter(|monomial| monomial.degree(symbol) != BigInt::from(0)).map(|monomial| {
+            let exponent = monomial.degree(symbol);
+            let mut variables = monomial.variables.clone();
+            variables.push(Var::new(symbol, BigInt::from(-1)));
+            Monomial::new_monomial(variables, scalar_multiple(&monomial.coefficient, &exponent))
+        }).collect();
+        Polynomial::from_monomials(&x, monomials)
+    }
+
+    /*
+    Replaces the variables with the given values, the others are kept
+     */
+    pub fn substitute<T>(x: PolynomialInstance<T>, values: &[(&'static str, T)]) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+        let monomials = x.monomials.iter().map(|monomial| {
+            let mut coefficient = monomial.coefficient.clone();
+            let mut variables: Vec<Var> = Vec::new();
+            for v in &monomial.variables {
+                match values.iter().find(|(symbol, _)| *symbol == v.get_symbol()) {
+                    Some((_, value)) => {
+                        let exponent = v.get_exponent();
+                        let power = power(value, exponent.magnitude().iter_u64_digits().next().unwrap_or(0));
+                        coefficient = if exponent < BigInt::from(0) { coefficient.div(&power) } else { coefficient.mul(&power) };
+                    },
+                    None => variables.push(v.clone())
+                }
+            }
+            Monomial::new_monomial(variables, coefficient)
+        }).collect();
+        Polynomial::from_monomials(&x, monomials)
+    }
+
+    pub fn evaluate<T>(x: PolynomialInstance<T>, values: &[(&'static str, T)]) -> Result<T> where T: Instance + Operand + Clone + Number {
+        let result = Polynomial::substitute(x, values);
+        if result.monomials.iter().any(|monomial| !monomial.variables.is_empty()) {
+            return Err(Error::InvalidInput("A value is needed for every variable".to_string()));
+        }
+        Ok(match result.monomials.first() {
+            Some(constant) => constant.coefficient.clone(),
+            None => T::zero()
+        })
+    }
+}
+
+// value^exponent with exponent > 0
+fn power<T: Operand + Clone>(value: &T, exponent: u64) -> T {
+    let mut result = value.clone();
+    for bit in (0..63 - exponent.leading_zeros()).rev() {
+        result = result.mul(&result);
+        if (exponent >> bit) & 1 == 1 {
+            result = result.mul(value);
+        }
+    }
+    result
+}
+
+// n value by double and add, without building n in the coefficient ring
+pub(crate) fn scalar_multiple<T: Operand + Clone>(value: &T, n: &BigInt) -> T {
+    let mut result = value.sub(value);
+    for bit in (0..n.bits()).rev() {
+        result = result.add(&result);
+        if n.magnitude().bit(bit) {
+            result = result.add(value);
+        }
+    }
+    if *n < BigInt::from(0) { result.neg() } else { result }
+}
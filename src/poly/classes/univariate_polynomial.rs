@@ -16,13 +16,16 @@ use crate::numbers::numbers::StatefulClass;
 use crate::numbers::numbers::Number;
 use crate::numbers::sets::Class::ClassTypes;
 use crate::poly::instances::univariate_polynomial_instance::UnivariatePolynomialInstance;
+use crate::poly::evaluation;
+use crate::poly::multiplication;
 use crate::utilities;
-use crate::utilities::utils::poly_divmod;
+use crate::utilities::utils::try_poly_divmod;
 use crate::variables::vars::Var;
 use crate::numbers::numbers::Instance;
 use crate::numbers::numbers::Operand;
 use crate::algebras::Rings::classes::PolynomialRing::PolynomialRing;
 use crate::algebras::Rings::instances::PolynomialRing_instance::PolynomialRingInstance;
+use crate::error::{unwrap_or_panic, Error, Result};
 
 // utilities
 fn clean<T>(mut coeff: Vec<T>) -> Vec<T> where T: Instance + Operand + Clone + Number {
@@ -36,17 +39,20 @@ fn clean<T>(mut coeff: Vec<T>) -> Vec<T> where T: Instance + Operand + Clone + N
     coeff
 }
 
+// None in a polynomial chooses the algorithm from the degrees of the operands
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum PolyMultiplicationAlgorithm {
-    ToomCook,
     Naive,
-    EvaluationForm
+    Karatsuba,
+    ToomCook,
+    NTT
 }
 
 // POLYNOMIAL
 #[derive(Clone)]
 pub struct UnivariatePolynomial {
     pub class: ClassTypes,
-    pub multiplication_algorithm: Option<String>
+    pub multiplication_algorithm: Option<PolyMultiplicationAlgorithm>
 }
 
 impl PartialEq for UnivariatePolynomial {
@@ -57,26 +63,18 @@ impl PartialEq for UnivariatePolynomial {
 impl Eq for UnivariatePolynomial {}
 
 impl UnivariatePolynomial {
-    pub fn new(multiplication_algorithm: Option<String>) -> UnivariatePolynomial {
+    pub fn new(multiplication_algorithm: Option<PolyMultiplicationAlgorithm>) -> UnivariatePolynomial {
         UnivariatePolynomial {class: ClassTypes::UnivariatePolynomial, multiplication_algorithm}
     }
 
 
-    pub fn new_instance<T>(coefficients: Vec<T>, var: Var, multiplication_algorithm: Option<String>, clean_coefficients: bool) -> UnivariatePolynomialInstance<T> where T: Instance + Clone + PartialEq + Operand + Number {
-        let class: UnivariatePolynomial = UnivariatePolynomial::new(multiplication_algorithm.clone());
+    pub fn new_instance<T>(coefficients: Vec<T>, var: Var, multiplication_algorithm: Option<PolyMultiplicationAlgorithm>, clean_coefficients: bool) -> UnivariatePolynomialInstance<T> where T: Instance + Clone + PartialEq + Operand + Number {
+        let class: UnivariatePolynomial = UnivariatePolynomial::new(multiplication_algorithm);
         
         if clean_coefficients {
-            if multiplication_algorithm.is_none() {
-                UnivariatePolynomialInstance { class: RefCell::new(class), coefficients: clean::<T>(coefficients), var: var, clean_coefficients: clean_coefficients}
-            } else {
-                UnivariatePolynomialInstance { class: RefCell::new(class), coefficients: clean::<T>(coefficients), var: var, clean_coefficients: clean_coefficients }
-            }
+            UnivariatePolynomialInstance { class: RefCell::new(class), coefficients: clean::<T>(coefficients), var: var, clean_coefficients: clean_coefficients}
         } else {
-            if multiplication_algorithm.is_none() {
-                UnivariatePolynomialInstance { class: RefCell::new(class), coefficients: coefficients, var: var, clean_coefficients: clean_coefficients}
-            } else {
-                UnivariatePolynomialInstance { class: RefCell::new(class), coefficients: coefficients, var: var, clean_coefficients: clean_coefficients }
-            }
+            UnivariatePolynomialInstance { class: RefCell::new(class), coefficients: coefficients, var: var, clean_coefficients: clean_coefficients}
         }
     }
 
@@ -97,6 +95,32 @@ impl UnivariatePolynomial {
 }
 
 impl UnivariatePolynomial {
+    // the polynomial of degree < n through n points with distinct abscissas, the coefficients must be a field
+    pub fn lagrange_interpolation<T>(points: &[(T, T)], var: Var) -> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number + 'static {
+        unwrap_or_panic(UnivariatePolynomial::try_lagrange_interpolation(points, var))
+    }
+
+    pub fn try_lagrange_interpolation<T>(points: &[(T, T)], var: Var) -> Result<UnivariatePolynomialInstance<T>> where T: Instance + Operand + Clone + PartialEq + Number + 'static {
+        let coefficients = evaluation::lagrange(points)?;
+        Ok(UnivariatePolynomial::from_interpolation(coefficients, points, var))
+    }
+
+    pub fn newton_interpolation<T>(points: &[(T, T)], var: Var) -> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number + 'static {
+        unwrap_or_panic(UnivariatePolynomial::try_newton_interpolation(points, var))
+    }
+
+    pub fn try_newton_interpolation<T>(points: &[(T, T)], var: Var) -> Result<UnivariatePolynomialInstance<T>> where T: Instance + Operand + Clone + PartialEq + Number + 'static {
+        let coefficients = evaluation::newton(points)?;
+        Ok(UnivariatePolynomial::from_interpolation(coefficients, points, var))
+    }
+
+    fn from_interpolation<T>(mut coefficients: Vec<T>, points: &[(T, T)], var: Var) -> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number {
+        if coefficients.is_empty() {
+            coefficients.push(points[0].1.sub(&points[0].1));
+        }
+        UnivariatePolynomial::new_instance(coefficients, var, None, true)
+    }
+
     pub fn neg<T>(x: UnivariatePolynomialInstance<T>) -> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number{
         let mut coefficients: Vec<T> = x.coefficients.clone();
         for i in 0..coefficients.len() {
@@ -107,6 +131,10 @@ impl UnivariatePolynomial {
     }
 
     pub fn add<T>(x: UnivariatePolynomialInstance<T>, y: UnivariatePolynomialInstance<T>) -> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number{
+        unwrap_or_panic(UnivariatePolynomial::try_add(x, y))
+    }
+
+    pub fn try_add<T>(x: UnivariatePolynomialInstance<T>, y: UnivariatePolynomialInstance<T>) -> Result<UnivariatePolynomialInstance<T>> where T: Instance + Operand + Clone + PartialEq + Number{
         if x.var == y.var {
 
             let mut coeff = Vec::new();
@@ -130,13 +158,17 @@ impl UnivariatePolynomial {
                 }
             }
 
-            UnivariatePolynomial::new_instance(coeff, x.var.clone(), x.class.into_inner().multiplication_algorithm, x.clean_coefficients)
+            Ok(UnivariatePolynomial::new_instance(coeff, x.var.clone(), x.class.into_inner().multiplication_algorithm, x.clean_coefficients))
         } else {
-            panic!("Cannot add these polynomials")
+            Err(Error::IncompatibleOperands("Cannot add these polynomials".to_string()))
         }
     }
 
     pub fn sub<T>(x: UnivariatePolynomialInstance<T>, y: UnivariatePolynomialInstance<T>) -> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number{
+        unwrap_or_panic(UnivariatePolynomial::try_sub(x, y))
+    }
+
+    pub fn try_sub<T>(x: UnivariatePolynomialInstance<T>, y: UnivariatePolynomialInstance<T>) -> Result<UnivariatePolynomialInstance<T>> where T: Instance + Operand + Clone + PartialEq + Number{
         if  x.var == y.var {
             let mut coeff = Vec::new();
             if x.coefficients.len() > y.coefficients.len() {
@@ -159,43 +191,25 @@ impl UnivariatePolynomial {
                 }
             }
 
-            UnivariatePolynomial::new_instance(coeff, x.var.clone(), x.class.into_inner().multiplication_algorithm, x.clean_coefficients)
+            Ok(UnivariatePolynomial::new_instance(coeff, x.var.clone(), x.class.into_inner().multiplication_algorithm, x.clean_coefficients))
         } else {
-            panic!("ERROR: Cannot sub these polynomials")
+            Err(Error::IncompatibleOperands("ERROR: Cannot sub these polynomials".to_string()))
         }
     }
 
 
-    pub fn mul<T>(x: UnivariatePolynomialInstance<T>, y: UnivariatePolynomialInstance<T>) -> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number{
-        if x.var == y.var {
-            // SCHOOLBOOK MULTIPLICATION
-            //if x.multiplication_algorithm == "Naive" {
-                let len = x.coefficients.len() + y.coefficients.len() -1;
-                let mut coeff: Vec<T> = vec![T::zero(); len];
-                
-                // schoolbook multiplication
-                for i in 0..x.coefficients.len() {
-                    // perform x[i] * y
-                    for j in 0..y.coefficients.len() {
-                        coeff[i+j] = coeff[i+j].clone().add(&(x.coefficients[i].clone().mul(&(y.coefficients[j]))));
-                    }
-                }
-                
-                return UnivariatePolynomial::new_instance(coeff, x.var.clone(), x.class.into_inner().multiplication_algorithm, x.clean_coefficients)
-            // } else if x.multiplication_algorithm == "EvaluationMethod" {
-            //     // need a random function
-            //     // get degree of polynomials
-            //     // get higher degree points
-            //     // evaluate points
-            //     // lagrange interpolation
-            // }
-
+    pub fn mul<T>(x: UnivariatePolynomialInstance<T>, y: UnivariatePolynomialInstance<T>) -> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number + 'static {
+        unwrap_or_panic(UnivariatePolynomial::try_mul(x, y))
+    }
 
+    pub fn try_mul<T>(x: UnivariatePolynomialInstance<T>, y: UnivariatePolynomialInstance<T>) -> Result<UnivariatePolynomialInstance<T>> where T: Instance + Operand + Clone + PartialEq + Number + 'static {
+        if x.var == y.var {
+            let algorithm = x.class.borrow().multiplication_algorithm;
+            let coeff: Vec<T> = multiplication::multiply(&x.coefficients, &y.coefficients, algorithm);
+            Ok(UnivariatePolynomial::new_instance(coeff, x.var.clone(), algorithm, x.clean_coefficients))
         } else {
-            panic!("Cannot multiply those 2 polynomials");
+            Err(Error::IncompatibleOperands("Cannot multiply those 2 polynomials".to_string()))
         }
-
-
     }
 
     pub fn mul_by_scalar<T>(x: UnivariatePolynomialInstance<T>, y: T) -> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number  {
@@ -214,8 +228,16 @@ impl UnivariatePolynomial {
     }
 
     pub fn div<T>(x: UnivariatePolynomialInstance<T>, y: UnivariatePolynomialInstance<T>) -> (UnivariatePolynomialInstance<T>, UnivariatePolynomialInstance<T>) where T: Display + Instance + Operand + Clone + PartialEq + Number + ClassInstance + 'static{
-        let q_and_r: Vec<UnivariatePolynomialInstance<T>> = poly_divmod(&x.clone(), &y.clone());
-        (q_and_r[0].clone(), q_and_r[1].clone())
+        unwrap_or_panic(UnivariatePolynomial::try_div(x, y))
+    }
+
+    // (quotient, remainder)
+    pub fn try_div<T>(x: UnivariatePolynomialInstance<T>, y: UnivariatePolynomialInstance<T>) -> Result<(UnivariatePolynomialInstance<T>, UnivariatePolynomialInstance<T>)> where T: Display + Instance + Operand + Clone + PartialEq + Number + ClassInstance + 'static{
+        if x.var != y.var {
+            return Err(Error::IncompatibleOperands("Cannot divide those 2 polynomials".to_string()));
+        }
+        let q_and_r: Vec<UnivariatePolynomialInstance<T>> = try_poly_divmod(&x.clone(), &y.clone())?;
+        Ok((q_and_r[0].clone(), q_and_r[1].clone()))
     }
 
 }
@@ -0,0 +1,236 @@
+use crate::error::{Error, Result};
+use crate::numbers::numbers::{Instance, Number, Operand};
+use crate::poly::gcd::{derivative, divmod, gcd, trim};
+use crate::poly::multiplication;
+
+/*
+    Evaluation, interpolation and real roots of coefficient vectors (lowest degree first).
+    Interpolation needs field coefficients, the real roots an ordered field (QQ, RR):
+    over RR the remainders are rounded, so the polynomial should be square-free.
+*/
+
+// below this number of points Horner's rule is used for each of them
+const MULTIPOINT_THRESHOLD: usize = 32;
+
+pub fn horner<T>(a: &[T], x: &T) -> T where T: Operand + Clone {
+    a.iter().rev().fold(x.sub(x), |acc, c| acc.mul(x).add(c))
+}
+
+/*
+    Remainder tree: the subproduct tree of the points is built once, then f is reduced
+    from the root down modulo the product of the (x - a_i) of each node. The divisions
+    by those monic products use Newton iteration
+*/
+pub fn multipoint<T>(a: &[T], points: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if points.len() <= MULTIPOINT_THRESHOLD {
+        return points.iter().map(|x| horner(a, x)).collect();
+    }
+    let a = trim(a);
+    let one = match a.last() {
+        Some(lc) => lc.div(lc),
+        None => return points.iter().map(|x| x.sub(x)).collect()
+    };
+    let mut values = Vec::with_capacity(points.len());
+    SubproductTree::new(points, &one).evaluate(&a, points, &mut values);
+    values
+}
+
+/*
+    L = sum y_i / M'(x_i) M / (x - x_i) with M the product of the (x - x_i)
+*/
+pub fn lagrange<T>(points: &[(T, T)]) -> Result<Vec<T>> where T: Instance + Operand + Clone + Number + 'static {
+    check_points(points)?;
+    let xs: Vec<T> = points.iter().map(|(x, _)| x.clone()).collect();
+    // the points are distinct, so only a single point can be (0, 0)
+    let one = match points.iter().flat_map(|(x, y)| [x, y]).find(|c| !(*c).clone().is_zero()) {
+        Some(c) => c.div(c),
+        None => return Ok(Vec::new())
+    };
+    let m = linear_product(&xs, &one);
+    let weights = multipoint(&derivative(&m), &xs);
+    let zero = xs[0].sub(&xs[0]);
+    let mut result = vec![zero; points.len()];
+    for ((x, y), weight) in points.iter().zip(&weights) {
+        let factor = y.div(weight);
+        // synthetic division of M by (x - x_i)
+        let mut carry = m[m.len() - 1].clone();
+        for k in (0..m.len() - 1).rev() {
+            result[k] = result[k].add(&carry.mul(&factor));
+            carry = m[k].add(&carry.mul(x));
+        }
+    }
+    Ok(trim(&result))
+}
+
+// divided differences, then the Newton form c_0 + (x - x_0)(c_1 + (x - x_1)(...)) is expanded
+pub fn newton<T>(points: &[(T, T)]) -> Result<Vec<T>> where T: Instance + Operand + Clone + Number + 'static {
+    check_points(points)?;
+    let n = points.len();
+    let mut c: Vec<T> = points.iter().map(|(_, y)| y.clone()).collect();
+    for j in 1..n {
+        for i in (j..n).rev() {
+            c[i] = c[i].sub(&c[i - 1]).div(&points[i].0.sub(&points[i - j].0));
+        }
+    }
+    let mut result = vec![c[n - 1].clone()];
+    for k in (0..n - 1).rev() {
+        let x = &points[k].0;
+        let mut shifted = vec![c[k].clone()];
+        shifted.extend(result.iter().cloned());
+        for (i, coefficient) in result.iter().enumerate() {
+            shifted[i] = shifted[i].sub(&coefficient.mul(x));
+        }
+        result = shifted;
+    }
+    Ok(trim(&result))
+}
+
+/*
+    Isolating intervals (a, b] of the distinct real roots, in increasing order: the
+    interval (-B, B] from Cauchy's bound is bisected, the number of roots in (a, b]
+    is the difference of the sign variations of the Sturm sequence in a and b
+*/
+pub fn real_root_intervals<T>(a: &[T]) -> Vec<(T, T)> where T: Instance + Operand + Clone + Number + 'static {
+    let f = trim(a);
+    if f.len() < 2 {
+        return Vec::new();
+    }
+    let f = divmod(&f, &gcd(&f, &derivative(&f))).0;
+    let sequence = sturm_sequence(&f);
+
+    let lc = &f[f.len() - 1];
+    let (zero, one) = (lc.sub(lc), lc.div(lc));
+    let two = one.add(&one);
+    let absolute = |c: T| if c.less_than(&zero) { c.neg() } else { c };
+    let bound = f.iter().map(|c| absolute(c.div(lc))).fold(zero.clone(), |m, c| if c.greater_than(&m) { c } else { m }).add(&one);
+
+    let mut result = Vec::new();
+    let mut intervals = vec![(bound.neg(), bound)];
+    while let Some((a, b)) = intervals.pop() {
+        match sign_variations(&sequence, &a).saturating_sub(sign_variations(&sequence, &b)) {
+            0 => continue,
+            1 => result.push((a, b)),
+            _ => {
+                let middle = a.add(&b).div(&two);
+                intervals.push((middle.clone(), b));
+                intervals.push((a, middle));
+            }
+        }
+    }
+    result
+}
+
+fn sturm_sequence<T>(f: &[T]) -> Vec<Vec<T>> where T: Instance + Operand + Clone + Number + 'static {
+    let mut sequence = vec![f.to_vec(), derivative(f)];
+    loop {
+        let k = sequence.len();
+        let remainder = divmod(&sequence[k - 2], &sequence[k - 1]).1;
+        if remainder.is_empty() {
+            return sequence;
+        }
+        sequence.push(remainder.iter().map(|c| c.neg()).collect());
+    }
+}
+
+fn sign_variations<T>(sequence: &[Vec<T>], x: &T) -> usize where T: Operand + Clone + Number {
+    let zero = x.sub(x);
+    let signs: Vec<bool> = sequence.iter().map(|p| horner(p, x)).filter(|value| !value.clone().is_zero()).map(|value| value.less_than(&zero)).collect();
+    signs.windows(2).filter(|pair| pair[0] != pair[1]).count()
+}
+
+fn check_points<T: Operand>(points: &[(T, T)]) -> Result<()> {
+    if points.is_empty() {
+        return Err(Error::InvalidInput("At least one point is needed".to_string()));
+    }
+    for (i, (x, _)) in points.iter().enumerate() {
+        if points[..i].iter().any(|(other, _)| other.equal(x)) {
+            return Err(Error::InvalidInput("The interpolation points must be distinct".to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn mul<T>(a: &[T], b: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    multiplication::multiply(a, b, None)
+}
+
+// the product of the (x - x_i), split in halves to use the fast multiplications
+fn linear_product<T>(xs: &[T], one: &T) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if xs.len() == 1 {
+        return vec![xs[0].neg(), one.clone()];
+    }
+    let (left, right) = xs.split_at(xs.len() / 2);
+    mul(&linear_product(left, one), &linear_product(right, one))
+}
+
+/*
+    The product of the (x - a_i) of the points of a node, whose children split
+    the points in halves until MULTIPOINT_THRESHOLD points are left
+*/
+struct SubproductTree<T> {
+    product: Vec<T>,
+    children: Option<Box<(SubproductTree<T>, SubproductTree<T>)>>
+}
+
+impl<T> SubproductTree<T> where T: Instance + Operand + Clone + Number + 'static {
+    fn new(points: &[T], one: &T) -> SubproductTree<T> {
+        if points.len() <= MULTIPOINT_THRESHOLD {
+            return SubproductTree { product: linear_product(points, one), children: None };
+        }
+        let (left, right) = points.split_at(points.len() / 2);
+        let (left, right) = (SubproductTree::new(left, one), SubproductTree::new(right, one));
+        SubproductTree { product: mul(&left.product, &right.product), children: Some(Box::new((left, right))) }
+    }
+
+    // the values of a at the points of the node, Horner's rule on the remainder at the leaves
+    fn evaluate(&self, a: &[T], points: &[T], values: &mut Vec<T>) {
+        let remainder = fast_divmod(a, &self.product).1;
+        match &self.children {
+            Some(children) => {
+                let (left, right) = points.split_at(points.len() / 2);
+                children.0.evaluate(&remainder, left, values);
+                children.1.evaluate(&remainder, right, values);
+            },
+            None => values.extend(points.iter().map(|x| horner(&remainder, x)))
+        }
+    }
+}
+
+// inverse of the power series m modulo x^n (m[0] invertible): g = g (2 - m g)
+fn series_inverse<T>(m: &[T], n: usize) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    let one = m[0].div(&m[0]);
+    let two = one.add(&one);
+    let mut inverse = vec![one.div(&m[0])];
+    let mut precision = 1;
+    while precision < n {
+        precision = (2 * precision).min(n);
+        let mut correction: Vec<T> = mul(&m[..precision.min(m.len())], &inverse).into_iter().take(precision).map(|c| c.neg()).collect();
+        correction[0] = correction[0].add(&two);
+        inverse = mul(&inverse, &correction).into_iter().take(precision).collect();
+    }
+    inverse
+}
+
+// quotient and remainder from the reversed polynomials: rev(q) = rev(a) / rev(b) mod x^(deg a - deg b + 1)
+fn fast_divmod<T>(a: &[T], b: &[T]) -> (Vec<T>, Vec<T>) where T: Instance + Operand + Clone + Number + 'static {
+    let a = trim(a);
+    if a.len() < b.len() {
+        return (Vec::new(), a);
+    }
+    let k = a.len() - b.len() + 1;
+    let reversed_a: Vec<T> = a.iter().rev().take(k).cloned().collect();
+    let reversed_b: Vec<T> = b.iter().rev().cloned().collect();
+    let mut quotient: Vec<T> = mul(&reversed_a, &series_inverse(&reversed_b, k)).into_iter().take(k).collect();
+    quotient.resize(k, a[0].sub(&a[0]));
+    quotient.reverse();
+    let quotient = trim(&quotient);
+    let product = mul(b, &quotient);
+    let remainder: Vec<T> = a.iter().enumerate().map(|(i, c)| match product.get(i) {
+        Some(p) => c.sub(p),
+        None => c.clone()
+    }).take(b.len() - 1).collect();
+    (quotient, trim(&remainder))
+}
@@ -0,0 +1,155 @@
+use num_bigint::BigInt;
+
+use crate::algebras::FiniteField::instances::Zmod_instance::ZmodInstance;
+use crate::arith::random::get_random_bigint_with_bounds;
+use crate::error::{Error, Result};
+use crate::numbers::numbers::{Class, Operand};
+use crate::poly::gcd::{add, divmod, gcd, monic, mulmod, powmod, sub, trim};
+
+/*
+    Factorisation over Zmod p (p prime) of coefficient vectors (lowest degree first), with the
+    arithmetic of poly::gcd. The distinct-degree and equal-degree steps expect monic square-free
+    polynomials, the square-free part is computed with gcd::square_free_decomposition.
+*/
+
+// pairs (g, d) where g is the product of the irreducible factors of degree d
+pub fn distinct_degree(f: &[ZmodInstance], p: &BigInt) -> Vec<(Vec<ZmodInstance>, usize)> {
+    let mut result = Vec::new();
+    let mut f = monic(&trim(f));
+    if f.len() < 2 {
+        return result;
+    }
+    let x = variable(&f);
+    let mut h = divmod(&x, &f).1;
+    let mut degree = 1;
+    // h = x^(p^degree) mod f
+    while f.len() > 2 * degree {
+        h = powmod(&h, p, &f);
+        let g = gcd(&f, &sub(&h, &x));
+        if g.len() > 1 {
+            f = divmod(&f, &g).0;
+            h = divmod(&h, &f).1;
+            result.push((g, degree));
+        }
+        degree += 1;
+    }
+    if f.len() > 1 {
+        let degree = f.len() - 1;
+        result.push((f, degree));
+    }
+    result
+}
+
+/*
+    Cantor–Zassenhaus: f must be a product of distinct irreducible factors of the given degree,
+    otherwise the random splitting would never end
+*/
+pub fn equal_degree(f: &[ZmodInstance], degree: usize, p: &BigInt) -> Result<Vec<Vec<ZmodInstance>>> {
+    let f = monic(&trim(f));
+    if degree == 0 || f.len() < 2 || !(f.len() - 1).is_multiple_of(degree) || !has_factors_of_degree(&f, degree, p) {
+        return Err(Error::InvalidInput("The polynomial must be a square-free product of irreducible factors of the given degree".to_string()));
+    }
+    Ok(split_equal_degree(&f, degree, p))
+}
+
+// the distinct roots, from the linear factors of gcd(f, x^p - x)
+pub fn roots(f: &[ZmodInstance], p: &BigInt) -> Vec<ZmodInstance> {
+    let f = monic(&trim(f));
+    if f.len() < 2 {
+        return Vec::new();
+    }
+    let x = variable(&f);
+    let g = gcd(&f, &sub(&powmod(&x, p, &f), &x));
+    if g.len() < 2 {
+        return Vec::new();
+    }
+    let mut roots: Vec<ZmodInstance> = split_equal_degree(&g, 1, p).into_iter().map(|factor| factor[0].neg()).collect();
+    roots.sort_by(|a, b| a.value.value.cmp(&b.value.value));
+    roots
+}
+
+// Rabin's test: the irreducible polynomials of degree n are the ones with all their factors of degree n
+pub fn is_irreducible(f: &[ZmodInstance], p: &BigInt) -> bool {
+    let f = monic(&trim(f));
+    f.len() >= 2 && has_factors_of_degree(&f, f.len() - 1, p)
+}
+
+/*
+    f (monic) divides x^(p^d) - x, which is square-free, and is coprime with x^(p^(d/q)) - x
+    for every prime q dividing d: f is square-free and its irreducible factors have degree d
+*/
+fn has_factors_of_degree(f: &[ZmodInstance], d: usize, p: &BigInt) -> bool {
+    let x = variable(f);
+    // h = x^(p^k) mod f, raised to the p-th power up to the next exponent (d / q increases with q decreasing)
+    let mut h = divmod(&x, f).1;
+    let mut k = 0;
+    for q in prime_divisors(d).into_iter().rev() {
+        h = (k..d / q).fold(h, |h, _| powmod(&h, p, f));
+        k = d / q;
+        if gcd(f, &sub(&h, &x)).len() != 1 {
+            return false;
+        }
+    }
+    h = (k..d).fold(h, |h, _| powmod(&h, p, f));
+    divmod(&sub(&h, &x), f).1.is_empty()
+}
+
+/*
+    A random a splits f through gcd(f, a^((p^d - 1)/2) - 1), or gcd(f, a + a^2 + ... + a^(2^(d-1))) for p = 2
+*/
+fn split_equal_degree(f: &[ZmodInstance], degree: usize, p: &BigInt) -> Vec<Vec<ZmodInstance>> {
+    if f.len() - 1 <= degree {
+        return vec![f.to_vec()];
+    }
+    let field = f[0].class.borrow().clone();
+    let one = vec![field.apply(BigInt::from(1))];
+    let exponent = (p.pow(degree as u32) - 1) / 2;
+    loop {
+        let a = trim(&(0..f.len() - 1).map(|_| field.apply(get_random_bigint_with_bounds(BigInt::from(0), p.clone()))).collect::<Vec<ZmodInstance>>());
+        if a.len() < 2 {
+            continue;
+        }
+        let b = if *p == BigInt::from(2) {
+            let mut power = a.clone();
+            let mut trace = a;
+            for _ in 1..degree {
+                power = mulmod(&power, &power, f);
+                trace = add(&trace, &power);
+            }
+            trace
+        } else {
+            sub(&powmod(&a, &exponent, f), &one)
+        };
+        let g = gcd(f, &b);
+        if g.len() > 1 && g.len() < f.len() {
+            let mut factors = split_equal_degree(&g, degree, p);
+            factors.extend(split_equal_degree(&divmod(f, &g).0, degree, p));
+            return factors;
+        }
+    }
+}
+
+// the polynomial x over the field of the coefficients of f
+fn variable(f: &[ZmodInstance]) -> Vec<ZmodInstance> {
+    let lc = &f[f.len() - 1];
+    let one = lc.div(lc);
+    vec![one.sub(&one), one]
+}
+
+fn prime_divisors(mut n: usize) -> Vec<usize> {
+    let mut divisors = Vec::new();
+    let mut q = 2;
+    while q * q <= n {
+        if n.is_multiple_of(q) {
+            divisors.push(q);
+            while n.is_multiple_of(q) {
+                n /= q;
+            }
+        }
+        q += 1;
+    }
+    if n > 1 {
+        divisors.push(n);
+    }
+    divisors
+}
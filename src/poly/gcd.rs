@@ -0,0 +1,516 @@
+use std::any::{Any, TypeId};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::algebras::FiniteField::classes::Zmod::Zmod;
+use crate::algebras::FiniteField::instances::Zmod_instance::ZmodInstance;
+use crate::error::{Error, Result};
+use crate::numbers::classes::ZZ::ZZ;
+use crate::numbers::instances::ZZ_instance::ZZinstance;
+use crate::numbers::numbers::{Class, Instance, Number, Operand};
+use crate::poly::classes::polynomial::scalar_multiple;
+use crate::poly::multiplication;
+
+/*
+    Gcd, resultant and square-free decomposition of coefficient vectors (lowest degree first,
+    the zero polynomial is the empty vector). The coefficients are a field (QQ, Zmod with a
+    prime modulus) or ZZ: over a field the gcd is monic, over ZZ it comes from the
+    pseudo-remainders of primitive polynomials and has a positive leading coefficient.
+    The resultant follows the subresultant sequence, which only needs exact divisions.
+    Products, powers and inverses modulo a polynomial are computed over a field. Zmod coefficients
+    with a common modulus are lifted to their residues, as for the products in multiplication.
+*/
+
+pub fn gcd<T>(a: &[T], b: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if is_integer::<T>() {
+        return integer_gcd(&trim(a), &trim(b));
+    }
+    if let Some((field, p, x, y)) = lift(a, b) {
+        if let Some(g) = residue_gcd(x, y, &p) {
+            return lower(&field, g);
+        }
+    }
+    let (mut a, mut b) = (trim(a), trim(b));
+    while !b.is_empty() {
+        let remainder = divmod(&a, &b).1;
+        a = b;
+        b = remainder;
+    }
+    monic(&a)
+}
+
+/*
+    (d, s, t) with s a + t b = d, the monic gcd. Only over a field
+*/
+pub fn xgcd<T>(a: &[T], b: &[T]) -> Result<(Vec<T>, Vec<T>, Vec<T>)> where T: Instance + Operand + Clone + Number + 'static {
+    if is_integer::<T>() {
+        return Err(Error::InvalidInput("The extended gcd needs field coefficients".to_string()));
+    }
+    let (a, b) = (trim(a), trim(b));
+    let one = match a.last().or(b.last()) {
+        Some(c) => c.div(c),
+        None => return Ok((Vec::new(), Vec::new(), Vec::new()))
+    };
+    let (mut r0, mut s0, mut t0) = (a, vec![one.clone()], Vec::new());
+    let (mut r1, mut s1, mut t1) = (b, Vec::new(), vec![one.clone()]);
+    while !r1.is_empty() {
+        let (q, r) = divmod(&r0, &r1);
+        let s = sub(&s0, &mul(&q, &s1));
+        let t = sub(&t0, &mul(&q, &t1));
+        (r0, s0, t0) = (r1, s1, t1);
+        (r1, s1, t1) = (r, s, t);
+    }
+    let inverse = one.div(r0.last().unwrap());
+    Ok((scale(&r0, &inverse), scale(&s0, &inverse), scale(&t0, &inverse)))
+}
+
+/*
+    Subresultant algorithm (Cohen, Algorithm 3.3.7) without the content removal
+*/
+pub fn resultant<T>(a: &[T], b: &[T]) -> T where T: Instance + Operand + Clone + Number + 'static {
+    let (mut a, mut b) = (trim(a), trim(b));
+    if a.is_empty() || b.is_empty() {
+        return match a.first().or(b.first()) {
+            Some(c) => c.sub(c),
+            None => T::zero()
+        };
+    }
+    let one = a[a.len() - 1].div(&a[a.len() - 1]);
+    let mut negative = false;
+    if a.len() < b.len() {
+        std::mem::swap(&mut a, &mut b);
+        negative = (a.len() - 1) % 2 == 1 && (b.len() - 1) % 2 == 1;
+    }
+    let (mut g, mut h) = (one.clone(), one.clone());
+    while b.len() > 1 {
+        let (degree_a, degree_b) = (a.len() - 1, b.len() - 1);
+        let delta = degree_a - degree_b;
+        if degree_a % 2 == 1 && degree_b % 2 == 1 {
+            negative = !negative;
+        }
+        let remainder = pseudo_remainder(&a, &b);
+        if remainder.is_empty() {
+            return one.sub(&one);
+        }
+        let divisor = g.mul(&power(&h, delta, &one));
+        a = b;
+        b = remainder.iter().map(|c| c.div(&divisor)).collect();
+        g = a[a.len() - 1].clone();
+        if delta > 0 {
+            h = power(&g, delta, &one).div(&power(&h, delta - 1, &one));
+        }
+    }
+    let degree_a = a.len() - 1;
+    let result = if degree_a == 0 { h } else { power(&b[0], degree_a, &one).div(&power(&h, degree_a - 1, &one)) };
+    if negative { result.neg() } else { result }
+}
+
+// (-1)^(n(n-1)/2) lc^(n-2-deg f') res(f, f'), the derivative may lose degrees in characteristic p
+pub fn discriminant<T>(a: &[T]) -> Result<T> where T: Instance + Operand + Clone + Number + 'static {
+    let f = trim(a);
+    if f.len() < 2 {
+        return Err(Error::InvalidInput("The discriminant needs a polynomial of positive degree".to_string()));
+    }
+    let n = f.len() - 1;
+    let lc = f[n].clone();
+    let df = derivative(&f);
+    if df.is_empty() {
+        return Ok(lc.sub(&lc));
+    }
+    let result = resultant(&f, &df);
+    let result = match (n - 1).checked_sub(df.len()) {
+        Some(exponent) => result.mul(&power(&lc, exponent, &lc.div(&lc))),
+        None => result.div(&lc)
+    };
+    Ok(if (n * (n - 1) / 2) % 2 == 1 { result.neg() } else { result })
+}
+
+pub fn derivative<T>(a: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    trim(&a.iter().enumerate().skip(1).map(|(i, c)| scalar_multiple(c, &BigInt::from(i))).collect::<Vec<T>>())
+}
+
+/*
+    Square-free factors with their multiplicity, f is their product up to a constant. The factors
+    are monic (primitive over ZZ). In characteristic p, what is left after the gcd steps is a
+    p-th power: its root is decomposed again (Musser's algorithm)
+*/
+pub fn square_free_decomposition<T>(a: &[T]) -> Vec<(Vec<T>, usize)> where T: Instance + Operand + Clone + Number + 'static {
+    let f = normalize(&trim(a));
+    let mut result = Vec::new();
+    if f.len() < 2 {
+        return result;
+    }
+    let mut c = gcd(&f, &derivative(&f));
+    let mut w = divmod(&f, &c).0;
+    let mut multiplicity = 1;
+    while w.len() > 1 {
+        let y = gcd(&w, &c);
+        let z = divmod(&w, &y).0;
+        if z.len() > 1 {
+            result.push((normalize(&z), multiplicity));
+        }
+        multiplicity += 1;
+        c = divmod(&c, &y).0;
+        w = y;
+    }
+    if c.len() > 1 {
+        if let Some(p) = characteristic(&f) {
+            // the Frobenius is the identity on Zmod p, so the root keeps the coefficients
+            // of the exponents that are multiples of p (only the constant one when p > deg c)
+            let root: Vec<T> = c.iter().enumerate().filter(|(i, _)| BigInt::from(*i).is_multiple_of(&p)).map(|(_, c)| c.clone()).collect();
+            for (g, m) in square_free_decomposition(&root) {
+                // g is not constant, so p is at most the degree of c
+                result.push((g, m * p.to_usize().unwrap()));
+            }
+            result.sort_by_key(|(_, m)| *m);
+        }
+    }
+    result
+}
+
+/*
+    Quotient and remainder. Over ZZ the leading coefficient of b must divide the ones met
+    (exact division), otherwise it must be invertible
+*/
+pub fn divmod<T>(a: &[T], b: &[T]) -> (Vec<T>, Vec<T>) where T: Instance + Operand + Clone + Number + 'static {
+    if let Some((field, p, x, y)) = lift(a, b) {
+        if let Some((quotient, remainder)) = residue_divmod(x, &y, &p) {
+            return (lower(&field, quotient), lower(&field, remainder));
+        }
+    }
+    let (mut remainder, b) = (trim(a), trim(b));
+    if remainder.len() < b.len() {
+        return (Vec::new(), remainder);
+    }
+    let lc = &b[b.len() - 1];
+    // over a field the leading coefficient is inverted once
+    let inverse = if is_integer::<T>() { None } else { Some(lc.div(lc).div(lc)) };
+    let mut quotient = vec![lc.sub(lc); remainder.len() - b.len() + 1];
+    while remainder.len() >= b.len() {
+        let shift = remainder.len() - b.len();
+        let leading = &remainder[remainder.len() - 1];
+        let factor = match &inverse {
+            Some(inverse) => leading.mul(inverse),
+            None => leading.div(lc)
+        };
+        for (i, c) in b.iter().enumerate() {
+            remainder[shift + i] = remainder[shift + i].sub(&factor.mul(c));
+        }
+        quotient[shift] = factor;
+        remainder.pop();
+        while remainder.last().is_some_and(|c| c.clone().is_zero()) {
+            remainder.pop();
+        }
+    }
+    (trim(&quotient), remainder)
+}
+
+pub fn mulmod<T>(a: &[T], b: &[T], modulus: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if let Some((field, p, x, m)) = lift(a, modulus) {
+        let y = multiplication::lift_residues(b);
+        if let Some((_, product)) = residue_divmod(residue_mul(&x, &y, &p), &m, &p) {
+            return lower(&field, product);
+        }
+    }
+    divmod(&mul(a, b), modulus).1
+}
+
+// a^exponent modulo the polynomial modulus (square and multiply)
+pub fn powmod<T>(a: &[T], exponent: &BigInt, modulus: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if let Some((field, p, x, m)) = lift(a, modulus) {
+        if let Some(result) = residue_powmod(x, exponent, &m, &p) {
+            return lower(&field, result);
+        }
+    }
+    let lc = &modulus[modulus.len() - 1];
+    let base = divmod(a, modulus).1;
+    let mut result = divmod(&[lc.div(lc)], modulus).1;
+    for i in (0..exponent.bits()).rev() {
+        result = mulmod(&result, &result, modulus);
+        if exponent.bit(i) {
+            result = mulmod(&result, &base, modulus);
+        }
+    }
+    result
+}
+
+// inverse of a modulo the polynomial modulus, from the extended gcd
+pub fn invmod<T>(a: &[T], modulus: &[T]) -> Result<Vec<T>> where T: Instance + Operand + Clone + Number + 'static {
+    if let Some((field, p, x, m)) = lift(a, modulus) {
+        if let Some(inverse) = residue_invmod(x, &m, &p) {
+            return inverse.map(|inverse| lower(&field, inverse));
+        }
+    }
+    let (d, s, _) = xgcd(a, modulus)?;
+    if d.len() != 1 {
+        return Err(Error::NotInvertible("The polynomial is not invertible modulo the given one".to_string()));
+    }
+    Ok(divmod(&s, modulus).1)
+}
+
+pub fn trim<T>(a: &[T]) -> Vec<T> where T: Number + Clone {
+    let mut a = a.to_vec();
+    while a.last().is_some_and(|c| c.clone().is_zero()) {
+        a.pop();
+    }
+    a
+}
+
+
+// --------------------- helpers ---------------------
+
+fn is_integer<T: 'static>() -> bool {
+    TypeId::of::<T>() == TypeId::of::<ZZinstance>()
+}
+
+// the modulus of Zmod coefficients, None in characteristic zero
+fn characteristic<T: Instance + 'static>(a: &[T]) -> Option<BigInt> {
+    if TypeId::of::<T>() != TypeId::of::<ZmodInstance>() {
+        return None;
+    }
+    let value = a.first()?.as_any().downcast_ref::<ZmodInstance>().unwrap();
+    let module = value.class.borrow().module.clone();
+    module.map(|m| m.value)
+}
+
+pub(crate) fn mul<T>(a: &[T], b: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    trim(&multiplication::multiply(a, b, None))
+}
+
+pub(crate) fn add<T>(a: &[T], b: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number {
+    let coefficients: Vec<T> = (0..a.len().max(b.len())).map(|i| match (a.get(i), b.get(i)) {
+        (Some(x), Some(y)) => x.add(y),
+        (Some(x), None) | (None, Some(x)) => x.clone(),
+        (None, None) => unreachable!()
+    }).collect();
+    trim(&coefficients)
+}
+
+pub(crate) fn sub<T>(a: &[T], b: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number {
+    let coefficients: Vec<T> = (0..a.len().max(b.len())).map(|i| match (a.get(i), b.get(i)) {
+        (Some(x), Some(y)) => x.sub(y),
+        (Some(x), None) => x.clone(),
+        (None, Some(y)) => y.neg(),
+        (None, None) => unreachable!()
+    }).collect();
+    trim(&coefficients)
+}
+
+pub(crate) fn scale<T: Operand>(a: &[T], factor: &T) -> Vec<T> {
+    a.iter().map(|c| c.mul(factor)).collect()
+}
+
+// value^exponent, one is given because T::one() does not know the modulus
+fn power<T: Operand + Clone>(value: &T, exponent: usize, one: &T) -> T {
+    let mut result = one.clone();
+    for bit in (0..usize::BITS - exponent.leading_zeros()).rev() {
+        result = result.mul(&result);
+        if (exponent >> bit) & 1 == 1 {
+            result = result.mul(value);
+        }
+    }
+    result
+}
+
+pub(crate) fn monic<T>(a: &[T]) -> Vec<T> where T: Operand + Clone {
+    match a.last() {
+        Some(lc) => scale(a, &lc.div(lc).div(lc)),
+        None => Vec::new()
+    }
+}
+
+// monic over a field, primitive with a positive leading coefficient over ZZ
+fn normalize<T>(a: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if is_integer::<T>() {
+        positive(&primitive_part(a))
+    } else {
+        monic(a)
+    }
+}
+
+// lc(b)^(deg a - deg b + 1) a mod b, without divisions
+fn pseudo_remainder<T>(a: &[T], b: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number {
+    let mut remainder = trim(a);
+    let lc = &b[b.len() - 1];
+    let mut exponent = (remainder.len() + 1).saturating_sub(b.len());
+    while remainder.len() >= b.len() {
+        let shift = remainder.len() - b.len();
+        let leading = remainder[remainder.len() - 1].clone();
+        remainder = scale(&remainder, lc);
+        for (i, c) in b.iter().enumerate() {
+            remainder[shift + i] = remainder[shift + i].sub(&leading.mul(c));
+        }
+        remainder.pop();
+        remainder = trim(&remainder);
+        exponent -= 1;
+    }
+    if exponent > 0 {
+        remainder = scale(&remainder, &power(lc, exponent, &lc.div(lc)));
+    }
+    remainder
+}
+
+
+// --------------------- Zmod coefficients ---------------------
+
+/*
+    The residues of a and b with their modulus, when T is Zmod and the coefficients share it.
+    The residue functions return None when a leading coefficient is not invertible, the generic
+    path then reports it
+*/
+fn lift<T: Instance + 'static>(a: &[T], b: &[T]) -> Option<(Zmod, BigInt, Vec<BigInt>, Vec<BigInt>)> {
+    if TypeId::of::<T>() != TypeId::of::<ZmodInstance>() {
+        return None;
+    }
+    let field = multiplication::common_modulus(a, b)?;
+    let p = field.module.clone()?.value;
+    Some((field, p, trim_residues(multiplication::lift_residues(a)), trim_residues(multiplication::lift_residues(b))))
+}
+
+fn lower<T: 'static>(field: &Zmod, residues: Vec<BigInt>) -> Vec<T> {
+    multiplication::downcast_vector(residues.into_iter().map(|value| field.apply(value)).collect::<Vec<ZmodInstance>>())
+}
+
+fn residue_inverse(c: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let extended = c.extended_gcd(p);
+    extended.gcd.is_one().then(|| extended.x.mod_floor(p))
+}
+
+fn trim_residues(mut a: Vec<BigInt>) -> Vec<BigInt> {
+    while a.last().is_some_and(|c| c.is_zero()) {
+        a.pop();
+    }
+    a
+}
+
+fn residue_mul(a: &[BigInt], b: &[BigInt], p: &BigInt) -> Vec<BigInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let product = multiplication::multiply_integers(a, b, multiplication::default_algorithm(a.len().min(b.len())));
+    trim_residues(product.into_iter().map(|c| c.mod_floor(p)).collect())
+}
+
+fn residue_divmod(mut remainder: Vec<BigInt>, b: &[BigInt], p: &BigInt) -> Option<(Vec<BigInt>, Vec<BigInt>)> {
+    let inverse = residue_inverse(b.last()?, p)?;
+    if remainder.len() < b.len() {
+        return Some((Vec::new(), remainder));
+    }
+    let mut quotient = vec![BigInt::zero(); remainder.len() - b.len() + 1];
+    while remainder.len() >= b.len() {
+        let shift = remainder.len() - b.len();
+        let factor = (remainder.pop().unwrap() * &inverse).mod_floor(p);
+        for (i, c) in b[..b.len() - 1].iter().enumerate() {
+            remainder[shift + i] = (&remainder[shift + i] - &factor * c).mod_floor(p);
+        }
+        quotient[shift] = factor;
+        remainder = trim_residues(remainder);
+    }
+    Some((quotient, remainder))
+}
+
+fn residue_gcd(mut a: Vec<BigInt>, mut b: Vec<BigInt>, p: &BigInt) -> Option<Vec<BigInt>> {
+    while !b.is_empty() {
+        let remainder = residue_divmod(a, &b, p)?.1;
+        a = b;
+        b = remainder;
+    }
+    let inverse = match a.last() {
+        Some(lc) => residue_inverse(lc, p)?,
+        None => return Some(a)
+    };
+    Some(a.iter().map(|c| (c * &inverse).mod_floor(p)).collect())
+}
+
+fn residue_powmod(a: Vec<BigInt>, exponent: &BigInt, modulus: &[BigInt], p: &BigInt) -> Option<Vec<BigInt>> {
+    let base = residue_divmod(a, modulus, p)?.1;
+    let mut result = residue_divmod(vec![BigInt::one()], modulus, p)?.1;
+    for i in (0..exponent.bits()).rev() {
+        result = residue_divmod(residue_mul(&result, &result, p), modulus, p)?.1;
+        if exponent.bit(i) {
+            result = residue_divmod(residue_mul(&result, &base, p), modulus, p)?.1;
+        }
+    }
+    Some(result)
+}
+
+// the extended Euclid loop, keeping only the cofactor of a
+fn residue_invmod(a: Vec<BigInt>, modulus: &[BigInt], p: &BigInt) -> Option<Result<Vec<BigInt>>> {
+    let (mut r0, mut s0) = (residue_divmod(a, modulus, p)?.1, vec![BigInt::one()]);
+    let (mut r1, mut s1) = (modulus.to_vec(), Vec::new());
+    while !r1.is_empty() {
+        let (q, r) = residue_divmod(r0, &r1, p)?;
+        let product = residue_mul(&q, &s1, p);
+        let s = trim_residues((0..s0.len().max(product.len())).map(|i| {
+            let x = s0.get(i).cloned().unwrap_or_default();
+            (x - product.get(i).cloned().unwrap_or_default()).mod_floor(p)
+        }).collect());
+        (r0, s0) = (r1, s1);
+        (r1, s1) = (r, s);
+    }
+    if r0.len() != 1 {
+        return Some(Err(Error::NotInvertible("The polynomial is not invertible modulo the given one".to_string())));
+    }
+    let inverse = residue_inverse(&r0[0], p)?;
+    Some(Ok(s0.iter().map(|c| (c * &inverse).mod_floor(p)).collect()))
+}
+
+
+// --------------------- integer coefficients ---------------------
+
+fn integer_gcd<T>(a: &[T], b: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if a.is_empty() || b.is_empty() {
+        return positive(if a.is_empty() { b } else { a });
+    }
+    let content = content(a).gcd(&content(b));
+    let (mut a, mut b) = (primitive_part(a), primitive_part(b));
+    if a.len() < b.len() {
+        std::mem::swap(&mut a, &mut b);
+    }
+    let g = loop {
+        if b.is_empty() {
+            break a;
+        }
+        if b.len() == 1 {
+            break vec![from_integer(BigInt::from(1))];
+        }
+        let remainder = pseudo_remainder(&a, &b);
+        a = b;
+        b = primitive_part(&remainder);
+    };
+    scale(&positive(&g), &from_integer(content))
+}
+
+fn integers<T: Instance>(a: &[T]) -> Vec<BigInt> {
+    a.iter().map(|c| c.as_any().downcast_ref::<ZZinstance>().unwrap().value.clone()).collect()
+}
+
+fn from_integer<T: 'static>(value: BigInt) -> T {
+    let value: Box<dyn Any> = Box::new(ZZ::new().new_instance(value));
+    *value.downcast::<T>().unwrap()
+}
+
+fn content<T: Instance>(a: &[T]) -> BigInt {
+    integers(a).iter().fold(BigInt::from(0), |g, c| g.gcd(c))
+}
+
+fn primitive_part<T>(a: &[T]) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    let content = content(a);
+    if content == BigInt::from(0) {
+        return Vec::new();
+    }
+    let content: T = from_integer(content);
+    a.iter().map(|c| c.div(&content)).collect()
+}
+
+fn positive<T>(a: &[T]) -> Vec<T> where T: Instance + Operand + Clone {
+    match a.last() {
+        Some(lc) if integers(std::slice::from_ref(lc))[0].is_negative() => a.iter().map(|c| c.neg()).collect(),
+        _ => a.to_vec()
+    }
+}
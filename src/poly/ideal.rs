@@ -0,0 +1,361 @@
+use std::cell::OnceCell;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use num_bigint::BigInt;
+
+use crate::numbers::numbers::Instance;
+use crate::numbers::numbers::Number;
+use crate::numbers::numbers::Operand;
+use crate::poly::classes::monomial::Monomial;
+use crate::poly::classes::polynomial::{MonomialOrder, Polynomial};
+use crate::poly::instances::monomial_instance::MonomialInstance;
+use crate::poly::instances::polynomial_instance::PolynomialInstance;
+use crate::variables::vars::Var;
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GroebnerAlgorithm {
+    Buchberger,
+    F4
+}
+
+/*
+    Ideals of multivariate polynomials, the coefficients must be a field (QQ, Zmod with a prime modulus).
+    The Gröbner bases are reduced: monic, sorted from the largest leading monomial, and no
+    monomial of an element is divisible by the leading monomial of another one.
+    The basis used by reduce and contains is computed on the first call and kept
+*/
+#[derive(Clone)]
+pub struct Ideal<T> {
+    pub generators: Vec<PolynomialInstance<T>>,
+    pub order: MonomialOrder,
+    pub variables: Vec<&'static str>,
+    basis: OnceCell<Vec<PolynomialInstance<T>>>
+}
+
+impl<T> Ideal<T> where T: Instance + Operand + Clone + Number {
+    // the monomial order of the first generator
+    pub fn new(generators: Vec<PolynomialInstance<T>>) -> Ideal<T> {
+        let (order, variables) = match generators.first() {
+            Some(generator) => {
+                let class = generator.class.borrow();
+                (class.order, class.variables.clone())
+            },
+            None => (MonomialOrder::GrevLex, Vec::new())
+        };
+        Ideal::new_with_order(generators, order, variables)
+    }
+
+    pub fn new_with_order(generators: Vec<PolynomialInstance<T>>, order: MonomialOrder, variables: Vec<&'static str>) -> Ideal<T> {
+        let generators = generators.iter().map(|generator| generator.with_order(order, variables.clone())).filter(|generator| !generator.is_zero()).collect();
+        Ideal { generators, order, variables, basis: OnceCell::new() }
+    }
+
+    pub fn groebner_basis(&self, algorithm: GroebnerAlgorithm) -> Vec<PolynomialInstance<T>> {
+        let mut state = GroebnerState::new(self.order, self.variables.clone());
+        for generator in &self.generators {
+            state.update(monic(generator));
+        }
+        match algorithm {
+            GroebnerAlgorithm::Buchberger => state.buchberger(),
+            GroebnerAlgorithm::F4 => state.f4()
+        }
+        state.reduced_basis()
+    }
+
+    // the normal form of f with respect to the Gröbner basis, zero when f is in the ideal
+    pub fn reduce(&self, f: &PolynomialInstance<T>) -> PolynomialInstance<T> {
+        let basis = self.basis.get_or_init(|| self.groebner_basis(GroebnerAlgorithm::Buchberger));
+        normal_form(&f.with_order(self.order, self.variables.clone()), basis)
+    }
+
+    pub fn contains(&self, f: &PolynomialInstance<T>) -> bool {
+        self.reduce(f).is_zero()
+    }
+
+    /*
+    The polynomials of the ideal without the given variables, taken from
+    a lex basis where those variables are the largest ones
+     */
+    pub fn elimination_ideal(&self, symbols: &[&'static str]) -> Ideal<T> {
+        let mut variables = symbols.to_vec();
+        variables.extend(self.variables.iter().filter(|symbol| !symbols.contains(symbol)));
+        let lex = Ideal::new_with_order(self.generators.clone(), MonomialOrder::Lex, variables);
+        let kept = lex.groebner_basis(GroebnerAlgorithm::Buchberger).into_iter().filter(|g| g.variables().iter().all(|symbol| !symbols.contains(symbol))).collect();
+        Ideal::new_with_order(kept, self.order, self.variables.clone())
+    }
+}
+
+/*
+    Full reduction of f by the basis: no monomial of the result is divisible
+    by a leading monomial of the basis. The basis must have the order of f
+*/
+pub fn normal_form<T>(f: &PolynomialInstance<T>, basis: &[PolynomialInstance<T>]) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+    let mut rest = f.clone();
+    let mut remainder: Vec<MonomialInstance<T>> = Vec::new();
+    while let Some(term) = rest.leading_monomial() {
+        match basis.iter().find(|g| !g.is_zero() && g.monomials[0].divides(&term)) {
+            Some(g) => {
+                let factor = Monomial::div(term, g.monomials[0].clone());
+                rest = rest - g.clone() * factor;
+            },
+            None => {
+                rest.monomials.remove(0);
+                remainder.push(term);
+            }
+        }
+    }
+    let class = f.class.borrow();
+    Polynomial::new_with_order(remainder, class.order, class.variables.clone())
+}
+
+fn monic<T>(f: &PolynomialInstance<T>) -> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+    let leading = f.monomials[0].coefficient.clone();
+    let inverse = leading.div(&leading).div(&leading);
+    f.clone() * Monomial::new_monomial(Vec::new(), inverse)
+}
+
+// exponents sorted by symbol, as the variables of a monomial
+type Exponents = Vec<(&'static str, BigInt)>;
+
+fn exponents<T>(m: &MonomialInstance<T>) -> Exponents {
+    m.variables.iter().map(|v| (v.get_symbol(), v.get_exponent())).collect()
+}
+
+fn lcm(a: &Exponents, b: &Exponents) -> Exponents {
+    let mut result = a.clone();
+    for (symbol, exponent) in b {
+        match result.iter_mut().find(|(s, _)| s == symbol) {
+            Some((_, e)) => if exponent > e { *e = exponent.clone() },
+            None => result.push((symbol, exponent.clone()))
+        }
+    }
+    result.sort();
+    result
+}
+
+fn divides(a: &Exponents, b: &Exponents) -> bool {
+    a.iter().all(|(symbol, exponent)| b.iter().any(|(s, e)| s == symbol && e >= exponent))
+}
+
+fn coprime(a: &Exponents, b: &Exponents) -> bool {
+    a.iter().all(|(symbol, _)| b.iter().all(|(s, _)| s != symbol))
+}
+
+fn total_degree(a: &Exponents) -> BigInt {
+    a.iter().map(|(_, e)| e.clone()).sum()
+}
+
+
+/*
+    Every polynomial added to the basis is kept, the pairs refer to their indices.
+    The elements are monic, so the leading coefficient is the one of the field
+*/
+struct GroebnerState<T> {
+    order: MonomialOrder,
+    variables: Vec<&'static str>,
+    polynomials: Vec<PolynomialInstance<T>>,
+    basis: Vec<usize>,
+    pairs: Vec<(usize, usize)>
+}
+
+impl<T> GroebnerState<T> where T: Instance + Operand + Clone + Number {
+    fn new(order: MonomialOrder, variables: Vec<&'static str>) -> GroebnerState<T> {
+        GroebnerState { order, variables, polynomials: Vec::new(), basis: Vec::new(), pairs: Vec::new() }
+    }
+
+    fn leading(&self, i: usize) -> Exponents {
+        exponents(&self.polynomials[i].monomials[0])
+    }
+
+    fn pair_lcm(&self, i: usize, j: usize) -> Exponents {
+        lcm(&self.leading(i), &self.leading(j))
+    }
+
+    fn basis_polynomials(&self) -> Vec<PolynomialInstance<T>> {
+        self.basis.iter().map(|&i| self.polynomials[i].clone()).collect()
+    }
+
+    // monomial with the exponents and the coefficient one
+    fn monomial(&self, exponents: &Exponents) -> MonomialInstance<T> {
+        let one = self.polynomials[0].monomials[0].coefficient.clone();
+        Monomial::new_monomial(exponents.iter().map(|(symbol, exponent)| Var::new(symbol, exponent.clone())).collect(), one)
+    }
+
+    fn compare(&self, a: &Exponents, b: &Exponents) -> Ordering {
+        self.order.compare(&self.monomial(a), &self.monomial(b), &self.variables)
+    }
+
+    // f with the leading monomial multiplied up to the given monomial
+    fn multiple(&self, i: usize, target: &Exponents) -> PolynomialInstance<T> {
+        let f = &self.polynomials[i];
+        f.clone() * Monomial::div(self.monomial(target), f.monomials[0].clone())
+    }
+
+    /*
+    Adds a monic polynomial with the Gebauer–Möller criteria: the new pairs whose lcm is
+    a multiple of another new lcm are dropped (but coprime ones, which reduce to zero,
+    are still used to drop the others), then the old pairs the new leading monomial
+    makes redundant and the basis elements it divides
+     */
+    fn update(&mut self, h: PolynomialInstance<T>) {
+        let k = self.polynomials.len();
+        self.polynomials.push(h);
+        let leading = self.leading(k);
+
+        let candidates = self.basis.clone();
+        let mut kept: Vec<usize> = Vec::new();
+        for (index, &g) in candidates.iter().enumerate() {
+            let l = self.pair_lcm(k, g);
+            let redundant = candidates[index + 1..].iter().chain(kept.iter()).any(|&other| divides(&self.pair_lcm(k, other), &l));
+            if coprime(&leading, &self.leading(g)) || !redundant {
+                kept.push(g);
+            }
+        }
+        let new_pairs: Vec<(usize, usize)> = kept.into_iter().filter(|&g| !coprime(&leading, &self.leading(g))).map(|g| (g, k)).collect();
+
+        let old_pairs = std::mem::take(&mut self.pairs);
+        self.pairs = old_pairs.into_iter().filter(|&(i, j)| {
+            let l = self.pair_lcm(i, j);
+            !divides(&leading, &l) || self.pair_lcm(i, k) == l || self.pair_lcm(j, k) == l
+        }).collect();
+        self.pairs.extend(new_pairs);
+
+        let basis = std::mem::take(&mut self.basis);
+        self.basis = basis.into_iter().filter(|&g| !divides(&leading, &self.leading(g))).collect();
+        self.basis.push(k);
+    }
+
+    fn s_polynomial(&self, i: usize, j: usize) -> PolynomialInstance<T> {
+        let l = self.pair_lcm(i, j);
+        self.multiple(i, &l) - self.multiple(j, &l)
+    }
+
+    // the pair with the smallest lcm first (normal strategy)
+    fn buchberger(&mut self) {
+        while !self.pairs.is_empty() {
+            let mut index = 0;
+            for candidate in 1..self.pairs.len() {
+                let (i, j) = self.pairs[candidate];
+                let (a, b) = self.pairs[index];
+                if self.compare(&self.pair_lcm(i, j), &self.pair_lcm(a, b)) == Ordering::Less {
+                    index = candidate;
+                }
+            }
+            let (i, j) = self.pairs.remove(index);
+            let h = normal_form(&self.s_polynomial(i, j), &self.basis_polynomials());
+            if !h.is_zero() {
+                self.update(monic(&h));
+            }
+        }
+    }
+
+    /*
+    F4: the pairs with the smallest lcm degree are reduced together. Their multiples and
+    the multiples of the basis needed to reduce every monomial (symbolic preprocessing)
+    form a matrix whose echelon form gives the new elements
+     */
+    fn f4(&mut self) {
+        while !self.pairs.is_empty() {
+            let degree = self.pairs.iter().map(|&(i, j)| total_degree(&self.pair_lcm(i, j))).min().unwrap();
+            let (selected, rest): (Vec<_>, Vec<_>) = self.pairs.iter().partition(|&&(i, j)| total_degree(&self.pair_lcm(i, j)) == degree);
+            self.pairs = rest;
+
+            let mut rows: Vec<PolynomialInstance<T>> = Vec::new();
+            let mut multiples: HashSet<(usize, Exponents)> = HashSet::new();
+            for (i, j) in selected {
+                let l = self.pair_lcm(i, j);
+                for k in [i, j] {
+                    if multiples.insert((k, l.clone())) {
+                        rows.push(self.multiple(k, &l));
+                    }
+                }
+            }
+            // symbolic preprocessing
+            let mut done: HashSet<Exponents> = rows.iter().map(|row| exponents(&row.monomials[0])).collect();
+            let mut queue: Vec<Exponents> = rows.iter().flat_map(|row| row.monomials.iter().map(exponents)).collect();
+            let mut position = 0;
+            while position < queue.len() {
+                let m = queue[position].clone();
+                position += 1;
+                if !done.insert(m.clone()) {
+                    continue;
+                }
+                if let Some(&g) = self.basis.iter().find(|&&g| divides(&self.leading(g), &m)) {
+                    let row = self.multiple(g, &m);
+                    queue.extend(row.monomials.iter().map(exponents));
+                    rows.push(row);
+                }
+            }
+
+            let leading: HashSet<Exponents> = rows.iter().map(|row| exponents(&row.monomials[0])).collect();
+            for row in self.echelon_form(rows) {
+                if !leading.contains(&exponents(&row.monomials[0])) {
+                    self.update(monic(&row));
+                }
+            }
+        }
+    }
+
+    // reduced row echelon form of the polynomials, columns sorted from the largest monomial
+    fn echelon_form(&self, rows: Vec<PolynomialInstance<T>>) -> Vec<PolynomialInstance<T>> {
+        let mut columns: Vec<Exponents> = rows.iter().flat_map(|row| row.monomials.iter().map(exponents)).collect();
+        columns.sort();
+        columns.dedup();
+        columns.sort_by(|a, b| self.compare(b, a));
+
+        let one = self.polynomials[0].monomials[0].coefficient.clone();
+        let zero = one.sub(&one);
+        let mut matrix: Vec<Vec<T>> = rows.iter().map(|row| {
+            let mut dense = vec![zero.clone(); columns.len()];
+            for monomial in &row.monomials {
+                let column = columns.iter().position(|c| *c == exponents(monomial)).unwrap();
+                dense[column] = monomial.coefficient.clone();
+            }
+            dense
+        }).collect();
+
+        let mut rank = 0;
+        for column in 0..columns.len() {
+            let pivot = match (rank..matrix.len()).find(|&r| !matrix[r][column].clone().is_zero()) {
+                Some(pivot) => pivot,
+                None => continue
+            };
+            matrix.swap(rank, pivot);
+            let inverse = one.div(&matrix[rank][column]);
+            matrix[rank] = matrix[rank].iter().map(|c| c.mul(&inverse)).collect();
+            for r in 0..matrix.len() {
+                if r != rank && !matrix[r][column].clone().is_zero() {
+                    let factor = matrix[r][column].clone();
+                    matrix[r] = matrix[r].iter().zip(&matrix[rank]).map(|(a, b)| a.sub(&factor.mul(b))).collect();
+                }
+            }
+            rank += 1;
+        }
+
+        matrix.truncate(rank);
+        matrix.into_iter().map(|dense| {
+            let monomials = dense.into_iter().zip(&columns).filter(|(c, _)| !c.clone().is_zero()).map(|(c, e)| {
+                Monomial::new_monomial(e.iter().map(|(symbol, exponent)| Var::new(symbol, exponent.clone())).collect(), c)
+            }).collect();
+            Polynomial::new_with_order(monomials, self.order, self.variables.clone())
+        }).collect()
+    }
+
+    fn reduced_basis(&self) -> Vec<PolynomialInstance<T>> {
+        let basis = self.basis_polynomials();
+        // minimal basis: one element for each minimal leading monomial
+        let minimal: Vec<PolynomialInstance<T>> = basis.iter().enumerate().filter(|(i, g)| {
+            let lm = &g.monomials[0];
+            !basis.iter().enumerate().any(|(j, other)| j != *i && other.monomials[0].divides(lm) && (!lm.divides(&other.monomials[0]) || j < *i))
+        }).map(|(_, g)| g.clone()).collect();
+
+        let mut reduced: Vec<PolynomialInstance<T>> = (0..minimal.len()).map(|i| {
+            let others: Vec<PolynomialInstance<T>> = minimal.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, g)| g.clone()).collect();
+            monic(&normal_form(&minimal[i], &others))
+        }).collect();
+        reduced.sort_by(|a, b| self.order.compare(&b.monomials[0], &a.monomials[0], &self.variables));
+        reduced
+    }
+}
@@ -36,6 +36,23 @@ impl<T> MonomialInstance<T> where T: Instance + Operand + Clone + Number {
 
         false
     }
+
+    // exponent of the variable, 0 if it does not appear
+    pub fn degree(&self, symbol: &str) -> BigInt {
+        match self.variables.iter().find(|v| v.get_symbol() == symbol) {
+            Some(v) => v.get_exponent(),
+            None => BigInt::from(0)
+        }
+    }
+
+    // the variables of self divide the ones of other
+    pub fn divides(&self, other: &MonomialInstance<T>) -> bool {
+        self.variables.iter().all(|v| other.degree(v.get_symbol()) >= v.get_exponent())
+    }
+
+    pub fn total_degree(&self) -> BigInt {
+        self.variables.iter().map(|v| v.get_exponent()).sum()
+    }
 }
 
 impl<T> PartialEq for MonomialInstance<T> where T: Instance + PartialEq {
@@ -8,7 +8,8 @@ use crate::numbers::numbers::Instance;
 use crate::numbers::numbers::Number;
 use crate::numbers::numbers::Operand;
 use crate::numbers::sets::Class::ClassTypes;
-use crate::poly::classes::polynomial::Polynomial;
+use crate::error::{unwrap_or_panic, Result};
+use crate::poly::classes::polynomial::{MonomialOrder, Polynomial};
 use crate::poly::instances::monomial_instance::MonomialInstance;
 use crate::variables::vars::Var;
 
@@ -21,12 +22,73 @@ pub struct PolynomialInstance<T> {
     pub monomials: Vec<MonomialInstance<T>>
 }
 
-impl<T> PolynomialInstance<T> where T: Instance + Operand + Clone {
-   
+impl<T> PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+    pub fn is_zero(&self) -> bool {
+        self.monomials.is_empty()
+    }
 
-    // pub fn ToUnivariatePolynomial(self) -> UnivariatePolynomial<T> {
-    //     // write the code to convert a polynomial to univariate polynomial
-    // }
+    // leading term (with its coefficient) for the monomial order of the polynomial
+    pub fn leading_monomial(&self) -> Option<MonomialInstance<T>> {
+        self.monomials.first().cloned()
+    }
+
+    pub fn leading_coefficient(&self) -> Option<T> {
+        self.monomials.first().map(|monomial| monomial.coefficient.clone())
+    }
+
+    // -1 for the zero polynomial
+    pub fn total_degree(&self) -> BigInt {
+        self.monomials.iter().map(|monomial| monomial.total_degree()).max().unwrap_or(BigInt::from(-1))
+    }
+
+    pub fn degree(&self, symbol: &str) -> BigInt {
+        self.monomials.iter().map(|monomial| monomial.degree(symbol)).max().unwrap_or(BigInt::from(-1))
+    }
+
+    pub fn variables(&self) -> Vec<&'static str> {
+        let mut symbols: Vec<&'static str> = self.monomials.iter().flat_map(|monomial| monomial.variables.iter().map(|v| v.get_symbol())).collect();
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+
+    pub fn order(&self) -> MonomialOrder {
+        self.class.borrow().order
+    }
+
+    // the same polynomial sorted with another monomial order
+    pub fn with_order(&self, order: MonomialOrder, variables: Vec<&'static str>) -> PolynomialInstance<T> {
+        Polynomial::new_with_order(self.monomials.clone(), order, variables)
+    }
+
+    pub fn derivative(&self, symbol: &'static str) -> PolynomialInstance<T> {
+        Polynomial::derivative(self.clone(), symbol)
+    }
+
+    pub fn substitute(&self, values: &[(&'static str, T)]) -> PolynomialInstance<T> {
+        Polynomial::substitute(self.clone(), values)
+    }
+
+    pub fn evaluate(&self, values: &[(&'static str, T)]) -> T {
+        unwrap_or_panic(self.try_evaluate(values))
+    }
+
+    pub fn try_evaluate(&self, values: &[(&'static str, T)]) -> Result<T> {
+        Polynomial::evaluate(self.clone(), values)
+    }
+
+    pub fn pow(&self, exponent: u64) -> PolynomialInstance<T> {
+        Polynomial::pow(self.clone(), exponent)
+    }
+
+    // (quotient, remainder)
+    pub fn div_rem(&self, other: &PolynomialInstance<T>) -> (PolynomialInstance<T>, PolynomialInstance<T>) {
+        unwrap_or_panic(self.try_div_rem(other))
+    }
+
+    pub fn try_div_rem(&self, other: &PolynomialInstance<T>) -> Result<(PolynomialInstance<T>, PolynomialInstance<T>)> {
+        Polynomial::div(self.clone(), other.clone())
+    }
 }
 
 impl<T> PartialEq for PolynomialInstance<T> where T: Instance + PartialEq {
@@ -47,17 +109,42 @@ impl<T> PartialEq for PolynomialInstance<T> where T: Instance + PartialEq {
 impl<T> Eq for PolynomialInstance<T> where T: Instance + PartialEq {}
 
 
-// add function
-
-// mul function
+// OPERATIONS
 
-// div function
+impl<T> std::ops::Neg for PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+    type Output = PolynomialInstance<T>;
+    fn neg(self) -> PolynomialInstance<T> {
+        Polynomial::neg(self)
+    }
+}
 
-// sub function
+impl<T> std::ops::Add for PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+    type Output = PolynomialInstance<T>;
+    fn add(self, rhs: PolynomialInstance<T>) -> PolynomialInstance<T> {
+        Polynomial::add(self, rhs)
+    }
+}
 
-// pow to BigInt
+impl<T> std::ops::Sub for PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+    type Output = PolynomialInstance<T>;
+    fn sub(self, rhs: PolynomialInstance<T>) -> PolynomialInstance<T> {
+        Polynomial::sub(self, rhs)
+    }
+}
 
+impl<T> std::ops::Mul for PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+    type Output = PolynomialInstance<T>;
+    fn mul(self, rhs: PolynomialInstance<T>) -> PolynomialInstance<T> {
+        Polynomial::mul(self, rhs)
+    }
+}
 
+impl<T> std::ops::Mul<MonomialInstance<T>> for PolynomialInstance<T> where T: Instance + Operand + Clone + Number {
+    type Output = PolynomialInstance<T>;
+    fn mul(self, rhs: MonomialInstance<T>) -> PolynomialInstance<T> {
+        Polynomial::mul_by_monomial(self, rhs)
+    }
+}
 
 
 impl<T> std::fmt::Display for PolynomialInstance<T> where T: Instance + Operand + Clone +  std::fmt::Display {
@@ -67,42 +154,36 @@ impl<T> std::fmt::Display for PolynomialInstance<T> where T: Instance + Operand
         // stream: `f`. Returns `fmt::Result` which indicates whether the
         // operation succeeded or failed. Note that `write!` uses syntax which
         // is very similar to `println!`.
-        let mut printable = format!("");
-
-        for i in &self.monomials {
-            let monomial = format!("{i}");
-            printable = format!("{printable}{monomial}")
+        if self.monomials.is_empty() {
+            return write!(f, "0");
         }
+        let printable: Vec<String> = self.monomials.iter().map(|i| format!("{i}")).collect();
 
-        write!(f, "{0}", printable )
+        write!(f, "{0}", printable.join(" + "))
     }
 }
 
 
 impl<T> Operand for PolynomialInstance<T> where T: Instance + Operand + Clone + Eq + Number {
     fn neg(&self) -> Self {
-        let mut monomials: Vec<MonomialInstance<T>> = self.monomials.clone();
-        for i in 0..monomials.len() {
-            monomials[i] = -monomials[i].clone();
-        }
-
-        Polynomial::new(monomials)
+        Polynomial::neg(self.clone())
     }
     
     fn add(&self, other: &Self) -> Self {
-        self.clone() //*self + *other
+        self.clone() + other.clone()
     }
 
     fn sub(&self, other: &Self) -> Self {
-        self.clone() //*self - *other
+        self.clone() - other.clone()
     }
 
     fn mul(&self, other: &Self) -> Self {
-        self.clone() //*self * *other
+        self.clone() * other.clone()
     }
 
+    // quotient of the division with respect to the monomial order
     fn div(&self, other: &Self) -> Self {
-        self.clone() //*self / *other
+        self.div_rem(other).0
     }
 
     fn equal(&self, other:&Self) -> bool {
@@ -11,7 +11,6 @@ use crate::arith::random::get_random_bigint_with_bounds;
 use crate::numbers::instances::RR_instance::RRinstance;
 use crate::numbers::instances::ZZ_instance;
 use crate::numbers::instances::ZZ_instance::ZZinstance;
-use crate::numbers::numbers::Class;
 use crate::numbers::numbers::ClassInstance;
 use crate::numbers::numbers::Number;
 use crate::numbers::numbers::StatefulClass;
@@ -24,18 +23,19 @@ use crate::numbers::numbers::Instance;
 use crate::numbers::numbers::Operand;
 use crate::algebras::Rings::classes::PolynomialRing::PolynomialRing;
 use crate::algebras::Rings::instances::PolynomialRing_instance::PolynomialRingInstance;
+use crate::error::{unwrap_or_panic, Error, Result};
+use crate::poly::gcd;
+use crate::poly::factorization;
+use crate::poly::evaluation;
+use crate::arith::primes::is_prime;
 
 
-pub enum PolyMultiplicationAlgorithm {
-    ToomCook,
-    Naive,
-    EvaluationForm
-}
+pub use crate::poly::classes::univariate_polynomial::PolyMultiplicationAlgorithm;
 
 // utilities
 fn clean<T>(mut coeff: Vec<T>) -> Vec<T> where T: Instance + Operand + Clone + Number {
     loop {
-        if coeff[coeff.len()-1].clone().is_zero() {
+        if coeff[coeff.len()-1].clone().is_zero() && coeff.len() > 1 {
             coeff.pop();
         } else {
             break;
@@ -101,6 +101,165 @@ impl<T> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + Pa
 }
 
 
+/*
+    Gcd, resultant and square-free decomposition, the coefficients must be
+    a field (QQ, Zmod with a prime modulus) or ZZ
+*/
+impl<T> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number + 'static {
+    fn with_coefficients(&self, coefficients: Vec<T>) -> UnivariatePolynomialInstance<T> {
+        let mut coefficients = coefficients;
+        if coefficients.is_empty() {
+            coefficients.push(self.coefficients[0].sub(&self.coefficients[0]));
+        }
+        UnivariatePolynomial::new_instance(coefficients, self.var.clone(), self.class.borrow().multiplication_algorithm, self.clean_coefficients)
+    }
+
+    fn check_variable(&self, other: &UnivariatePolynomialInstance<T>) -> Result<()> {
+        if self.var != other.var {
+            return Err(Error::IncompatibleOperands("The polynomials have different variables".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn derivative(&self) -> UnivariatePolynomialInstance<T> {
+        self.with_coefficients(gcd::derivative(&self.coefficients))
+    }
+
+    // monic over a field, with a positive leading coefficient over ZZ
+    pub fn gcd(&self, other: &UnivariatePolynomialInstance<T>) -> UnivariatePolynomialInstance<T> {
+        unwrap_or_panic(self.try_gcd(other))
+    }
+
+    pub fn try_gcd(&self, other: &UnivariatePolynomialInstance<T>) -> Result<UnivariatePolynomialInstance<T>> {
+        self.check_variable(other)?;
+        Ok(self.with_coefficients(gcd::gcd(&self.coefficients, &other.coefficients)))
+    }
+
+    // (d, s, t) with s self + t other = d, only for field coefficients
+    pub fn xgcd(&self, other: &UnivariatePolynomialInstance<T>) -> (UnivariatePolynomialInstance<T>, UnivariatePolynomialInstance<T>, UnivariatePolynomialInstance<T>) {
+        unwrap_or_panic(self.try_xgcd(other))
+    }
+
+    pub fn try_xgcd(&self, other: &UnivariatePolynomialInstance<T>) -> Result<(UnivariatePolynomialInstance<T>, UnivariatePolynomialInstance<T>, UnivariatePolynomialInstance<T>)> {
+        self.check_variable(other)?;
+        let (d, s, t) = gcd::xgcd(&self.coefficients, &other.coefficients)?;
+        Ok((self.with_coefficients(d), self.with_coefficients(s), self.with_coefficients(t)))
+    }
+
+    pub fn resultant(&self, other: &UnivariatePolynomialInstance<T>) -> T {
+        unwrap_or_panic(self.try_resultant(other))
+    }
+
+    pub fn try_resultant(&self, other: &UnivariatePolynomialInstance<T>) -> Result<T> {
+        self.check_variable(other)?;
+        Ok(gcd::resultant(&self.coefficients, &other.coefficients))
+    }
+
+    pub fn discriminant(&self) -> T {
+        unwrap_or_panic(self.try_discriminant())
+    }
+
+    pub fn try_discriminant(&self) -> Result<T> {
+        gcd::discriminant(&self.coefficients)
+    }
+
+    // square-free factors with their multiplicity, self is their product up to a constant
+    pub fn square_free_decomposition(&self) -> Vec<(UnivariatePolynomialInstance<T>, usize)> {
+        gcd::square_free_decomposition(&self.coefficients).into_iter().map(|(factor, multiplicity)| (self.with_coefficients(factor), multiplicity)).collect()
+    }
+}
+
+
+/*
+    Evaluation and real roots, the real roots need an ordered field (QQ, RR)
+*/
+impl<T> UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number + 'static {
+    // Horner's rule
+    pub fn evaluate(&self, x: &T) -> T {
+        evaluation::horner(&self.coefficients, x)
+    }
+
+    pub fn evaluate_multipoint(&self, points: &[T]) -> Vec<T> {
+        evaluation::multipoint(&self.coefficients, points)
+    }
+
+    // isolating intervals (a, b] of the distinct real roots, in increasing order (Sturm sequences)
+    pub fn real_root_intervals(&self) -> Vec<(T, T)> {
+        evaluation::real_root_intervals(&self.coefficients)
+    }
+}
+
+/*
+    Factorisation over Zmod p, the modulus must be prime
+*/
+impl UnivariatePolynomialInstance<ZmodInstance> {
+    fn prime_modulus(&self) -> Result<BigInt> {
+        let module = self.coefficients[0].class.borrow().module.clone();
+        match module {
+            Some(p) if is_prime(p.value.magnitude().clone()) => Ok(p.value),
+            _ => Err(Error::InvalidInput("The coefficients must be integers modulo a prime".to_string()))
+        }
+    }
+
+    // the monic irreducible factors with their multiplicity, sorted by degree
+    pub fn factor(&self) -> Vec<(UnivariatePolynomialInstance<ZmodInstance>, usize)> {
+        unwrap_or_panic(self.try_factor())
+    }
+
+    pub fn try_factor(&self) -> Result<Vec<(UnivariatePolynomialInstance<ZmodInstance>, usize)>> {
+        let p = self.prime_modulus()?;
+        let mut factors: Vec<(Vec<ZmodInstance>, usize)> = Vec::new();
+        for (part, multiplicity) in self.square_free_decomposition() {
+            for (product, degree) in factorization::distinct_degree(&part.coefficients, &p) {
+                factors.extend(factorization::equal_degree(&product, degree, &p)?.into_iter().map(|factor| (factor, multiplicity)));
+            }
+        }
+        let residues = |factor: &[ZmodInstance]| factor.iter().rev().map(|c| c.value.value.clone()).collect::<Vec<BigInt>>();
+        factors.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| residues(a).cmp(&residues(b))));
+        Ok(factors.into_iter().map(|(factor, multiplicity)| (self.with_coefficients(factor), multiplicity)).collect())
+    }
+
+    // (g, d) with g the product of the irreducible factors of degree d, self must be square-free
+    pub fn distinct_degree_factorization(&self) -> Vec<(UnivariatePolynomialInstance<ZmodInstance>, usize)> {
+        unwrap_or_panic(self.try_distinct_degree_factorization())
+    }
+
+    pub fn try_distinct_degree_factorization(&self) -> Result<Vec<(UnivariatePolynomialInstance<ZmodInstance>, usize)>> {
+        let p = self.prime_modulus()?;
+        Ok(factorization::distinct_degree(&self.coefficients, &p).into_iter().map(|(g, degree)| (self.with_coefficients(g), degree)).collect())
+    }
+
+    // the factors of a square-free product of irreducible polynomials of the given degree, an error for any other polynomial
+    pub fn equal_degree_factorization(&self, degree: usize) -> Vec<UnivariatePolynomialInstance<ZmodInstance>> {
+        unwrap_or_panic(self.try_equal_degree_factorization(degree))
+    }
+
+    pub fn try_equal_degree_factorization(&self, degree: usize) -> Result<Vec<UnivariatePolynomialInstance<ZmodInstance>>> {
+        let p = self.prime_modulus()?;
+        Ok(factorization::equal_degree(&self.coefficients, degree, &p)?.into_iter().map(|factor| self.with_coefficients(factor)).collect())
+    }
+
+    // the distinct roots in Zmod p, in increasing order
+    pub fn roots(&self) -> Vec<ZmodInstance> {
+        unwrap_or_panic(self.try_roots())
+    }
+
+    pub fn try_roots(&self) -> Result<Vec<ZmodInstance>> {
+        let p = self.prime_modulus()?;
+        Ok(factorization::roots(&self.coefficients, &p))
+    }
+
+    pub fn is_irreducible(&self) -> bool {
+        unwrap_or_panic(self.try_is_irreducible())
+    }
+
+    pub fn try_is_irreducible(&self) -> Result<bool> {
+        let p = self.prime_modulus()?;
+        Ok(factorization::is_irreducible(&self.coefficients, &p))
+    }
+}
+
+
 impl<T> std::ops::Neg for UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number {
     type Output = UnivariatePolynomialInstance<T>;
     fn neg(self) -> UnivariatePolynomialInstance<T> {
@@ -123,7 +282,7 @@ impl<T> std::ops::Sub for UnivariatePolynomialInstance<T> where T: Instance + Op
 }
 
 
-impl<T> std::ops::Mul for UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number {
+impl<T> std::ops::Mul for UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + PartialEq + Number + 'static {
     type Output = UnivariatePolynomialInstance<T>;
     fn mul(self, rhs: UnivariatePolynomialInstance<T>) -> UnivariatePolynomialInstance<T> {
         UnivariatePolynomial::mul(self, rhs)
@@ -195,7 +354,7 @@ impl<T> std::fmt::Display for UnivariatePolynomialInstance<T> where T: Instance
 
 
 
-impl<T> Operand for UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + Eq + Number{
+impl<T> Operand for UnivariatePolynomialInstance<T> where T: Instance + Operand + Clone + Eq + Number + 'static {
     fn neg(&self) -> Self {
         -self.clone()
     }
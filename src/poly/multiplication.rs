@@ -0,0 +1,438 @@
+use std::any::{Any, TypeId};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive};
+
+use crate::algebras::FiniteField::classes::Zmod::Zmod;
+use crate::algebras::FiniteField::instances::Zmod_instance::ZmodInstance;
+use crate::numbers::classes::ZZ::ZZ;
+use crate::numbers::instances::ZZ_instance::ZZinstance;
+use crate::numbers::numbers::{Class, Instance, Number, Operand};
+use crate::poly::classes::univariate_polynomial::PolyMultiplicationAlgorithm;
+
+/*
+    Multiplication of coefficient vectors (lowest degree first).
+    Integer and Zmod coefficients are lifted to BigInt, so that every algorithm works over Z
+    and the Zmod products are reduced once at the end. Toom-3 and NTT need the lifting,
+    any other coefficient type is multiplied with Karatsuba.
+*/
+
+// lengths (of the shortest operand) from which the next algorithm is used by default
+const KARATSUBA_THRESHOLD: usize = 32;
+const TOOM_COOK_THRESHOLD: usize = 64;
+const NTT_THRESHOLD: usize = 128;
+
+pub fn multiply<T>(x: &[T], y: &[T], algorithm: Option<PolyMultiplicationAlgorithm>) -> Vec<T> where T: Instance + Operand + Clone + Number + 'static {
+    if x.is_empty() || y.is_empty() {
+        return vec![T::zero(); (x.len() + y.len()).saturating_sub(1)];
+    }
+    let algorithm = algorithm.unwrap_or_else(|| default_algorithm(x.len().min(y.len())));
+
+    if TypeId::of::<T>() == TypeId::of::<ZZinstance>() {
+        let zz = ZZ::new();
+        let product = multiply_integers(&lift_integers(x), &lift_integers(y), algorithm);
+        let coefficients: Vec<ZZinstance> = product.into_iter().map(|value| zz.new_instance(value)).collect();
+        return downcast_vector(coefficients);
+    }
+    if TypeId::of::<T>() == TypeId::of::<ZmodInstance>() {
+        if let Some(field) = common_modulus(x, y) {
+            let product = multiply_integers(&lift_residues(x), &lift_residues(y), algorithm);
+            let coefficients: Vec<ZmodInstance> = product.into_iter().map(|value| field.apply(value)).collect();
+            return downcast_vector(coefficients);
+        }
+    }
+
+    match algorithm {
+        PolyMultiplicationAlgorithm::Naive => schoolbook(x, y),
+        _ => karatsuba(x, y)
+    }
+}
+
+pub fn default_algorithm(length: usize) -> PolyMultiplicationAlgorithm {
+    if length < KARATSUBA_THRESHOLD {
+        PolyMultiplicationAlgorithm::Naive
+    } else if length < TOOM_COOK_THRESHOLD {
+        PolyMultiplicationAlgorithm::Karatsuba
+    } else if length < NTT_THRESHOLD {
+        PolyMultiplicationAlgorithm::ToomCook
+    } else {
+        PolyMultiplicationAlgorithm::NTT
+    }
+}
+
+pub fn multiply_integers(a: &[BigInt], b: &[BigInt], algorithm: PolyMultiplicationAlgorithm) -> Vec<BigInt> {
+    match algorithm {
+        PolyMultiplicationAlgorithm::Naive => schoolbook(a, b),
+        PolyMultiplicationAlgorithm::Karatsuba => karatsuba(a, b),
+        PolyMultiplicationAlgorithm::ToomCook => toom_cook_3(a, b),
+        PolyMultiplicationAlgorithm::NTT => ntt_multiply(a, b)
+    }
+}
+
+
+// --------------------- lifting ---------------------
+
+fn lift_integers<T: Instance>(values: &[T]) -> Vec<BigInt> {
+    values.iter().map(|value| value.as_any().downcast_ref::<ZZinstance>().unwrap().value.clone()).collect()
+}
+
+pub(crate) fn lift_residues<T: Instance>(values: &[T]) -> Vec<BigInt> {
+    values.iter().map(|value| value.as_any().downcast_ref::<ZmodInstance>().unwrap().value.value.clone()).collect()
+}
+
+// the field shared by all the coefficients with a modulus, None if there is none or they differ
+pub(crate) fn common_modulus<T: Instance>(x: &[T], y: &[T]) -> Option<Zmod> {
+    let mut field: Option<Zmod> = None;
+    for value in x.iter().chain(y) {
+        let class = value.as_any().downcast_ref::<ZmodInstance>().unwrap().class.borrow();
+        match (&field, &class.module) {
+            (_, None) => continue,
+            (None, Some(_)) => field = Some(class.clone()),
+            (Some(current), Some(_)) if *current == *class => continue,
+            _ => return None
+        }
+    }
+    field
+}
+
+pub(crate) fn downcast_vector<V: 'static, T: 'static>(values: Vec<V>) -> Vec<T> {
+    let values: Box<dyn Any> = Box::new(values);
+    *values.downcast::<Vec<T>>().unwrap()
+}
+
+
+// --------------------- generic algorithms ---------------------
+
+trait Coefficient: Clone {
+    fn zero() -> Self;
+    fn add(&self, other: &Self) -> Self;
+    fn sub(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+}
+
+impl Coefficient for BigInt {
+    fn zero() -> BigInt {
+        BigInt::from(0)
+    }
+    fn add(&self, other: &BigInt) -> BigInt {
+        self + other
+    }
+    fn sub(&self, other: &BigInt) -> BigInt {
+        self - other
+    }
+    fn mul(&self, other: &BigInt) -> BigInt {
+        self * other
+    }
+}
+
+impl<T> Coefficient for T where T: Operand + Number + Clone {
+    fn zero() -> T {
+        <T as Number>::zero()
+    }
+    fn add(&self, other: &T) -> T {
+        Operand::add(self, other)
+    }
+    fn sub(&self, other: &T) -> T {
+        Operand::sub(self, other)
+    }
+    fn mul(&self, other: &T) -> T {
+        Operand::mul(self, other)
+    }
+}
+
+fn add_vectors<C: Coefficient>(a: &[C], b: &[C]) -> Vec<C> {
+    let mut result = a.to_vec();
+    add_shifted(&mut result, b, 0);
+    result
+}
+
+fn sub_vectors<C: Coefficient>(a: &[C], b: &[C]) -> Vec<C> {
+    let mut result = a.to_vec();
+    if result.len() < b.len() {
+        result.resize(b.len(), C::zero());
+    }
+    for (i, value) in b.iter().enumerate() {
+        result[i] = result[i].sub(value);
+    }
+    result
+}
+
+// result += values * x^shift
+fn add_shifted<C: Coefficient>(result: &mut Vec<C>, values: &[C], shift: usize) {
+    if result.len() < shift + values.len() {
+        result.resize(shift + values.len(), C::zero());
+    }
+    for (i, value) in values.iter().enumerate() {
+        result[shift + i] = result[shift + i].add(value);
+    }
+}
+
+fn schoolbook<C: Coefficient>(a: &[C], b: &[C]) -> Vec<C> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut result = vec![C::zero(); a.len() + b.len() - 1];
+    for (i, a_i) in a.iter().enumerate() {
+        for (j, b_j) in b.iter().enumerate() {
+            result[i + j] = result[i + j].add(&a_i.mul(b_j));
+        }
+    }
+    result
+}
+
+// unbalanced operands: the longest one is cut in blocks of the length of the shortest one
+fn multiply_by_blocks<C: Coefficient>(long: &[C], short: &[C], multiply: fn(&[C], &[C]) -> Vec<C>) -> Vec<C> {
+    let mut result = Vec::new();
+    for (index, block) in long.chunks(short.len()).enumerate() {
+        add_shifted(&mut result, &multiply(block, short), index * short.len());
+    }
+    result
+}
+
+// a b = z0 + (z1 - z0 - z2) x^m + z2 x^2m with z1 = (a0 + a1)(b0 + b1)
+fn karatsuba<C: Coefficient>(a: &[C], b: &[C]) -> Vec<C> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if short.len() < KARATSUBA_THRESHOLD {
+        return schoolbook(long, short);
+    }
+    if 2 * short.len() <= long.len() {
+        return multiply_by_blocks(long, short, karatsuba);
+    }
+
+    let m = long.len() / 2;
+    let (a0, a1) = long.split_at(m);
+    let (b0, b1) = short.split_at(m);
+    let z0 = karatsuba(a0, b0);
+    let z2 = karatsuba(a1, b1);
+    let z1 = sub_vectors(&sub_vectors(&karatsuba(&add_vectors(a0, a1), &add_vectors(b0, b1)), &z0), &z2);
+
+    let mut result = z0;
+    add_shifted(&mut result, &z1, m);
+    add_shifted(&mut result, &z2, 2 * m);
+    result.resize(a.len() + b.len() - 1, C::zero());
+    result
+}
+
+
+// --------------------- integer algorithms ---------------------
+
+fn scale(values: &[BigInt], factor: i64) -> Vec<BigInt> {
+    values.iter().map(|value| value * factor).collect()
+}
+
+// the divisions of the interpolation are exact
+fn divide_exact(values: &[BigInt], divisor: i64) -> Vec<BigInt> {
+    values.iter().map(|value| value / divisor).collect()
+}
+
+/*
+    Toom-3: evaluation in 0, 1, -1, -2, infinity and the interpolation sequence of Bodrato
+*/
+fn toom_cook_3(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if short.len() < TOOM_COOK_THRESHOLD {
+        return karatsuba(long, short);
+    }
+    if 3 * short.len() <= 2 * long.len() {
+        return multiply_by_blocks(long, short, toom_cook_3);
+    }
+
+    let m = long.len().div_ceil(3);
+    let part = |values: &[BigInt], i: usize| values[(i * m).min(values.len())..((i + 1) * m).min(values.len())].to_vec();
+    let evaluate = |values: &[BigInt]| {
+        let (p0, p1, p2) = (part(values, 0), part(values, 1), part(values, 2));
+        let sum = add_vectors(&p0, &p2);
+        let at_minus_one = sub_vectors(&sum, &p1);
+        let at_minus_two = sub_vectors(&scale(&add_vectors(&at_minus_one, &p2), 2), &p0);
+        [p0, add_vectors(&sum, &p1), at_minus_one, at_minus_two, p2]
+    };
+    let (a_values, b_values) = (evaluate(long), evaluate(short));
+    let [r0, r1, r_minus_one, r_minus_two, r_infinity] = [0, 1, 2, 3, 4].map(|i| toom_cook_3(&a_values[i], &b_values[i]));
+
+    let mut r3 = divide_exact(&sub_vectors(&r_minus_two, &r1), 3);
+    let mut r1 = divide_exact(&sub_vectors(&r1, &r_minus_one), 2);
+    let mut r2 = sub_vectors(&r_minus_one, &r0);
+    r3 = add_vectors(&divide_exact(&sub_vectors(&r2, &r3), 2), &scale(&r_infinity, 2));
+    r2 = sub_vectors(&add_vectors(&r2, &r1), &r_infinity);
+    r1 = sub_vectors(&r1, &r3);
+
+    let mut result = r0;
+    for (i, values) in [r1, r2, r3, r_infinity].iter().enumerate() {
+        add_shifted(&mut result, values, (i + 1) * m);
+    }
+    result.resize(a.len() + b.len() - 1, BigInt::from(0));
+    result
+}
+
+/*
+    NTT multiplication modulo primes p = k 2^32 + 1 < 2^62 and Chinese remaindering.
+    The product of the primes exceeds twice the largest possible coefficient, so negative
+    coefficients are recovered from the symmetric representatives.
+*/
+fn ntt_multiply(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let result_length = a.len() + b.len() - 1;
+    let length = result_length.next_power_of_two();
+    let largest = |values: &[BigInt]| values.iter().map(|value| value.abs()).max().unwrap();
+    let bound: BigInt = largest(a) * largest(b) * a.len().min(b.len());
+    let primes = ntt_primes(&(bound * 2));
+
+    let residues: Vec<Vec<u64>> = primes.iter().map(|&(p, generator)| {
+        let reduce = |values: &[BigInt]| {
+            let modulus = BigInt::from(p);
+            let mut reduced: Vec<u64> = values.iter().map(|value| value.mod_floor(&modulus).to_u64().unwrap()).collect();
+            reduced.resize(length, 0);
+            reduced
+        };
+        let root = pow_mod(generator, (p - 1) / length as u64, p);
+        let (mut a_hat, mut b_hat) = (reduce(a), reduce(b));
+        ntt(&mut a_hat, root, p);
+        ntt(&mut b_hat, root, p);
+        let mut c: Vec<u64> = a_hat.iter().zip(&b_hat).map(|(x, y)| mul_mod(*x, *y, p)).collect();
+        ntt(&mut c, pow_mod(root, p - 2, p), p);
+        let length_inverse = pow_mod(length as u64, p - 2, p);
+        c.truncate(result_length);
+        c.iter().map(|value| mul_mod(*value, length_inverse, p)).collect()
+    }).collect();
+
+    crt(&primes.iter().map(|(p, _)| *p).collect::<Vec<u64>>(), &residues, result_length)
+}
+
+// Garner's algorithm, coefficient by coefficient
+fn crt(primes: &[u64], residues: &[Vec<u64>], length: usize) -> Vec<BigInt> {
+    let modulus: BigInt = primes.iter().fold(BigInt::one(), |product, p| product * p);
+    let half = &modulus >> 1;
+    // inverses[j][i] = p_i^-1 mod p_j
+    let inverses: Vec<Vec<u64>> = primes.iter().map(|&p_j| primes.iter().map(|&p_i| pow_mod(p_i % p_j, p_j - 2, p_j)).collect()).collect();
+
+    (0..length).map(|index| {
+        let mut digits: Vec<u64> = Vec::with_capacity(primes.len());
+        for (j, &p_j) in primes.iter().enumerate() {
+            let mut digit = residues[j][index];
+            for (i, previous) in digits.iter().enumerate() {
+                digit = mul_mod((digit + p_j - previous % p_j) % p_j, inverses[j][i], p_j);
+            }
+            digits.push(digit);
+        }
+        let value = digits.iter().zip(primes).rev().fold(BigInt::from(0), |value, (digit, p)| value * p + digit);
+        if value > half { value - &modulus } else { value }
+    }).collect()
+}
+
+// iterative Cooley-Tukey transform, root is a primitive n-th root of unity
+fn ntt(values: &mut [u64], root: u64, p: u64) {
+    let n = values.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut length = 2;
+    while length <= n {
+        let w = pow_mod(root, (n / length) as u64, p);
+        let mut twiddles = vec![1u64; length / 2];
+        for i in 1..length / 2 {
+            twiddles[i] = mul_mod(twiddles[i - 1], w, p);
+        }
+        for start in (0..n).step_by(length) {
+            for (i, twiddle) in twiddles.iter().enumerate() {
+                let u = values[start + i];
+                let v = mul_mod(values[start + i + length / 2], *twiddle, p);
+                values[start + i] = if u + v >= p { u + v - p } else { u + v };
+                values[start + i + length / 2] = if u >= v { u - v } else { u + p - v };
+            }
+        }
+        length <<= 1;
+    }
+}
+
+// (p, primitive root) with p = k 2^32 + 1 from the largest k, until their product exceeds bound
+fn ntt_primes(bound: &BigInt) -> Vec<(u64, u64)> {
+    let mut primes = Vec::new();
+    let mut product = BigInt::one();
+    let mut k: u64 = (1 << 30) - 1;
+    while product <= *bound {
+        let p = (k << 32) + 1;
+        if is_prime(p) {
+            primes.push((p, primitive_root(p, k)));
+            product *= p;
+        }
+        k -= 1;
+    }
+    primes
+}
+
+fn primitive_root(p: u64, k: u64) -> u64 {
+    let mut factors = vec![2];
+    let (mut rest, mut d) = (k, 2);
+    while d * d <= rest {
+        if rest % d == 0 {
+            factors.push(d);
+            while rest % d == 0 {
+                rest /= d;
+            }
+        }
+        d += 1;
+    }
+    if rest > 1 {
+        factors.push(rest);
+    }
+    (2..).find(|g| factors.iter().all(|q| pow_mod(*g, (p - 1) / q, p) != 1)).unwrap()
+}
+
+// deterministic Miller-Rabin for 64 bits integers
+fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(base) = BASES.iter().find(|base| n.is_multiple_of(**base)) {
+        return n == *base;
+    }
+    let (mut d, mut s) = (n - 1, 0);
+    while d % 2 == 0 {
+        d /= 2;
+        s += 1;
+    }
+    BASES.iter().all(|base| {
+        let mut x = pow_mod(*base, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((a as u128 * b as u128) % p as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, p: u64) -> u64 {
+    let mut result = 1;
+    base %= p;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, p);
+        }
+        base = mul_mod(base, base, p);
+        exponent >>= 1;
+    }
+    result
+}
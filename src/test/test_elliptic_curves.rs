@@ -0,0 +1,340 @@
+use crate::algebras::FiniteField::classes::Zmod::Zmod;
+use crate::algebras::FiniteField::instances::Zmod_instance::ZmodInstance;
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_factory::{EllipticCurve, EllipticCurveFactory};
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_finite_field::{EllipticCurve_finite_field, PointCountingAlgorithm};
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_generic::EllipticCurve_generic;
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_rational_field::{EllipticCurve_rational_field, KodairaSymbol, ReductionType};
+use crate::algebras::Groups::EllipticCurves::elliptic_curve_point::EllipticCurvePoint;
+use crate::cryptography::asymmetric::interfaces::interfaces::KeyAgreementInterface;
+use crate::cryptography::asymmetric::primitives::ecdh::ECDH;
+use crate::error::Error;
+use crate::numbers::classes::QQ::QQ;
+use crate::numbers::classes::ZZ::ZZ;
+use crate::numbers::numbers::Class;
+use num_bigint::BigInt;
+use num_traits::Num;
+
+pub fn test() {
+    test_point_arithmetic();
+    test_scalar_multiplication();
+    test_general_weierstrass();
+    test_p256_generator_order();
+    test_finite_field_invariants();
+    test_point_counting();
+    test_schoof();
+    test_group_structure();
+    test_large_order();
+    test_minimal_model();
+    test_local_data();
+    test_torsion();
+    test_reduction();
+}
+
+
+fn curve_over(p: i64, coefficients: Vec<i64>) -> (Zmod, Box<EllipticCurve_generic<ZmodInstance>>) {
+    let field = Zmod::new(Some(ZZ::new().new_instance(BigInt::from(p))));
+    let coefficients: Vec<ZmodInstance> = coefficients.into_iter().map(|c| field.apply(BigInt::from(c))).collect();
+    let curve = EllipticCurveFactory::gen_elliptic_curve::<EllipticCurve_generic<ZmodInstance>, ZmodInstance>(coefficients);
+    (field, curve)
+}
+
+fn all_points(p: i64, field: &Zmod, curve: &EllipticCurve_generic<ZmodInstance>) -> Vec<EllipticCurvePoint<ZmodInstance>> {
+    let mut points = vec![curve.infinity()];
+    for x in 0..p {
+        for y in 0..p {
+            let (x, y) = (field.apply(BigInt::from(x)), field.apply(BigInt::from(y)));
+            if curve.is_on_curve(&x, &y) {
+                points.push(curve.point(x, y));
+            }
+        }
+    }
+    points
+}
+
+/*
+    y^2 = x^3 + 2x + 3 over F_97
+*/
+fn test_point_arithmetic() {
+    let (field, curve) = curve_over(97, vec![0, 0, 0, 2, 3]);
+    let p = curve.point(field.apply(BigInt::from(3)), field.apply(BigInt::from(6)));
+
+    let double = p.clone() + p.clone();
+    assert!(double.xy() == (field.apply(BigInt::from(80)), field.apply(BigInt::from(10))));
+    assert!(double == p.double());
+
+    let triple = double.clone() + p.clone();
+    assert!(triple.xy() == (field.apply(BigInt::from(80)), field.apply(BigInt::from(87))));
+    assert!(triple == -double.clone());
+
+    assert!((p.clone() - p.clone()).is_infinity());
+    assert!((p.clone() + curve.infinity()) == p);
+    assert!((curve.infinity() + p.clone()) == p);
+    assert!((triple.clone() + double.clone()).is_infinity());
+    assert_eq!(format!("{}", p), "(3 : 6 : 1)");
+    assert_eq!(format!("{}", curve.infinity()), "(0 : 1 : 0)");
+}
+
+fn test_scalar_multiplication() {
+    let (field, curve) = curve_over(97, vec![0, 0, 0, 2, 3]);
+    let p = curve.point(field.apply(BigInt::from(3)), field.apply(BigInt::from(6)));
+
+    assert!((p.clone() * BigInt::from(0)).is_infinity());
+    assert!(p.clone() * BigInt::from(1) == p);
+    assert!(p.clone() * BigInt::from(4) == -p.clone());
+    assert!((p.clone() * BigInt::from(5)).is_infinity());
+    assert!(p.clone() * BigInt::from(-2) == (p.clone() * BigInt::from(3)));
+    assert!(p.clone() * ZZ::new().new_instance(BigInt::from(7)) == p.clone() * BigInt::from(2));
+
+    let points = all_points(97, &field, &curve);
+    let order = BigInt::from(points.len());
+    for point in points.iter() {
+        assert!((point.clone() * order.clone()).is_infinity());
+    }
+}
+
+/*
+    y^2 + xy + y = x^3 - x^2 + 3x + 5 over F_101, uses the chord and tangent law
+*/
+fn test_general_weierstrass() {
+    let (field, curve) = curve_over(101, vec![1, -1, 1, 3, 5]);
+    let points = all_points(101, &field, &curve);
+    let order = BigInt::from(points.len());
+
+    for point in points.iter() {
+        assert!((point.clone() * order.clone()).is_infinity());
+        assert!((point.clone() + (-point.clone())).is_infinity());
+        assert!(point.clone() * BigInt::from(3) == point.clone() + point.clone() + point.clone());
+    }
+
+    let (p, q, r) = (points[3].clone(), points[7].clone(), points[11].clone());
+    assert!((p.clone() + q.clone()) + r.clone() == p.clone() + (q.clone() + r.clone()));
+    assert!(p.clone() + q.clone() == q.clone() + p.clone());
+}
+
+fn test_p256_generator_order() {
+    let p = BigInt::from_str_radix("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff", 16).unwrap();
+    let field = Zmod::new(Some(ZZ::new().new_instance(p.clone())));
+    let coefficients = vec![
+        field.apply(BigInt::from(0)),
+        field.apply(BigInt::from(0)),
+        field.apply(BigInt::from(0)),
+        field.apply(BigInt::from(-3)),
+        field.apply(BigInt::from_str_radix("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b", 16).unwrap())
+    ];
+    let curve = EllipticCurveFactory::gen_elliptic_curve::<EllipticCurve_generic<ZmodInstance>, ZmodInstance>(coefficients.clone());
+    let g = curve.point(
+        field.apply(BigInt::from_str_radix("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296", 16).unwrap()),
+        field.apply(BigInt::from_str_radix("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5", 16).unwrap())
+    );
+    let n = BigInt::from_str_radix("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551", 16).unwrap();
+
+    assert!((g.clone() * n.clone()).is_infinity());
+    assert!(g.clone() * (n.clone() - BigInt::from(1)) == -g.clone());
+
+    let two_g = g.double().xy();
+    assert!(two_g.0 == field.apply(BigInt::from_str_radix("7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978", 16).unwrap()));
+    assert!(two_g.1 == field.apply(BigInt::from_str_radix("07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1", 16).unwrap()));
+
+    // the order is too large to be counted, it must be given
+    let curve = EllipticCurve_finite_field::new(coefficients);
+    assert!(matches!(curve.try_order(), Err(Error::InvalidInput(_))));
+    curve.set_order(n.clone());
+    assert_eq!(curve.order(), n);
+}
+
+fn finite_field_curve(p: i64, coefficients: Vec<i64>) -> (Zmod, EllipticCurve_finite_field) {
+    let field = Zmod::new(Some(ZZ::new().new_instance(BigInt::from(p))));
+    let coefficients: Vec<ZmodInstance> = coefficients.into_iter().map(|c| field.apply(BigInt::from(c))).collect();
+    (field.clone(), EllipticCurve_finite_field::new(coefficients))
+}
+
+fn test_finite_field_invariants() {
+    let (field, curve) = finite_field_curve(97, vec![0, 0, 0, 2, 3]);
+    assert!(curve.j_invariant() == field.apply(BigInt::from(36)));
+    assert_eq!(curve.order(), BigInt::from(100));
+    assert_eq!(curve.trace_of_frobenius(), BigInt::from(-2));
+    assert_eq!(curve.order(), BigInt::from(curve.points().len()));
+
+    let p = curve.point(field.apply(BigInt::from(3)), field.apply(BigInt::from(6)));
+    assert_eq!(curve.point_order(&p), BigInt::from(5));
+    for _ in 0..10 {
+        let random = curve.random_point();
+        assert!((random.clone() * curve.order()).is_infinity());
+        assert!(curve.order().clone() % curve.point_order(&random) == BigInt::from(0));
+    }
+}
+
+fn test_point_counting() {
+    let (_, short) = finite_field_curve(100003, vec![0, 0, 0, 2, 3]);
+    let (_, general) = finite_field_curve(100003, vec![1, 2, 3, 4, 5]);
+    for algorithm in [PointCountingAlgorithm::Naive, PointCountingAlgorithm::BabyStepGiantStep, PointCountingAlgorithm::Schoof] {
+        assert_eq!(short.order_with_algorithm(algorithm), BigInt::from(100294));
+        assert_eq!(general.order_with_algorithm(algorithm), BigInt::from(99848));
+    }
+
+    // characteristic 2 and 3
+    let (_, curve) = finite_field_curve(2, vec![1, 0, 1, 0, 1]);
+    assert_eq!(curve.order(), BigInt::from(curve.points().len()));
+    let (_, curve) = finite_field_curve(3, vec![0, 1, 0, 0, 1]);
+    assert_eq!(curve.order(), BigInt::from(curve.points().len()));
+}
+
+fn test_schoof() {
+    let (_, curve) = finite_field_curve(1099511627791, vec![0, 0, 0, 3, 7]);
+    let order = curve.order_with_algorithm(PointCountingAlgorithm::Schoof);
+    assert_eq!(order, curve.order_with_algorithm(PointCountingAlgorithm::BabyStepGiantStep));
+    for _ in 0..5 {
+        assert!((curve.random_point() * order.clone()).is_infinity());
+    }
+}
+
+/*
+    y^2 = x^3 - x over F_103 has full 2-torsion: E = Z/52 x Z/2
+*/
+fn test_group_structure() {
+    let (_, curve) = finite_field_curve(103, vec![0, 0, 0, -1, 0]);
+    assert_eq!(curve.order(), BigInt::from(104));
+    assert_eq!(curve.abelian_group(), (BigInt::from(52), BigInt::from(2)));
+
+    let (_, curve) = finite_field_curve(97, vec![0, 0, 0, 2, 3]);
+    let (n1, n2) = curve.abelian_group();
+    assert_eq!(n1.clone() * n2.clone(), BigInt::from(100));
+    assert!(n1 % n2 == BigInt::from(0));
+
+    // P-256 has prime order
+    let p = BigInt::from_str_radix("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff", 16).unwrap();
+    let field = Zmod::new(Some(ZZ::new().new_instance(p)));
+    let curve = EllipticCurve_finite_field::new(vec![
+        field.apply(BigInt::from(0)),
+        field.apply(BigInt::from(0)),
+        field.apply(BigInt::from(0)),
+        field.apply(BigInt::from(-3)),
+        field.apply(BigInt::from_str_radix("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b", 16).unwrap())
+    ]);
+    let n = BigInt::from_str_radix("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551", 16).unwrap();
+    curve.set_order(n.clone());
+    assert_eq!(curve.point_order(&curve.random_point()), n.clone());
+    assert_eq!(curve.abelian_group(), (n, BigInt::from(1)));
+}
+
+/*
+    secp256k1 (SEC 2, section 2.4.1) built from its coefficients: y^2 = x^3 + 7 over a 256-bit prime field,
+    beyond the counting bound the order is only known once it has been set
+*/
+fn test_large_order() {
+    let p = BigInt::from_str_radix("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f", 16).unwrap();
+    let n = BigInt::from_str_radix("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141", 16).unwrap();
+    let field = Zmod::new(Some(ZZ::new().new_instance(p.clone())));
+    let curve = EllipticCurve_finite_field::new([0, 0, 0, 0, 7].into_iter().map(|c| field.apply(BigInt::from(c))).collect());
+    let g = curve.point(
+        field.apply(BigInt::from_str_radix("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798", 16).unwrap()),
+        field.apply(BigInt::from_str_radix("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8", 16).unwrap())
+    );
+
+    assert!(matches!(curve.try_order(), Err(Error::InvalidInput(_))));
+    assert!(matches!(curve.try_trace_of_frobenius(), Err(Error::InvalidInput(_))));
+    assert!(matches!(curve.try_point_order(&g), Err(Error::InvalidInput(_))));
+    assert!(matches!(curve.try_abelian_group(), Err(Error::InvalidInput(_))));
+    assert!(ECDH::try_from_curve(Box::new(curve.clone()), g.clone()).is_err());
+
+    assert!(matches!(curve.try_set_order(p.clone() * 2), Err(Error::InvalidInput(_))));
+    assert!(matches!(curve.try_set_order(n.clone() + 1), Err(Error::InvalidInput(_))));
+    curve.try_set_order(n.clone()).unwrap();
+    assert_eq!(curve.trace_of_frobenius(), p + 1 - n.clone());
+    assert_eq!(curve.point_order(&g), n.clone());
+    assert_eq!(curve.abelian_group(), (n, BigInt::from(1)));
+
+    let alice = ECDH::try_from_curve(Box::new(curve.clone()), g.clone()).unwrap();
+    let bob = ECDH::try_from_curve(Box::new(curve), g).unwrap();
+    assert_eq!(alice.compute_shared_secret(bob.get_public_key()), bob.compute_shared_secret(alice.get_public_key()));
+}
+
+fn rational_curve(coefficients: Vec<(i64, i64)>) -> EllipticCurve_rational_field {
+    let qq = QQ::new();
+    EllipticCurve_rational_field::new(coefficients.into_iter().map(|(n, d)| qq.new_instance(BigInt::from(n), BigInt::from(d))).collect())
+}
+
+fn integral_curve(coefficients: Vec<i64>) -> EllipticCurve_rational_field {
+    rational_curve(coefficients.into_iter().map(|c| (c, 1)).collect())
+}
+
+fn test_minimal_model() {
+    // 11a1 and its model y^2 = x^3 - 27 c4 x - 54 c6
+    let curve = integral_curve(vec![0, -1, 1, -10, -20]);
+    assert!(curve.is_minimal());
+    assert_eq!(curve.minimal_discriminant(), BigInt::from(-161051));
+    let short = integral_curve(vec![0, 0, 0, -13392, -1080432]);
+    assert!(!short.is_minimal());
+    assert!(short.minimal_model().base_curve() == curve.base_curve());
+    assert!(short.j_invariant() == curve.j_invariant());
+
+    // y^2 = x^3 + 1/64 is y^2 = x^3 + 1 (36a1)
+    let curve = rational_curve(vec![(0, 1), (0, 1), (0, 1), (0, 1), (1, 64)]);
+    assert!(!curve.is_integral());
+    assert!(curve.minimal_model().base_curve() == integral_curve(vec![0, 0, 0, 0, 1]).base_curve());
+    assert_eq!(curve.conductor(), BigInt::from(36));
+}
+
+fn test_local_data() {
+    let curve = integral_curve(vec![0, -1, 1, -10, -20]);
+    let local_data = curve.local_data(BigInt::from(11));
+    assert_eq!(local_data.reduction_type, ReductionType::SplitMultiplicative);
+    assert_eq!(local_data.kodaira_symbol, KodairaSymbol::I(5));
+    assert_eq!((local_data.conductor_exponent, local_data.tamagawa_number, local_data.discriminant_valuation), (1, 5, 5));
+    assert_eq!(curve.local_data(BigInt::from(7)).reduction_type, ReductionType::Good);
+    assert_eq!(curve.conductor(), BigInt::from(11));
+
+    assert_eq!(integral_curve(vec![0, 0, 1, -1, 0]).conductor(), BigInt::from(37));
+    assert_eq!(integral_curve(vec![1, -1, 1, -3, 3]).conductor(), BigInt::from(26));
+
+    // additive reduction at 2 and 3
+    let curve = integral_curve(vec![0, 0, 0, 0, 1]);
+    assert_eq!(curve.local_data(BigInt::from(2)).kodaira_symbol, KodairaSymbol::IV);
+    assert_eq!(curve.local_data(BigInt::from(3)).kodaira_symbol, KodairaSymbol::III);
+    assert_eq!(format!("{}", curve.local_data(BigInt::from(2)).kodaira_symbol), "IV");
+    let curve = integral_curve(vec![0, 0, 0, -1, 0]);
+    assert_eq!(curve.local_data(BigInt::from(2)).kodaira_symbol, KodairaSymbol::III);
+    assert_eq!(curve.conductor(), BigInt::from(32));
+
+    // the quadratic twist of 37a1 by 5 has type I0* at 5: y^2 = x^3 - 27 c4 25 x - 54 c6 125
+    let curve = integral_curve(vec![0, 0, 0, -27 * 48 * 25, 54 * 216 * 125]);
+    let local_data = curve.local_data(BigInt::from(5));
+    assert_eq!(local_data.kodaira_symbol, KodairaSymbol::IStar(0));
+    assert_eq!((local_data.conductor_exponent, local_data.discriminant_valuation), (2, 6));
+    assert_eq!(curve.conductor(), BigInt::from(37 * 25));
+}
+
+fn test_torsion() {
+    let qq = QQ::new();
+    let curve = integral_curve(vec![0, -1, 1, -10, -20]);
+    let torsion = curve.torsion_points();
+    assert_eq!(torsion.len(), 5);
+    assert!(torsion.contains(&curve.point(qq.apply(BigInt::from(5)), qq.apply(BigInt::from(5)))));
+    assert!(torsion.contains(&curve.point(qq.apply(BigInt::from(16)), qq.apply(BigInt::from(-61)))));
+    for point in torsion.iter() {
+        assert!((point.clone() * BigInt::from(5)).is_infinity());
+    }
+
+    assert_eq!(integral_curve(vec![0, 0, 1, -1, 0]).torsion_order(), 1);
+    assert_eq!(integral_curve(vec![1, -1, 1, -3, 3]).torsion_order(), 7);
+    assert_eq!(integral_curve(vec![0, 0, 0, 0, 1]).torsion_structure(), (6, 1));
+    assert_eq!(integral_curve(vec![0, 0, 0, -1, 0]).torsion_structure(), (2, 2));
+
+    let curve = rational_curve(vec![(0, 1), (0, 1), (0, 1), (0, 1), (1, 64)]);
+    let torsion = curve.torsion_points();
+    assert_eq!(torsion.len(), 6);
+    assert!(torsion.contains(&curve.point(qq.new_instance(BigInt::from(-1), BigInt::from(4)), qq.zero())));
+}
+
+fn test_reduction() {
+    // the torsion subgroup injects into E(F_p) for the primes of good reduction
+    let curve = integral_curve(vec![0, 0, 0, -13392, -1080432]);
+    for (p, order) in [(3, 5), (7, 10), (13, 10), (23, 25)] {
+        assert!(curve.has_good_reduction(BigInt::from(p)));
+        assert_eq!(curve.reduction(BigInt::from(p)).order(), BigInt::from(order));
+    }
+    assert!(!curve.has_good_reduction(BigInt::from(11)));
+    assert!(matches!(curve.try_reduction(BigInt::from(11)), Err(Error::InvalidInput(_))));
+    assert!(matches!(curve.try_local_data(BigInt::from(15)), Err(Error::InvalidInput(_))));
+    assert_eq!(integral_curve(vec![1, -1, 1, -3, 3]).reduction(BigInt::from(17)).order(), BigInt::from(21));
+}
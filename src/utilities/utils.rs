@@ -15,6 +15,7 @@ use crate::algebras::Rings::instances::PolynomialRing_instance::PolynomialRingIn
 use crate::numbers::numbers::StatefulClass;
 use crate::poly::classes::univariate_polynomial::UnivariatePolynomial;
 use crate::poly::instances::univariate_polynomial_instance::UnivariatePolynomialInstance;
+use crate::error::{unwrap_or_panic, Error, Result};
 
 
 pub fn round_to_bigint(a: BigDecimal) -> BigInt {
@@ -32,13 +33,79 @@ pub fn modular_inverse(a: BigInt, module: BigInt) -> BigInt {
     x
 }
 
+/*
+    Legendre symbol (a/p) for an odd prime p through Euler's criterion: 1, -1 or 0 when p divides a
+*/
+pub fn legendre_symbol(a: BigInt, p: BigInt) -> i32 {
+    let a = a.mod_floor(&p);
+    if a == BigInt::from(0) {
+        return 0;
+    }
+    if a.modpow(&((p.clone() - 1) >> 1), &p) == BigInt::from(1) {
+        1
+    } else {
+        -1
+    }
+}
+
+/*
+    Square root modulo an odd prime p (Tonelli-Shanks). Returns None if a is not a quadratic residue.
+*/
+pub fn modular_sqrt(a: BigInt, p: BigInt) -> Option<BigInt> {
+    let a = a.mod_floor(&p);
+    if p == BigInt::from(2) || a == BigInt::from(0) {
+        return Some(a);
+    }
+    if legendre_symbol(a.clone(), p.clone()) != 1 {
+        return None;
+    }
+    if p.clone() % 4 == BigInt::from(3) {
+        return Some(a.modpow(&((p.clone() + 1) >> 2), &p));
+    }
+
+    // p - 1 = q * 2^s with q odd
+    let mut q: BigInt = p.clone() - 1;
+    let mut s: u32 = 0;
+    while q.is_even() {
+        q >>= 1;
+        s += 1;
+    }
+    let mut z = BigInt::from(2);
+    while legendre_symbol(z.clone(), p.clone()) != -1 {
+        z += 1;
+    }
+
+    let mut m = s;
+    let mut c = z.modpow(&q, &p);
+    let mut t = a.modpow(&q, &p);
+    let mut r = a.modpow(&((q + 1) >> 1), &p);
+    while t != BigInt::from(1) {
+        let mut i = 0;
+        let mut t2i = t.clone();
+        while t2i != BigInt::from(1) {
+            t2i = (&t2i * &t2i) % &p;
+            i += 1;
+        }
+        let b = c.modpow(&(BigInt::from(1) << (m - i - 1)), &p);
+        m = i;
+        c = (&b * &b) % &p;
+        t = (t * &c) % &p;
+        r = (r * b) % &p;
+    }
+    Some(r)
+}
+
 
 
 pub fn poly_divmod<T>(p: &UnivariatePolynomialInstance<T>, q: &UnivariatePolynomialInstance<T>) -> Vec<UnivariatePolynomialInstance<T>> where T: Display + Instance + Clone + PartialEq + Operand + Number + ClassInstance+ 'static {
+    unwrap_or_panic(try_poly_divmod(p, q))
+}
+
+pub fn try_poly_divmod<T>(p: &UnivariatePolynomialInstance<T>, q: &UnivariatePolynomialInstance<T>) -> Result<Vec<UnivariatePolynomialInstance<T>>> where T: Display + Instance + Clone + PartialEq + Operand + Number + ClassInstance+ 'static {
     //getting general class
     let generator: Box<dyn StatefulClass> = p.coefficients[0].get_class();
     if (*q).clone() == UnivariatePolynomial::zero(q.var.clone(), &generator) {
-        panic!("Cannot divide by 0");
+        Err(Error::DivisionByZero)
     } else {
         let mut l: UnivariatePolynomialInstance<T> = UnivariatePolynomial::zero(q.var.clone(), &generator);
         let mut r: UnivariatePolynomialInstance<T> = (*p).clone();
@@ -60,7 +127,7 @@ pub fn poly_divmod<T>(p: &UnivariatePolynomialInstance<T>, q: &UnivariatePolynom
         let mut result: Vec<UnivariatePolynomialInstance<T>> = Vec::new();
         result.push(l);
         result.push(r);
-        result
+        Ok(result)
         
     }
 }
@@ -125,7 +192,7 @@ pub fn egcd<T>(a: PolynomialRingInstance<T>, b: PolynomialRingInstance<T>) -> Ve
 // utilities
 pub fn clean_coefficients<T>(mut coeff: Vec<T>) -> Vec<T> where T: Instance + Operand + Clone + Number {
     loop {
-        if coeff[coeff.len()-1].clone().is_zero() {
+        if coeff[coeff.len()-1].clone().is_zero() && coeff.len() > 1 {
             coeff.pop();
         } else {
             break;
@@ -24,6 +24,14 @@ impl Var {
         self.types = types;
     }
 
+    pub fn get_symbol(&self) -> &'static str {
+        self.symbol
+    }
+
+    pub fn get_exponent(&self) -> BigInt {
+        self.exponent.clone()
+    }
+
   
 }
 